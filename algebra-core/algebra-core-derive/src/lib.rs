@@ -126,27 +126,31 @@ pub fn derive_canonical_deserialize(input: proc_macro::TokenStream) -> proc_macr
     proc_macro::TokenStream::from(impl_canonical_deserialize(&ast))
 }
 
-/// Returns two TokenStreams, one for the compressed deserialize, one for the
-/// uncompressed.
-fn impl_deserialize_field(ty: &Type) -> (TokenStream, TokenStream) {
+/// Returns three TokenStreams, one for the compressed deserialize, one for
+/// the uncompressed, and one for the unchecked.
+fn impl_deserialize_field(ty: &Type) -> (TokenStream, TokenStream, TokenStream) {
     // Check if type is a tuple.
     match ty {
         Type::Tuple(tuple) => {
             let mut compressed_fields = Vec::new();
             let mut uncompressed_fields = Vec::new();
+            let mut unchecked_fields = Vec::new();
             for elem_ty in tuple.elems.iter() {
-                let (compressed, uncompressed) = impl_deserialize_field(elem_ty);
+                let (compressed, uncompressed, unchecked) = impl_deserialize_field(elem_ty);
                 compressed_fields.push(compressed);
                 uncompressed_fields.push(uncompressed);
+                unchecked_fields.push(unchecked);
             }
             (
                 quote! { (#(#compressed_fields)*), },
                 quote! { (#(#uncompressed_fields)*), },
+                quote! { (#(#unchecked_fields)*), },
             )
         }
         _ => (
             quote! { CanonicalDeserialize::deserialize(reader)?, },
             quote! { CanonicalDeserialize::deserialize_uncompressed(reader)?, },
+            quote! { CanonicalDeserialize::deserialize_unchecked(reader)?, },
         ),
     }
 }
@@ -158,26 +162,31 @@ fn impl_canonical_deserialize(ast: &syn::DeriveInput) -> TokenStream {
 
     let deserialize_body;
     let deserialize_uncompressed_body;
+    let deserialize_unchecked_body;
 
     match ast.data {
         Data::Struct(ref data_struct) => {
             let mut tuple = false;
             let mut compressed_field_cases = Vec::<TokenStream>::new();
             let mut uncompressed_field_cases = Vec::<TokenStream>::new();
+            let mut unchecked_field_cases = Vec::<TokenStream>::new();
             for field in data_struct.fields.iter() {
                 match &field.ident {
                     None => {
                         tuple = true;
-                        let (compressed, uncompressed) = impl_deserialize_field(&field.ty);
+                        let (compressed, uncompressed, unchecked) =
+                            impl_deserialize_field(&field.ty);
                         compressed_field_cases.push(compressed);
                         uncompressed_field_cases.push(uncompressed);
+                        unchecked_field_cases.push(unchecked);
                     }
                     // struct field without len_type
                     Some(ident) => {
-                        let (compressed_field, uncompressed_field) =
+                        let (compressed_field, uncompressed_field, unchecked_field) =
                             impl_deserialize_field(&field.ty);
                         compressed_field_cases.push(quote! { #ident: #compressed_field });
                         uncompressed_field_cases.push(quote! { #ident: #uncompressed_field });
+                        unchecked_field_cases.push(quote! { #ident: #unchecked_field });
                     }
                 }
             }
@@ -193,6 +202,11 @@ fn impl_canonical_deserialize(ast: &syn::DeriveInput) -> TokenStream {
                         #(#uncompressed_field_cases)*
                     ))
                 });
+                deserialize_unchecked_body = quote!({
+                    Ok(#name (
+                        #(#unchecked_field_cases)*
+                    ))
+                });
             } else {
                 deserialize_body = quote!({
                     Ok(#name {
@@ -204,6 +218,11 @@ fn impl_canonical_deserialize(ast: &syn::DeriveInput) -> TokenStream {
                         #(#uncompressed_field_cases)*
                     })
                 });
+                deserialize_unchecked_body = quote!({
+                    Ok(#name {
+                        #(#unchecked_field_cases)*
+                    })
+                });
             }
         }
         _ => panic!(
@@ -222,6 +241,10 @@ fn impl_canonical_deserialize(ast: &syn::DeriveInput) -> TokenStream {
             fn deserialize_uncompressed<R: Read>(reader: &mut R) -> Result<Self, SerializationError> {
                 #deserialize_uncompressed_body
             }
+            #[allow(unused_mut,unused_variables)]
+            fn deserialize_unchecked<R: Read>(reader: &mut R) -> Result<Self, SerializationError> {
+                #deserialize_unchecked_body
+            }
         }
     };
     gen
@@ -250,6 +250,20 @@ pub trait AffineCurve:
     fn mul<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(&self, other: S)
         -> Self::Projective;
 
+    /// Performs scalar multiplication of this element, which must lie in the
+    /// prime order subgroup: e.g. it was deserialized with checks, is a
+    /// multiple of `prime_subgroup_generator`, or passed
+    /// `is_in_correct_subgroup_assuming_on_curve`. Unlike `mul`, this uses
+    /// the GLV endomorphism of the curve even if it has a cofactor, and its
+    /// result is wrong on points outside the subgroup.
+    #[must_use]
+    fn mul_subgroup<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(
+        &self,
+        other: S,
+    ) -> Self::Projective {
+        self.mul(other)
+    }
+
     /// Multiply this element by the cofactor and output the
     /// resulting projective element.
     #[must_use]
@@ -265,6 +279,36 @@ pub trait AffineCurve:
     /// `Self::ScalarField`.
     #[must_use]
     fn mul_by_cofactor_inv(&self) -> Self;
+
+    /// If the curve admits an efficient endomorphism acting on the prime order
+    /// subgroup as multiplication by some `lambda`, splits `scalar * self`
+    /// into `k1 * p1 + k2 * p2`, where `k1` and `k2` have roughly half the
+    /// bit length of `scalar`. Returns `None` otherwise, or if `scalar` is
+    /// not reduced modulo the group order.
+    ///
+    /// `self` must lie in the prime order subgroup: on other points, such as
+    /// those from `from_random_bytes` or unchecked deserialization, the
+    /// endomorphism does not act as multiplication by `lambda`, and
+    /// `k1 * p1 + k2 * p2` is not `scalar * self`. `mul` and
+    /// `VariableBaseMSM::multi_scalar_mul` only use the split on curves whose
+    /// cofactor is one, while `mul_subgroup` and
+    /// `VariableBaseMSM::multi_scalar_mul_subgroup` always use it.
+    fn glv_split(
+        &self,
+        _scalar: &<Self::ScalarField as PrimeField>::BigInt,
+    ) -> Option<(
+        (Self, <Self::ScalarField as PrimeField>::BigInt),
+        (Self, <Self::ScalarField as PrimeField>::BigInt),
+    )> {
+        None
+    }
+
+    /// Given pairs of indices `(i, j)`, sets `bases[i] = bases[i] + bases[j]`
+    /// for every pair, sharing the cost of the field inversions required by
+    /// affine addition across all pairs.
+    ///
+    /// Every index may appear in at most one pair.
+    fn batch_add_in_place(bases: &mut [Self], index: &[(usize, usize)]);
 }
 
 impl<C: ProjectiveCurve> Group for C {
@@ -0,0 +1,120 @@
+use crate::{
+    biginteger::BigInteger,
+    curves::{models::ModelParameters, AffineCurve, ProjectiveCurve},
+    fields::{FpParameters, PrimeField},
+};
+use num_traits::Zero;
+
+/// Parameters of the GLV endomorphism `phi: (x, y) -> (omega * x, y)` of a
+/// short Weierstrass curve with `a = 0`, where `omega` is a primitive cube
+/// root of unity. On the prime order subgroup `phi` acts as multiplication by
+/// `lambda`, a primitive cube root of unity in the scalar field.
+///
+/// Scalars are decomposed as `k = k1 + lambda * k2 mod r` by rounding
+/// `(k, 0)` to the short basis `(a1, b1), (a2, b2)` of the lattice
+/// `{(x, y) : x + lambda * y = 0 mod r}`, as described in Gallant, Lambert
+/// and Vanstone, "Faster Point Multiplication on Elliptic Curves with
+/// Efficient Endomorphisms". Both `k1` and `k2` have roughly half the bit
+/// length of `r`.
+pub struct GLVParameters<P: ModelParameters + ?Sized> {
+    /// The coefficient `omega` of the endomorphism.
+    pub omega: P::BaseField,
+    /// The eigenvalue `lambda` of the endomorphism on the prime order
+    /// subgroup.
+    pub lambda: P::ScalarField,
+    /// `b1`, reduced modulo `r`.
+    pub b1: P::ScalarField,
+    /// `b2`, reduced modulo `r`.
+    pub b2: P::ScalarField,
+    /// `round(2^(64 * n) * b2 / d)`, where `n` is the number of limbs of `r`
+    /// and `d = a1 * b2 - a2 * b1 = ±r`, together with whether it is negative.
+    pub g1: (&'static [u64], bool),
+    /// `round(-2^(64 * n) * b1 / d)`, together with whether it is negative.
+    pub g2: (&'static [u64], bool),
+}
+
+impl<P: ModelParameters + ?Sized> GLVParameters<P> {
+    /// Decomposes `k` into `(k1, k2)` such that `k = k1 + lambda * k2 mod r`,
+    /// where each half is returned as a flag indicating whether it is
+    /// negative, together with its absolute value.
+    ///
+    /// Returns `None` if `k` is not reduced modulo `r`.
+    pub fn decompose(
+        &self,
+        k: &<P::ScalarField as PrimeField>::BigInt,
+    ) -> Option<(
+        (bool, <P::ScalarField as PrimeField>::BigInt),
+        (bool, <P::ScalarField as PrimeField>::BigInt),
+    )> {
+        let k_field = P::ScalarField::from_repr(*k)?;
+
+        let c1 = Self::round_quotient(k, self.g1);
+        let c2 = Self::round_quotient(k, self.g2);
+
+        let k2 = -(c1 * &self.b1 + &(c2 * &self.b2));
+        let k1 = k_field - &(self.lambda * &k2);
+        Some((Self::to_signed(k1), Self::to_signed(k2)))
+    }
+
+    /// Computes `sign(g) * floor(k * |g| / 2^(64 * n))` in the scalar field.
+    fn round_quotient(
+        k: &<P::ScalarField as PrimeField>::BigInt,
+        (g, is_negative): (&'static [u64], bool),
+    ) -> P::ScalarField {
+        let k = k.as_ref();
+        let n = k.len();
+
+        // Schoolbook multiplication, of which we only keep the limbs above `n`.
+        let mut product = vec![0u64; n + g.len()];
+        for (i, k_i) in k.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, g_j) in g.iter().enumerate() {
+                let t = u128::from(*k_i) * u128::from(*g_j) + u128::from(product[i + j]) + carry;
+                product[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            product[i + g.len()] = carry as u64;
+        }
+
+        let mut quotient = <P::ScalarField as PrimeField>::BigInt::default();
+        quotient.as_mut()[..g.len()].copy_from_slice(&product[n..]);
+        let quotient = P::ScalarField::from_repr(quotient).unwrap();
+        if is_negative {
+            -quotient
+        } else {
+            quotient
+        }
+    }
+
+    fn to_signed(f: P::ScalarField) -> (bool, <P::ScalarField as PrimeField>::BigInt) {
+        let repr = f.into_repr();
+        if repr > <P::ScalarField as PrimeField>::Params::MODULUS_MINUS_ONE_DIV_TWO {
+            (true, (-f).into_repr())
+        } else {
+            (false, repr)
+        }
+    }
+}
+
+/// Computes `k1 * p1 + k2 * p2` with a single double-and-add pass over the
+/// bits of both scalars.
+pub(crate) fn glv_mul<G: AffineCurve, B: BigInteger>(
+    p1: &G,
+    k1: &B,
+    p2: &G,
+    k2: &B,
+) -> G::Projective {
+    let p12 = p1.into_projective().add_mixed(p2).into_affine();
+
+    let mut res = G::Projective::zero();
+    for i in (0..core::cmp::max(k1.num_bits(), k2.num_bits()) as usize).rev() {
+        res.double_in_place();
+        match (k1.get_bit(i), k2.get_bit(i)) {
+            (true, true) => res.add_assign_mixed(&p12),
+            (true, false) => res.add_assign_mixed(p1),
+            (false, true) => res.add_assign_mixed(p2),
+            (false, false) => {},
+        }
+    }
+    res
+}
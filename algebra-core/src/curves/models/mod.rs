@@ -3,10 +3,12 @@ use crate::fields::{Field, PrimeField, SquareRootField};
 pub mod bls12;
 pub mod bn;
 pub mod bw6;
+pub mod glv;
 pub mod mnt4;
 pub mod mnt6;
 pub mod short_weierstrass_jacobian;
 pub mod short_weierstrass_projective;
+pub mod subgroup;
 pub mod twisted_edwards_extended;
 
 pub trait ModelParameters: Send + Sync + 'static {
@@ -21,6 +23,13 @@ pub trait SWModelParameters: ModelParameters {
     const COFACTOR_INV: Self::ScalarField;
     const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField);
 
+    /// Parameters of the GLV endomorphism, for curves that admit one.
+    const GLV: Option<glv::GLVParameters<Self>> = None;
+
+    /// Parameters of an endomorphism that allows a fast check of membership
+    /// in the prime order subgroup, for curves that admit one.
+    const SUBGROUP_CHECK: Option<subgroup::SubgroupCheckParameters<Self>> = None;
+
     #[inline(always)]
     fn mul_by_a(elem: &Self::BaseField) -> Self::BaseField {
         let mut copy = *elem;
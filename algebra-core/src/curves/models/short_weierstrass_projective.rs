@@ -18,8 +18,8 @@ use rand::{
 
 use crate::{
     bytes::{FromBytes, ToBytes},
-    curves::{AffineCurve, ProjectiveCurve},
-    fields::{BitIterator, Field, PrimeField, SquareRootField},
+    curves::{models::glv::glv_mul, AffineCurve, ProjectiveCurve},
+    fields::{batch_inversion, BitIterator, Field, PrimeField, SquareRootField},
 };
 
 #[derive(Derivative)]
@@ -110,8 +110,19 @@ impl<P: Parameters> GroupAffine<P> {
     /// Checks that the current point is in the prime order subgroup given
     /// the point on the curve.
     pub fn is_in_correct_subgroup_assuming_on_curve(&self) -> bool {
-        self.mul_bits(BitIterator::new(P::ScalarField::characteristic()))
-            .is_zero()
+        if self.is_zero() || P::COFACTOR == [1] {
+            // Every point of a curve of prime order lies in the subgroup.
+            true
+        } else if let Some(check) = P::SUBGROUP_CHECK {
+            let (t, is_negative) = check.eigenvalue;
+            let t_p = self.mul_bits(BitIterator::new(t));
+            let t_p = if is_negative { -t_p } else { t_p };
+            let (x, y) = check.psi(&self.x, &self.y);
+            t_p == Self::new(x, y, false).into_projective()
+        } else {
+            self.mul_bits(BitIterator::new(P::ScalarField::characteristic()))
+                .is_zero()
+        }
     }
 }
 
@@ -172,8 +183,40 @@ impl<P: Parameters> AffineCurve for GroupAffine<P> {
     }
 
     fn mul<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(&self, by: S) -> GroupProjective<P> {
-        let bits = BitIterator::new(by.into());
-        self.mul_bits(bits)
+        // The endomorphism only acts as multiplication by `lambda` on the
+        // prime order subgroup, which is the whole curve only if the cofactor
+        // is one.
+        if P::COFACTOR == [1] {
+            self.mul_subgroup(by)
+        } else {
+            self.mul_bits(BitIterator::new(by.into()))
+        }
+    }
+
+    fn mul_subgroup<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(
+        &self,
+        by: S,
+    ) -> GroupProjective<P> {
+        let by = by.into();
+        match self.glv_split(&by) {
+            Some(((p1, k1), (p2, k2))) => glv_mul(&p1, &k1, &p2, &k2),
+            None => self.mul_bits(BitIterator::new(by)),
+        }
+    }
+
+    fn glv_split(
+        &self,
+        scalar: &<Self::ScalarField as PrimeField>::BigInt,
+    ) -> Option<(
+        (Self, <Self::ScalarField as PrimeField>::BigInt),
+        (Self, <Self::ScalarField as PrimeField>::BigInt),
+    )> {
+        let glv = P::GLV?;
+        let ((k1_neg, k1), (k2_neg, k2)) = glv.decompose(scalar)?;
+        let p1 = if k1_neg { -*self } else { *self };
+        let p2 = Self::new(self.x * &glv.omega, self.y, self.infinity);
+        let p2 = if k2_neg { -p2 } else { p2 };
+        Some(((p1, k1), (p2, k2)))
     }
 
     #[inline]
@@ -184,6 +227,59 @@ impl<P: Parameters> AffineCurve for GroupAffine<P> {
     fn mul_by_cofactor_inv(&self) -> Self {
         self.mul(P::COFACTOR_INV).into()
     }
+
+    fn batch_add_in_place(bases: &mut [Self], index: &[(usize, usize)]) {
+        // Compute the denominators of the slopes, leaving zero for the pairs
+        // whose sum does not require an inversion.
+        let mut denominators = index
+            .iter()
+            .map(|&(i, j)| {
+                let (a, b) = (&bases[i], &bases[j]);
+                if a.is_zero() || b.is_zero() {
+                    P::BaseField::zero()
+                } else if a.x == b.x {
+                    if a.y == b.y {
+                        a.y.double()
+                    } else {
+                        P::BaseField::zero()
+                    }
+                } else {
+                    b.x - &a.x
+                }
+            })
+            .collect::<Vec<_>>();
+
+        batch_inversion(&mut denominators);
+
+        for (&(i, j), inverse) in index.iter().zip(&denominators) {
+            let b = bases[j];
+            let a = &mut bases[i];
+            if b.is_zero() {
+                continue;
+            } else if a.is_zero() {
+                *a = b;
+                continue;
+            }
+
+            let lambda = if a.x == b.x {
+                if a.y != b.y || a.y.is_zero() {
+                    // `b = -a`, or `a` is a point of order two.
+                    *a = Self::zero();
+                    continue;
+                }
+                // lambda = (3 * x^2 + a) / (2 * y)
+                let x2 = a.x.square();
+                (x2.double() + &x2 + &P::COEFF_A) * inverse
+            } else {
+                // lambda = (y2 - y1) / (x2 - x1)
+                (b.y - &a.y) * inverse
+            };
+
+            let x3 = lambda.square() - &a.x - &b.x;
+            a.y = lambda * &(a.x - &x3) - &a.y;
+            a.x = x3;
+        }
+    }
 }
 
 impl<P: Parameters> Neg for GroupAffine<P> {
@@ -425,6 +521,23 @@ impl<P: Parameters> ProjectiveCurve for GroupProjective<P> {
         }
     }
 
+    /// Performs scalar multiplication of this element, using the GLV
+    /// endomorphism of the curve if it applies to every point of the curve.
+    #[inline]
+    fn mul<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(self, other: S) -> Self {
+        if P::GLV.is_some() && P::COFACTOR == [1] {
+            return GroupAffine::from(self).mul(other);
+        }
+        let mut res = Self::zero();
+        for bit in BitIterator::new(other.into()) {
+            res.double_in_place();
+            if bit {
+                res += &self;
+            }
+        }
+        res
+    }
+
     fn add_assign_mixed(&mut self, other: &GroupAffine<P>) {
         if other.is_zero() {
             return;
@@ -0,0 +1,31 @@
+use crate::{curves::models::ModelParameters, fields::Field};
+
+/// Parameters of an endomorphism `psi: (x, y) -> (c_x * x^q, c_y * y^q)` of a
+/// short Weierstrass curve over a field of characteristic `q`, which acts as
+/// multiplication by a small integer `t` on the prime order subgroup.
+///
+/// For the curves on which these are set, a point on the curve lies in the
+/// prime order subgroup if and only if `psi(P) = [t]P`, which is much cheaper
+/// to check than `[r]P = 0` since `t` is at most half the bit length of `r`.
+/// See Scott, "A note on group membership tests for G1, G2 and GT on BLS
+/// pairing-friendly curves", and Bowe, "Faster subgroup checks for
+/// BLS12-381".
+pub struct SubgroupCheckParameters<P: ModelParameters + ?Sized> {
+    /// The coefficient `c_x` of the endomorphism.
+    pub coeff_x: P::BaseField,
+    /// The coefficient `c_y` of the endomorphism.
+    pub coeff_y: P::BaseField,
+    /// The absolute value of `t`, together with whether it is negative.
+    pub eigenvalue: (&'static [u64], bool),
+}
+
+impl<P: ModelParameters + ?Sized> SubgroupCheckParameters<P> {
+    /// Applies the endomorphism to the coordinates `(x, y)` of a point.
+    pub fn psi(&self, x: &P::BaseField, y: &P::BaseField) -> (P::BaseField, P::BaseField) {
+        let mut x = *x;
+        let mut y = *y;
+        x.frobenius_map(1);
+        y.frobenius_map(1);
+        (x * &self.coeff_x, y * &self.coeff_y)
+    }
+}
@@ -23,7 +23,7 @@ use crate::{
         },
         AffineCurve, ProjectiveCurve,
     },
-    fields::{BitIterator, Field, PrimeField, SquareRootField},
+    fields::{batch_inversion, BitIterator, Field, PrimeField, SquareRootField},
 };
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
@@ -163,6 +163,32 @@ impl<P: Parameters> AffineCurve for GroupAffine<P> {
     fn mul_by_cofactor_inv(&self) -> Self {
         self.mul(P::COFACTOR_INV).into()
     }
+
+    fn batch_add_in_place(bases: &mut [Self], index: &[(usize, usize)]) {
+        // The addition law is complete, so every pair contributes the two
+        // denominators `1 + d * x1 * x2 * y1 * y2` and `1 - d * x1 * x2 * y1 * y2`.
+        let mut denominators = Vec::with_capacity(2 * index.len());
+        for &(i, j) in index {
+            let (a, b) = (&bases[i], &bases[j]);
+            let dx1x2y1y2 = P::COEFF_D * &(a.x * &b.x) * &(a.y * &b.y);
+            denominators.push(P::BaseField::one() + &dx1x2y1y2);
+            denominators.push(P::BaseField::one() - &dx1x2y1y2);
+        }
+
+        batch_inversion(&mut denominators);
+
+        for (&(i, j), inverses) in index.iter().zip(denominators.chunks_exact(2)) {
+            let b = bases[j];
+            let a = &mut bases[i];
+            let y1y2 = a.y * &b.y;
+            let x1x2 = a.x * &b.x;
+            let x1y2 = a.x * &b.y;
+            let y1x2 = a.y * &b.x;
+
+            a.x = (x1y2 + &y1x2) * &inverses[0];
+            a.y = (y1y2 - &P::mul_by_a(&x1x2)) * &inverses[1];
+        }
+    }
 }
 
 impl<P: Parameters> Neg for GroupAffine<P> {
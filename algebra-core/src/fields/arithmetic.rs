@@ -69,7 +69,11 @@ macro_rules! impl_field_mul_assign {
                     _carry2 = carry;
                 }
                 (self.0).0.copy_from_slice(&r[$limbs..]);
-                self.reduce();
+                // A final carry means the result wrapped around the backing
+                // capacity, which the subtraction of the modulus undoes.
+                if _carry2 != 0 || !self.is_valid() {
+                    self.0.sub_noborrow(&P::MODULUS);
+                }
             }
         }
     };
@@ -169,7 +173,10 @@ macro_rules! impl_field_square_in_place {
                 _carry2 = carry;
             }
             (self.0).0.copy_from_slice(&r[$limbs..]);
-            self.reduce();
+            // See `mul_assign` for why a final carry is handled here.
+            if _carry2 != 0 || !self.is_valid() {
+                self.0.sub_noborrow(&P::MODULUS);
+            }
             self
         }
     };
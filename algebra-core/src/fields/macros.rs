@@ -74,10 +74,14 @@ macro_rules! impl_Fp {
 
             #[inline]
             fn double_in_place(&mut self) -> &mut Self {
-                // This cannot exceed the backing capacity.
+                // This can only exceed the backing capacity if the modulus
+                // uses its top bit, in which case the wrapped-around result
+                // is fixed up by the subtraction below.
+                let carry = (self.0).0[$limbs - 1] >> 63 == 1;
                 self.0.mul2();
-                // However, it may need to be reduced.
-                self.reduce();
+                if carry || !self.is_valid() {
+                    self.0.sub_noborrow(&P::MODULUS);
+                }
                 self
             }
 
@@ -93,18 +97,24 @@ macro_rules! impl_Fp {
                     *result_byte = *in_byte;
                 }
 
-                let mask: u64 = 0xffffffffffffffff >> P::REPR_SHAVE_BITS;
-                // the flags will be at the same byte with the lowest shaven bits or the one after
-                let flags_byte_position: usize = 7 - P::REPR_SHAVE_BITS as usize / 8;
-                let flags_mask: u8 = ((1 << P::REPR_SHAVE_BITS % 8) - 1) << (8 - P::REPR_SHAVE_BITS % 8);
-                // take the last 8 bytes and pass the mask
-                let last_bytes = &mut result_bytes[($limbs - 1) * 8..];
                 let mut flags: u8 = 0;
-                for (i, (b, m)) in last_bytes.iter_mut().zip(&mask.to_le_bytes()).enumerate() {
-                    if i == flags_byte_position {
-                        flags = *b & flags_mask
+                if P::REPR_SHAVE_BITS == 0 {
+                    // A modulus that fills its limbs leaves no room for flags,
+                    // so they live in the byte right after it.
+                    flags = bytes.get($limbs * 8).copied().unwrap_or(0);
+                } else {
+                    let mask: u64 = 0xffffffffffffffff >> P::REPR_SHAVE_BITS;
+                    // the flags will be at the same byte with the lowest shaven bits or the one after
+                    let flags_byte_position: usize = 7 - P::REPR_SHAVE_BITS as usize / 8;
+                    let flags_mask: u8 = ((1 << P::REPR_SHAVE_BITS % 8) - 1) << (8 - P::REPR_SHAVE_BITS % 8);
+                    // take the last 8 bytes and pass the mask
+                    let last_bytes = &mut result_bytes[($limbs - 1) * 8..];
+                    for (i, (b, m)) in last_bytes.iter_mut().zip(&mask.to_le_bytes()).enumerate() {
+                        if i == flags_byte_position {
+                            flags = *b & flags_mask
+                        }
+                        *b &= m;
                     }
-                    *b &= m;
                 }
 
                 Self::deserialize(&mut &result_bytes[..]).ok().map(|f| (f, flags))
@@ -143,8 +153,13 @@ macro_rules! impl_Fp {
                             if b.0.is_even() {
                                 b.0.div2();
                             } else {
-                                b.0.add_nocarry(&P::MODULUS);
+                                // Keep the bit that falls off the top when the
+                                // modulus uses all of the backing capacity.
+                                let carry = b.0.add_nocarry(&P::MODULUS);
                                 b.0.div2();
+                                if carry {
+                                    (b.0).0[$limbs - 1] |= 1 << 63;
+                                }
                             }
                         }
 
@@ -154,8 +169,13 @@ macro_rules! impl_Fp {
                             if c.0.is_even() {
                                 c.0.div2();
                             } else {
-                                c.0.add_nocarry(&P::MODULUS);
+                                // Keep the bit that falls off the top when the
+                                // modulus uses all of the backing capacity.
+                                let carry = c.0.add_nocarry(&P::MODULUS);
                                 c.0.div2();
+                                if carry {
+                                    (c.0).0[$limbs - 1] |= 1 << 63;
+                                }
                             }
                         }
 
@@ -419,10 +439,13 @@ macro_rules! impl_Fp {
         impl<'a, P: $FpParameters> AddAssign<&'a Self> for $Fp<P> {
             #[inline]
             fn add_assign(&mut self, other: &Self) {
-                // This cannot exceed the backing capacity.
-                self.0.add_nocarry(&other.0);
-                // However, it may need to be reduced
-                self.reduce();
+                // This can only exceed the backing capacity if the modulus
+                // uses its top bit; the wrapped-around sum is then fixed up by
+                // subtracting the modulus.
+                let carry = self.0.add_nocarry(&other.0);
+                if carry || !self.is_valid() {
+                    self.0.sub_noborrow(&P::MODULUS);
+                }
             }
         }
 
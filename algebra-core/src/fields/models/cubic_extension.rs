@@ -491,6 +491,8 @@ impl<P: CubicExtParameters> fmt::Display for CubicExtField<P> {
 }
 
 impl<P: CubicExtParameters> CanonicalSerializeWithFlags for CubicExtField<P> {
+    const SPARE_BITS: usize = <P::BaseField as CanonicalSerializeWithFlags>::SPARE_BITS;
+
     #[inline]
     fn serialize_with_flags<W: Write, F: Flags>(
         &self,
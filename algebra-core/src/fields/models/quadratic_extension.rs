@@ -486,6 +486,8 @@ impl<P: QuadExtParameters> fmt::Display for QuadExtField<P> {
 }
 
 impl<P: QuadExtParameters> CanonicalSerializeWithFlags for QuadExtField<P> {
+    const SPARE_BITS: usize = <P::BaseField as CanonicalSerializeWithFlags>::SPARE_BITS;
+
     #[inline]
     fn serialize_with_flags<W: Write, F: Flags>(
         &self,
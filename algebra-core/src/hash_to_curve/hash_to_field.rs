@@ -0,0 +1,124 @@
+use crate::{
+    biginteger::BigInteger,
+    fields::{Field, Fp2, Fp2Parameters, FpParameters, PrimeField},
+    Vec, Zero,
+};
+use digest::{generic_array::typenum::Unsigned, BlockInput, Digest};
+
+/// Fields onto which uniformly random byte strings can be hashed, as in
+/// section 5 of the draft.
+pub trait HashToField: Field {
+    /// The number of uniformly random bytes from which a single element is
+    /// derived, that is `m * L` in the notation of the draft.
+    const UNIFORM_BYTES_LEN: usize;
+
+    /// Derives an element from `Self::UNIFORM_BYTES_LEN` uniformly random
+    /// bytes, with a statistical distance of at most `2^-128` from uniform.
+    fn from_uniform_bytes(bytes: &[u8]) -> Self;
+
+    /// Returns the "sign" of the element, as defined in section 4.1 of the
+    /// draft.
+    fn sgn0(&self) -> bool;
+}
+
+impl<F: PrimeField> HashToField for F {
+    /// `L = ceil((ceil(log2(p)) + k) / 8)`, for a security level of `k = 128`
+    /// bits.
+    const UNIFORM_BYTES_LEN: usize = (<F::Params as FpParameters>::MODULUS_BITS as usize + 135) / 8;
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        // Interpret the bytes as a big-endian integer, reduced modulo `p`.
+        let radix = F::from(256u64);
+        bytes
+            .iter()
+            .fold(F::zero(), |acc, byte| acc * &radix + &F::from(*byte))
+    }
+
+    fn sgn0(&self) -> bool {
+        self.into_repr().is_odd()
+    }
+}
+
+impl<P: Fp2Parameters> HashToField for Fp2<P> {
+    const UNIFORM_BYTES_LEN: usize = 2 * <P::Fp as HashToField>::UNIFORM_BYTES_LEN;
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        let (c0, c1) = bytes.split_at(<P::Fp as HashToField>::UNIFORM_BYTES_LEN);
+        Fp2::new(P::Fp::from_uniform_bytes(c0), P::Fp::from_uniform_bytes(c1))
+    }
+
+    fn sgn0(&self) -> bool {
+        self.c0.sgn0() || (self.c0.is_zero() && self.c1.sgn0())
+    }
+}
+
+/// Hashes `msg` to `count` field elements, under the domain separation tag
+/// `dst`, as in section 5.3 of the draft.
+pub fn hash_to_field<F: HashToField, H: Digest + BlockInput>(
+    msg: &[u8],
+    dst: &[u8],
+    count: usize,
+) -> Vec<F> {
+    let uniform_bytes = expand_message_xmd::<H>(msg, dst, count * F::UNIFORM_BYTES_LEN);
+    uniform_bytes
+        .chunks(F::UNIFORM_BYTES_LEN)
+        .map(F::from_uniform_bytes)
+        .collect()
+}
+
+/// Expands `msg` into `len_in_bytes` uniformly random bytes under the domain
+/// separation tag `dst`, using the hash function `H`, as in section 5.4.1 of
+/// the draft.
+///
+/// # Panics
+///
+/// If `len_in_bytes` is larger than `255` times the output size of `H`.
+pub fn expand_message_xmd<H: Digest + BlockInput>(
+    msg: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Vec<u8> {
+    let b_in_bytes = H::OutputSize::to_usize();
+    let r_in_bytes = H::BlockSize::to_usize();
+    let ell = (len_in_bytes + b_in_bytes - 1) / b_in_bytes;
+    assert!(
+        ell <= 255,
+        "requested too many bytes from expand_message_xmd"
+    );
+
+    // Tags longer than 255 bytes are replaced by their hash, as in section
+    // 5.4.3 of the draft.
+    let mut dst_prime = if dst.len() > 255 {
+        H::new()
+            .chain(b"H2C-OVERSIZE-DST-")
+            .chain(dst)
+            .result()
+            .to_vec()
+    } else {
+        dst.to_vec()
+    };
+    dst_prime.push(dst_prime.len() as u8);
+
+    let b_0 = H::new()
+        .chain(vec![0u8; r_in_bytes])
+        .chain(msg)
+        .chain((len_in_bytes as u16).to_be_bytes())
+        .chain([0u8])
+        .chain(&dst_prime)
+        .result();
+
+    let mut b_i = H::new().chain(&b_0).chain([1u8]).chain(&dst_prime).result();
+    let mut uniform_bytes = Vec::with_capacity(ell * b_in_bytes);
+    uniform_bytes.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let b_0_xor_b_i: Vec<u8> = b_0.iter().zip(&b_i).map(|(a, b)| a ^ b).collect();
+        b_i = H::new()
+            .chain(b_0_xor_b_i)
+            .chain([i as u8])
+            .chain(&dst_prime)
+            .result();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    uniform_bytes
+}
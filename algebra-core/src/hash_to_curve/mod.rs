@@ -0,0 +1,146 @@
+//! Hashing to elliptic curves, as specified in the IETF draft "Hashing to
+//! Elliptic Curves" (draft-irtf-cfrg-hash-to-curve).
+//!
+//! A message is first hashed to two field elements with `expand_message_xmd`.
+//! Each of them is mapped with the simplified SWU map to a curve `E'` that is
+//! isogenous to the target curve `E`, and then carried over to `E` by the
+//! isogeny. The sum of the two resulting points is finally multiplied by the
+//! effective cofactor, so that the output lies in the prime order subgroup.
+
+use crate::{
+    curves::{
+        models::{short_weierstrass_jacobian::GroupAffine, SWModelParameters},
+        AffineCurve, ProjectiveCurve,
+    },
+    fields::{BitIterator, Field, SquareRootField},
+    One, Zero,
+};
+use digest::{BlockInput, Digest};
+
+mod hash_to_field;
+pub use self::hash_to_field::*;
+
+/// Parameters for hashing to a short Weierstrass curve `E` with the
+/// simplified SWU map to an isogenous curve `E': y^2 = x^3 + A' * x + B'`,
+/// as in section 6.6.3 of the draft.
+pub trait SWUParameters: SWModelParameters {
+    /// The identifier of the curve in the suite IDs of the draft, such as
+    /// `BLS12381G1`.
+    const CURVE_ID: &'static str;
+
+    /// The coefficient `A'` of `E'`, which must be nonzero.
+    const ISO_COEFF_A: Self::BaseField;
+    /// The coefficient `B'` of `E'`, which must be nonzero.
+    const ISO_COEFF_B: Self::BaseField;
+    /// The non-square `Z` of the simplified SWU map.
+    const ZETA: Self::BaseField;
+
+    /// The isogeny `E' -> E` maps `(x, y)` to
+    /// `(x_num(x) / x_den(x), y * y_num(x) / y_den(x))`. Its polynomials are
+    /// given by their coefficients, in increasing order of degree.
+    const ISOGENY_X_NUM: &'static [Self::BaseField];
+    /// See `ISOGENY_X_NUM`.
+    const ISOGENY_X_DEN: &'static [Self::BaseField];
+    /// See `ISOGENY_X_NUM`.
+    const ISOGENY_Y_NUM: &'static [Self::BaseField];
+    /// See `ISOGENY_X_NUM`.
+    const ISOGENY_Y_DEN: &'static [Self::BaseField];
+
+    /// The effective cofactor `h_eff`, multiplication by which maps points of
+    /// `E` to the prime order subgroup.
+    const H_EFF: &'static [u64];
+}
+
+/// Curves onto which messages can be hashed.
+pub trait HashToCurve: AffineCurve {
+    /// The identifier of the curve in the suite IDs of the draft, such as
+    /// `BLS12381G1`.
+    const CURVE_ID: &'static str;
+
+    /// Hashes `msg` to a point of the prime order subgroup, under the domain
+    /// separation tag `dst`, using the hash function `H` in
+    /// `expand_message_xmd`. This is the `hash_to_curve` operation of the
+    /// draft.
+    fn hash_to_curve<H: Digest + BlockInput>(msg: &[u8], dst: &[u8]) -> Self;
+}
+
+impl<P: SWUParameters> HashToCurve for GroupAffine<P>
+where
+    P::BaseField: HashToField,
+{
+    const CURVE_ID: &'static str = P::CURVE_ID;
+
+    fn hash_to_curve<H: Digest + BlockInput>(msg: &[u8], dst: &[u8]) -> Self {
+        let u = hash_to_field::<P::BaseField, H>(msg, dst, 2);
+        let q0 = map_to_curve::<P>(u[0]);
+        let q1 = map_to_curve::<P>(u[1]);
+        let r = q0.into_projective().add_mixed(&q1).into_affine();
+        r.mul_bits(BitIterator::new(P::H_EFF)).into_affine()
+    }
+}
+
+/// Maps a field element to a point of `E`, by composing the simplified SWU
+/// map to `E'` with the isogeny `E' -> E`. The resulting point need not lie
+/// in the prime order subgroup.
+pub fn map_to_curve<P: SWUParameters>(u: P::BaseField) -> GroupAffine<P>
+where
+    P::BaseField: HashToField,
+{
+    let (x, y) = simplified_swu::<P>(u);
+    iso_map::<P>(&x, &y)
+}
+
+/// The simplified SWU map to `E'`, as in section 6.6.2 of the draft.
+fn simplified_swu<P: SWUParameters>(u: P::BaseField) -> (P::BaseField, P::BaseField)
+where
+    P::BaseField: HashToField,
+{
+    let a = P::ISO_COEFF_A;
+    let b = P::ISO_COEFF_B;
+    let g = |x: &P::BaseField| (x.square() + &a) * x + &b;
+
+    // x1 = (-B / A) * (1 + 1 / (Z^2 * u^4 + Z * u^2)), or B / (Z * A) in the
+    // exceptional case where the denominator is zero.
+    let z_u2 = P::ZETA * &u.square();
+    let x1 = match (z_u2.square() + &z_u2).inverse() {
+        Some(tv1) => -b * &a.inverse().unwrap() * &(P::BaseField::one() + &tv1),
+        None => b * &(P::ZETA * &a).inverse().unwrap(),
+    };
+
+    // Exactly one of g(x1) and g(Z * u^2 * x1) = Z^3 * u^6 * g(x1) is a
+    // square, since Z is not.
+    let (x, y) = match g(&x1).sqrt() {
+        Some(y1) => (x1, y1),
+        None => {
+            let x2 = z_u2 * &x1;
+            let y2 = g(&x2).sqrt().expect("Z * g(x1) is a square");
+            (x2, y2)
+        },
+    };
+
+    let y = if u.sgn0() != y.sgn0() { -y } else { y };
+    (x, y)
+}
+
+/// The isogeny `E' -> E`, which maps the exceptional points at which its
+/// denominators vanish to the identity.
+fn iso_map<P: SWUParameters>(x: &P::BaseField, y: &P::BaseField) -> GroupAffine<P> {
+    let evaluate = |coeffs: &[P::BaseField]| {
+        coeffs
+            .iter()
+            .rev()
+            .fold(P::BaseField::zero(), |acc, c| acc * x + c)
+    };
+
+    match (
+        evaluate(P::ISOGENY_X_DEN).inverse(),
+        evaluate(P::ISOGENY_Y_DEN).inverse(),
+    ) {
+        (Some(x_den_inv), Some(y_den_inv)) => GroupAffine::new(
+            evaluate(P::ISOGENY_X_NUM) * &x_den_inv,
+            evaluate(P::ISOGENY_Y_NUM) * y * &y_den_inv,
+            false,
+        ),
+        _ => GroupAffine::zero(),
+    }
+}
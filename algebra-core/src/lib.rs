@@ -82,6 +82,8 @@ pub use to_field_vec::ToConstraintField;
 pub mod msm;
 pub use self::msm::*;
 
+pub mod hash_to_curve;
+
 pub use num_traits::{One, Zero};
 
 pub mod prelude {
@@ -1,7 +1,8 @@
 use crate::{
-    prelude::{AffineCurve, BigInteger, FpParameters, One, PrimeField, ProjectiveCurve, Zero},
+    prelude::{AffineCurve, BigInteger, PrimeField, ProjectiveCurve, Zero},
     Vec,
 };
+use core::cmp::Ordering;
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
@@ -15,65 +16,71 @@ impl VariableBaseMSM {
     where
         G::Projective: ProjectiveCurve<Affine = G>,
     {
-        let c = if scalars.len() < 32 {
+        let size = core::cmp::min(bases.len(), scalars.len());
+        let c = if size < 32 {
             3
         } else {
-            super::ln_without_floats(scalars.len()) + 2
+            super::ln_without_floats(size) + 2
         };
 
-        let num_bits = <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
-        let fr_one = G::ScalarField::one().into_repr();
+        let num_bits = scalars[..size]
+            .iter()
+            .map(|s| s.num_bits() as usize)
+            .max()
+            .unwrap_or(0);
+        // Recentering the digits carries into the window above, so the top
+        // window must be small enough to absorb the final carry without
+        // carrying itself. Covering `num_bits + 2` bits guarantees this.
+        let num_windows = (num_bits + 1) / c + 1;
+
+        #[cfg(feature = "parallel")]
+        let scalars_iter = scalars[..size].par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let scalars_iter = scalars[..size].iter();
+
+        let digits: Vec<_> = scalars_iter
+            .map(|s| make_digits(s, c, num_windows))
+            .collect();
 
         let zero = G::Projective::zero();
-        let window_starts: Vec<_> = (0..num_bits).step_by(c).collect();
+        let windows: Vec<_> = (0..num_windows).collect();
 
         #[cfg(feature = "parallel")]
-        let window_starts_iter = window_starts.into_par_iter();
+        let windows_iter = windows.into_par_iter();
         #[cfg(not(feature = "parallel"))]
-        let window_starts_iter = window_starts.into_iter();
+        let windows_iter = windows.into_iter();
 
         // Each window is of size `c`.
         // We divide up the bits 0..num_bits into windows of size `c`, and
         // in parallel process each such window.
-        let window_sums: Vec<_> = window_starts_iter
-            .map(|w_start| {
+        let window_sums: Vec<_> = windows_iter
+            .map(|w| {
+                // Digits lie in [-2^(c - 1), 2^(c - 1)), so by folding the sign
+                // into the base we only need 2^(c - 1) buckets (the "zero"
+                // bucket is again skipped).
+                let mut elems = Vec::with_capacity(size);
+                let mut bucket_index = Vec::with_capacity(size);
+                for (d, base) in digits.iter().zip(bases) {
+                    let digit = d[w];
+                    match digit.cmp(&0) {
+                        Ordering::Greater => {
+                            elems.push(*base);
+                            bucket_index.push((digit - 1) as usize);
+                        },
+                        Ordering::Less => {
+                            elems.push(-*base);
+                            bucket_index.push((-digit - 1) as usize);
+                        },
+                        Ordering::Equal => {},
+                    }
+                }
+                let buckets = batch_bucketed_add(1 << (c - 1), &mut elems, &bucket_index);
+
                 let mut res = zero;
-                // We don't need the "zero" bucket, so we only have 2^c - 1 buckets
-                let mut buckets = vec![zero; (1 << c) - 1];
-                scalars
-                    .iter()
-                    .zip(bases)
-                    .filter(|(s, _)| !s.is_zero())
-                    .for_each(|(&scalar, base)| {
-                        if scalar == fr_one {
-                            // We only process unit scalars once in the first window.
-                            if w_start == 0 {
-                                res.add_assign_mixed(base);
-                            }
-                        } else {
-                            let mut scalar = scalar;
-
-                            // We right-shift by w_start, thus getting rid of the
-                            // lower bits.
-                            scalar.divn(w_start as u32);
-
-                            // We mod the remaining bits by the window size.
-                            let scalar = scalar.as_ref()[0] % (1 << c);
-
-                            // If the scalar is non-zero, we update the corresponding
-                            // bucket.
-                            // (Recall that `buckets` doesn't have a zero bucket.)
-                            if scalar != 0 {
-                                buckets[(scalar - 1) as usize].add_assign_mixed(base);
-                            }
-                        }
-                    });
-                let buckets = G::Projective::batch_normalization_into_affine(&buckets);
-
-                let mut running_sum = G::Projective::zero();
-                for b in buckets.into_iter().rev() {
-                    running_sum.add_assign_mixed(&b);
-                    res += running_sum;
+                let mut running_sum = zero;
+                for b in buckets.iter().rev() {
+                    running_sum.add_assign_mixed(b);
+                    res += &running_sum;
                 }
 
                 res
@@ -101,6 +108,112 @@ impl VariableBaseMSM {
         bases: &[G],
         scalars: &[<G::ScalarField as PrimeField>::BigInt],
     ) -> G::Projective {
-        Self::msm_inner(bases, scalars)
+        // The endomorphism only acts as multiplication by `lambda` on the
+        // prime order subgroup, which is the whole curve only if the cofactor
+        // is one.
+        if G::COFACTOR == [1] {
+            Self::multi_scalar_mul_subgroup(bases, scalars)
+        } else {
+            Self::msm_inner(bases, scalars)
+        }
     }
+
+    /// Like `multi_scalar_mul`, for `bases` which all lie in the prime order
+    /// subgroup, as in `AffineCurve::mul_subgroup`. If the curve has an
+    /// efficient endomorphism, every term is traded for two terms with
+    /// scalars of half the length.
+    pub fn multi_scalar_mul_subgroup<G: AffineCurve>(
+        bases: &[G],
+        scalars: &[<G::ScalarField as PrimeField>::BigInt],
+    ) -> G::Projective {
+        #[cfg(feature = "parallel")]
+        let split_iter = bases.par_iter().zip(scalars);
+        #[cfg(not(feature = "parallel"))]
+        let split_iter = bases.iter().zip(scalars);
+
+        let split: Option<Vec<_>> = split_iter.map(|(b, s)| b.glv_split(s)).collect();
+        match split {
+            Some(split) => {
+                let (bases, scalars): (Vec<_>, Vec<_>) = split
+                    .into_iter()
+                    .flat_map(|(t1, t2)| core::iter::once(t1).chain(core::iter::once(t2)))
+                    .unzip();
+                Self::msm_inner(&bases, &scalars)
+            },
+            None => Self::msm_inner(bases, scalars),
+        }
+    }
+}
+
+/// Decomposes `scalar` into `num_windows` signed digits in base `2^w`, each
+/// lying in `[-2^(w - 1), 2^(w - 1))`, such that
+/// `scalar = sum_i digits[i] * 2^(w * i)`.
+fn make_digits<B: BigInteger>(scalar: &B, w: usize, num_windows: usize) -> Vec<i64> {
+    let limbs = scalar.as_ref();
+    let radix: u64 = 1 << w;
+    let window_mask: u64 = radix - 1;
+
+    let mut carry = 0u64;
+    (0..num_windows)
+        .map(|i| {
+            // Read the `w` bits of the scalar starting at `bit_offset`, which
+            // may straddle two limbs.
+            let bit_offset = i * w;
+            let u64_idx = bit_offset / 64;
+            let bit_idx = bit_offset % 64;
+            let bit_buf = if u64_idx >= limbs.len() {
+                0
+            } else if bit_idx + w <= 64 || u64_idx == limbs.len() - 1 {
+                limbs[u64_idx] >> bit_idx
+            } else {
+                (limbs[u64_idx] >> bit_idx) | (limbs[u64_idx + 1] << (64 - bit_idx))
+            };
+
+            // Recenter the digit into [-2^(w - 1), 2^(w - 1)), carrying the
+            // excess into the next window.
+            let coef = carry + (bit_buf & window_mask);
+            carry = (coef + radix / 2) >> w;
+            coef as i64 - (carry << w) as i64
+        })
+        .collect()
+}
+
+/// Sums `elems` into `num_buckets` buckets, where `elems[i]` belongs to bucket
+/// `bucket_index[i]`. The elements of every bucket are added pairwise in
+/// rounds, and each round is performed with batched affine additions so that
+/// it costs a single field inversion across all buckets.
+fn batch_bucketed_add<G: AffineCurve>(
+    num_buckets: usize,
+    elems: &mut [G],
+    bucket_index: &[usize],
+) -> Vec<G> {
+    // For every bucket, the positions in `elems` of its partial sums.
+    let mut pending = vec![Vec::new(); num_buckets];
+    for (i, &b) in bucket_index.iter().enumerate() {
+        pending[b].push(i);
+    }
+
+    let mut index = Vec::new();
+    loop {
+        index.clear();
+        for p in pending.iter_mut().filter(|p| p.len() > 1) {
+            // Add every odd position into the even one preceding it, and keep
+            // only the even positions for the next round.
+            index.extend(p.chunks_exact(2).map(|pair| (pair[0], pair[1])));
+            let kept = (p.len() + 1) / 2;
+            for k in 0..kept {
+                p[k] = p[2 * k];
+            }
+            p.truncate(kept);
+        }
+        if index.is_empty() {
+            break;
+        }
+        G::batch_add_in_place(elems, &index);
+    }
+
+    pending
+        .iter()
+        .map(|p| p.first().map_or_else(G::zero, |&i| elems[i]))
+        .collect()
 }
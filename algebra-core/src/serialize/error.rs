@@ -27,6 +27,23 @@ impl From<io::Error> for SerializationError {
     }
 }
 
+#[cfg(feature = "std")]
+impl From<SerializationError> for io::Error {
+    fn from(e: SerializationError) -> io::Error {
+        match e {
+            SerializationError::IoError(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl From<SerializationError> for io::Error {
+    fn from(_: SerializationError) -> io::Error {
+        io::Error
+    }
+}
+
 impl fmt::Display for SerializationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         match self {
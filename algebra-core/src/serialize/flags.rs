@@ -41,6 +41,9 @@ pub enum SWFlags {
 }
 
 impl SWFlags {
+    /// Number of bits required for these flags, as a constant.
+    pub const BIT_SIZE: usize = 2;
+
     #[inline]
     pub fn infinity() -> Self {
         SWFlags::Infinity
@@ -114,7 +117,7 @@ impl Flags for SWFlags {
     /// Number of bits required for these flags.
     #[inline]
     fn len() -> usize {
-        2
+        Self::BIT_SIZE
     }
 }
 
@@ -127,6 +130,9 @@ pub enum EdwardsFlags {
 }
 
 impl EdwardsFlags {
+    /// Number of bits required for these flags, as a constant.
+    pub const BIT_SIZE: usize = 1;
+
     #[inline]
     pub fn from_y_sign(is_positive: bool) -> Self {
         if is_positive {
@@ -184,6 +190,6 @@ impl Flags for EdwardsFlags {
     /// Number of bits required for these flags.
     #[inline]
     fn len() -> usize {
-        1
+        Self::BIT_SIZE
     }
 }
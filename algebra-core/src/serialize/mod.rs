@@ -16,12 +16,24 @@ use core::convert::TryFrom;
 
 /// Serializer in little endian format allowing to encode flags.
 pub trait CanonicalSerializeWithFlags: CanonicalSerialize {
+    /// Number of unused high bits in the last byte of the serialization,
+    /// which are available to flags.
+    const SPARE_BITS: usize = 0;
+
     /// Serializes `self` and `flags` into `writer`.
     fn serialize_with_flags<W: Write, F: Flags>(
         &self,
         writer: &mut W,
         flags: F,
     ) -> Result<(), SerializationError>;
+
+    /// Serialized size of `self` when `F` is encoded alongside it. This is
+    /// larger than `serialized_size` when the flags do not fit into the
+    /// spare bits.
+    #[inline]
+    fn serialized_size_with_flags<F: Flags>(&self) -> usize {
+        self.serialized_size() + flags_overflow_size(Self::SPARE_BITS, F::len())
+    }
 }
 
 /// Helper trait to get serialized size for constant sized structs.
@@ -102,6 +114,14 @@ pub trait CanonicalDeserialize: Sized {
     fn deserialize_uncompressed<R: Read>(reader: &mut R) -> Result<Self, SerializationError> {
         Self::deserialize(reader)
     }
+
+    /// Reads `Self` from `reader` without compression, and without checking
+    /// that curve points lie on the curve and in the prime order subgroup.
+    /// This is only safe for trusted data, which it reads much faster.
+    #[inline]
+    fn deserialize_unchecked<R: Read>(reader: &mut R) -> Result<Self, SerializationError> {
+        Self::deserialize_uncompressed(reader)
+    }
 }
 
 macro_rules! impl_uint {
@@ -223,6 +243,16 @@ impl<T: CanonicalDeserialize> CanonicalDeserialize for Vec<T> {
         }
         Ok(values)
     }
+
+    #[inline]
+    fn deserialize_unchecked<R: Read>(reader: &mut R) -> Result<Self, SerializationError> {
+        let len = u64::deserialize(reader)?;
+        let mut values = vec![];
+        for _ in 0..len {
+            values.push(T::deserialize_unchecked(reader)?);
+        }
+        Ok(values)
+    }
 }
 
 #[inline]
@@ -236,9 +266,20 @@ pub const fn buffer_byte_size(modulus_bits: usize) -> usize {
     (modulus_bits + 7) / 8
 }
 
+/// Number of extra bytes needed to store `flag_bits` bits of flags after a
+/// serialization with `spare_bits` unused high bits.
+#[inline]
+pub const fn flags_overflow_size(spare_bits: usize, flag_bits: usize) -> usize {
+    buffer_byte_size(flag_bits + 8 - spare_bits) - 1
+}
+
 macro_rules! impl_prime_field_serializer {
     ($field: ident, $params: ident, $byte_size: expr) => {
         impl<P: $params> CanonicalSerializeWithFlags for $field<P> {
+            const SPARE_BITS: usize =
+                8 * crate::serialize::buffer_byte_size(P::MODULUS_BITS as usize)
+                    - P::MODULUS_BITS as usize;
+
             #[allow(unused_qualifications)]
             fn serialize_with_flags<W: crate::io::Write, F: crate::serialize::Flags>(
                 &self,
@@ -247,13 +288,15 @@ macro_rules! impl_prime_field_serializer {
             ) -> Result<(), crate::serialize::SerializationError> {
                 const BYTE_SIZE: usize = $byte_size;
 
-                let (output_bit_size, output_byte_size) =
-                    crate::serialize::buffer_bit_byte_size($field::<P>::size_in_bits());
-                if F::len() > (output_bit_size - P::MODULUS_BITS as usize) {
+                // Flags that do not fit into the spare bits of the last byte
+                // spill over into one extra byte.
+                let output_byte_size =
+                    crate::serialize::buffer_byte_size(P::MODULUS_BITS as usize + F::len());
+                if output_byte_size > BYTE_SIZE + 1 {
                     return Err(crate::serialize::SerializationError::NotEnoughSpace);
                 }
 
-                let mut bytes = [0u8; BYTE_SIZE];
+                let mut bytes = [0u8; BYTE_SIZE + 1];
                 self.write(&mut bytes[..])?;
 
                 bytes[output_byte_size - 1] |= flags.u8_bitmask();
@@ -293,18 +336,18 @@ macro_rules! impl_prime_field_serializer {
             ) -> Result<(Self, F), crate::serialize::SerializationError> {
                 const BYTE_SIZE: usize = $byte_size;
 
-                let (output_bit_size, output_byte_size) =
-                    crate::serialize::buffer_bit_byte_size($field::<P>::size_in_bits());
-                if F::len() > (output_bit_size - P::MODULUS_BITS as usize) {
+                let output_byte_size =
+                    crate::serialize::buffer_byte_size(P::MODULUS_BITS as usize + F::len());
+                if output_byte_size > BYTE_SIZE + 1 {
                     return Err(crate::serialize::SerializationError::NotEnoughSpace);
                 }
 
-                let mut masked_bytes = [0; BYTE_SIZE];
+                let mut masked_bytes = [0; BYTE_SIZE + 1];
                 reader.read_exact(&mut masked_bytes[..output_byte_size])?;
 
                 let flags = F::from_u8_remove_flags(&mut masked_bytes[output_byte_size - 1]);
 
-                Ok((Self::read(&masked_bytes[..])?, flags))
+                Ok((Self::read(&masked_bytes[..BYTE_SIZE])?, flags))
             }
         }
 
@@ -372,11 +415,15 @@ macro_rules! impl_sw_curve_serializer {
             }
         }
 
+        #[allow(unused_qualifications)]
         impl<P: $params> ConstantSerializedSize for GroupAffine<P> {
-            const SERIALIZED_SIZE: usize =
-                <P::BaseField as ConstantSerializedSize>::SERIALIZED_SIZE;
+            const SERIALIZED_SIZE: usize = <P::BaseField as ConstantSerializedSize>::SERIALIZED_SIZE
+                + crate::serialize::flags_overflow_size(
+                    <P::BaseField as crate::serialize::CanonicalSerializeWithFlags>::SPARE_BITS,
+                    crate::serialize::SWFlags::BIT_SIZE,
+                );
             const UNCOMPRESSED_SIZE: usize =
-                2 * <P::BaseField as ConstantSerializedSize>::SERIALIZED_SIZE;
+                <P::BaseField as ConstantSerializedSize>::SERIALIZED_SIZE + Self::SERIALIZED_SIZE;
         }
 
         impl<P: $params> CanonicalDeserialize for GroupAffine<P> {
@@ -407,11 +454,22 @@ macro_rules! impl_sw_curve_serializer {
                     CanonicalDeserializeWithFlags::deserialize_with_flags(reader)?;
 
                 let p = GroupAffine::<P>::new(x, y, flags.is_infinity());
-                if !p.is_in_correct_subgroup_assuming_on_curve() {
+                if !p.is_on_curve() || !p.is_in_correct_subgroup_assuming_on_curve() {
                     return Err(crate::serialize::SerializationError::InvalidData);
                 }
                 Ok(p)
             }
+
+            #[allow(unused_qualifications)]
+            fn deserialize_unchecked<R: crate::io::Read>(
+                reader: &mut R,
+            ) -> Result<Self, crate::serialize::SerializationError> {
+                let x: P::BaseField = CanonicalDeserialize::deserialize(reader)?;
+                let (y, flags): (P::BaseField, crate::serialize::SWFlags) =
+                    CanonicalDeserializeWithFlags::deserialize_with_flags(reader)?;
+
+                Ok(GroupAffine::<P>::new(x, y, flags.is_infinity()))
+            }
         }
     };
 }
@@ -457,9 +515,15 @@ macro_rules! impl_edwards_curve_serializer {
             }
         }
 
+        #[allow(unused_qualifications)]
         impl<P: $params> ConstantSerializedSize for GroupAffine<P> {
-            const SERIALIZED_SIZE: usize =
-                <P::BaseField as ConstantSerializedSize>::SERIALIZED_SIZE;
+            const SERIALIZED_SIZE: usize = <P::BaseField as ConstantSerializedSize>::SERIALIZED_SIZE
+                + crate::serialize::flags_overflow_size(
+                    <P::BaseField as crate::serialize::CanonicalSerializeWithFlags>::SPARE_BITS,
+                    crate::serialize::EdwardsFlags::BIT_SIZE,
+                );
+            // Unlike short Weierstrass points, uncompressed points carry no
+            // flags, so no extra byte is needed even for full-width moduli.
             const UNCOMPRESSED_SIZE: usize =
                 2 * <P::BaseField as ConstantSerializedSize>::SERIALIZED_SIZE;
         }
@@ -491,11 +555,21 @@ macro_rules! impl_edwards_curve_serializer {
                 let y: P::BaseField = CanonicalDeserialize::deserialize(reader)?;
 
                 let p = GroupAffine::<P>::new(x, y);
-                if !p.is_in_correct_subgroup_assuming_on_curve() {
+                if !p.is_on_curve() || !p.is_in_correct_subgroup_assuming_on_curve() {
                     return Err(crate::serialize::SerializationError::InvalidData);
                 }
                 Ok(p)
             }
+
+            #[allow(unused_qualifications)]
+            fn deserialize_unchecked<R: crate::io::Read>(
+                reader: &mut R,
+            ) -> Result<Self, crate::serialize::SerializationError> {
+                let x: P::BaseField = CanonicalDeserialize::deserialize(reader)?;
+                let y: P::BaseField = CanonicalDeserialize::deserialize(reader)?;
+
+                Ok(GroupAffine::<P>::new(x, y))
+            }
         }
     };
 }
@@ -549,6 +623,13 @@ macro_rules! impl_tuple {
                     $ty::deserialize_uncompressed(reader)?,
                 )+))
             }
+
+            #[inline]
+            fn deserialize_unchecked<R: Read>(reader: &mut R) -> Result<Self, SerializationError> {
+                Ok(($(
+                    $ty::deserialize_unchecked(reader)?,
+                )+))
+            }
         }
     }
 }
@@ -620,6 +701,13 @@ where
             reader,
         )?))
     }
+
+    #[inline]
+    fn deserialize_unchecked<R: Read>(reader: &mut R) -> Result<Self, SerializationError> {
+        Ok(Cow::Owned(<T as ToOwned>::Owned::deserialize_unchecked(
+            reader,
+        )?))
+    }
 }
 
 impl<T: CanonicalSerialize> CanonicalSerialize for Option<T> {
@@ -677,6 +765,18 @@ impl<T: CanonicalDeserialize> CanonicalDeserialize for Option<T> {
 
         Ok(data)
     }
+
+    #[inline]
+    fn deserialize_unchecked<R: Read>(reader: &mut R) -> Result<Self, SerializationError> {
+        let is_some = bool::deserialize(reader)?;
+        let data = if is_some {
+            Some(T::deserialize_unchecked(reader)?)
+        } else {
+            None
+        };
+
+        Ok(data)
+    }
 }
 
 impl CanonicalSerialize for bool {
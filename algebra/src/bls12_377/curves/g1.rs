@@ -1,10 +1,12 @@
 use algebra_core::{
     biginteger::{BigInteger256, BigInteger384},
-    curves::models::{ModelParameters, SWModelParameters},
+    curves::models::{
+        glv::GLVParameters, subgroup::SubgroupCheckParameters, ModelParameters, SWModelParameters,
+    },
     field_new, Zero,
 };
 
-use crate::bls12_377::{Fq, Fr};
+use crate::bls12_377::{Fq, Fr, FQ_ONE};
 
 #[derive(Clone, Default, PartialEq, Eq)]
 pub struct Parameters;
@@ -46,6 +48,62 @@ impl SWModelParameters for Parameters {
     const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
         (G1_GENERATOR_X, G1_GENERATOR_Y);
 
+    /// GLV endomorphism (x, y) -> (omega * x, y), acting as multiplication
+    /// by lambda on the prime order subgroup, with lattice basis
+    /// (a1, b1), (a2, b2) for the scalar decomposition:
+    /// omega = 258664426012969093929703085429980814127835149614277183275038967946009968870203535512256352201271898244626862047231,
+    /// lambda = 8444461749428370424248824938781546531284005582649182570233710176290576793600,
+    /// (a1, b1) = (1, -91893752504881257701523279626832445440),
+    /// (a2, b2) = (91893752504881257701523279626832445441, 1)
+    #[rustfmt::skip]
+    const GLV: Option<GLVParameters<Self>> = Some(GLVParameters {
+        omega: field_new!(Fq, BigInteger384([
+            0x2c766f925a7b8727,
+            0x3d7f6b0253d58b5,
+            0x838ec0deec122131,
+            0xbd5eb3e9f658bb10,
+            0x6942bd126ed3e52e,
+            0x1673786dd04ed6a,
+        ])),
+        lambda: field_new!(Fr, BigInteger256([
+            0xe8866d32af90182d,
+            0xb62199b311362ad8,
+            0x94da24e3c0d36cfa,
+            0xeebbd77c57dabbd,
+        ])),
+        b1: field_new!(Fr, BigInteger256([
+            0x5b916d32af90181f,
+            0xcecf17c3b1362aca,
+            0x4afded3ab5c7cce7,
+            0x98c324b570ca104,
+        ])),
+        b2: field_new!(Fr, BigInteger256([
+            0x7d1c7ffffffffff3,
+            0x7257f50f6ffffff2,
+            0x16d81575512c0fee,
+            0xd4bda322bbb9a9d,
+        ])),
+        g1: (&[0xe], false),
+        g2: (&[0x7f72ed32af90181e, 0xb3f7aa969fd37160, 0x3], false),
+    });
+
+    /// SUBGROUP_CHECK: psi = (x, y) -> (omega * x, y), with omega a cube root
+    /// of unity such that psi(P) = [-X^2]P on the prime order subgroup
+    #[rustfmt::skip]
+    const SUBGROUP_CHECK: Option<SubgroupCheckParameters<Self>> =
+        Some(SubgroupCheckParameters {
+            coeff_x: field_new!(Fq, BigInteger384([
+                0x2c766f925a7b8727,
+                0x3d7f6b0253d58b5,
+                0x838ec0deec122131,
+                0xbd5eb3e9f658bb10,
+                0x6942bd126ed3e52e,
+                0x1673786dd04ed6a,
+            ])),
+            coeff_y: FQ_ONE,
+            eigenvalue: (&[0xa11800000000001, 0x452217cc90000001], true),
+        });
+
     #[inline(always)]
     fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
         Self::BaseField::zero()
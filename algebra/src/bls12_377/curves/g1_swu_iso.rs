@@ -0,0 +1,176 @@
+//! Parameters for hashing to G1 with the simplified SWU map to a
+//! 2-isogenous curve, following the IETF draft "Hashing to Elliptic
+//! Curves".
+//!
+//! The draft defines no suite for BLS12-377. The isogenous curve, `ZETA` and
+//! the isogeny are those of the `ark-bls12-377` crate, version 0.4.0, of
+//! arkworks (https://github.com/arkworks-rs/curves), whose test vectors the
+//! tests check against.
+
+use algebra_core::{biginteger::BigInteger384, field_new, hash_to_curve::SWUParameters};
+
+use crate::bls12_377::{g1, Fq};
+
+impl SWUParameters for g1::Parameters {
+    const CURVE_ID: &'static str = "BLS12377G1";
+
+    /// ISO_COEFF_A =
+    /// -1214244723973690791128375447109562158972679420810657492172059308940688039074521928197201891164175
+    #[rustfmt::skip]
+    const ISO_COEFF_A: Fq = field_new!(Fq, BigInteger384([
+        0xef6dc9934d3ce250,
+        0x74af9b7f7e982df3,
+        0xff914ed397c8e910,
+        0x95d6f551d83676ca,
+        0x83527885cb405a6f,
+        0xc361fbac151eaf,
+    ]));
+
+    /// ISO_COEFF_B = 22
+    #[rustfmt::skip]
+    const ISO_COEFF_B: Fq = field_new!(Fq, BigInteger384([
+        0x9a76bffffffff2e9,
+        0x5a3e286faffff932,
+        0xdc25c143d08286d2,
+        0xe1cd141e77fcf991,
+        0x3167b6320cca6b5c,
+        0x63347edb6f8ed7,
+    ]));
+
+    /// ZETA = -11
+    #[rustfmt::skip]
+    const ZETA: Fq = field_new!(Fq, BigInteger384([
+        0xf54900000000068c,
+        0xde669a6a40000366,
+        0xa166d075f4c36096,
+        0x1c2ae2ea447c0cfe,
+        0xca69a7c72feb6eef,
+        0xa582e39e2ac109,
+    ]));
+
+    /// The 2-isogeny E' -> E.
+    #[rustfmt::skip]
+    const ISOGENY_X_NUM: &'static [Fq] = &[
+        field_new!(Fq, BigInteger384([
+            0x823123adc3dca4eb,
+            0x7a0f9955afee024c,
+            0xd28c7eda6a6936da,
+            0xa3f9423c4b6291ac,
+            0xd71abcad5823fd42,
+            0x10be7dd0a10e548,
+        ])),
+        field_new!(Fq, BigInteger384([
+            0xac492836d2c23c6d,
+            0x8999b34a056153a5,
+            0x4db250a866fb9367,
+            0x2e621304854e2c3f,
+            0x2e7c2456fee6b206,
+            0x23815f9d6011c0,
+        ])),
+        field_new!(Fq, BigInteger384([
+            0x40b37fffffffffda,
+            0x945027e0dfffffec,
+            0x67df6cea629f4ffc,
+            0x1ed3a5eddb9f18c1,
+            0x933d256fe00f213a,
+            0x23599878bf7d26,
+        ])),
+    ];
+
+    #[rustfmt::skip]
+    const ISOGENY_X_DEN: &'static [Fq] = &[
+        field_new!(Fq, BigInteger384([
+            0xb124a0db4b08f1b4,
+            0x2666cd2815854e96,
+            0x36c942a19bee4d9e,
+            0xb9884c121538b0fd,
+            0xb9f0915bfb9ac818,
+            0x8e057e75804700,
+        ])),
+        field_new!(Fq, BigInteger384([
+            0x2cdffffffffff68,
+            0x51409f837fffffb1,
+            0x9f7db3a98a7d3ff2,
+            0x7b4e97b76e7c6305,
+            0x4cf495bf803c84e8,
+            0x8d6661e2fdf49a,
+        ])),
+    ];
+
+    #[rustfmt::skip]
+    const ISOGENY_Y_NUM: &'static [Fq] = &[
+        field_new!(Fq, BigInteger384([
+            0xc02040000000010b,
+            0x8da461e1000008a,
+            0x47d767c707ae1818,
+            0x42595071ff9b6645,
+            0xbf8effb14c3760a4,
+            0xb6c71aca88a4dc,
+        ])),
+        field_new!(Fq, BigInteger384([
+            0x71b06cd965863b7,
+            0x87569d4402cfa42,
+            0xbf01385547102ade,
+            0x2767fb48c5ee44b5,
+            0xd5d0b6afb3393ded,
+            0x195ce06a2426d14,
+        ])),
+        field_new!(Fq, BigInteger384([
+            0xc4f21c523c235aa4,
+            0x59ec3b912011fd78,
+            0x84052a14777e011b,
+            0xd2a48980486fcc26,
+            0xa8d7b962b4aaafa6,
+            0x10c5f3277f2a315,
+        ])),
+        field_new!(Fq, BigInteger384([
+            0x2059bfffffffffed,
+            0x4a2813f06ffffff6,
+            0xb3efb675314fa7fe,
+            0xf69d2f6edcf8c60,
+            0x499e92b7f007909d,
+            0x11accc3c5fbe93,
+        ])),
+    ];
+
+    #[rustfmt::skip]
+    const ISOGENY_Y_DEN: &'static [Fq] = &[
+        field_new!(Fq, BigInteger384([
+            0x78aa4000000004c3,
+            0xbb1d1bb090000279,
+            0x60ec8942da31d86d,
+            0x73f3d01d8efc2280,
+            0xeb0c634543ffb46d,
+            0x9f7bc32f5f8ded,
+        ])),
+        field_new!(Fq, BigInteger384([
+            0xf454523c235b15a,
+            0x45780f4ee11fdb3d,
+            0xcb9e8369dc96259,
+            0xc5c85fb95369a8ca,
+            0x4ca26eaebc0d0824,
+            0xfc8ec4ca4549e,
+        ])),
+        field_new!(Fq, BigInteger384([
+            0x136de291e11ad51c,
+            0x73346778408febc4,
+            0xa45bc7e4d3cae8da,
+            0x2c98e4363faa12f7,
+            0x2dd1b413f2d0584a,
+            0x1aa107b6080d502,
+        ])),
+        field_new!(Fq, BigInteger384([
+            0x2cdffffffffff68,
+            0x51409f837fffffb1,
+            0x9f7db3a98a7d3ff2,
+            0x7b4e97b76e7c6305,
+            0x4cf495bf803c84e8,
+            0x8d6661e2fdf49a,
+        ])),
+    ];
+
+    /// H_EFF = X - 1
+    ///       = 9586122913090633728
+    #[rustfmt::skip]
+    const H_EFF: &'static [u64] = &[0x8508c00000000000];
+}
@@ -1,10 +1,12 @@
 use algebra_core::{
     biginteger::{BigInteger256, BigInteger384},
-    curves::models::{ModelParameters, SWModelParameters},
+    curves::models::{
+        glv::GLVParameters, subgroup::SubgroupCheckParameters, ModelParameters, SWModelParameters,
+    },
     field_new, Zero,
 };
 
-use crate::bls12_377::{g1, Fq, Fq2, Fr};
+use crate::bls12_377::{g1, Fq, Fq2, Fr, FQ_ZERO};
 
 #[derive(Clone, Default, PartialEq, Eq)]
 pub struct Parameters;
@@ -69,6 +71,79 @@ impl SWModelParameters for Parameters {
     const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
         (G2_GENERATOR_X, G2_GENERATOR_Y);
 
+    /// GLV endomorphism (x, y) -> (omega * x, y), acting as multiplication
+    /// by lambda on the prime order subgroup, with lattice basis
+    /// (a1, b1), (a2, b2) for the scalar decomposition:
+    /// omega = 258664426012969093929703085429980814127835149614277183275038967946009968870203535512256352201271898244626862047231,
+    /// lambda = 91893752504881257701523279626832445440,
+    /// (a1, b1) = (1, 91893752504881257701523279626832445441),
+    /// (a2, b2) = (91893752504881257701523279626832445440, -1)
+    #[rustfmt::skip]
+    const GLV: Option<GLVParameters<Self>> = Some(GLVParameters {
+        omega: field_new!(Fq2,
+            field_new!(Fq, BigInteger384([
+                0x2c766f925a7b8727,
+                0x3d7f6b0253d58b5,
+                0x838ec0deec122131,
+                0xbd5eb3e9f658bb10,
+                0x6942bd126ed3e52e,
+                0x1673786dd04ed6a,
+            ])),
+            FQ_ZERO,
+        ),
+        lambda: field_new!(Fr, BigInteger256([
+            0xae8012cd506fe7e2,
+            0x8adb5f3b1ec9d536,
+            0x15b65fe3a66fe319,
+            0x91f331343200452,
+        ])),
+        b1: field_new!(Fr, BigInteger256([
+            0x218b12cd506fe7d4,
+            0xa388dd4bbec9d528,
+            0xcbda283a9b644306,
+            0x3bfa7e6d4aef998,
+        ])),
+        b2: field_new!(Fr, BigInteger256([
+            0x8cf500000000000e,
+            0xe75281ef6000000e,
+            0x49dc37a90b0ba012,
+            0x55f8b2c6e710ab9,
+        ])),
+        g1: (&[0xe], false),
+        g2: (&[0x7f72ed32af90182c, 0xb3f7aa969fd37160, 0x3], false),
+    });
+
+    /// SUBGROUP_CHECK: psi = (x, y) -> (c_x * x^q, c_y * y^q), the untwist-
+    /// Frobenius-twist endomorphism, with psi(P) = [X]P on the prime order
+    /// subgroup
+    #[rustfmt::skip]
+    const SUBGROUP_CHECK: Option<SubgroupCheckParameters<Self>> =
+        Some(SubgroupCheckParameters {
+            coeff_x: field_new!(Fq2,
+                field_new!(Fq, BigInteger384([
+                    0x5892506da58478da,
+                    0x133366940ac2a74b,
+                    0x9b64a150cdf726cf,
+                    0x5cc426090a9c587e,
+                    0x5cf848adfdcd640c,
+                    0x4702bf3ac02380,
+                ])),
+                FQ_ZERO,
+            ),
+            coeff_y: field_new!(Fq2,
+                field_new!(Fq, BigInteger384([
+                    0x982c13d9d084771f,
+                    0xfd49de0c6da34a32,
+                    0x61a530d183ab0e53,
+                    0xdf8fe44106dd9879,
+                    0x40f29b58d88472bc,
+                    0x158723199046d5d,
+                ])),
+                FQ_ZERO,
+            ),
+            eigenvalue: (&[0x8508c00000000001], false),
+        });
+
     #[inline(always)]
     fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
         Self::BaseField::zero()
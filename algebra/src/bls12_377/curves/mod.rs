@@ -5,7 +5,9 @@ use algebra_core::curves::{
 };
 
 pub mod g1;
+mod g1_swu_iso;
 pub mod g2;
+mod g2_swu_iso;
 
 #[cfg(test)]
 mod tests;
@@ -4,7 +4,10 @@ use crate::{
     bls12_381::*,
     curves::{
         bls12,
-        models::{ModelParameters, SWModelParameters},
+        models::{
+            glv::GLVParameters, subgroup::SubgroupCheckParameters, ModelParameters,
+            SWModelParameters,
+        },
     },
     field_new, Zero,
 };
@@ -52,6 +55,62 @@ impl SWModelParameters for Parameters {
     const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
         (G1_GENERATOR_X, G1_GENERATOR_Y);
 
+    /// GLV endomorphism (x, y) -> (omega * x, y), acting as multiplication
+    /// by lambda on the prime order subgroup, with lattice basis
+    /// (a1, b1), (a2, b2) for the scalar decomposition:
+    /// omega = 4002409555221667392624310435006688643935503118305586438271171395842971157480381377015405980053539358417135540939436,
+    /// lambda = 228988810152649578064853576960394133503,
+    /// (a1, b1) = (1, 228988810152649578064853576960394133504),
+    /// (a2, b2) = (228988810152649578064853576960394133503, -1)
+    #[rustfmt::skip]
+    const GLV: Option<GLVParameters<Self>> = Some(GLVParameters {
+        omega: field_new!(Fq, BigInteger384([
+            0xcd03c9e48671f071,
+            0x5dab22461fcda5d2,
+            0x587042afd3851b95,
+            0x8eb60ebe01bacb9e,
+            0x3f97d6e83d050d2,
+            0x18f0206554638741,
+        ])),
+        lambda: field_new!(Fr, BigInteger256([
+            0x92d9090b093011d2,
+            0xfc9cbd719d6aa073,
+            0xc1f14ef0cd65a1a6,
+            0x17f6d35e72fcdeb,
+        ])),
+        b1: field_new!(Fr, BigInteger256([
+            0x92d9090d093011d0,
+            0x5521756b9d6de875,
+            0x5b7d9ee0ba21f19c,
+            0x19a41e8f93f4d35b,
+        ])),
+        b2: field_new!(Fr, BigInteger256([
+            0xfffffffd00000003,
+            0xfb38ec08fffb13fc,
+            0x99ad88181ce5880f,
+            0x5bc8f5f97cd877d8,
+        ])),
+        g1: (&[0x2], false),
+        g2: (&[0x63f6e522f6cfee30, 0x7c6becf1e01faadd, 0x1], false),
+    });
+
+    /// SUBGROUP_CHECK: psi = (x, y) -> (omega * x, y), with omega a cube root
+    /// of unity such that psi(P) = [-X^2]P on the prime order subgroup
+    #[rustfmt::skip]
+    const SUBGROUP_CHECK: Option<SubgroupCheckParameters<Self>> =
+        Some(SubgroupCheckParameters {
+            coeff_x: field_new!(Fq, BigInteger384([
+                0x30f1361b798a64e8,
+                0xf3b8ddab7ece5a2a,
+                0x16a8ca3ac61577f7,
+                0xc26a2ff874fd029b,
+                0x3636b76660701c6e,
+                0x51ba4ab241b6160,
+            ])),
+            coeff_y: FQ_ONE,
+            eigenvalue: (&[0x100000000, 0xac45a4010001a402], true),
+        });
+
     #[inline(always)]
     fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
         Self::BaseField::zero()
@@ -4,7 +4,10 @@ use crate::{
     bls12_381::*,
     curves::{
         bls12,
-        models::{ModelParameters, SWModelParameters},
+        models::{
+            glv::GLVParameters, subgroup::SubgroupCheckParameters, ModelParameters,
+            SWModelParameters,
+        },
     },
     field_new, Zero,
 };
@@ -56,6 +59,86 @@ impl SWModelParameters for Parameters {
     const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
         (G2_GENERATOR_X, G2_GENERATOR_Y);
 
+    /// GLV endomorphism (x, y) -> (omega * x, y), acting as multiplication
+    /// by lambda on the prime order subgroup, with lattice basis
+    /// (a1, b1), (a2, b2) for the scalar decomposition:
+    /// omega = 4002409555221667392624310435006688643935503118305586438271171395842971157480381377015405980053539358417135540939436,
+    /// lambda = 52435875175126190479447740508185965837461563690374988244538805122978187051009,
+    /// (a1, b1) = (1, -228988810152649578064853576960394133503),
+    /// (a2, b2) = (228988810152649578064853576960394133504, 1)
+    #[rustfmt::skip]
+    const GLV: Option<GLVParameters<Self>> = Some(GLVParameters {
+        omega: field_new!(Fq2,
+            field_new!(Fq, BigInteger384([
+                0xcd03c9e48671f071,
+                0x5dab22461fcda5d2,
+                0x587042afd3851b95,
+                0x8eb60ebe01bacb9e,
+                0x3f97d6e83d050d2,
+                0x18f0206554638741,
+            ])),
+            FQ_ZERO,
+        ),
+        lambda: field_new!(Fr, BigInteger256([
+            0x6d26f6f1f6cfee31,
+            0xfe9c2e9762907389,
+            0xd7bc39274f7fe668,
+            0x5a4988c395a8a9ec,
+        ])),
+        b1: field_new!(Fr, BigInteger256([
+            0x6d26f6f3f6cfee2f,
+            0x5720e6916293bb8b,
+            0x714889173c3c365e,
+            0x726e3a1d426daf5c,
+        ])),
+        b2: field_new!(Fr, BigInteger256([
+            0x1fffffffe,
+            0x5884b7fa00034802,
+            0x998c4fefecbc4ff5,
+            0x1824b159acc5056f,
+        ])),
+        g1: (&[0x2], false),
+        g2: (&[0x63f6e522f6cfee2e, 0x7c6becf1e01faadd, 0x1], false),
+    });
+
+    /// SUBGROUP_CHECK: psi = (x, y) -> (c_x * x^q, c_y * y^q), the untwist-
+    /// Frobenius-twist endomorphism, with psi(P) = [X]P on the prime order
+    /// subgroup
+    #[rustfmt::skip]
+    const SUBGROUP_CHECK: Option<SubgroupCheckParameters<Self>> =
+        Some(SubgroupCheckParameters {
+            coeff_x: field_new!(Fq2,
+                FQ_ZERO,
+                field_new!(Fq, BigInteger384([
+                    0x890dc9e4867545c3,
+                    0x2af322533285a5d5,
+                    0x50880866309b7e2c,
+                    0xa20d1b8c7e881024,
+                    0x14e4f04fe2db9068,
+                    0x14e56d3f1564853a,
+                ])),
+            ),
+            coeff_y: field_new!(Fq2,
+                field_new!(Fq, BigInteger384([
+                    0x3e2f585da55c9ad1,
+                    0x4294213d86c18183,
+                    0x382844c88b623732,
+                    0x92ad2afd19103e18,
+                    0x1d794e4fac7cf0b9,
+                    0xbd592fc7d825ec8,
+                ])),
+                field_new!(Fq, BigInteger384([
+                    0x7bcfa7a25aa30fda,
+                    0xdc17dec12a927e7c,
+                    0x2f088dd86b4ebef1,
+                    0xd1ca2087da74d4a7,
+                    0x2da2596696cebc1d,
+                    0xe2b7eedbbfd87d2,
+                ])),
+            ),
+            eigenvalue: (&[0xd201000000010000], true),
+        });
+
     #[inline(always)]
     fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
         Self::BaseField::zero()
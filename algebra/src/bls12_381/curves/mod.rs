@@ -3,7 +3,9 @@ use algebra_core::curves::bls12::{Bls12, Bls12Parameters, TwistType};
 use crate::bls12_381::{Fq, Fq12Parameters, Fq2Parameters, Fq6Parameters};
 
 pub mod g1;
+mod g1_swu_iso;
 pub mod g2;
+mod g2_swu_iso;
 
 #[cfg(test)]
 mod tests;
@@ -2,17 +2,22 @@
 use algebra_core::{
     curves::{models::SWModelParameters, AffineCurve, PairingEngine, ProjectiveCurve},
     fields::{Field, FpParameters, PrimeField, SquareRootField},
+    hash_to_curve::HashToCurve,
     test_rng, CanonicalSerialize, One, Zero,
 };
-use core::ops::{AddAssign, MulAssign};
+use core::{
+    ops::{AddAssign, MulAssign},
+    str::FromStr,
+};
 use rand::Rng;
+use sha2::Sha256;
 
 use crate::{
     bls12_381::{
         g1, g2, Bls12_381, Fq, Fq12, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective,
     },
     tests::{
-        curves::{curve_tests, sw_tests},
+        curves::{curve_tests, sw_glv_cofactor_test, sw_subgroup_non_member_test, sw_tests},
         groups::group_test,
     },
 };
@@ -39,6 +44,16 @@ fn test_g1_generator() {
     assert!(generator.is_in_correct_subgroup_assuming_on_curve());
 }
 
+#[test]
+fn test_g1_subgroup_check() {
+    sw_subgroup_non_member_test::<g1::Parameters>();
+}
+
+#[test]
+fn test_g1_glv_mul_subgroup() {
+    sw_glv_cofactor_test::<g1::Parameters>();
+}
+
 #[test]
 fn test_g2_projective_curve() {
     curve_tests::<G2Projective>();
@@ -61,6 +76,11 @@ fn test_g2_generator() {
     assert!(generator.is_in_correct_subgroup_assuming_on_curve());
 }
 
+#[test]
+fn test_g2_subgroup_check() {
+    sw_subgroup_non_member_test::<g2::Parameters>();
+}
+
 #[test]
 fn test_bilinearity() {
     let mut rng = test_rng();
@@ -120,3 +140,84 @@ fn test_g1_generator_raw() {
         x.add_assign(&Fq::one());
     }
 }
+
+#[test]
+fn test_g1_hash_to_curve() {
+    // Test vectors of the BLS12381G1_XMD:SHA-256_SSWU_RO_ suite, from
+    // appendix J.9.1 of the draft.
+    let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+    let vectors = [
+        (
+            "",
+            "794311575721400831362957049303781044852006323422624111893352859557450008308620925451441746926395141598720928151969",
+            "1343412193624222137939591894701031123123641958980729764240763391191550653712890272928110356903136085217047453540965",
+        ),
+        (
+            "abc",
+            "513738460217615943921285247703448567647875874745567372796164155472383127756567780059136521508428662765965997467907",
+            "1786897908129645780825838873875416513994655004408749907941296449131605892957529391590865627492442562626458913769565",
+        ),
+        (
+            "abcdef0123456789",
+            "2751628761372137084683207295437105268166375184027748372156952770986741873369176463286511518644061904904607431667096",
+            "563036982304416203921640398061260377444881693369806087719971277317609936727208012968659302318886963927918562170633",
+        ),
+    ];
+
+    for (msg, x, y) in vectors.iter() {
+        let p = G1Affine::hash_to_curve::<Sha256>(msg.as_bytes(), dst);
+        let expected = G1Affine::new(Fq::from_str(x).unwrap(), Fq::from_str(y).unwrap(), false);
+        assert_eq!(p, expected);
+        assert!(p.is_in_correct_subgroup_assuming_on_curve());
+    }
+}
+
+#[test]
+fn test_g2_hash_to_curve() {
+    // Test vectors of the BLS12381G2_XMD:SHA-256_SSWU_RO_ suite, from
+    // appendix J.9.2 of the draft.
+    let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+    let vectors = [
+        (
+            "",
+            (
+                "193548053368451749411421515628510806626565736652086807419354395577367693778571452628423727082668900187036482254730",
+                "891930009643099423308102777951250899694559203647724988361022851024990473423938537113948850338098230396747396259901",
+            ),
+            (
+                "771717272055834152378281705972671257005357145478800908373659404991537354153455452961747174765859335819766715637138",
+                "2810310118582126634041133454180705304393079139103252956502404531123692847658283858246402311867775854528543237781718",
+            ),
+        ),
+        (
+            "abc",
+            (
+                "424958340463073975547762735517193206833255107941790909009827635556634414746056077714431786321247871628515967727334",
+                "3018679803970127877262826393814472528557413504329194740495363852840690589001358162447917674089074634504498585239512",
+            ),
+            (
+                "3621308185128395459888995526527127556614768604472132176060423302734876099689739385100475320409412954617897892887112",
+                "102447784096837908713257069727879782642075240724579670654226801345708452018676587771714457671432122751958633012502",
+            ),
+        ),
+        (
+            "abcdef0123456789",
+            (
+                "2785790728239146617702443308248535381016035748520698399690132325213972292102741627498014391457605127656937478044880",
+                "3855709393631831880910167818276435187147963371126198799654803099743427431977934703201153169947378798970358200024876",
+            ),
+            (
+                "821938378705205565995357931232097952117504537366318395539093959918654729488074273868834599496909844419980823111624",
+                "1802420335575779950982935580421454302087567926385222707947527353462942499437987207287862072369052390195154530059198",
+            ),
+        ),
+    ];
+
+    let fq2 =
+        |(c0, c1): &(&str, &str)| Fq2::new(Fq::from_str(c0).unwrap(), Fq::from_str(c1).unwrap());
+    for (msg, x, y) in vectors.iter() {
+        let p = G2Affine::hash_to_curve::<Sha256>(msg.as_bytes(), dst);
+        assert_eq!(p, G2Affine::new(fq2(x), fq2(y), false));
+        assert!(p.is_in_correct_subgroup_assuming_on_curve());
+    }
+}
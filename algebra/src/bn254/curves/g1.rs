@@ -1,6 +1,6 @@
 use algebra_core::{
     biginteger::BigInteger256,
-    curves::models::{ModelParameters, SWModelParameters},
+    curves::models::{glv::GLVParameters, ModelParameters, SWModelParameters},
     field_new, Zero,
 };
 
@@ -43,6 +43,43 @@ impl SWModelParameters for Parameters {
     const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
         (G1_GENERATOR_X, G1_GENERATOR_Y);
 
+    /// GLV endomorphism (x, y) -> (omega * x, y), acting as multiplication
+    /// by lambda on the prime order subgroup, with lattice basis
+    /// (a1, b1), (a2, b2) for the scalar decomposition:
+    /// omega = 21888242871839275220042445260109153167277707414472061641714758635765020556616,
+    /// lambda = 21888242871839275217838484774961031246154997185409878258781734729429964517155,
+    /// (a1, b1) = (147946756881789319000765030803803410728, -9931322734385697763),
+    /// (a2, b2) = (9931322734385697763, 147946756881789319010696353538189108491)
+    #[rustfmt::skip]
+    const GLV: Option<GLVParameters<Self>> = Some(GLVParameters {
+        omega: field_new!(Fq, BigInteger256([
+            0x3350c88e13e80b9c,
+            0x7dce557cdb5e56b9,
+            0x6001b4b8b615564a,
+            0x2682e617020217e0,
+        ])),
+        lambda: field_new!(Fr, BigInteger256([
+            0x363f29955fcd653,
+            0x73e7950b5fc1e200,
+            0xc5fce83e576d9d24,
+            0x59c805da1c3a4d4,
+        ])),
+        b1: field_new!(Fr, BigInteger256([
+            0x1f7cc8d147e0b3d8,
+            0x71b98e53f86c230d,
+            0xc13246fc769bb9eb,
+            0x1b76169efc0e7649,
+        ])),
+        b2: field_new!(Fr, BigInteger256([
+            0x5001b1ef4f009a9b,
+            0xbd5e82c846474116,
+            0x5fec7030d27930cf,
+            0x3e59388fe18f17e,
+        ])),
+        g1: (&[0x5398fd0300ff6565, 0x4ccef014a773d2d2, 0x2], false),
+        g2: (&[0xd91d232ec7e0b3d7, 0x2], false),
+    });
+
     #[inline(always)]
     fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
         Self::BaseField::zero()
@@ -1,10 +1,12 @@
 use algebra_core::{
     biginteger::BigInteger256,
-    curves::models::{ModelParameters, SWModelParameters},
+    curves::models::{
+        glv::GLVParameters, subgroup::SubgroupCheckParameters, ModelParameters, SWModelParameters,
+    },
     field_new, Zero,
 };
 
-use crate::bn254::{g1, Fq, Fq2, Fr};
+use crate::bn254::{g1, Fq, Fq2, Fr, FQ_ZERO};
 
 #[derive(Clone, Default, PartialEq, Eq)]
 pub struct Parameters;
@@ -63,6 +65,83 @@ impl SWModelParameters for Parameters {
     const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
         (G2_GENERATOR_X, G2_GENERATOR_Y);
 
+    /// GLV endomorphism (x, y) -> (omega * x, y), acting as multiplication
+    /// by lambda on the prime order subgroup, with lattice basis
+    /// (a1, b1), (a2, b2) for the scalar decomposition:
+    /// omega = 21888242871839275220042445260109153167277707414472061641714758635765020556616,
+    /// lambda = 4407920970296243842393367215006156084916469457145843978461,
+    /// (a1, b1) = (9931322734385697763, -147946756881789319000765030803803410728),
+    /// (a2, b2) = (147946756881789319010696353538189108491, 9931322734385697763)
+    #[rustfmt::skip]
+    const GLV: Option<GLVParameters<Self>> = Some(GLVParameters {
+        omega: field_new!(Fq2,
+            field_new!(Fq, BigInteger256([
+                0x3350c88e13e80b9c,
+                0x7dce557cdb5e56b9,
+                0x6001b4b8b615564a,
+                0x2682e617020217e0,
+            ])),
+            FQ_ZERO,
+        ),
+        lambda: field_new!(Fr, BigInteger256([
+            0x93e7cede4a0329b3,
+            0x7d4fdca77a96c167,
+            0x8be4ba08b19a750a,
+            0x1cbd5653a5661c25,
+        ])),
+        b1: field_new!(Fr, BigInteger256([
+            0xd4637ad3591eb18e,
+            0xf91bd72c3b060c6d,
+            0x97318e89386c6da1,
+            0x1108a44ae70a3861,
+        ])),
+        b2: field_new!(Fr, BigInteger256([
+            0x24652cc2a81f4c29,
+            0xb67a59f4814d4d84,
+            0xf71dfeba0ae59e71,
+            0x14ee37d3e52329df,
+        ])),
+        g1: (&[0xd91d232ec7e0b3d7, 0x2], false),
+        g2: (&[0x7a7bd9d4391eb18e, 0x4ccef014a773d2cf, 0x2], false),
+    });
+
+    /// SUBGROUP_CHECK: psi = (x, y) -> (c_x * x^q, c_y * y^q), the untwist-
+    /// Frobenius-twist endomorphism, with psi(P) = [6 * X^2]P on the prime order
+    /// subgroup
+    #[rustfmt::skip]
+    const SUBGROUP_CHECK: Option<SubgroupCheckParameters<Self>> =
+        Some(SubgroupCheckParameters {
+            coeff_x: field_new!(Fq2,
+                field_new!(Fq, BigInteger256([
+                    0xb5773b104563ab30,
+                    0x347f91c8a9aa6454,
+                    0x7a007127242e0991,
+                    0x1956bcd8118214ec,
+                ])),
+                field_new!(Fq, BigInteger256([
+                    0x6e849f1ea0aa4757,
+                    0xaa1c7b6d89f89141,
+                    0xb6e713cdfae0ca3a,
+                    0x26694fbb4e82ebc3,
+                ])),
+            ),
+            coeff_y: field_new!(Fq2,
+                field_new!(Fq, BigInteger256([
+                    0xe4bbdd0c2936b629,
+                    0xbb30f162e133bacb,
+                    0x31a9d1b6f9645366,
+                    0x253570bea500f8dd,
+                ])),
+                field_new!(Fq, BigInteger256([
+                    0xa1d77ce45ffe77c7,
+                    0x7affd117826d1db,
+                    0x6d16bd27bb7edc6b,
+                    0x2c87200285defecc,
+                ])),
+            ),
+            eigenvalue: (&[0xf83e9682e87cfd46, 0x6f4d8248eeb859fb], false),
+        });
+
     #[inline(always)]
     fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
         Self::BaseField::zero()
@@ -10,7 +10,7 @@ use rand::Rng;
 use crate::{
     bn254::{g1, g2, Bn254, Fq, Fq12, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective},
     tests::{
-        curves::{curve_tests, sw_tests},
+        curves::{curve_tests, sw_subgroup_non_member_test, sw_tests},
         groups::group_test,
     },
 };
@@ -59,6 +59,11 @@ fn test_g2_generator() {
     assert!(generator.is_in_correct_subgroup_assuming_on_curve());
 }
 
+#[test]
+fn test_g2_subgroup_check() {
+    sw_subgroup_non_member_test::<g2::Parameters>();
+}
+
 #[test]
 fn test_bilinearity() {
     let mut rng = test_rng();
@@ -2,7 +2,7 @@ use crate::{
     biginteger::{BigInteger384, BigInteger768},
     bw6_761::{Fq, Fr},
     curves::{
-        models::{ModelParameters, SWModelParameters},
+        models::{glv::GLVParameters, ModelParameters, SWModelParameters},
         short_weierstrass_jacobian::{GroupAffine, GroupProjective},
     },
     field_new,
@@ -69,6 +69,59 @@ impl SWModelParameters for Parameters {
     /// AFFINE_GENERATOR_COEFFS = (G1_GENERATOR_X, G1_GENERATOR_Y)
     const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
         (G1_GENERATOR_X, G1_GENERATOR_Y);
+
+    /// GLV endomorphism (x, y) -> (omega * x, y), acting as multiplication
+    /// by lambda on the prime order subgroup, with lattice basis
+    /// (a1, b1), (a2, b2) for the scalar decomposition:
+    /// omega = 4922464560225523242118178942575080391082002530232324381063048548642823052024664478336818169867474395270858391911405337707247735739826664939444490469542109391530482826728203582549674992333383150446779312029624171857054392282775648,
+    /// lambda = 258664426012969093929703085429980814127835149614277183275038967946009968870203535512256352201271898244626862047231,
+    /// a1 = 293634935485640680722085584138834120324914961969255022593,
+    /// b1 = -293634935485640680722085584138834120315328839056164388863,
+    /// a2 = 293634935485640680722085584138834120315328839056164388863,
+    /// b2 = 587269870971281361444171168277668240640243801025419411456
+    #[rustfmt::skip]
+    const GLV: Option<GLVParameters<Self>> = Some(GLVParameters {
+        omega: field_new!(Fq, BigInteger768([
+            0x7f96b51bd840c549,
+            0xd59782096496171f,
+            0x49b046fd9ce14bbc,
+            0x4b6163bba7527a56,
+            0xef6c92fb771d59f1,
+            0x425bedbac1dfdc7,
+            0xd3ac39de759c0ffd,
+            0x9f43ed0e063a81d0,
+            0x5bd7d20b4f9a3ce2,
+            0x411f03c36cf5c3c,
+            0x2d658fd49661c472,
+            0x1100249ae760b93,
+        ])),
+        lambda: field_new!(Fr, BigInteger384([
+            0x2c766f925a7b8727,
+            0x3d7f6b0253d58b5,
+            0x838ec0deec122131,
+            0xbd5eb3e9f658bb10,
+            0x6942bd126ed3e52e,
+            0x1673786dd04ed6a,
+        ])),
+        b1: field_new!(Fr, BigInteger384([
+            0x74e339ddb5613b70,
+            0xb9fb95ba7f4cc592,
+            0x455ac137f7424867,
+            0xd79cc4a654e7545b,
+            0x899bf95012409d81,
+            0xb12b68b8940f1b,
+        ])),
+        b2: field_new!(Fr, BigInteger384([
+            0xa7cada2793875586,
+            0x45fd2ce31904fcc2,
+            0x415bbc5a34cdbad7,
+            0xe8b865456e813812,
+            0xbf3cdd30fb65ddaf,
+            0xf13c57340c0553,
+        ])),
+        g1: (&[0x2289a5d86c78aa7b, 0x85f6e7602b7a9d8c, 0x4061751dd380c860, 0xe], false),
+        g2: (&[0x7ccf39ddb5613b70, 0x42fb73b015bd4e9e, 0x2030ba8ee9c06430, 0x7], false),
+    });
     #[inline(always)]
     fn mul_by_a(_elem: &Self::BaseField) -> Self::BaseField {
         use crate::Zero;
@@ -2,7 +2,7 @@ use crate::{
     biginteger::{BigInteger384, BigInteger768},
     bw6_761::{Fq, Fr},
     curves::{
-        models::{ModelParameters, SWModelParameters},
+        models::{glv::GLVParameters, ModelParameters, SWModelParameters},
         short_weierstrass_jacobian::{GroupAffine, GroupProjective},
     },
     field_new,
@@ -69,6 +69,59 @@ impl SWModelParameters for Parameters {
     /// AFFINE_GENERATOR_COEFFS = (G2_GENERATOR_X, G2_GENERATOR_Y)
     const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
         (G2_GENERATOR_X, G2_GENERATOR_Y);
+
+    /// GLV endomorphism (x, y) -> (omega * x, y), acting as multiplication
+    /// by lambda on the prime order subgroup, with lattice basis
+    /// (a1, b1), (a2, b2) for the scalar decomposition:
+    /// omega = 4922464560225523242118178942575080391082002530232324381063048548642823052024664478336818169867474395270858391911405337707247735739826664939444490469542109391530482826728203582549674992333383150446779312029624171857054392282775648,
+    /// lambda = 80949648264912719408558363140637477264845294720710499478137287262712535938301461879813459410945,
+    /// a1 = 293634935485640680722085584138834120315328839056164388863,
+    /// b1 = -293634935485640680722085584138834120324914961969255022593,
+    /// a2 = 587269870971281361444171168277668240640243801025419411456,
+    /// b2 = 293634935485640680722085584138834120315328839056164388863
+    #[rustfmt::skip]
+    const GLV: Option<GLVParameters<Self>> = Some(GLVParameters {
+        omega: field_new!(Fq, BigInteger768([
+            0x7f96b51bd840c549,
+            0xd59782096496171f,
+            0x49b046fd9ce14bbc,
+            0x4b6163bba7527a56,
+            0xef6c92fb771d59f1,
+            0x425bedbac1dfdc7,
+            0xd3ac39de759c0ffd,
+            0x9f43ed0e063a81d0,
+            0x5bd7d20b4f9a3ce2,
+            0x411f03c36cf5c3c,
+            0x2d658fd49661c472,
+            0x1100249ae760b93,
+        ])),
+        lambda: field_new!(Fr, BigInteger384([
+            0xdacd106da5847973,
+            0xd8fe2454bac2a79a,
+            0x1ada4fd6fd832edc,
+            0xfb9868449d150908,
+            0xd63eb8aeea32285e,
+            0x167d6a36f873fd0,
+        ])),
+        b1: field_new!(Fr, BigInteger384([
+            0x685aabfab7176f0b,
+            0x17129aa697ae3dab,
+            0x983ce49d8df944c1,
+            0x59cdb0073d8c8721,
+            0x7d622f3f5eface09,
+            0xbd2862b24fc7b,
+        ])),
+        b2: field_new!(Fr, BigInteger384([
+            0x102586224a9ec491,
+            0x5d0fc789b0b33a6e,
+            0xd998a0f7c2c6ff98,
+            0x4286154cac0dbf33,
+            0x3c9f0c705a60abb9,
+            0xfd0edd5f3101cf,
+        ])),
+        g1: (&[0x7ccf39ddb5613b70, 0x42fb73b015bd4e9e, 0x2030ba8ee9c06430, 0x7], false),
+        g2: (&[0xa5ba6bfab7176f0b, 0x42fb73b015bd4eed, 0x2030ba8ee9c06430, 0x7], false),
+    });
     #[inline(always)]
     fn mul_by_a(_elem: &Self::BaseField) -> Self::BaseField {
         use crate::Zero;
@@ -175,5 +175,10 @@ pub mod ed_on_bw6_761;
 pub(crate) mod bw6_761;
 ///////////////////////////////////////////////////////////////////////////////
 
+///////////////////////////////////////////////////////////////////////////////
+#[cfg(feature = "secp256k1")]
+pub mod secp256k1;
+///////////////////////////////////////////////////////////////////////////////
+
 #[cfg(test)]
 pub(crate) mod tests;
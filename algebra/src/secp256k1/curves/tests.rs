@@ -0,0 +1,72 @@
+use algebra_core::{
+    curves::{AffineCurve, ProjectiveCurve},
+    serialize::ConstantSerializedSize,
+    test_rng,
+};
+use core::str::FromStr;
+use rand::Rng;
+
+use crate::secp256k1::*;
+
+use crate::tests::{curves::*, groups::*};
+
+#[test]
+fn test_projective_curve() {
+    curve_tests::<Secp256k1Projective>();
+
+    sw_tests::<Secp256k1Parameters>();
+}
+
+#[test]
+fn test_projective_group() {
+    let mut rng = test_rng();
+    let a: Secp256k1Projective = rng.gen();
+    let b: Secp256k1Projective = rng.gen();
+    group_test(a, b);
+}
+
+#[test]
+fn test_generator() {
+    let generator = Secp256k1Affine::prime_subgroup_generator();
+    assert!(generator.is_on_curve());
+    assert!(generator.is_in_correct_subgroup_assuming_on_curve());
+}
+
+#[test]
+fn test_scalar_multiplication() {
+    // Public keys for the secret keys 2 and 3.
+    let generator = Secp256k1Affine::prime_subgroup_generator();
+    let two = Secp256k1Affine::new(
+        Fq::from_str(
+            "89565891926547004231252920425935692360644145829622209833684329913297188986597",
+        )
+        .unwrap(),
+        Fq::from_str(
+            "12158399299693830322967808612713398636155367887041628176798871954788371653930",
+        )
+        .unwrap(),
+        false,
+    );
+    let three = Secp256k1Affine::new(
+        Fq::from_str(
+            "112711660439710606056748659173929673102114977341539408544630613555209775888121",
+        )
+        .unwrap(),
+        Fq::from_str(
+            "25583027980570883691656905877401976406448868254816295069919888960541586679410",
+        )
+        .unwrap(),
+        false,
+    );
+    assert_eq!(generator.mul(Fr::from(2u64)).into_affine(), two);
+    assert_eq!(generator.mul(Fr::from(3u64)).into_affine(), three);
+    assert_eq!(generator.mul(-Fr::from(3u64)).into_affine(), -three);
+}
+
+#[test]
+fn test_serialized_size() {
+    // A full-width base field leaves no spare bits for the flags, which take
+    // an extra byte, so the sizes match those of SEC 1 encodings.
+    assert_eq!(Secp256k1Affine::SERIALIZED_SIZE, 33);
+    assert_eq!(Secp256k1Affine::UNCOMPRESSED_SIZE, 65);
+}
@@ -0,0 +1,146 @@
+use algebra_core::{test_rng, CanonicalSerialize, Field, One, Zero};
+use core::str::FromStr;
+use rand::Rng;
+
+use crate::{
+    secp256k1::{Fq, Fr},
+    tests::fields::{
+        fft_field_test, field_serialization_full_width_test, field_test, primefield_test,
+        sqrt_field_test,
+    },
+};
+
+#[test]
+fn test_fr() {
+    let mut rng = test_rng();
+    let a: Fr = rng.gen();
+    let b: Fr = rng.gen();
+    field_test(a, b);
+    primefield_test::<Fr>();
+    fft_field_test::<Fr>();
+    sqrt_field_test(a);
+    field_serialization_full_width_test::<Fr>(a.serialized_size());
+}
+
+#[test]
+fn test_fq() {
+    let mut rng = test_rng();
+    let a: Fq = rng.gen();
+    let b: Fq = rng.gen();
+    field_test(a, b);
+    primefield_test::<Fq>();
+    fft_field_test::<Fq>();
+    sqrt_field_test(a);
+    field_serialization_full_width_test::<Fq>(a.serialized_size());
+}
+
+// The moduli use every bit of their four limbs, so the following operands are
+// chosen close to the modulus to exercise the carries out of the top limb.
+
+#[test]
+fn test_fq_add() {
+    let f1 = Fq::from_str(
+        "115792089237316195423570985008687907853269984665640564039457584007908834671660",
+    )
+    .unwrap();
+    let f2 = Fq::from_str(
+        "115792089237316195423570985008687907853269984665640564039457584007908834671658",
+    )
+    .unwrap();
+    let f3 = Fq::from_str(
+        "115792089237316195423570985008687907853269984665640564039457584007908834671655",
+    )
+    .unwrap();
+    assert_eq!(f1 + &f2, f3);
+    assert_eq!(f3 - &f2, f1);
+}
+
+#[test]
+fn test_fq_double_in_place() {
+    let mut f1 = Fq::from_str(
+        "115792089237316195423570985008687907853269984665640564039457584007908834671656",
+    )
+    .unwrap();
+    let f2 = Fq::from_str(
+        "115792089237316195423570985008687907853269984665640564039457584007908834671649",
+    )
+    .unwrap();
+    f1.double_in_place();
+    assert_eq!(f1, f2);
+}
+
+#[test]
+fn test_fq_mul() {
+    let f1 = Fq::from_str(
+        "115792089237316193816632940749697632311307892324477961517254590225115999370276",
+    )
+    .unwrap();
+    let f2 = Fq::from_str(
+        "115792089237316195423570985008687907853269984665640564039457584007908834659318",
+    )
+    .unwrap();
+    let f3 =
+        Fq::from_str("19837650156377234951565522029951652328136595958248577551795622515").unwrap();
+    assert_eq!(f1 * &f2, f3);
+    assert_eq!(f1.square() * &f2.square(), f3.square());
+}
+
+#[test]
+fn test_fq_inverse() {
+    let f1 = Fq::from_str(
+        "115792089237316195423570985008687907853269984665640564039457584007908834671661",
+    )
+    .unwrap();
+    let f2 = Fq::from_str(
+        "57896044618658097711785492504343953926634992332820282019728792003954417335831",
+    )
+    .unwrap();
+    assert_eq!(f1.inverse().unwrap(), f2);
+    assert!((-Fq::one()).inverse().unwrap() == -Fq::one());
+    assert!(Fq::zero().inverse().is_none());
+}
+
+#[test]
+fn test_fr_add() {
+    let f1 = Fr::from_str(
+        "115792089237316195423570985008687907852837564279074904382605163141518161494336",
+    )
+    .unwrap();
+    let f2 = Fr::from_str(
+        "115792089237316195423570985008687907852837564279074904382605163141518161494335",
+    )
+    .unwrap();
+    let f3 = Fr::from_str(
+        "115792089237316195423570985008687907852837564279074904382605163141518161494334",
+    )
+    .unwrap();
+    assert_eq!(f1 + &f2, f3);
+    assert_eq!(f1.double(), f3 + &Fr::one());
+}
+
+#[test]
+fn test_fr_mul() {
+    let f1 = Fr::from_str(
+        "115792089237316195423569452513147041963979205932047754073421544402395977892158",
+    )
+    .unwrap();
+    let f2 = Fr::from_str(
+        "115792089237316195423570985008687907852837564279074904382605163141518161494238",
+    )
+    .unwrap();
+    let f3 = Fr::from_str("151717058545722996977476355687880609178255173096176615721").unwrap();
+    assert_eq!(f1 * &f2, f3);
+}
+
+#[test]
+fn test_fr_inverse() {
+    let f1 = Fr::from_str(
+        "115792089237316195423570985008687907852837564279074904382605163141518161494335",
+    )
+    .unwrap();
+    let f2 = Fr::from_str(
+        "57896044618658097711785492504343953926418782139537452191302581570759080747168",
+    )
+    .unwrap();
+    assert_eq!(f1.inverse().unwrap(), f2);
+}
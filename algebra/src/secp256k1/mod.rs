@@ -0,0 +1,22 @@
+//! This module implements secp256k1, the short Weierstrass curve from the
+//! [SEC 2](https://www.secg.org/sec2-v2.pdf) standard that is used for ECDSA
+//! signatures in Bitcoin and Ethereum. The curve has prime order, and since its
+//! equation has `a = 0` it admits a GLV endomorphism.
+//!
+//! Both the base field and the scalar field have 256-bit moduli, so neither
+//! leaves any spare bits in its representation.
+//!
+//! Curve information:
+//! * Base field: q =
+//!   115792089237316195423570985008687907853269984665640564039457584007908834671663
+//! * Scalar field: r =
+//!   115792089237316195423570985008687907852837564279074904382605163141518161494337
+//! * valuation(q - 1, 2) = 1
+//! * valuation(r - 1, 2) = 6
+//! * Curve equation: y^2 = x^3 + 7
+
+mod curves;
+mod fields;
+
+pub use curves::*;
+pub use fields::*;
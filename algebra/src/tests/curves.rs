@@ -2,6 +2,7 @@
 use algebra_core::{
     curves::{AffineCurve, ProjectiveCurve},
     io::Cursor,
+    msm::VariableBaseMSM,
     CanonicalDeserialize, CanonicalSerialize, Field, MontgomeryModelParameters, One, PrimeField,
     SWFlags, SWModelParameters, SerializationError, TEModelParameters, UniformRand, Vec, Zero,
 };
@@ -177,6 +178,40 @@ fn random_negation_test<G: ProjectiveCurve>() {
     }
 }
 
+fn random_batch_addition_test<G: ProjectiveCurve>() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    for _ in 0..ITERATIONS {
+        let a = G::rand(&mut rng);
+        let b = G::rand(&mut rng);
+
+        let mut bases = vec![
+            a.into_affine(),
+            b.into_affine(),
+            a.into_affine(),
+            a.into_affine(),
+            b.into_affine(),
+            (-b).into_affine(),
+            G::Affine::zero(),
+            a.into_affine(),
+            a.into_affine(),
+            G::Affine::zero(),
+            G::Affine::zero(),
+            G::Affine::zero(),
+        ];
+        let index = [(0, 1), (2, 3), (4, 5), (6, 7), (8, 9), (10, 11)];
+        G::Affine::batch_add_in_place(&mut bases, &index);
+
+        // Generic addition, doubling, inverse, and the three cases involving zero
+        assert_eq!(bases[0].into_projective(), a + &b);
+        assert_eq!(bases[2].into_projective(), a.double());
+        assert!(bases[4].is_zero());
+        assert_eq!(bases[6].into_projective(), a);
+        assert_eq!(bases[8].into_projective(), a);
+        assert!(bases[10].is_zero());
+    }
+}
+
 fn random_transformation_test<G: ProjectiveCurve>() {
     let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
 
@@ -288,11 +323,132 @@ pub fn curve_tests<G: ProjectiveCurve>() {
     random_doubling_test::<G>();
     random_negation_test::<G>();
     random_transformation_test::<G>();
+    random_batch_addition_test::<G>();
 }
 
 pub fn sw_tests<P: SWModelParameters>() {
     sw_curve_serialization_test::<P>();
     sw_from_random_bytes::<P>();
+    sw_glv_test::<P>();
+    sw_subgroup_check_test::<P>();
+    sw_unchecked_serialization_test::<P>();
+}
+
+pub fn sw_glv_test<P: SWModelParameters>() {
+    use algebra_core::curves::models::{short_weierstrass_jacobian, short_weierstrass_projective};
+
+    if P::GLV.is_none() {
+        return;
+    }
+
+    macro_rules! glv_test {
+        ($model: ident) => {{
+            use $model::{GroupAffine, GroupProjective};
+
+            fn double_and_add<P: SWModelParameters>(
+                a: &GroupAffine<P>,
+                s: P::ScalarField,
+            ) -> GroupProjective<P> {
+                let mut res = GroupProjective::<P>::zero();
+                for bit in algebra_core::BitIterator::new(s.into_repr()) {
+                    res.double_in_place();
+                    if bit {
+                        res.add_assign_mixed(a);
+                    }
+                }
+                res
+            }
+
+            let glv = P::GLV.unwrap();
+            let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+            for _ in 0..ITERATIONS {
+                let a = GroupProjective::<P>::rand(&mut rng).into_affine();
+                let s = P::ScalarField::rand(&mut rng);
+
+                // The endomorphism acts as multiplication by lambda.
+                let phi_a = GroupAffine::<P>::new(a.x * &glv.omega, a.y, a.infinity);
+                assert_eq!(phi_a.into_projective(), a.into_projective().mul(glv.lambda));
+
+                // k = k1 + lambda * k2
+                let ((k1_neg, k1), (k2_neg, k2)) = glv.decompose(&s.into_repr()).unwrap();
+                let k1 = P::ScalarField::from_repr(k1).unwrap();
+                let k2 = P::ScalarField::from_repr(k2).unwrap();
+                let k1 = if k1_neg { -k1 } else { k1 };
+                let k2 = if k2_neg { -k2 } else { k2 };
+                assert_eq!(k1 + &(glv.lambda * &k2), s);
+
+                // Compare against plain double-and-add.
+                let expected = double_and_add(&a, s);
+                let ((p1, k1), (p2, k2)) = a.glv_split(&s.into_repr()).unwrap();
+                assert_eq!(p1.mul(k1) + &p2.mul(k2), expected);
+                assert_eq!(a.mul(s), expected);
+                assert_eq!(a.into_projective().mul(s), expected);
+                assert_eq!(a.mul_subgroup(s), expected);
+
+                // Points outside the prime order subgroup are multiplied
+                // correctly as well.
+                let b = loop {
+                    if let Some(b) =
+                        GroupAffine::<P>::get_point_from_x(P::BaseField::rand(&mut rng), true)
+                    {
+                        break b;
+                    }
+                };
+                let expected = double_and_add(&b, s);
+                assert_eq!(b.mul(s), expected);
+                assert_eq!(b.into_projective().mul(s), expected);
+            }
+        }};
+    }
+
+    glv_test!(short_weierstrass_jacobian);
+    glv_test!(short_weierstrass_projective);
+
+    use short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    let bases = (0..ITERATIONS)
+        .map(|_| GroupProjective::<P>::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let scalars = (0..ITERATIONS)
+        .map(|_| P::ScalarField::rand(&mut rng).into_repr())
+        .collect::<Vec<_>>();
+    let expected = bases
+        .iter()
+        .zip(&scalars)
+        .fold(GroupProjective::<P>::zero(), |acc, (b, s)| acc + &b.mul(*s));
+    assert_eq!(
+        VariableBaseMSM::multi_scalar_mul_subgroup(&bases, &scalars),
+        expected
+    );
+}
+
+/// Checks that `mul_subgroup` goes through the GLV endomorphism even though
+/// the curve has a cofactor: on a point outside the prime order subgroup the
+/// endomorphism does not act as multiplication by lambda, so the result
+/// differs from the one of `mul`.
+pub fn sw_glv_cofactor_test<P: SWModelParameters>() {
+    use algebra_core::curves::models::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+
+    assert!(P::GLV.is_some());
+    assert_ne!(P::COFACTOR, [1]);
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    for _ in 0..ITERATIONS {
+        let s = P::ScalarField::rand(&mut rng);
+
+        let a = GroupProjective::<P>::rand(&mut rng).into_affine();
+        assert_eq!(a.mul_subgroup(s), a.mul(s));
+
+        let b = loop {
+            if let Some(b) = GroupAffine::<P>::get_point_from_x(P::BaseField::rand(&mut rng), true)
+            {
+                break b;
+            }
+        };
+        assert!(!b.is_in_correct_subgroup_assuming_on_curve());
+        assert_ne!(b.mul_subgroup(s), b.mul(s));
+    }
 }
 
 pub fn sw_from_random_bytes<P: SWModelParameters>() {
@@ -318,6 +474,103 @@ pub fn sw_from_random_bytes<P: SWModelParameters>() {
     }
 }
 
+pub fn sw_subgroup_check_test<P: SWModelParameters>() {
+    use algebra_core::{
+        curves::models::short_weierstrass_jacobian::{GroupAffine, GroupProjective},
+        BitIterator,
+    };
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let mut num_tested = 0;
+    while num_tested < ITERATIONS {
+        // Points on the curve, which need not lie in the prime order subgroup.
+        let p = match GroupAffine::<P>::get_point_from_x(P::BaseField::rand(&mut rng), true) {
+            Some(p) => p,
+            None => continue,
+        };
+        num_tested += 1;
+
+        let mut r_p = GroupProjective::<P>::zero();
+        for bit in BitIterator::new(P::ScalarField::characteristic()) {
+            r_p.double_in_place();
+            if bit {
+                r_p.add_assign_mixed(&p);
+            }
+        }
+        assert_eq!(p.is_in_correct_subgroup_assuming_on_curve(), r_p.is_zero());
+
+        let p = p.scale_by_cofactor().into_affine();
+        assert!(p.is_in_correct_subgroup_assuming_on_curve());
+    }
+}
+
+/// Checks that points sampled with `from_random_bytes`, which are not
+/// multiplied by the cofactor, are rejected by the subgroup check. Only
+/// meaningful for curves with a large cofactor, on which such a point is in
+/// the subgroup with negligible probability.
+pub fn sw_subgroup_non_member_test<P: SWModelParameters>() {
+    use algebra_core::{
+        curves::models::short_weierstrass_jacobian::{GroupAffine, GroupProjective},
+        BitIterator,
+    };
+    use rand::RngCore;
+
+    assert_ne!(P::COFACTOR, &[1]);
+    let buf_size = GroupAffine::<P>::zero().serialized_size();
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let mut num_tested = 0;
+    while num_tested < ITERATIONS {
+        let mut bytes = vec![0u8; buf_size];
+        rng.fill_bytes(&mut bytes);
+        let p = match GroupAffine::<P>::from_random_bytes(&bytes) {
+            Some(p) if !p.is_zero() => p,
+            _ => continue,
+        };
+        num_tested += 1;
+
+        assert!(p.is_on_curve());
+        assert!(!p.is_in_correct_subgroup_assuming_on_curve());
+        let mut r_p = GroupProjective::<P>::zero();
+        for bit in BitIterator::new(P::ScalarField::characteristic()) {
+            r_p.double_in_place();
+            if bit {
+                r_p.add_assign_mixed(&p);
+            }
+        }
+        assert!(!r_p.is_zero());
+        assert!(p
+            .mul_by_cofactor()
+            .is_in_correct_subgroup_assuming_on_curve());
+    }
+}
+
+pub fn sw_unchecked_serialization_test<P: SWModelParameters>() {
+    use algebra_core::curves::models::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    for i in 0..ITERATIONS {
+        let a = if i == 0 {
+            GroupAffine::<P>::zero()
+        } else {
+            GroupProjective::<P>::rand(&mut rng).into_affine()
+        };
+        let mut serialized = vec![0; a.uncompressed_size()];
+        let mut cursor = Cursor::new(&mut serialized[..]);
+        a.serialize_uncompressed(&mut cursor).unwrap();
+
+        let mut cursor = Cursor::new(&serialized[..]);
+        let b = GroupAffine::<P>::deserialize_unchecked(&mut cursor).unwrap();
+        assert_eq!(a, b);
+
+        let mut cursor = Cursor::new(&serialized[..serialized.len() - 1]);
+        GroupAffine::<P>::deserialize_unchecked(&mut cursor).unwrap_err();
+    }
+}
+
 pub fn sw_curve_serialization_test<P: SWModelParameters>() {
     use algebra_core::curves::models::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
 
@@ -424,6 +677,7 @@ where
 {
     edwards_curve_serialization_test::<P>();
     edwards_from_random_bytes::<P>();
+    edwards_unchecked_serialization_test::<P>();
 }
 
 pub fn edwards_from_random_bytes<P: TEModelParameters>()
@@ -465,6 +719,30 @@ where
     }
 }
 
+pub fn edwards_unchecked_serialization_test<P: TEModelParameters>() {
+    use algebra_core::curves::models::twisted_edwards_extended::{GroupAffine, GroupProjective};
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    for i in 0..ITERATIONS {
+        let a = if i == 0 {
+            GroupAffine::<P>::zero()
+        } else {
+            GroupProjective::<P>::rand(&mut rng).into_affine()
+        };
+        let mut serialized = vec![0; a.uncompressed_size()];
+        let mut cursor = Cursor::new(&mut serialized[..]);
+        a.serialize_uncompressed(&mut cursor).unwrap();
+
+        let mut cursor = Cursor::new(&serialized[..]);
+        let b = GroupAffine::<P>::deserialize_unchecked(&mut cursor).unwrap();
+        assert_eq!(a, b);
+
+        let mut cursor = Cursor::new(&serialized[..serialized.len() - 1]);
+        GroupAffine::<P>::deserialize_unchecked(&mut cursor).unwrap_err();
+    }
+}
+
 pub fn edwards_curve_serialization_test<P: TEModelParameters>() {
     use algebra_core::curves::models::twisted_edwards_extended::{GroupAffine, GroupProjective};
 
@@ -2,7 +2,7 @@
 use crate::{
     fields::{FftField, FftParameters, Field, LegendreSymbol, PrimeField, SquareRootField},
     io::Cursor,
-    Flags, SWFlags,
+    EdwardsFlags, Flags, SWFlags,
 };
 use algebra_core::buffer_bit_byte_size;
 use rand::{Rng, SeedableRng};
@@ -465,3 +465,56 @@ pub fn field_serialization_test<F: Field>(buf_size: usize) {
         }
     }
 }
+
+/// Serialization tests for fields whose modulus uses every bit of its
+/// `buf_size` bytes, so that flags spill over into an extra byte.
+pub fn field_serialization_full_width_test<F: Field>(buf_size: usize) {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    assert_eq!(F::SPARE_BITS, 0);
+    for _ in 0..ITERATIONS {
+        let a = F::rand(&mut rng);
+        assert_eq!(a.serialized_size(), buf_size);
+        assert_eq!(a.serialized_size_with_flags::<SWFlags>(), buf_size + 1);
+        assert_eq!(a.serialized_size_with_flags::<EdwardsFlags>(), buf_size + 1);
+        {
+            let mut serialized = vec![0u8; buf_size];
+            let mut cursor = Cursor::new(&mut serialized[..]);
+            a.serialize(&mut cursor).unwrap();
+
+            let mut cursor = Cursor::new(&serialized[..]);
+            let b = F::deserialize(&mut cursor).unwrap();
+            assert_eq!(a, b);
+        }
+
+        {
+            let mut serialized = vec![0u8; buf_size + 1];
+            let mut cursor = Cursor::new(&mut serialized[..]);
+            a.serialize_with_flags(&mut cursor, SWFlags::from_y_sign(true))
+                .unwrap();
+            let mut cursor = Cursor::new(&serialized[..]);
+            let (b, flags) = F::deserialize_with_flags::<_, SWFlags>(&mut cursor).unwrap();
+            assert_eq!(flags.is_positive(), Some(true));
+            assert!(!flags.is_infinity());
+            assert_eq!(a, b);
+        }
+
+        {
+            let mut serialized = vec![0u8; buf_size + 1];
+            let mut cursor = Cursor::new(&mut serialized[..]);
+            a.serialize_with_flags(&mut cursor, EdwardsFlags::from_y_sign(true))
+                .unwrap();
+            let mut cursor = Cursor::new(&serialized[..]);
+            let (b, flags) = F::deserialize_with_flags::<_, EdwardsFlags>(&mut cursor).unwrap();
+            assert!(flags.is_positive());
+            assert_eq!(a, b);
+        }
+
+        {
+            let mut serialized = vec![0u8; buf_size];
+            let mut cursor = Cursor::new(&mut serialized[..]);
+            a.serialize_with_flags(&mut cursor, SWFlags::from_y_sign(true))
+                .unwrap_err();
+        }
+    }
+}
@@ -1,7 +1,7 @@
 #![cfg(feature = "bls12_381")]
 use crate::bls12_381::{Fr, G1Projective};
 use algebra_core::{
-    msm::VariableBaseMSM, AffineCurve, PrimeField, ProjectiveCurve, UniformRand, Zero,
+    msm::VariableBaseMSM, AffineCurve, Field, One, PrimeField, ProjectiveCurve, UniformRand, Zero,
 };
 use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
@@ -55,3 +55,41 @@ fn test_with_bls12_unequal_numbers() {
 
     assert_eq!(naive.into_affine(), fast.into_affine());
 }
+
+#[test]
+fn test_with_bls12_edge_case_scalars() {
+    const SAMPLES: usize = 1 << 8;
+
+    let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+
+    // Small, unit, zero and maximal scalars over a handful of repeated bases,
+    // so that buckets see doublings and cancellations.
+    let v = (0..SAMPLES)
+        .map(|i| match i % 5 {
+            0 => Fr::zero(),
+            1 => Fr::one(),
+            2 => -Fr::one(),
+            3 => Fr::from((i as u64) << 3),
+            _ => Fr::rand(&mut rng),
+        })
+        .map(|s| s.into_repr())
+        .collect::<Vec<_>>();
+    let bases = (0..4)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+    let g = (0..SAMPLES)
+        .map(|i| match i % 7 {
+            0 => -bases[i % 4],
+            _ => bases[i % 4],
+        })
+        .collect::<Vec<_>>();
+
+    let naive = naive_var_base_msm(g.as_slice(), v.as_slice());
+    let fast = VariableBaseMSM::multi_scalar_mul(g.as_slice(), v.as_slice());
+
+    assert_eq!(naive.into_affine(), fast.into_affine());
+    assert_eq!(
+        VariableBaseMSM::multi_scalar_mul(&g[..1], &[Fr::one().double().into_repr()]),
+        g[0].into_projective().double()
+    );
+}
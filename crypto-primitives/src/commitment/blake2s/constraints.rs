@@ -1,9 +1,8 @@
 use r1cs_core::{ConstraintSystem, SynthesisError};
 
 use crate::{
-    commitment::blake2s::Blake2sCommitment,
-    prf::blake2s::constraints::{blake2s_gadget, Blake2sOutputGadget},
-    CommitmentGadget, Vec,
+    commitment::blake2s::Blake2sCommitment, crh::DigestGadget,
+    prf::blake2s::constraints::blake2s_gadget, CommitmentGadget, Vec,
 };
 use algebra_core::{Field, PrimeField};
 use r1cs_std::prelude::*;
@@ -21,7 +20,7 @@ pub struct Blake2sCommitmentGadget;
 impl<ConstraintF: PrimeField> CommitmentGadget<Blake2sCommitment, ConstraintF>
     for Blake2sCommitmentGadget
 {
-    type OutputGadget = Blake2sOutputGadget;
+    type OutputGadget = DigestGadget;
     type ParametersGadget = Blake2sParametersGadget;
     type RandomnessGadget = Blake2sRandomnessGadget;
 
@@ -43,7 +42,7 @@ impl<ConstraintF: PrimeField> CommitmentGadget<Blake2sCommitment, ConstraintF>
             let chunk = int.to_bytes(&mut cs.ns(|| format!("Result ToBytes {}", i)))?;
             result.extend_from_slice(&chunk);
         }
-        Ok(Blake2sOutputGadget(result))
+        Ok(DigestGadget(result))
     }
 }
 
@@ -7,6 +7,7 @@ use algebra_core::bytes::ToBytes;
 pub mod blake2s;
 pub mod injective_map;
 pub mod pedersen;
+pub mod poseidon;
 
 #[cfg(feature = "r1cs")]
 pub mod constraints;
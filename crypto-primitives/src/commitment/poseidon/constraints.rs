@@ -0,0 +1,102 @@
+use crate::{
+    commitment::{poseidon::PoseidonCommitment, CommitmentGadget},
+    crh::{
+        poseidon::{
+            constraints::{poseidon_sponge_gadget, PoseidonParametersGadget},
+            PoseidonRoundParams, COMMITMENT_DOMAIN,
+        },
+        utils::constraints::bytes_to_field_elements_gadget,
+    },
+};
+use algebra_core::PrimeField;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+use core::marker::PhantomData;
+
+pub struct PoseidonCommitmentGadget<F: PrimeField, P: PoseidonRoundParams> {
+    #[doc(hidden)]
+    _field: PhantomData<F>,
+    #[doc(hidden)]
+    _params: PhantomData<P>,
+}
+
+impl<F, P> CommitmentGadget<PoseidonCommitment<F, P>, F> for PoseidonCommitmentGadget<F, P>
+where
+    F: PrimeField,
+    P: PoseidonRoundParams,
+{
+    type OutputGadget = FpGadget<F>;
+    type ParametersGadget = PoseidonParametersGadget<F>;
+    type RandomnessGadget = FpGadget<F>;
+
+    fn check_commitment_gadget<CS: ConstraintSystem<F>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+        r: &Self::RandomnessGadget,
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        assert!(input.len() * 8 <= P::INPUT_SIZE_BITS);
+        let mut padded_input = input.to_vec();
+        padded_input.resize(P::INPUT_SIZE_BITS / 8, UInt8::constant(0u8));
+
+        let mut elements = bytes_to_field_elements_gadget::<F, CS>(&padded_input);
+        elements.push(r.clone());
+        poseidon_sponge_gadget::<F, P, _>(cs, &parameters.parameters, COMMITMENT_DOMAIN, &elements)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        commitment::poseidon::{constraints::PoseidonCommitmentGadget, PoseidonCommitment},
+        crh::poseidon::PoseidonRounds128,
+        *,
+    };
+    use algebra::{ed_on_bls12_381::Fq as Fr, test_rng, UniformRand};
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::{prelude::*, test_constraint_system::TestConstraintSystem};
+
+    type TestCOMM = PoseidonCommitment<Fr, PoseidonRounds128>;
+    type TestCOMMGadget = PoseidonCommitmentGadget<Fr, PoseidonRounds128>;
+
+    #[test]
+    fn commitment_gadget_test() {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+
+        let input = [1u8; 32];
+        let rng = &mut test_rng();
+        let randomness = Fr::rand(rng);
+        let parameters = TestCOMM::setup(rng).unwrap();
+        let primitive_result = TestCOMM::commit(&parameters, &input, &randomness).unwrap();
+        assert_ne!(
+            primitive_result,
+            TestCOMM::commit(&parameters, &input, &Fr::rand(rng)).unwrap()
+        );
+
+        let input_bytes = UInt8::alloc_vec(cs.ns(|| "input"), &input).unwrap();
+        let randomness_var =
+            <TestCOMMGadget as CommitmentGadget<TestCOMM, Fr>>::RandomnessGadget::alloc(
+                &mut cs.ns(|| "gadget_randomness"),
+                || Ok(&randomness),
+            )
+            .unwrap();
+        let gadget_parameters =
+            <TestCOMMGadget as CommitmentGadget<TestCOMM, Fr>>::ParametersGadget::alloc(
+                &mut cs.ns(|| "gadget_parameters"),
+                || Ok(&parameters),
+            )
+            .unwrap();
+        let gadget_result =
+            <TestCOMMGadget as CommitmentGadget<TestCOMM, Fr>>::check_commitment_gadget(
+                &mut cs.ns(|| "gadget_evaluation"),
+                &gadget_parameters,
+                &input_bytes,
+                &randomness_var,
+            )
+            .unwrap();
+
+        assert_eq!(primitive_result, gadget_result.value.unwrap());
+        assert!(cs.is_satisfied());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::Error;
+use algebra_core::PrimeField;
+use core::marker::PhantomData;
+use rand::Rng;
+
+use super::CommitmentScheme;
+use crate::crh::poseidon::{
+    pad_and_pack, poseidon_sponge, PoseidonParameters, PoseidonRoundParams, COMMITMENT_DOMAIN,
+};
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// The commitment `(input, r) -> Poseidon(input || r)` to inputs of at most
+/// `P::INPUT_SIZE_BITS` bits, which is hiding since the randomness `r` is a
+/// uniform field element.
+pub struct PoseidonCommitment<F: PrimeField, P: PoseidonRoundParams> {
+    field: PhantomData<F>,
+    params: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: PoseidonRoundParams> CommitmentScheme for PoseidonCommitment<F, P> {
+    type Output = F;
+    type Parameters = PoseidonParameters<F>;
+    type Randomness = F;
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, Error> {
+        Ok(PoseidonParameters::new::<P>())
+    }
+
+    fn commit(
+        parameters: &Self::Parameters,
+        input: &[u8],
+        randomness: &Self::Randomness,
+    ) -> Result<Self::Output, Error> {
+        let commit_time = start_timer!(|| "PoseidonCommitment::Commit");
+        let mut elements = pad_and_pack::<F, P>(input)?;
+        elements.push(*randomness);
+        let result = poseidon_sponge::<F, P>(parameters, COMMITMENT_DOMAIN, &elements);
+        end_timer!(commit_time);
+        Ok(result)
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{
+    crh::{blake2s::Blake2sCRH, CRHGadget, DigestGadget},
+    prf::blake2s::constraints::blake2s_gadget_with_parameters,
+    Vec,
+};
+use algebra_core::PrimeField;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+use core::borrow::Borrow;
+
+pub struct Blake2sCRHGadget;
+
+#[derive(Clone)]
+pub struct Blake2sCRHParametersGadget;
+
+impl<ConstraintF: PrimeField> CRHGadget<Blake2sCRH, ConstraintF> for Blake2sCRHGadget {
+    type OutputGadget = DigestGadget;
+    type ParametersGadget = Blake2sCRHParametersGadget;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        _parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        let input_bits = input
+            .iter()
+            .flat_map(|byte| byte.into_bits_le())
+            .collect::<Vec<_>>();
+        let parameters = Blake2sCRH::parameter_block().parameters();
+        let mut result = Vec::with_capacity(32);
+        for (i, int) in
+            blake2s_gadget_with_parameters(cs.ns(|| "Blake2s Eval"), &input_bits, &parameters)?
+                .into_iter()
+                .enumerate()
+        {
+            let chunk = int.to_bytes(&mut cs.ns(|| format!("Result ToBytes {}", i)))?;
+            result.extend_from_slice(&chunk);
+        }
+        Ok(DigestGadget(result))
+    }
+}
+
+impl<ConstraintF: PrimeField> AllocGadget<(), ConstraintF> for Blake2sCRHParametersGadget {
+    fn alloc_constant<T, CS: ConstraintSystem<ConstraintF>>(
+        _cs: CS,
+        _val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<()>,
+    {
+        Ok(Blake2sCRHParametersGadget)
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        _cs: CS,
+        _value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<()>,
+    {
+        Ok(Blake2sCRHParametersGadget)
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        _cs: CS,
+        _value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<()>,
+    {
+        Ok(Blake2sCRHParametersGadget)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crh::{
+        blake2s::{constraints::Blake2sCRHGadget, Blake2sCRH},
+        CRHGadget, CRH,
+    };
+    use algebra::{ed_on_bls12_381::Fq as Fr, test_rng};
+    use blake2::{Blake2s, Digest};
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::{prelude::*, test_constraint_system::TestConstraintSystem};
+    use rand::Rng;
+
+    type TestCRH = Blake2sCRH;
+    type TestCRHGadget = Blake2sCRHGadget;
+
+    #[test]
+    fn crh_primitive_gadget_test() {
+        let rng = &mut test_rng();
+        let parameters = TestCRH::setup(rng).unwrap();
+
+        for input_len in &[0, 1, 63, 64, 65, 200] {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let input = (0..*input_len).map(|_| rng.gen()).collect::<Vec<u8>>();
+            let input_bytes = UInt8::alloc_vec(cs.ns(|| "input"), &input).unwrap();
+
+            let primitive_result = TestCRH::evaluate(&parameters, &input).unwrap();
+            // The personalization separates the CRH from plain Blake2s.
+            assert_ne!(primitive_result[..], Blake2s::digest(&input)[..]);
+
+            let gadget_parameters =
+                <TestCRHGadget as CRHGadget<TestCRH, Fr>>::ParametersGadget::alloc(
+                    &mut cs.ns(|| "gadget_parameters"),
+                    || Ok(&parameters),
+                )
+                .unwrap();
+            let gadget_result = <TestCRHGadget as CRHGadget<TestCRH, Fr>>::check_evaluation_gadget(
+                &mut cs.ns(|| "gadget_evaluation"),
+                &gadget_parameters,
+                &input_bytes,
+            )
+            .unwrap();
+
+            let gadget_value = gadget_result
+                .0
+                .iter()
+                .map(|byte| byte.get_value().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(primitive_result[..], gadget_value[..]);
+            assert!(cs.is_satisfied());
+        }
+
+        // Zero bytes are not absorbed into the padding.
+        assert_ne!(
+            TestCRH::evaluate(&parameters, &[1u8, 0]).unwrap(),
+            TestCRH::evaluate(&parameters, &[1u8]).unwrap()
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{
+    crh::{CRH, CRH_PERSONALIZATION},
+    prf::blake2s::Blake2sWithParameterBlock,
+    Error,
+};
+use rand::Rng;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// Blake2s with a 32-byte digest, personalized with `CRH_PERSONALIZATION`.
+/// Blake2s itself encodes the input length in its final block.
+pub struct Blake2sCRH;
+
+impl Blake2sCRH {
+    pub(crate) fn parameter_block() -> Blake2sWithParameterBlock {
+        Blake2sWithParameterBlock {
+            digest_length: 32,
+            key_length: 0,
+            fan_out: 1,
+            depth: 1,
+            leaf_length: 0,
+            node_offset: 0,
+            xof_digest_length: 0,
+            node_depth: 0,
+            inner_length: 0,
+            salt: [0; 8],
+            personalization: CRH_PERSONALIZATION,
+        }
+    }
+}
+
+impl CRH for Blake2sCRH {
+    type Output = [u8; 32];
+    type Parameters = ();
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, Error> {
+        Ok(())
+    }
+
+    fn evaluate(_parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let eval_time = start_timer!(|| "Blake2sCRH::Eval");
+        let mut result = [0u8; 32];
+        result.copy_from_slice(&Self::parameter_block().evaluate(input));
+        end_timer!(eval_time);
+        Ok(result)
+    }
+}
@@ -1,7 +1,10 @@
-use algebra_core::Field;
-use core::fmt::Debug;
+use algebra_core::{Field, PrimeField};
+use core::{borrow::Borrow, fmt::Debug};
 
-use crate::crh::FixedLengthCRH;
+use crate::{
+    crh::{FixedLengthCRH, CRH},
+    Vec,
+};
 use r1cs_core::{ConstraintSystem, SynthesisError};
 
 use r1cs_std::prelude::*;
@@ -23,3 +26,153 @@ pub trait FixedLengthCRHGadget<H: FixedLengthCRH, ConstraintF: Field>: Sized {
         input: &[UInt8],
     ) -> Result<Self::OutputGadget, SynthesisError>;
 }
+
+pub trait CRHGadget<H: CRH, ConstraintF: Field>: Sized {
+    type OutputGadget: ConditionalEqGadget<ConstraintF>
+        + EqGadget<ConstraintF>
+        + ToBytesGadget<ConstraintF>
+        + CondSelectGadget<ConstraintF>
+        + AllocGadget<H::Output, ConstraintF>
+        + Debug
+        + Clone
+        + Sized;
+    type ParametersGadget: AllocGadget<H::Parameters, ConstraintF> + Clone;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError>;
+}
+
+/// The 32 bytes output by a hash function, such as Blake2s or SHA-256.
+#[derive(Clone, Debug)]
+pub struct DigestGadget(pub Vec<UInt8>);
+
+impl PartialEq for DigestGadget {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for DigestGadget {}
+
+impl<ConstraintF: PrimeField> EqGadget<ConstraintF> for DigestGadget {}
+
+impl<ConstraintF: PrimeField> ConditionalEqGadget<ConstraintF> for DigestGadget {
+    #[inline]
+    fn conditional_enforce_equal<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+        condition: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        for (i, (a, b)) in self.0.iter().zip(other.0.iter()).enumerate() {
+            a.conditional_enforce_equal(&mut cs.ns(|| format!("equal_{}", i)), b, condition)?;
+        }
+        Ok(())
+    }
+
+    fn cost() -> usize {
+        32 * <UInt8 as ConditionalEqGadget<ConstraintF>>::cost()
+    }
+}
+
+impl<ConstraintF: PrimeField> CondSelectGadget<ConstraintF> for DigestGadget {
+    fn conditionally_select<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        cond: &Boolean,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let mut bytes = Vec::with_capacity(true_value.0.len());
+        for (i, (t, f)) in true_value.0.iter().zip(&false_value.0).enumerate() {
+            let bits = t
+                .into_bits_le()
+                .iter()
+                .zip(f.into_bits_le())
+                .enumerate()
+                .map(|(j, (t, f))| {
+                    Boolean::conditionally_select(
+                        cs.ns(|| format!("select_{}_{}", i, j)),
+                        cond,
+                        t,
+                        &f,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            bytes.push(UInt8::from_bits_le(&bits));
+        }
+        Ok(DigestGadget(bytes))
+    }
+
+    fn cost() -> usize {
+        32 * 8 * <Boolean as CondSelectGadget<ConstraintF>>::cost()
+    }
+}
+
+impl<ConstraintF: PrimeField> ToBytesGadget<ConstraintF> for DigestGadget {
+    #[inline]
+    fn to_bytes<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        _cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        Ok(self.0.clone())
+    }
+}
+
+impl<ConstraintF: PrimeField> AllocGadget<[u8; 32], ConstraintF> for DigestGadget {
+    #[inline]
+    fn alloc_constant<T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<[u8; 32]>,
+    {
+        let mut bytes = vec![];
+        for (i, b) in val.borrow().iter().enumerate() {
+            bytes.push(UInt8::alloc_constant(cs.ns(|| format!("value {}", i)), b)?)
+        }
+
+        Ok(DigestGadget(bytes))
+    }
+
+    #[inline]
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<[u8; 32]>,
+    {
+        let zeros = [0u8; 32];
+        let value = match value_gen() {
+            Ok(val) => *(val.borrow()),
+            Err(_) => zeros,
+        };
+        let bytes = <UInt8>::alloc_vec(cs, &value)?;
+
+        Ok(DigestGadget(bytes))
+    }
+
+    #[inline]
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<[u8; 32]>,
+    {
+        let zeros = [0u8; 32];
+        let value = match value_gen() {
+            Ok(val) => *(val.borrow()),
+            Err(_) => zeros,
+        };
+        let bytes = <UInt8>::alloc_input_vec(cs, &value)?;
+
+        Ok(DigestGadget(bytes))
+    }
+}
@@ -0,0 +1,297 @@
+use crate::{
+    crh::{
+        keccak::{Keccak256, Sha3_256, KECCAK_DOMAIN, PI, RATE, RHO, ROUND_CONSTANTS, SHA3_DOMAIN},
+        CRHGadget, DigestGadget,
+    },
+    Vec,
+};
+use algebra_core::PrimeField;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::{bits::uint64::UInt64, prelude::*};
+
+use core::borrow::Borrow;
+
+fn rotl(x: &UInt64, by: u32) -> UInt64 {
+    x.rotr((64 - by as usize) % 64)
+}
+
+/// Applies Keccak-f[1600] to `state`, as `keccak_f1600` does. Only `theta`
+/// and `chi` cost constraints.
+pub fn keccak_f1600_gadget<ConstraintF, CS>(
+    mut cs: CS,
+    state: &mut [UInt64],
+) -> Result<(), SynthesisError>
+where
+    ConstraintF: PrimeField,
+    CS: ConstraintSystem<ConstraintF>,
+{
+    assert_eq!(state.len(), 25);
+    for (round, round_constant) in ROUND_CONSTANTS.iter().enumerate() {
+        let mut cs = cs.ns(|| format!("round {}", round));
+
+        // Theta
+        let mut c = Vec::with_capacity(5);
+        for x in 0..5 {
+            let mut cs = cs.ns(|| format!("theta column {}", x));
+            let mut acc = state[x].clone();
+            for y in 1..5 {
+                acc = acc.xor(cs.ns(|| format!("xor {}", y)), &state[x + 5 * y])?;
+            }
+            c.push(acc);
+        }
+        for x in 0..5 {
+            let mut cs = cs.ns(|| format!("theta update {}", x));
+            let d = c[(x + 4) % 5].xor(cs.ns(|| "d"), &rotl(&c[(x + 1) % 5], 1))?;
+            for y in 0..5 {
+                state[x + 5 * y] = state[x + 5 * y].xor(cs.ns(|| format!("xor {}", y)), &d)?;
+            }
+        }
+
+        // Rho and pi
+        let mut last = state[1].clone();
+        for (j, r) in PI.iter().zip(RHO.iter()) {
+            let tmp = state[*j].clone();
+            state[*j] = rotl(&last, *r);
+            last = tmp;
+        }
+
+        // Chi
+        for y in 0..5 {
+            let row = state[5 * y..5 * y + 5]
+                .iter()
+                .map(|lane| lane.to_bits_le())
+                .collect::<Vec<_>>();
+            for x in 0..5 {
+                let mut cs = cs.ns(|| format!("chi {} {}", x, y));
+                let bits = row[x]
+                    .iter()
+                    .zip(&row[(x + 1) % 5])
+                    .zip(&row[(x + 2) % 5])
+                    .enumerate()
+                    .map(|(i, ((a, b), c))| {
+                        let and = Boolean::and(cs.ns(|| format!("and {}", i)), &b.not(), c)?;
+                        Boolean::xor(cs.ns(|| format!("xor {}", i)), a, &and)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                state[x + 5 * y] = UInt64::from_bits_le(&bits);
+            }
+        }
+
+        // Iota
+        state[0] = state[0].xor(cs.ns(|| "iota"), &UInt64::constant(*round_constant))?;
+    }
+    Ok(())
+}
+
+/// Hashes `input` with the Keccak sponge, as `keccak_256_sponge` does. The
+/// length of `input` is fixed by the circuit.
+pub fn keccak_256_sponge_gadget<ConstraintF, CS>(
+    mut cs: CS,
+    input: &[UInt8],
+    domain: u8,
+) -> Result<Vec<UInt8>, SynthesisError>
+where
+    ConstraintF: PrimeField,
+    CS: ConstraintSystem<ConstraintF>,
+{
+    let num_zeros = RATE - 1 - input.len() % RATE;
+    let mut padding = vec![0u8; num_zeros + 1];
+    padding[0] = domain;
+    *padding.last_mut().unwrap() |= 0x80;
+    let mut padded_input = input.to_vec();
+    padded_input.extend(padding.into_iter().map(UInt8::constant));
+
+    let mut state = vec![UInt64::constant(0); 25];
+    for (i, block) in padded_input.chunks(RATE).enumerate() {
+        let mut cs = cs.ns(|| format!("block {}", i));
+        for (j, (lane, bytes)) in state.iter_mut().zip(block.chunks(8)).enumerate() {
+            let bits = bytes
+                .iter()
+                .flat_map(|byte| byte.into_bits_le())
+                .collect::<Vec<_>>();
+            *lane = lane.xor(
+                cs.ns(|| format!("absorb {}", j)),
+                &UInt64::from_bits_le(&bits),
+            )?;
+        }
+        keccak_f1600_gadget(cs.ns(|| "permute"), &mut state)?;
+    }
+
+    let mut result = Vec::with_capacity(32);
+    for (i, lane) in state.iter().take(4).enumerate() {
+        result.extend(lane.to_bytes(cs.ns(|| format!("squeeze {}", i)))?);
+    }
+    Ok(result)
+}
+
+pub struct Keccak256Gadget;
+
+pub struct Sha3_256Gadget;
+
+#[derive(Clone)]
+pub struct KeccakParametersGadget;
+
+impl<ConstraintF: PrimeField> CRHGadget<Keccak256, ConstraintF> for Keccak256Gadget {
+    type OutputGadget = DigestGadget;
+    type ParametersGadget = KeccakParametersGadget;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        _parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        Ok(DigestGadget(keccak_256_sponge_gadget(
+            cs,
+            input,
+            KECCAK_DOMAIN,
+        )?))
+    }
+}
+
+impl<ConstraintF: PrimeField> CRHGadget<Sha3_256, ConstraintF> for Sha3_256Gadget {
+    type OutputGadget = DigestGadget;
+    type ParametersGadget = KeccakParametersGadget;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        _parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        Ok(DigestGadget(keccak_256_sponge_gadget(
+            cs,
+            input,
+            SHA3_DOMAIN,
+        )?))
+    }
+}
+
+impl<ConstraintF: PrimeField> AllocGadget<(), ConstraintF> for KeccakParametersGadget {
+    fn alloc_constant<T, CS: ConstraintSystem<ConstraintF>>(
+        _cs: CS,
+        _val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<()>,
+    {
+        Ok(KeccakParametersGadget)
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        _cs: CS,
+        _value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<()>,
+    {
+        Ok(KeccakParametersGadget)
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        _cs: CS,
+        _value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<()>,
+    {
+        Ok(KeccakParametersGadget)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crh::{
+        keccak::{
+            constraints::{Keccak256Gadget, Sha3_256Gadget},
+            Keccak256, Sha3_256,
+        },
+        CRHGadget, CRH,
+    };
+    use algebra::{ed_on_bls12_381::Fq as Fr, test_rng};
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::{prelude::*, test_constraint_system::TestConstraintSystem};
+
+    fn hex_to_bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn check_gadget<H: CRH, HG: CRHGadget<H, Fr>>(input: &[u8], expected: &[u8])
+    where
+        HG::OutputGadget: ToBytesGadget<Fr>,
+    {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let input_bytes = UInt8::alloc_vec(cs.ns(|| "input"), input).unwrap();
+        let parameters = H::setup(&mut test_rng()).unwrap();
+        let parameters =
+            HG::ParametersGadget::alloc(&mut cs.ns(|| "gadget_parameters"), || Ok(&parameters))
+                .unwrap();
+        let result = HG::check_evaluation_gadget(
+            &mut cs.ns(|| "gadget_evaluation"),
+            &parameters,
+            &input_bytes,
+        )
+        .unwrap();
+
+        let result = result
+            .to_bytes(&mut cs.ns(|| "to_bytes"))
+            .unwrap()
+            .iter()
+            .map(|byte| byte.get_value().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(result, expected);
+        assert!(cs.is_satisfied());
+    }
+
+    const KECCAK_VECTORS: [(&str, &str); 2] = [
+        (
+            "",
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        ),
+        (
+            "abc",
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        ),
+    ];
+
+    // Test vectors from the NIST examples for FIPS 202.
+    const SHA3_VECTORS: [(&str, &str); 2] = [
+        (
+            "",
+            "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+        ),
+        (
+            "abc",
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+        ),
+    ];
+
+    /// The digest of 200 bytes `0xa3`, which span two blocks.
+    const SHA3_1600_BITS: &str = "79f38adec5c20307a98ef76e8324afbfd46cfd81b22e3973c65fa1bd9de31787";
+
+    #[test]
+    fn keccak_native_test() {
+        for (input, expected) in KECCAK_VECTORS.iter() {
+            let result = Keccak256::evaluate(&(), input.as_bytes()).unwrap();
+            assert_eq!(result[..], hex_to_bytes(expected)[..]);
+        }
+        for (input, expected) in SHA3_VECTORS.iter() {
+            let result = Sha3_256::evaluate(&(), input.as_bytes()).unwrap();
+            assert_eq!(result[..], hex_to_bytes(expected)[..]);
+        }
+        let result = Sha3_256::evaluate(&(), &[0xa3; 200]).unwrap();
+        assert_eq!(result[..], hex_to_bytes(SHA3_1600_BITS)[..]);
+    }
+
+    #[test]
+    fn keccak_gadget_test() {
+        let (input, expected) = KECCAK_VECTORS[1];
+        check_gadget::<Keccak256, Keccak256Gadget>(input.as_bytes(), &hex_to_bytes(expected));
+        let (input, expected) = SHA3_VECTORS[0];
+        check_gadget::<Sha3_256, Sha3_256Gadget>(input.as_bytes(), &hex_to_bytes(expected));
+        check_gadget::<Sha3_256, Sha3_256Gadget>(&[0xa3; 200], &hex_to_bytes(SHA3_1600_BITS));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{crh::CRH, Error};
+use rand::Rng;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// The number of bytes absorbed per permutation by the 256-bit variants,
+/// whose capacity is 512 bits.
+pub const RATE: usize = 136;
+
+/// The domain separation byte of the original Keccak padding, used by
+/// Ethereum.
+pub const KECCAK_DOMAIN: u8 = 0x01;
+/// The domain separation byte of FIPS 202, used by SHA3-256.
+pub const SHA3_DOMAIN: u8 = 0x06;
+
+pub(crate) const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The rotation offsets of the lanes visited by `PI`, in order.
+pub(crate) const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+/// The lanes visited by the combined `rho` and `pi` steps, starting from
+/// lane 1.
+pub(crate) const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// Applies Keccak-f[1600] to `state`, whose lane `(x, y)` is `state[x + 5y]`.
+pub fn keccak_f1600(state: &mut [u64; 25]) {
+    for round_constant in ROUND_CONSTANTS.iter() {
+        // Theta
+        let mut c = [0u64; 5];
+        for x in 0..5 {
+            c[x] = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[x + 5 * y] ^= d;
+            }
+        }
+
+        // Rho and pi
+        let mut last = state[1];
+        for (j, r) in PI.iter().zip(RHO.iter()) {
+            let tmp = state[*j];
+            state[*j] = last.rotate_left(*r);
+            last = tmp;
+        }
+
+        // Chi
+        for y in 0..5 {
+            let mut row = [0u64; 5];
+            row.copy_from_slice(&state[5 * y..5 * y + 5]);
+            for x in 0..5 {
+                state[x + 5 * y] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+
+        // Iota
+        state[0] ^= round_constant;
+    }
+}
+
+/// Hashes `input` with the Keccak sponge of rate `RATE`, padding it with
+/// `domain` and a final bit set, and squeezes 32 bytes.
+pub fn keccak_256_sponge(input: &[u8], domain: u8) -> [u8; 32] {
+    let mut padded_input = input.to_vec();
+    padded_input.push(domain);
+    while padded_input.len() % RATE != 0 {
+        padded_input.push(0);
+    }
+    *padded_input.last_mut().unwrap() |= 0x80;
+
+    let mut state = [0u64; 25];
+    for block in padded_input.chunks(RATE) {
+        for (lane, bytes) in state.iter_mut().zip(block.chunks(8)) {
+            let mut lane_bytes = [0u8; 8];
+            lane_bytes.copy_from_slice(bytes);
+            *lane ^= u64::from_le_bytes(lane_bytes);
+        }
+        keccak_f1600(&mut state);
+    }
+
+    let mut result = [0u8; 32];
+    for (bytes, lane) in result.chunks_mut(8).zip(state.iter()) {
+        bytes.copy_from_slice(&lane.to_le_bytes());
+    }
+    result
+}
+
+/// Keccak-256 as used by Ethereum, which predates the padding of FIPS 202.
+pub struct Keccak256;
+
+impl CRH for Keccak256 {
+    type Output = [u8; 32];
+    type Parameters = ();
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, Error> {
+        Ok(())
+    }
+
+    fn evaluate(_parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let eval_time = start_timer!(|| "Keccak256::Eval");
+        let result = keccak_256_sponge(input, KECCAK_DOMAIN);
+        end_timer!(eval_time);
+        Ok(result)
+    }
+}
+
+/// SHA3-256, as specified in FIPS 202.
+pub struct Sha3_256;
+
+impl CRH for Sha3_256 {
+    type Output = [u8; 32];
+    type Parameters = ();
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, Error> {
+        Ok(())
+    }
+
+    fn evaluate(_parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let eval_time = start_timer!(|| "Sha3_256::Eval");
+        let result = keccak_256_sponge(input, SHA3_DOMAIN);
+        end_timer!(eval_time);
+        Ok(result)
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    crh::{merkle_damgard::MerkleDamgardCRH, CRHGadget, FixedLengthCRH, FixedLengthCRHGadget},
+    Vec,
+};
+use algebra_core::Field;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+use core::marker::PhantomData;
+
+pub struct MerkleDamgardCRHGadget<H: FixedLengthCRH, HG> {
+    #[doc(hidden)]
+    _crh: PhantomData<H>,
+    #[doc(hidden)]
+    _crh_gadget: PhantomData<HG>,
+}
+
+impl<H, HG, ConstraintF> CRHGadget<MerkleDamgardCRH<H>, ConstraintF>
+    for MerkleDamgardCRHGadget<H, HG>
+where
+    H: FixedLengthCRH,
+    HG: FixedLengthCRHGadget<H, ConstraintF>,
+    ConstraintF: Field,
+{
+    type OutputGadget = HG::OutputGadget;
+    type ParametersGadget = HG::ParametersGadget;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        let (chaining_value_len, chunk_len) =
+            MerkleDamgardCRH::<H>::lengths().map_err(|_| SynthesisError::Unsatisfiable)?;
+
+        let mut padded_input = input.to_vec();
+        padded_input.extend(
+            MerkleDamgardCRH::<H>::padding(input.len(), chunk_len)
+                .into_iter()
+                .map(UInt8::constant),
+        );
+
+        let mut block: Vec<_> = MerkleDamgardCRH::<H>::initial_chaining_value(chaining_value_len)
+            .into_iter()
+            .map(UInt8::constant)
+            .collect();
+        let mut result = None;
+        for (i, chunk) in padded_input.chunks(chunk_len).enumerate() {
+            block.extend_from_slice(chunk);
+            let output =
+                HG::check_evaluation_gadget(cs.ns(|| format!("Chunk {}", i)), parameters, &block)?;
+            block = output.to_bytes(cs.ns(|| format!("Chunk {} ToBytes", i)))?;
+            assert_eq!(block.len(), chaining_value_len);
+            result = Some(output);
+        }
+        Ok(result.unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crh::{
+        bowe_hopwood::{constraints::BoweHopwoodPedersenCRHGadget, BoweHopwoodPedersenCRH},
+        merkle_damgard::{constraints::MerkleDamgardCRHGadget, MerkleDamgardCRH},
+        pedersen::{constraints::PedersenCRHGadget, PedersenCRH, PedersenWindow},
+        CRHGadget, CRH,
+    };
+    use algebra::{
+        ed_on_bls12_381::{EdwardsAffine as JubJub, Fq as Fr},
+        test_rng,
+    };
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::{
+        ed_on_bls12_381::EdwardsGadget, prelude::*, test_constraint_system::TestConstraintSystem,
+    };
+    use rand::Rng;
+
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    pub(super) struct Window;
+
+    impl PedersenWindow for Window {
+        const WINDOW_SIZE: usize = 128;
+        const NUM_WINDOWS: usize = 8;
+    }
+
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    pub(super) struct BoweHopwoodWindow;
+
+    impl PedersenWindow for BoweHopwoodWindow {
+        const WINDOW_SIZE: usize = 63;
+        const NUM_WINDOWS: usize = 16;
+    }
+
+    fn primitive_gadget_test<H: CRH, HG: CRHGadget<H, Fr>>()
+    where
+        HG::OutputGadget: AllocGadget<H::Output, Fr>,
+        H::Output: core::fmt::Debug,
+    {
+        let rng = &mut test_rng();
+        let parameters = H::setup(rng).unwrap();
+
+        for input_len in &[0, 1, 55, 56, 57, 100] {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let input = (0..*input_len).map(|_| rng.gen()).collect::<Vec<u8>>();
+            let input_bytes = UInt8::alloc_vec(cs.ns(|| "input"), &input).unwrap();
+
+            let primitive_result = H::evaluate(&parameters, &input).unwrap();
+
+            let gadget_parameters =
+                HG::ParametersGadget::alloc(&mut cs.ns(|| "gadget_parameters"), || Ok(&parameters))
+                    .unwrap();
+            let gadget_result = HG::check_evaluation_gadget(
+                &mut cs.ns(|| "gadget_evaluation"),
+                &gadget_parameters,
+                &input_bytes,
+            )
+            .unwrap();
+
+            let expected =
+                HG::OutputGadget::alloc(&mut cs.ns(|| "expected"), || Ok(&primitive_result))
+                    .unwrap();
+            gadget_result
+                .enforce_equal(&mut cs.ns(|| "check"), &expected)
+                .unwrap();
+            assert!(cs.is_satisfied());
+
+            // The length padding separates inputs with trailing zeros.
+            let mut extended_input = input.clone();
+            extended_input.push(0u8);
+            assert_ne!(
+                primitive_result,
+                H::evaluate(&parameters, &extended_input).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn pedersen_primitive_gadget_test() {
+        primitive_gadget_test::<
+            MerkleDamgardCRH<PedersenCRH<JubJub, Window>>,
+            MerkleDamgardCRHGadget<
+                PedersenCRH<JubJub, Window>,
+                PedersenCRHGadget<JubJub, Fr, EdwardsGadget>,
+            >,
+        >();
+    }
+
+    #[test]
+    fn bowe_hopwood_primitive_gadget_test() {
+        type H = BoweHopwoodPedersenCRH<JubJub, BoweHopwoodWindow>;
+        primitive_gadget_test::<
+            MerkleDamgardCRH<H>,
+            MerkleDamgardCRHGadget<H, BoweHopwoodPedersenCRHGadget<JubJub, Fr, EdwardsGadget>>,
+        >();
+    }
+
+    #[test]
+    fn short_crh_test() {
+        #[derive(Clone)]
+        struct ShortWindow;
+
+        impl PedersenWindow for ShortWindow {
+            const WINDOW_SIZE: usize = 64;
+            const NUM_WINDOWS: usize = 8;
+        }
+
+        type H = MerkleDamgardCRH<PedersenCRH<JubJub, ShortWindow>>;
+        let parameters = H::setup(&mut test_rng()).unwrap();
+        assert!(H::evaluate(&parameters, &[0u8; 8]).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    crh::{FixedLengthCRH, CRH, CRH_PERSONALIZATION},
+    Box, CryptoError, Error, Vec,
+};
+use algebra_core::{to_bytes, ToBytes};
+use core::marker::PhantomData;
+use rand::Rng;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// Extends the fixed-length CRH `H` to inputs of any length with the
+/// Merkle-Damgård construction. The input is padded with zeros and the
+/// 64-bit little-endian length of the input in bits, and is absorbed in
+/// chunks, each of which `H` hashes together with the bytes of the previous
+/// output. The first chaining value is `CRH_PERSONALIZATION`, padded with
+/// zeros.
+///
+/// The bytes of `H::Output` must match those of the corresponding output
+/// gadget, as they do for affine groups and `PedersenCRHCompressor`, and the
+/// input of `H` must exceed them by at least 8 bytes.
+pub struct MerkleDamgardCRH<H: FixedLengthCRH> {
+    crh: PhantomData<H>,
+}
+
+impl<H: FixedLengthCRH> MerkleDamgardCRH<H> {
+    /// Returns the length of the chaining value and of the chunks of
+    /// message absorbed by each evaluation of `H`.
+    pub(crate) fn lengths() -> Result<(usize, usize), Error> {
+        let block_len = H::INPUT_SIZE_BITS / 8;
+        let chaining_value_len = to_bytes![H::Output::default()]?.len();
+        if block_len < chaining_value_len + 8 {
+            return Err(Box::new(CryptoError::IncorrectInputLength(block_len)));
+        }
+        Ok((chaining_value_len, block_len - chaining_value_len))
+    }
+
+    pub(crate) fn initial_chaining_value(chaining_value_len: usize) -> Vec<u8> {
+        let mut chaining_value = vec![0u8; chaining_value_len];
+        for (c, p) in chaining_value.iter_mut().zip(&CRH_PERSONALIZATION) {
+            *c = *p;
+        }
+        chaining_value
+    }
+
+    /// Returns the bytes appended to an input of `input_len` bytes.
+    pub(crate) fn padding(input_len: usize, chunk_len: usize) -> Vec<u8> {
+        let num_zeros = (chunk_len - (input_len + 8) % chunk_len) % chunk_len;
+        let mut padding = vec![0u8; num_zeros];
+        padding.extend_from_slice(&(input_len as u64 * 8).to_le_bytes());
+        padding
+    }
+}
+
+impl<H: FixedLengthCRH> CRH for MerkleDamgardCRH<H> {
+    type Output = H::Output;
+    type Parameters = H::Parameters;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        H::setup(rng)
+    }
+
+    fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let eval_time = start_timer!(|| "MerkleDamgardCRH::Eval");
+        let (chaining_value_len, chunk_len) = Self::lengths()?;
+
+        let mut padded_input = input.to_vec();
+        padded_input.extend(Self::padding(input.len(), chunk_len));
+
+        let mut block = Self::initial_chaining_value(chaining_value_len);
+        let mut result = H::Output::default();
+        for chunk in padded_input.chunks(chunk_len) {
+            block.extend_from_slice(chunk);
+            result = H::evaluate(parameters, &block)?;
+            block = to_bytes![result]?;
+        }
+        end_timer!(eval_time);
+        Ok(result)
+    }
+}
@@ -0,0 +1,310 @@
+use crate::{
+    crh::{
+        mimc::{MiMCCRH, MiMCFeistelCRH, MiMCParameters, MiMCRoundParams, CRH_DOMAIN},
+        utils::constraints::bytes_to_field_elements_gadget,
+        FixedLengthCRHGadget,
+    },
+    Vec,
+};
+use algebra_core::PrimeField;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+use core::{borrow::Borrow, marker::PhantomData};
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "F: PrimeField"))]
+pub struct MiMCParametersGadget<F: PrimeField> {
+    pub(crate) parameters: MiMCParameters<F>,
+}
+
+/// Encrypts `x` under `key`, as `mimc_encrypt` does.
+pub fn mimc_encrypt_gadget<F, P, CS>(
+    mut cs: CS,
+    parameters: &MiMCParameters<F>,
+    key: &FpGadget<F>,
+    x: &FpGadget<F>,
+) -> Result<FpGadget<F>, SynthesisError>
+where
+    F: PrimeField,
+    P: MiMCRoundParams,
+    CS: ConstraintSystem<F>,
+{
+    let mut x = x.clone();
+    for (i, c) in parameters
+        .round_constants
+        .iter()
+        .take(P::ROUNDS)
+        .enumerate()
+    {
+        let mut cs = cs.ns(|| format!("Round {}", i));
+        x = x
+            .add(cs.ns(|| "Add key"), key)?
+            .add_constant(cs.ns(|| "Add constant"), c)?
+            .pow_by_constant(cs.ns(|| "Exponentiate"), &[P::EXPONENT])?;
+    }
+    x.add(cs.ns(|| "Add key"), key)
+}
+
+/// Hashes `input` as `mimc_hash` does.
+pub fn mimc_hash_gadget<F, P, CS>(
+    mut cs: CS,
+    parameters: &MiMCParameters<F>,
+    domain: u64,
+    input: &[FpGadget<F>],
+) -> Result<FpGadget<F>, SynthesisError>
+where
+    F: PrimeField,
+    P: MiMCRoundParams,
+    CS: ConstraintSystem<F>,
+{
+    let mut h =
+        FpGadget::zero(cs.ns(|| "zero"))?.add_constant(cs.ns(|| "domain"), &F::from(domain))?;
+    for (i, m) in input.iter().enumerate() {
+        let mut cs = cs.ns(|| format!("Absorb {}", i));
+        h = mimc_encrypt_gadget::<F, P, _>(cs.ns(|| "Encrypt"), parameters, &h, m)?
+            .add(cs.ns(|| "Add key"), &h)?
+            .add(cs.ns(|| "Add message"), m)?;
+    }
+    Ok(h)
+}
+
+/// Applies the Feistel permutation as `mimc_feistel_permutation` does.
+pub fn mimc_feistel_permutation_gadget<F, P, CS>(
+    mut cs: CS,
+    parameters: &MiMCParameters<F>,
+    xl: &FpGadget<F>,
+    xr: &FpGadget<F>,
+) -> Result<(FpGadget<F>, FpGadget<F>), SynthesisError>
+where
+    F: PrimeField,
+    P: MiMCRoundParams,
+    CS: ConstraintSystem<F>,
+{
+    let mut xl = xl.clone();
+    let mut xr = xr.clone();
+    for (i, c) in parameters
+        .round_constants
+        .iter()
+        .take(P::ROUNDS)
+        .enumerate()
+    {
+        let mut cs = cs.ns(|| format!("Round {}", i));
+        let tmp = xl
+            .add_constant(cs.ns(|| "Add constant"), c)?
+            .pow_by_constant(cs.ns(|| "Exponentiate"), &[P::EXPONENT])?
+            .add(cs.ns(|| "Add xr"), &xr)?;
+        xr = xl;
+        xl = tmp;
+    }
+    Ok((xl, xr))
+}
+
+/// Hashes `input` as `mimc_feistel_sponge` does.
+pub fn mimc_feistel_sponge_gadget<F, P, CS>(
+    mut cs: CS,
+    parameters: &MiMCParameters<F>,
+    domain: u64,
+    input: &[FpGadget<F>],
+) -> Result<FpGadget<F>, SynthesisError>
+where
+    F: PrimeField,
+    P: MiMCRoundParams,
+    CS: ConstraintSystem<F>,
+{
+    let mut xl = FpGadget::zero(cs.ns(|| "xl"))?;
+    let mut xr =
+        FpGadget::zero(cs.ns(|| "xr"))?.add_constant(cs.ns(|| "domain"), &F::from(domain))?;
+    for (i, m) in input.iter().enumerate() {
+        let mut cs = cs.ns(|| format!("Absorb {}", i));
+        let absorbed = xl.add(cs.ns(|| "Add message"), m)?;
+        let (new_xl, new_xr) = mimc_feistel_permutation_gadget::<F, P, _>(
+            cs.ns(|| "Permute"),
+            parameters,
+            &absorbed,
+            &xr,
+        )?;
+        xl = new_xl;
+        xr = new_xr;
+    }
+    Ok(xl)
+}
+
+/// Pads `input` with zeros to `P::INPUT_SIZE_BITS` and packs it into field
+/// elements, as `pad_and_pack` does.
+pub(crate) fn pad_and_pack_gadget<F, P, CS>(input: &[UInt8]) -> Vec<FpGadget<F>>
+where
+    F: PrimeField,
+    P: MiMCRoundParams,
+    CS: ConstraintSystem<F>,
+{
+    assert!(input.len() * 8 <= P::INPUT_SIZE_BITS);
+    let mut padded_input = input.to_vec();
+    padded_input.resize(P::INPUT_SIZE_BITS / 8, UInt8::constant(0u8));
+    bytes_to_field_elements_gadget::<F, CS>(&padded_input)
+}
+
+pub struct MiMCCRHGadget<F: PrimeField, P: MiMCRoundParams> {
+    #[doc(hidden)]
+    _field: PhantomData<F>,
+    #[doc(hidden)]
+    _params: PhantomData<P>,
+}
+
+impl<F, P> FixedLengthCRHGadget<MiMCCRH<F, P>, F> for MiMCCRHGadget<F, P>
+where
+    F: PrimeField,
+    P: MiMCRoundParams,
+{
+    type OutputGadget = FpGadget<F>;
+    type ParametersGadget = MiMCParametersGadget<F>;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<F>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        let elements = pad_and_pack_gadget::<F, P, CS>(input);
+        mimc_hash_gadget::<F, P, _>(cs, &parameters.parameters, CRH_DOMAIN, &elements)
+    }
+}
+
+pub struct MiMCFeistelCRHGadget<F: PrimeField, P: MiMCRoundParams> {
+    #[doc(hidden)]
+    _field: PhantomData<F>,
+    #[doc(hidden)]
+    _params: PhantomData<P>,
+}
+
+impl<F, P> FixedLengthCRHGadget<MiMCFeistelCRH<F, P>, F> for MiMCFeistelCRHGadget<F, P>
+where
+    F: PrimeField,
+    P: MiMCRoundParams,
+{
+    type OutputGadget = FpGadget<F>;
+    type ParametersGadget = MiMCParametersGadget<F>;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<F>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        let elements = pad_and_pack_gadget::<F, P, CS>(input);
+        mimc_feistel_sponge_gadget::<F, P, _>(cs, &parameters.parameters, CRH_DOMAIN, &elements)
+    }
+}
+
+impl<F: PrimeField> AllocGadget<MiMCParameters<F>, F> for MiMCParametersGadget<F> {
+    fn alloc_constant<T, CS: ConstraintSystem<F>>(_cs: CS, val: T) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<MiMCParameters<F>>,
+    {
+        Ok(Self {
+            parameters: val.borrow().clone(),
+        })
+    }
+
+    fn alloc<FN, T, CS: ConstraintSystem<F>>(cs: CS, value_gen: FN) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<MiMCParameters<F>>,
+    {
+        let parameters = value_gen()?.borrow().clone();
+        Self::alloc_constant(cs, parameters)
+    }
+
+    fn alloc_input<FN, T, CS: ConstraintSystem<F>>(
+        cs: CS,
+        value_gen: FN,
+    ) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<MiMCParameters<F>>,
+    {
+        let parameters = value_gen()?.borrow().clone();
+        Self::alloc_constant(cs, parameters)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crh::{
+        mimc::{
+            constraints::{MiMCCRHGadget, MiMCFeistelCRHGadget},
+            MiMCCRH, MiMCFeistelCRH, MiMCRoundParams,
+        },
+        FixedLengthCRH, FixedLengthCRHGadget,
+    };
+    use algebra::{ed_on_bls12_381::Fq as Fr, test_rng};
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::{prelude::*, test_constraint_system::TestConstraintSystem};
+    use rand::Rng;
+
+    #[derive(Clone)]
+    pub(crate) struct Rounds;
+
+    impl MiMCRoundParams for Rounds {
+        const ROUNDS: usize = 110;
+        const EXPONENT: u64 = 5;
+        const INPUT_SIZE_BITS: usize = 256;
+    }
+
+    #[derive(Clone)]
+    pub(crate) struct FeistelRounds;
+
+    impl MiMCRoundParams for FeistelRounds {
+        const ROUNDS: usize = 322;
+        const EXPONENT: u64 = 3;
+        const INPUT_SIZE_BITS: usize = 256;
+    }
+
+    fn crh_primitive_gadget_test<H, HG>()
+    where
+        H: FixedLengthCRH<Output = Fr>,
+        HG: FixedLengthCRHGadget<H, Fr, OutputGadget = FpGadget<Fr>>,
+    {
+        let rng = &mut test_rng();
+        let parameters = H::setup(rng).unwrap();
+
+        for input_len in &[0, 1, 31, 32] {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let input = (0..*input_len).map(|_| rng.gen()).collect::<Vec<u8>>();
+            let input_bytes = UInt8::alloc_vec(cs.ns(|| "input"), &input).unwrap();
+            let num_input_constraints = cs.num_constraints();
+
+            let primitive_result = H::evaluate(&parameters, &input).unwrap();
+
+            let gadget_parameters =
+                HG::ParametersGadget::alloc(&mut cs.ns(|| "gadget_parameters"), || Ok(&parameters))
+                    .unwrap();
+            let gadget_result = HG::check_evaluation_gadget(
+                &mut cs.ns(|| "gadget_evaluation"),
+                &gadget_parameters,
+                &input_bytes,
+            )
+            .unwrap();
+            println!(
+                "number of constraints for evaluation: {}",
+                cs.num_constraints() - num_input_constraints
+            );
+
+            assert_eq!(primitive_result, gadget_result.value.unwrap());
+            assert!(cs.is_satisfied());
+        }
+
+        assert!(H::evaluate(&parameters, &[0u8; 33]).is_err());
+    }
+
+    #[test]
+    fn mimc_crh_primitive_gadget_test() {
+        crh_primitive_gadget_test::<MiMCCRH<Fr, Rounds>, MiMCCRHGadget<Fr, Rounds>>();
+    }
+
+    #[test]
+    fn mimc_feistel_crh_primitive_gadget_test() {
+        crh_primitive_gadget_test::<
+            MiMCFeistelCRH<Fr, FeistelRounds>,
+            MiMCFeistelCRHGadget<Fr, FeistelRounds>,
+        >();
+    }
+}
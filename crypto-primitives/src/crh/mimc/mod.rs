@@ -0,0 +1,180 @@
+use crate::{
+    crh::{
+        utils::{bytes_to_field_elements, cache_per_type, field_elements_from_seed},
+        FixedLengthCRH,
+    },
+    Box, CryptoError, Error, Vec,
+};
+use algebra_core::PrimeField;
+use core::marker::PhantomData;
+use rand::Rng;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// The domain with which `MiMCCRH` and `MiMCFeistelCRH` initialize their
+/// state.
+pub const CRH_DOMAIN: u64 = 0;
+/// The domain of `MiMCPRF` and `MiMCFeistelPRF`.
+pub const PRF_DOMAIN: u64 = 1;
+
+/// The shape of a MiMC permutation with round function `x -> x^EXPONENT`,
+/// following http://eprint.iacr.org/2016/492.
+///
+/// Over a field of `n` bits, MiMC-n/n needs `ceil(n / log2(EXPONENT))`
+/// rounds, and `EXPONENT` must be coprime to `p - 1`; over the scalar field
+/// of BLS12-381 this means `EXPONENT = 5` and 110 rounds. MiMC-2n/n, the
+/// Feistel variant, needs twice as many rounds but accepts any exponent,
+/// e.g. `EXPONENT = 3` and 322 rounds.
+pub trait MiMCRoundParams: Clone + 'static {
+    const ROUNDS: usize;
+    const EXPONENT: u64;
+    /// The maximum input length of `MiMCCRH` and `MiMCFeistelCRH`.
+    const INPUT_SIZE_BITS: usize;
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct MiMCParameters<F: PrimeField> {
+    /// One constant for each round.
+    pub round_constants: Vec<F>,
+}
+
+impl<F: PrimeField> MiMCParameters<F> {
+    /// Derives the round constants of `P` deterministically with
+    /// `field_elements_from_seed`.
+    pub fn new<P: MiMCRoundParams>() -> Self {
+        let mut seed = b"MiMC".to_vec();
+        seed.extend_from_slice(&(P::ROUNDS as u64).to_le_bytes());
+        seed.extend_from_slice(&P::EXPONENT.to_le_bytes());
+        Self {
+            round_constants: field_elements_from_seed(&seed, P::ROUNDS),
+        }
+    }
+
+    /// Returns `new::<P>()`, which is derived only once per thread if `std`
+    /// is enabled, as `PoseidonParameters::cached` does.
+    pub fn cached<P: MiMCRoundParams>() -> Self {
+        cache_per_type::<(F, P), _, _>(Self::new::<P>)
+    }
+}
+
+/// Encrypts `x` under `key` with MiMC-n/n: each round maps `x` to
+/// `(x + key + c_i)^EXPONENT`, and `key` is added to the result.
+pub fn mimc_encrypt<F: PrimeField, P: MiMCRoundParams>(
+    parameters: &MiMCParameters<F>,
+    key: F,
+    mut x: F,
+) -> F {
+    for c in parameters.round_constants.iter().take(P::ROUNDS) {
+        x = (x + &key + c).pow(&[P::EXPONENT]);
+    }
+    x + &key
+}
+
+/// Hashes `input` with MiMC-n/n in the Miyaguchi-Preneel mode, starting
+/// from `domain`: `h <- E_h(m) + h + m` for each element `m`.
+pub fn mimc_hash<F: PrimeField, P: MiMCRoundParams>(
+    parameters: &MiMCParameters<F>,
+    domain: u64,
+    input: &[F],
+) -> F {
+    input.iter().fold(F::from(domain), |h, m| {
+        mimc_encrypt::<F, P>(parameters, h, *m) + &h + m
+    })
+}
+
+/// Applies the MiMC-2n/n Feistel permutation to `(xl, xr)`: each round
+/// maps `(xl, xr)` to `(xr + (xl + c_i)^EXPONENT, xl)`.
+pub fn mimc_feistel_permutation<F: PrimeField, P: MiMCRoundParams>(
+    parameters: &MiMCParameters<F>,
+    mut xl: F,
+    mut xr: F,
+) -> (F, F) {
+    for c in parameters.round_constants.iter().take(P::ROUNDS) {
+        let tmp = xr + &(xl + c).pow(&[P::EXPONENT]);
+        xr = xl;
+        xl = tmp;
+    }
+    (xl, xr)
+}
+
+/// Hashes `input` with a sponge over the Feistel permutation, which absorbs
+/// into `xl` and squeezes `xl`. The capacity element `xr` is initialized to
+/// `domain`.
+pub fn mimc_feistel_sponge<F: PrimeField, P: MiMCRoundParams>(
+    parameters: &MiMCParameters<F>,
+    domain: u64,
+    input: &[F],
+) -> F {
+    let (xl, _) = input
+        .iter()
+        .fold((F::zero(), F::from(domain)), |(xl, xr), m| {
+            mimc_feistel_permutation::<F, P>(parameters, xl + m, xr)
+        });
+    xl
+}
+
+/// Pads `input` with zeros to `P::INPUT_SIZE_BITS` and packs it into field
+/// elements.
+pub(crate) fn pad_and_pack<F: PrimeField, P: MiMCRoundParams>(
+    input: &[u8],
+) -> Result<Vec<F>, Error> {
+    if input.len() * 8 > P::INPUT_SIZE_BITS {
+        return Err(Box::new(CryptoError::IncorrectInputLength(input.len())));
+    }
+    let mut padded_input = input.to_vec();
+    padded_input.resize(P::INPUT_SIZE_BITS / 8, 0u8);
+    Ok(bytes_to_field_elements(&padded_input))
+}
+
+pub struct MiMCCRH<F: PrimeField, P: MiMCRoundParams> {
+    field: PhantomData<F>,
+    params: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: MiMCRoundParams> FixedLengthCRH for MiMCCRH<F, P> {
+    const INPUT_SIZE_BITS: usize = P::INPUT_SIZE_BITS;
+    type Output = F;
+    type Parameters = MiMCParameters<F>;
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, Error> {
+        let time = start_timer!(|| format!("MiMCCRH::Setup: {} rounds", P::ROUNDS));
+        let parameters = MiMCParameters::new::<P>();
+        end_timer!(time);
+        Ok(parameters)
+    }
+
+    fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let eval_time = start_timer!(|| "MiMCCRH::Eval");
+        let elements = pad_and_pack::<F, P>(input)?;
+        let result = mimc_hash::<F, P>(parameters, CRH_DOMAIN, &elements);
+        end_timer!(eval_time);
+        Ok(result)
+    }
+}
+
+pub struct MiMCFeistelCRH<F: PrimeField, P: MiMCRoundParams> {
+    field: PhantomData<F>,
+    params: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: MiMCRoundParams> FixedLengthCRH for MiMCFeistelCRH<F, P> {
+    const INPUT_SIZE_BITS: usize = P::INPUT_SIZE_BITS;
+    type Output = F;
+    type Parameters = MiMCParameters<F>;
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, Error> {
+        let time = start_timer!(|| format!("MiMCFeistelCRH::Setup: {} rounds", P::ROUNDS));
+        let parameters = MiMCParameters::new::<P>();
+        end_timer!(time);
+        Ok(parameters)
+    }
+
+    fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let eval_time = start_timer!(|| "MiMCFeistelCRH::Eval");
+        let elements = pad_and_pack::<F, P>(input)?;
+        let result = mimc_feistel_sponge::<F, P>(parameters, CRH_DOMAIN, &elements);
+        end_timer!(eval_time);
+        Ok(result)
+    }
+}
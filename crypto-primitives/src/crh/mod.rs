@@ -2,9 +2,16 @@ use algebra_core::bytes::ToBytes;
 use core::hash::Hash;
 use rand::Rng;
 
+pub mod blake2s;
 pub mod bowe_hopwood;
 pub mod injective_map;
+pub mod keccak;
+pub mod merkle_damgard;
+pub mod mimc;
 pub mod pedersen;
+pub mod poseidon;
+pub mod sha256;
+pub mod utils;
 
 use crate::Error;
 
@@ -21,3 +28,16 @@ pub trait FixedLengthCRH {
     fn setup<R: Rng>(r: &mut R) -> Result<Self::Parameters, Error>;
     fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error>;
 }
+
+/// The personalization with which implementations of `CRH` separate their
+/// outputs from other uses of the underlying hash function.
+pub const CRH_PERSONALIZATION: [u8; 8] = *b"ZexeVCRH";
+
+/// A collision-resistant hash function on inputs of any length.
+pub trait CRH {
+    type Output: ToBytes + Clone + Eq + Hash + Default;
+    type Parameters: Clone + Default;
+
+    fn setup<R: Rng>(r: &mut R) -> Result<Self::Parameters, Error>;
+    fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error>;
+}
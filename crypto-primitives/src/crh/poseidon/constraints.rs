@@ -0,0 +1,215 @@
+use crate::{
+    crh::{
+        poseidon::{PoseidonCRH, PoseidonParameters, PoseidonRoundParams, CRH_DOMAIN},
+        utils::constraints::bytes_to_field_elements_gadget,
+        FixedLengthCRHGadget,
+    },
+    Vec,
+};
+use algebra_core::PrimeField;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+use core::{borrow::Borrow, marker::PhantomData};
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "F: PrimeField"))]
+pub struct PoseidonParametersGadget<F: PrimeField> {
+    pub(crate) parameters: PoseidonParameters<F>,
+}
+
+pub struct PoseidonCRHGadget<F: PrimeField, P: PoseidonRoundParams> {
+    #[doc(hidden)]
+    _field: PhantomData<F>,
+    #[doc(hidden)]
+    _params: PhantomData<P>,
+}
+
+impl<F, P> FixedLengthCRHGadget<PoseidonCRH<F, P>, F> for PoseidonCRHGadget<F, P>
+where
+    F: PrimeField,
+    P: PoseidonRoundParams,
+{
+    type OutputGadget = FpGadget<F>;
+    type ParametersGadget = PoseidonParametersGadget<F>;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<F>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        assert!(input.len() * 8 <= P::INPUT_SIZE_BITS);
+        let mut padded_input = input.to_vec();
+        padded_input.resize(P::INPUT_SIZE_BITS / 8, UInt8::constant(0u8));
+
+        let elements = bytes_to_field_elements_gadget::<F, CS>(&padded_input);
+        poseidon_sponge_gadget::<F, P, _>(cs, &parameters.parameters, CRH_DOMAIN, &elements)
+    }
+}
+
+/// Applies the Poseidon permutation to `state`. Only the S-boxes cost
+/// constraints.
+pub fn poseidon_permutation_gadget<F, P, CS>(
+    mut cs: CS,
+    parameters: &PoseidonParameters<F>,
+    state: &mut [FpGadget<F>],
+) -> Result<(), SynthesisError>
+where
+    F: PrimeField,
+    P: PoseidonRoundParams,
+    CS: ConstraintSystem<F>,
+{
+    assert_eq!(state.len(), P::WIDTH);
+    let half_full_rounds = P::FULL_ROUNDS / 2;
+    let num_rounds = P::FULL_ROUNDS + P::PARTIAL_ROUNDS;
+    for (round, constants) in parameters
+        .round_constants
+        .chunks(P::WIDTH)
+        .take(num_rounds)
+        .enumerate()
+    {
+        let mut cs = cs.ns(|| format!("Round {}", round));
+        for (i, (s, c)) in state.iter_mut().zip(constants).enumerate() {
+            s.add_constant_in_place(cs.ns(|| format!("Add constant {}", i)), c)?;
+        }
+        let num_sboxes =
+            if round < half_full_rounds || round >= half_full_rounds + P::PARTIAL_ROUNDS {
+                P::WIDTH
+            } else {
+                1
+            };
+        for (i, s) in state.iter_mut().take(num_sboxes).enumerate() {
+            *s = s.pow_by_constant(cs.ns(|| format!("S-box {}", i)), &[P::SBOX])?;
+        }
+        let mut new_state = Vec::with_capacity(P::WIDTH);
+        for (i, row) in parameters.mds.iter().enumerate() {
+            let mut cs = cs.ns(|| format!("MDS row {}", i));
+            let mut acc = FpGadget::zero(cs.ns(|| "zero"))?;
+            for (j, (m, s)) in row.iter().zip(state.iter()).enumerate() {
+                let term = s.mul_by_constant(cs.ns(|| format!("Multiply {}", j)), m)?;
+                acc.add_in_place(cs.ns(|| format!("Add {}", j)), &term)?;
+            }
+            new_state.push(acc);
+        }
+        state.clone_from_slice(&new_state);
+    }
+    Ok(())
+}
+
+/// Hashes `input` with the Poseidon sponge, as `poseidon_sponge` does.
+pub fn poseidon_sponge_gadget<F, P, CS>(
+    mut cs: CS,
+    parameters: &PoseidonParameters<F>,
+    domain: u64,
+    input: &[FpGadget<F>],
+) -> Result<FpGadget<F>, SynthesisError>
+where
+    F: PrimeField,
+    P: PoseidonRoundParams,
+    CS: ConstraintSystem<F>,
+{
+    let mut state = Vec::with_capacity(P::WIDTH);
+    state.push(
+        FpGadget::zero(cs.ns(|| "capacity"))?.add_constant(cs.ns(|| "domain"), &F::from(domain))?,
+    );
+    for i in 1..P::WIDTH {
+        state.push(FpGadget::zero(cs.ns(|| format!("rate {}", i)))?);
+    }
+
+    if input.is_empty() {
+        poseidon_permutation_gadget::<F, P, _>(cs.ns(|| "Permute"), parameters, &mut state)?;
+    }
+    for (i, chunk) in input.chunks(P::WIDTH - 1).enumerate() {
+        let mut cs = cs.ns(|| format!("Absorb {}", i));
+        for (j, (s, x)) in state[1..].iter_mut().zip(chunk).enumerate() {
+            s.add_in_place(cs.ns(|| format!("Add {}", j)), x)?;
+        }
+        poseidon_permutation_gadget::<F, P, _>(cs.ns(|| "Permute"), parameters, &mut state)?;
+    }
+    Ok(state.swap_remove(1))
+}
+
+impl<F: PrimeField> AllocGadget<PoseidonParameters<F>, F> for PoseidonParametersGadget<F> {
+    fn alloc_constant<T, CS: ConstraintSystem<F>>(_cs: CS, val: T) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<PoseidonParameters<F>>,
+    {
+        Ok(Self {
+            parameters: val.borrow().clone(),
+        })
+    }
+
+    fn alloc<FN, T, CS: ConstraintSystem<F>>(cs: CS, value_gen: FN) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<PoseidonParameters<F>>,
+    {
+        let parameters = value_gen()?.borrow().clone();
+        Self::alloc_constant(cs, parameters)
+    }
+
+    fn alloc_input<FN, T, CS: ConstraintSystem<F>>(
+        cs: CS,
+        value_gen: FN,
+    ) -> Result<Self, SynthesisError>
+    where
+        FN: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<PoseidonParameters<F>>,
+    {
+        let parameters = value_gen()?.borrow().clone();
+        Self::alloc_constant(cs, parameters)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crh::{
+        poseidon::{constraints::PoseidonCRHGadget, PoseidonCRH, PoseidonRounds128},
+        FixedLengthCRH, FixedLengthCRHGadget,
+    };
+    use algebra::{ed_on_bls12_381::Fq as Fr, test_rng};
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::{prelude::*, test_constraint_system::TestConstraintSystem};
+    use rand::Rng;
+
+    type TestCRH = PoseidonCRH<Fr, PoseidonRounds128>;
+    type TestCRHGadget = PoseidonCRHGadget<Fr, PoseidonRounds128>;
+
+    #[test]
+    fn crh_primitive_gadget_test() {
+        let rng = &mut test_rng();
+        let parameters = TestCRH::setup(rng).unwrap();
+
+        for input_len in &[0, 1, 31, 32, 64] {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let input = (0..*input_len).map(|_| rng.gen()).collect::<Vec<u8>>();
+            let input_bytes = UInt8::alloc_vec(cs.ns(|| "input"), &input).unwrap();
+            let num_input_constraints = cs.num_constraints();
+
+            let primitive_result = TestCRH::evaluate(&parameters, &input).unwrap();
+
+            let gadget_parameters =
+                <TestCRHGadget as FixedLengthCRHGadget<TestCRH, Fr>>::ParametersGadget::alloc(
+                    &mut cs.ns(|| "gadget_parameters"),
+                    || Ok(&parameters),
+                )
+                .unwrap();
+            let gadget_result =
+                <TestCRHGadget as FixedLengthCRHGadget<TestCRH, Fr>>::check_evaluation_gadget(
+                    &mut cs.ns(|| "gadget_evaluation"),
+                    &gadget_parameters,
+                    &input_bytes,
+                )
+                .unwrap();
+            println!(
+                "number of constraints for evaluation: {}",
+                cs.num_constraints() - num_input_constraints
+            );
+
+            assert_eq!(primitive_result, gadget_result.value.unwrap());
+            assert!(cs.is_satisfied());
+        }
+
+        assert!(TestCRH::evaluate(&parameters, &[0u8; 65]).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use crate::{Box, CryptoError, Error, Vec};
+use algebra_core::PrimeField;
+use core::marker::PhantomData;
+use rand::Rng;
+
+use crate::crh::{
+    utils::{bytes_to_field_elements, cache_per_type, field_elements_from_seed},
+    FixedLengthCRH,
+};
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// The domain with which `PoseidonCRH` initializes the capacity element of
+/// the sponge.
+pub const CRH_DOMAIN: u64 = 0;
+/// The domain of `PoseidonPRF`.
+pub const PRF_DOMAIN: u64 = 1;
+/// The domain of `PoseidonCommitment`.
+pub const COMMITMENT_DOMAIN: u64 = 2;
+/// The domain of `PoseidonSponge` transcripts.
+pub const FIAT_SHAMIR_DOMAIN: u64 = 3;
+
+/// The shape of a Poseidon permutation `x -> x^SBOX` over a prime field.
+/// `SBOX` must be coprime to `p - 1`, so that the S-box is a permutation.
+pub trait PoseidonRoundParams: Clone + 'static {
+    /// The number `t` of field elements in the state, one of which is the
+    /// capacity of the sponge.
+    const WIDTH: usize;
+    /// The number of rounds with a full S-box layer, half of which are
+    /// performed before the partial rounds.
+    const FULL_ROUNDS: usize;
+    /// The number of rounds in which the S-box is applied to a single
+    /// element.
+    const PARTIAL_ROUNDS: usize;
+    /// The exponent `alpha` of the S-box.
+    const SBOX: u64;
+    /// The maximum input length of `PoseidonCRH` and `PoseidonCommitment`.
+    const INPUT_SIZE_BITS: usize;
+}
+
+/// The Poseidon instance with `WIDTH = 3` and `SBOX = 5`, whose round numbers
+/// `FULL_ROUNDS = 8` and `PARTIAL_ROUNDS = 57` give 128-bit security over
+/// fields of about 255 bits, such as the scalar fields of BLS12-381 and
+/// BN254. It absorbs two field elements per permutation, and hashes inputs
+/// of up to 512 bits.
+#[derive(Clone)]
+pub struct PoseidonRounds128;
+
+impl PoseidonRoundParams for PoseidonRounds128 {
+    const WIDTH: usize = 3;
+    const FULL_ROUNDS: usize = 8;
+    const PARTIAL_ROUNDS: usize = 57;
+    const SBOX: u64 = 5;
+    const INPUT_SIZE_BITS: usize = 512;
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct PoseidonParameters<F: PrimeField> {
+    /// `WIDTH` constants for each round.
+    pub round_constants: Vec<F>,
+    /// The `WIDTH x WIDTH` MDS matrix.
+    pub mds: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> PoseidonParameters<F> {
+    /// Derives the parameters of `P` deterministically: the round constants
+    /// are sampled with `field_elements_from_seed`, and the MDS matrix is the
+    /// Cauchy matrix `(1 / (i + WIDTH + j))_{i, j}`.
+    ///
+    /// Panics if `P::SBOX` is not coprime to `p - 1`.
+    pub fn new<P: PoseidonRoundParams>() -> Self {
+        assert!(
+            is_coprime_to_p_minus_one::<F>(P::SBOX),
+            "the S-box x -> x^{} is not a permutation of the field",
+            P::SBOX
+        );
+
+        let mut seed = b"Poseidon".to_vec();
+        for value in &[
+            P::WIDTH,
+            P::FULL_ROUNDS,
+            P::PARTIAL_ROUNDS,
+            P::SBOX as usize,
+        ] {
+            seed.extend_from_slice(&(*value as u64).to_le_bytes());
+        }
+
+        let num_constants = (P::FULL_ROUNDS + P::PARTIAL_ROUNDS) * P::WIDTH;
+        let round_constants = field_elements_from_seed(&seed, num_constants);
+
+        let mds = (0..P::WIDTH)
+            .map(|i| {
+                (0..P::WIDTH)
+                    .map(|j| F::from((i + P::WIDTH + j) as u64).inverse().unwrap())
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            round_constants,
+            mds,
+        }
+    }
+
+    /// Returns `new::<P>()`, which is derived only once per thread if `std`
+    /// is enabled. This is for primitives that cannot take the parameters as
+    /// an argument, such as `PoseidonPRF`.
+    pub fn cached<P: PoseidonRoundParams>() -> Self {
+        cache_per_type::<(F, P), _, _>(Self::new::<P>)
+    }
+}
+
+/// Returns whether `alpha` is coprime to `p - 1`, i.e. whether `x -> x^alpha`
+/// is a permutation of `F`.
+fn is_coprime_to_p_minus_one<F: PrimeField>(alpha: u64) -> bool {
+    if alpha == 0 {
+        return false;
+    }
+    let alpha = u128::from(alpha);
+    let p_mod_alpha = F::characteristic()
+        .iter()
+        .rev()
+        .fold(0u128, |acc, limb| ((acc << 64) | u128::from(*limb)) % alpha);
+    let (mut a, mut b) = (alpha, (p_mod_alpha + alpha - 1) % alpha);
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a == 1
+}
+
+/// Applies the Poseidon permutation to `state`.
+pub fn poseidon_permutation<F: PrimeField, P: PoseidonRoundParams>(
+    parameters: &PoseidonParameters<F>,
+    state: &mut [F],
+) {
+    assert_eq!(state.len(), P::WIDTH);
+    let half_full_rounds = P::FULL_ROUNDS / 2;
+    let num_rounds = P::FULL_ROUNDS + P::PARTIAL_ROUNDS;
+    for (round, constants) in parameters
+        .round_constants
+        .chunks(P::WIDTH)
+        .take(num_rounds)
+        .enumerate()
+    {
+        for (s, c) in state.iter_mut().zip(constants) {
+            *s += c;
+        }
+        if round < half_full_rounds || round >= half_full_rounds + P::PARTIAL_ROUNDS {
+            for s in state.iter_mut() {
+                *s = s.pow(&[P::SBOX]);
+            }
+        } else {
+            state[0] = state[0].pow(&[P::SBOX]);
+        }
+        let new_state = parameters
+            .mds
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(state.iter())
+                    .fold(F::zero(), |acc, (m, s)| acc + &(*m * s))
+            })
+            .collect::<Vec<_>>();
+        state.copy_from_slice(&new_state);
+    }
+}
+
+/// Hashes `input` with the Poseidon sponge, whose capacity element is
+/// initialized to `domain`. Inputs are implicitly padded with zeros to a
+/// multiple of the rate, so different input lengths must use different
+/// domains.
+pub fn poseidon_sponge<F: PrimeField, P: PoseidonRoundParams>(
+    parameters: &PoseidonParameters<F>,
+    domain: u64,
+    input: &[F],
+) -> F {
+    let mut state = vec![F::zero(); P::WIDTH];
+    state[0] = F::from(domain);
+    if input.is_empty() {
+        poseidon_permutation::<F, P>(parameters, &mut state);
+    }
+    for chunk in input.chunks(P::WIDTH - 1) {
+        for (s, x) in state[1..].iter_mut().zip(chunk) {
+            *s += x;
+        }
+        poseidon_permutation::<F, P>(parameters, &mut state);
+    }
+    state[1]
+}
+
+/// Pads `input` with zeros to `P::INPUT_SIZE_BITS` and packs it into field
+/// elements.
+pub(crate) fn pad_and_pack<F: PrimeField, P: PoseidonRoundParams>(
+    input: &[u8],
+) -> Result<Vec<F>, Error> {
+    if input.len() * 8 > P::INPUT_SIZE_BITS {
+        return Err(Box::new(CryptoError::IncorrectInputLength(input.len())));
+    }
+    let mut padded_input = input.to_vec();
+    padded_input.resize(P::INPUT_SIZE_BITS / 8, 0u8);
+    Ok(bytes_to_field_elements(&padded_input))
+}
+
+pub struct PoseidonCRH<F: PrimeField, P: PoseidonRoundParams> {
+    field: PhantomData<F>,
+    params: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: PoseidonRoundParams> FixedLengthCRH for PoseidonCRH<F, P> {
+    const INPUT_SIZE_BITS: usize = P::INPUT_SIZE_BITS;
+    type Output = F;
+    type Parameters = PoseidonParameters<F>;
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, Error> {
+        let time = start_timer!(|| format!(
+            "PoseidonCRH::Setup: width {}, {} full and {} partial rounds",
+            P::WIDTH,
+            P::FULL_ROUNDS,
+            P::PARTIAL_ROUNDS
+        ));
+        let parameters = PoseidonParameters::new::<P>();
+        end_timer!(time);
+        Ok(parameters)
+    }
+
+    fn evaluate(parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let eval_time = start_timer!(|| "PoseidonCRH::Eval");
+        let elements = pad_and_pack::<F, P>(input)?;
+        let result = poseidon_sponge::<F, P>(parameters, CRH_DOMAIN, &elements);
+        end_timer!(eval_time);
+        Ok(result)
+    }
+}
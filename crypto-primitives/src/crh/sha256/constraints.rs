@@ -0,0 +1,365 @@
+use crate::{
+    crh::{sha256::Sha256, CRHGadget, DigestGadget},
+    Vec,
+};
+use algebra_core::PrimeField;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+use core::borrow::Borrow;
+
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+fn shr(x: &UInt32, by: usize) -> UInt32 {
+    let bits = x
+        .to_bits_le()
+        .into_iter()
+        .skip(by)
+        .chain(core::iter::repeat(Boolean::constant(false)))
+        .take(32)
+        .collect::<Vec<_>>();
+    UInt32::from_bits_le(&bits)
+}
+
+/// Computes `a ^ b ^ c`.
+fn xor3<ConstraintF, CS>(
+    mut cs: CS,
+    a: &UInt32,
+    b: &UInt32,
+    c: &UInt32,
+) -> Result<UInt32, SynthesisError>
+where
+    ConstraintF: PrimeField,
+    CS: ConstraintSystem<ConstraintF>,
+{
+    a.xor(cs.ns(|| "a ^ b"), b)?.xor(cs.ns(|| "a ^ b ^ c"), c)
+}
+
+/// Computes `(e & f) ^ (!e & g)` as `g ^ (e & (f ^ g))`.
+fn ch<ConstraintF, CS>(
+    mut cs: CS,
+    e: &UInt32,
+    f: &UInt32,
+    g: &UInt32,
+) -> Result<UInt32, SynthesisError>
+where
+    ConstraintF: PrimeField,
+    CS: ConstraintSystem<ConstraintF>,
+{
+    let f_xor_g = f.xor(cs.ns(|| "f ^ g"), g)?;
+    let bits = e
+        .to_bits_le()
+        .iter()
+        .zip(f_xor_g.to_bits_le())
+        .zip(g.to_bits_le())
+        .enumerate()
+        .map(|(i, ((e, f_xor_g), g))| {
+            let and = Boolean::and(cs.ns(|| format!("and {}", i)), e, &f_xor_g)?;
+            Boolean::xor(cs.ns(|| format!("xor {}", i)), &g, &and)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(UInt32::from_bits_le(&bits))
+}
+
+/// Computes `(a & b) ^ (a & c) ^ (b & c)` as `(a & b) ^ (c & (a ^ b))`.
+fn maj<ConstraintF, CS>(
+    mut cs: CS,
+    a: &UInt32,
+    b: &UInt32,
+    c: &UInt32,
+) -> Result<UInt32, SynthesisError>
+where
+    ConstraintF: PrimeField,
+    CS: ConstraintSystem<ConstraintF>,
+{
+    let a_xor_b = a.xor(cs.ns(|| "a ^ b"), b)?;
+    let bits = a
+        .to_bits_le()
+        .iter()
+        .zip(b.to_bits_le())
+        .zip(c.to_bits_le())
+        .zip(a_xor_b.to_bits_le())
+        .enumerate()
+        .map(|(i, (((a, b), c), a_xor_b))| {
+            let a_and_b = Boolean::and(cs.ns(|| format!("a & b {}", i)), a, &b)?;
+            let c_and_a_xor_b = Boolean::and(cs.ns(|| format!("c & (a ^ b) {}", i)), &c, &a_xor_b)?;
+            Boolean::xor(cs.ns(|| format!("xor {}", i)), &a_and_b, &c_and_a_xor_b)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(UInt32::from_bits_le(&bits))
+}
+
+/// Applies the SHA-256 compression function to `state` and a block of 16
+/// words.
+pub fn sha256_compression<ConstraintF, CS>(
+    mut cs: CS,
+    state: &mut [UInt32],
+    block: &[UInt32],
+) -> Result<(), SynthesisError>
+where
+    ConstraintF: PrimeField,
+    CS: ConstraintSystem<ConstraintF>,
+{
+    assert_eq!(state.len(), 8);
+    assert_eq!(block.len(), 16);
+
+    let mut w = block.to_vec();
+    for i in 16..64 {
+        let mut cs = cs.ns(|| format!("schedule {}", i));
+        let s0 = xor3(
+            cs.ns(|| "s0"),
+            &w[i - 15].rotr(7),
+            &w[i - 15].rotr(18),
+            &shr(&w[i - 15], 3),
+        )?;
+        let s1 = xor3(
+            cs.ns(|| "s1"),
+            &w[i - 2].rotr(17),
+            &w[i - 2].rotr(19),
+            &shr(&w[i - 2], 10),
+        )?;
+        let new_w = UInt32::addmany(
+            cs.ns(|| "sum"),
+            &[w[i - 16].clone(), s0, w[i - 7].clone(), s1],
+        )?;
+        w.push(new_w);
+    }
+
+    let mut v = state.to_vec();
+    for (i, (k, w)) in ROUND_CONSTANTS.iter().zip(&w).enumerate() {
+        let mut cs = cs.ns(|| format!("round {}", i));
+        let s1 = xor3(
+            cs.ns(|| "S1"),
+            &v[4].rotr(6),
+            &v[4].rotr(11),
+            &v[4].rotr(25),
+        )?;
+        let ch = ch(cs.ns(|| "ch"), &v[4], &v[5], &v[6])?;
+        let temp1 = UInt32::addmany(
+            cs.ns(|| "temp1"),
+            &[v[7].clone(), s1, ch, UInt32::constant(*k), w.clone()],
+        )?;
+        let s0 = xor3(
+            cs.ns(|| "S0"),
+            &v[0].rotr(2),
+            &v[0].rotr(13),
+            &v[0].rotr(22),
+        )?;
+        let maj = maj(cs.ns(|| "maj"), &v[0], &v[1], &v[2])?;
+
+        v[7] = v[6].clone();
+        v[6] = v[5].clone();
+        v[5] = v[4].clone();
+        v[4] = UInt32::addmany(cs.ns(|| "e"), &[v[3].clone(), temp1.clone()])?;
+        v[3] = v[2].clone();
+        v[2] = v[1].clone();
+        v[1] = v[0].clone();
+        v[0] = UInt32::addmany(cs.ns(|| "a"), &[temp1, s0, maj])?;
+    }
+
+    for (i, (s, v)) in state.iter_mut().zip(v).enumerate() {
+        *s = UInt32::addmany(cs.ns(|| format!("update state {}", i)), &[s.clone(), v])?;
+    }
+    Ok(())
+}
+
+/// Computes the SHA-256 digest of `input`, whose length is fixed by the
+/// circuit.
+pub fn sha256_gadget<ConstraintF, CS>(
+    mut cs: CS,
+    input: &[UInt8],
+) -> Result<Vec<UInt8>, SynthesisError>
+where
+    ConstraintF: PrimeField,
+    CS: ConstraintSystem<ConstraintF>,
+{
+    let mut padded_input = input.to_vec();
+    padded_input.push(UInt8::constant(0x80));
+    while padded_input.len() % 64 != 56 {
+        padded_input.push(UInt8::constant(0));
+    }
+    padded_input.extend(
+        (input.len() as u64 * 8)
+            .to_be_bytes()
+            .iter()
+            .map(|byte| UInt8::constant(*byte)),
+    );
+
+    let mut state = INITIAL_STATE
+        .iter()
+        .map(|h| UInt32::constant(*h))
+        .collect::<Vec<_>>();
+    for (i, block) in padded_input.chunks(64).enumerate() {
+        // Words are read in big-endian order.
+        let block = block
+            .chunks(4)
+            .map(|word| {
+                let bits = word
+                    .iter()
+                    .rev()
+                    .flat_map(|byte| byte.into_bits_le())
+                    .collect::<Vec<_>>();
+                UInt32::from_bits_le(&bits)
+            })
+            .collect::<Vec<_>>();
+        sha256_compression(cs.ns(|| format!("block {}", i)), &mut state, &block)?;
+    }
+
+    let mut result = Vec::with_capacity(32);
+    for (i, word) in state.iter().enumerate() {
+        let mut bytes = word.to_bytes(cs.ns(|| format!("word {} to bytes", i)))?;
+        bytes.reverse();
+        result.extend(bytes);
+    }
+    Ok(result)
+}
+
+pub struct Sha256Gadget;
+
+#[derive(Clone)]
+pub struct Sha256ParametersGadget;
+
+impl<ConstraintF: PrimeField> CRHGadget<Sha256, ConstraintF> for Sha256Gadget {
+    type OutputGadget = DigestGadget;
+    type ParametersGadget = Sha256ParametersGadget;
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        _parameters: &Self::ParametersGadget,
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        Ok(DigestGadget(sha256_gadget(cs, input)?))
+    }
+}
+
+impl<ConstraintF: PrimeField> AllocGadget<(), ConstraintF> for Sha256ParametersGadget {
+    fn alloc_constant<T, CS: ConstraintSystem<ConstraintF>>(
+        _cs: CS,
+        _val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<()>,
+    {
+        Ok(Sha256ParametersGadget)
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        _cs: CS,
+        _value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<()>,
+    {
+        Ok(Sha256ParametersGadget)
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        _cs: CS,
+        _value_gen: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<()>,
+    {
+        Ok(Sha256ParametersGadget)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::crh::{
+        sha256::{constraints::Sha256Gadget, Sha256},
+        CRHGadget, CRH,
+    };
+    use algebra::{ed_on_bls12_381::Fq as Fr, test_rng};
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::{prelude::*, test_constraint_system::TestConstraintSystem};
+    use rand::Rng;
+
+    fn hex_to_bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn check_gadget(input: &[u8], expected: &[u8]) {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let input_bytes = UInt8::alloc_vec(cs.ns(|| "input"), input).unwrap();
+        let parameters = <Sha256Gadget as CRHGadget<Sha256, Fr>>::ParametersGadget::alloc(
+            &mut cs.ns(|| "gadget_parameters"),
+            || Ok(()),
+        )
+        .unwrap();
+        let result = <Sha256Gadget as CRHGadget<Sha256, Fr>>::check_evaluation_gadget(
+            &mut cs.ns(|| "gadget_evaluation"),
+            &parameters,
+            &input_bytes,
+        )
+        .unwrap();
+
+        let result = result
+            .0
+            .iter()
+            .map(|byte| byte.get_value().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(result, expected);
+        assert!(cs.is_satisfied());
+    }
+
+    // Test vectors from the NIST examples for FIPS 180-2.
+    const NIST_VECTORS: [(&str, &str); 4] = [
+        (
+            "",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        ),
+        (
+            "abc",
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        ),
+        (
+            "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+        ),
+        (
+            "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+            "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1",
+        ),
+    ];
+
+    #[test]
+    fn sha256_native_test() {
+        for (input, expected) in NIST_VECTORS.iter() {
+            let result = Sha256::evaluate(&(), input.as_bytes()).unwrap();
+            assert_eq!(result[..], hex_to_bytes(expected)[..]);
+        }
+    }
+
+    #[test]
+    fn sha256_gadget_test() {
+        for (input, expected) in NIST_VECTORS.iter() {
+            check_gadget(input.as_bytes(), &hex_to_bytes(expected));
+        }
+
+        // Lengths around the padding boundaries.
+        let rng = &mut test_rng();
+        for input_len in &[55, 56, 63, 64] {
+            let input = (0..*input_len).map(|_| rng.gen()).collect::<Vec<u8>>();
+            check_gadget(&input, &Sha256::evaluate(&(), &input).unwrap());
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use crate::{crh::CRH, Error};
+use digest::Digest;
+use rand::Rng;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// SHA-256, as specified in FIPS 180-4. Unlike `Blake2sCRH`, the hash is not
+/// personalized, so that digests agree with those of other systems; callers
+/// that need domain separation should prefix their inputs.
+pub struct Sha256;
+
+impl CRH for Sha256 {
+    type Output = [u8; 32];
+    type Parameters = ();
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, Error> {
+        Ok(())
+    }
+
+    fn evaluate(_parameters: &Self::Parameters, input: &[u8]) -> Result<Self::Output, Error> {
+        let eval_time = start_timer!(|| "Sha256::Eval");
+        let mut result = [0u8; 32];
+        result.copy_from_slice(&sha2::Sha256::digest(input));
+        end_timer!(eval_time);
+        Ok(result)
+    }
+}
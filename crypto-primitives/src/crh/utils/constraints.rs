@@ -0,0 +1,33 @@
+use crate::{crh::utils::bytes_per_element, Vec};
+use algebra_core::PrimeField;
+use r1cs_core::{ConstraintSystem, ConstraintVar, LinearCombination};
+use r1cs_std::prelude::*;
+
+/// Packs `bytes` into field elements, as `bytes_to_field_elements` does.
+/// The elements are linear combinations of the bits, and so cost no
+/// constraints.
+pub fn bytes_to_field_elements_gadget<F: PrimeField, CS: ConstraintSystem<F>>(
+    bytes: &[UInt8],
+) -> Vec<FpGadget<F>> {
+    bytes
+        .chunks(bytes_per_element::<F>())
+        .map(|chunk| {
+            let mut lc = LinearCombination::zero();
+            let mut value = Some(F::zero());
+            let mut coeff = F::one();
+            for bit in chunk.iter().flat_map(|byte| byte.into_bits_le()) {
+                lc = lc + bit.lc(CS::one(), coeff);
+                value = match (value, bit.get_value()) {
+                    (Some(value), Some(true)) => Some(value + &coeff),
+                    (Some(value), Some(false)) => Some(value),
+                    _ => None,
+                };
+                coeff.double_in_place();
+            }
+            FpGadget {
+                value,
+                variable: ConstraintVar::LC(lc),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,80 @@
+//! Helpers shared by the algebraic hash functions, Poseidon and MiMC, and
+//! the primitives built on them.
+
+use crate::Vec;
+use algebra_core::{FpParameters, PrimeField};
+use blake2::Blake2s;
+use digest::Digest;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// The number of bytes packed into each field element.
+pub(crate) fn bytes_per_element<F: PrimeField>() -> usize {
+    <F::Params as FpParameters>::CAPACITY as usize / 8
+}
+
+/// Packs `bytes` into field elements, each of which holds
+/// `bytes_per_element` bytes in little-endian order.
+pub fn bytes_to_field_elements<F: PrimeField>(bytes: &[u8]) -> Vec<F> {
+    let radix = F::from(256u64);
+    bytes
+        .chunks(bytes_per_element::<F>())
+        .map(|chunk| {
+            chunk
+                .iter()
+                .rev()
+                .fold(F::zero(), |acc, byte| acc * &radix + &F::from(*byte))
+        })
+        .collect()
+}
+
+/// Samples `num_elements` field elements from `seed` with Blake2s in counter
+/// mode, rejecting digests that do not encode a field element.
+pub(crate) fn field_elements_from_seed<F: PrimeField>(seed: &[u8], num_elements: usize) -> Vec<F> {
+    let mut elements = Vec::with_capacity(num_elements);
+    let mut counter = 0u64;
+    while elements.len() < num_elements {
+        let hash = Blake2s::new()
+            .chain(seed)
+            .chain(&counter.to_le_bytes())
+            .result();
+        if let Some(c) = F::from_random_bytes(&hash) {
+            elements.push(c);
+        }
+        counter += 1;
+    }
+    elements
+}
+
+/// Returns the value cached for the type `K` by the current thread, or
+/// caches and returns `build()` if there is none.
+#[cfg(feature = "std")]
+pub(crate) fn cache_per_type<K: 'static, T: Clone + 'static, B: FnOnce() -> T>(build: B) -> T {
+    use std::{
+        any::{Any, TypeId},
+        cell::RefCell,
+        collections::BTreeMap,
+    };
+
+    thread_local! {
+        static CACHE: RefCell<BTreeMap<TypeId, Box<dyn Any>>> = RefCell::new(BTreeMap::new());
+    }
+
+    let key = TypeId::of::<K>();
+    CACHE.with(|cache| {
+        if let Some(value) = cache.borrow().get(&key) {
+            return value.downcast_ref::<T>().unwrap().clone();
+        }
+        let value = build();
+        cache.borrow_mut().insert(key, Box::new(value.clone()));
+        value
+    })
+}
+
+/// Without `std`, there is nowhere to cache values, so `build` is called
+/// every time.
+#[cfg(not(feature = "std"))]
+pub(crate) fn cache_per_type<K, T, B: FnOnce() -> T>(build: B) -> T {
+    build()
+}
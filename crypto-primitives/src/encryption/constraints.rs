@@ -0,0 +1,35 @@
+use crate::encryption::EncryptionScheme;
+use algebra_core::Field;
+use core::fmt::Debug;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+pub trait EncryptionGadget<E: EncryptionScheme, ConstraintF: Field> {
+    type ParametersGadget: AllocGadget<E::Parameters, ConstraintF> + Clone;
+    type PublicKeyGadget: AllocGadget<E::PublicKey, ConstraintF>
+        + EqGadget<ConstraintF>
+        + ToBytesGadget<ConstraintF>
+        + Clone;
+    type PlaintextGadget: AllocGadget<E::Plaintext, ConstraintF>
+        + EqGadget<ConstraintF>
+        + ToBytesGadget<ConstraintF>
+        + Clone;
+    type RandomnessGadget: AllocGadget<E::Randomness, ConstraintF> + Clone;
+    type CiphertextGadget: AllocGadget<E::Ciphertext, ConstraintF>
+        + EqGadget<ConstraintF>
+        + ToBytesGadget<ConstraintF>
+        + Clone
+        + Debug;
+
+    /// Computes the encryption of `plaintext` under `public_key` with
+    /// `randomness`. Enforcing equality with a claimed ciphertext proves that
+    /// the latter encrypts `plaintext`, which in turn may be bound to a
+    /// commitment through its `ToBytesGadget` representation.
+    fn check_encryption_gadget<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        public_key: &Self::PublicKeyGadget,
+        plaintext: &Self::PlaintextGadget,
+        randomness: &Self::RandomnessGadget,
+    ) -> Result<Self::CiphertextGadget, SynthesisError>;
+}
@@ -0,0 +1,447 @@
+use crate::{
+    encryption::{
+        elgamal::{ElGamal, ElGamalCiphertext, ElGamalParameters, ElGamalRandomness},
+        EncryptionGadget,
+    },
+    signature::schnorr::constraints::{alloc_input_bytes, scalar_bytes},
+    Vec,
+};
+use algebra_core::{fields::Field, groups::Group};
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::{prelude::*, Assignment};
+
+use core::{borrow::Borrow, marker::PhantomData};
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "G: Group, ConstraintF: Field, GG: GroupGadget<G, ConstraintF>"))]
+pub struct ElGamalParametersGadget<G: Group, ConstraintF: Field, GG: GroupGadget<G, ConstraintF>> {
+    generator: GG,
+    #[doc(hidden)]
+    _group: PhantomData<*const G>,
+    #[doc(hidden)]
+    _engine: PhantomData<*const ConstraintF>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ElGamalRandomnessGadget(Vec<UInt8>);
+
+#[derive(Derivative)]
+#[derivative(
+    Debug(bound = "G: Group, ConstraintF: Field, GG: GroupGadget<G, ConstraintF>"),
+    Clone(bound = "G: Group, ConstraintF: Field, GG: GroupGadget<G, ConstraintF>"),
+    PartialEq(bound = "G: Group, ConstraintF: Field, GG: GroupGadget<G, ConstraintF>"),
+    Eq(bound = "G: Group, ConstraintF: Field, GG: GroupGadget<G, ConstraintF>")
+)]
+pub struct ElGamalCiphertextGadget<G: Group, ConstraintF: Field, GG: GroupGadget<G, ConstraintF>> {
+    pub c1: GG,
+    pub c2: GG,
+    #[doc(hidden)]
+    _group: PhantomData<*const G>,
+    #[doc(hidden)]
+    _engine: PhantomData<*const ConstraintF>,
+}
+
+/// Encryption under `ElGamal<G>`, where public keys and plaintexts are
+/// represented by `GG` itself.
+pub struct ElGamalEncryptionGadget<G: Group, ConstraintF: Field, GG: GroupGadget<G, ConstraintF>> {
+    #[doc(hidden)]
+    _group: PhantomData<*const G>,
+    #[doc(hidden)]
+    _group_gadget: PhantomData<*const GG>,
+    #[doc(hidden)]
+    _engine: PhantomData<*const ConstraintF>,
+}
+
+impl<G, ConstraintF, GG> EncryptionGadget<ElGamal<G>, ConstraintF>
+    for ElGamalEncryptionGadget<G, ConstraintF, GG>
+where
+    G: Group,
+    ConstraintF: Field,
+    GG: GroupGadget<G, ConstraintF>,
+{
+    type ParametersGadget = ElGamalParametersGadget<G, ConstraintF, GG>;
+    type PublicKeyGadget = GG;
+    type PlaintextGadget = GG;
+    type RandomnessGadget = ElGamalRandomnessGadget;
+    type CiphertextGadget = ElGamalCiphertextGadget<G, ConstraintF, GG>;
+
+    fn check_encryption_gadget<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        public_key: &Self::PublicKeyGadget,
+        plaintext: &Self::PlaintextGadget,
+        randomness: &Self::RandomnessGadget,
+    ) -> Result<Self::CiphertextGadget, SynthesisError> {
+        let randomness = randomness
+            .0
+            .iter()
+            .flat_map(|b| b.into_bits_le())
+            .collect::<Vec<_>>();
+
+        // c1 := r · g
+        let zero = GG::zero(cs.ns(|| "Zero"))?;
+        let c1 = parameters.generator.mul_bits(
+            cs.ns(|| "Multiply generator"),
+            &zero,
+            randomness.iter(),
+        )?;
+        // c2 := m + r · pk
+        let c2 = public_key.mul_bits(
+            cs.ns(|| "Multiply public key"),
+            plaintext,
+            randomness.iter(),
+        )?;
+
+        Ok(ElGamalCiphertextGadget {
+            c1,
+            c2,
+            _group: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+}
+
+impl<G, ConstraintF, GG> AllocGadget<ElGamalParameters<G>, ConstraintF>
+    for ElGamalParametersGadget<G, ConstraintF, GG>
+where
+    G: Group,
+    ConstraintF: Field,
+    GG: GroupGadget<G, ConstraintF>,
+{
+    fn alloc_constant<T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<ElGamalParameters<G>>,
+    {
+        let generator = GG::alloc_constant(cs.ns(|| "Generator"), val.borrow().generator)?;
+        Ok(Self {
+            generator,
+            _group: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<ElGamalParameters<G>>,
+    {
+        let generator = GG::alloc_checked(cs.ns(|| "Generator"), || {
+            f().map(|pp| pp.borrow().generator)
+        })?;
+        Ok(Self {
+            generator,
+            _group: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<ElGamalParameters<G>>,
+    {
+        let generator = GG::alloc_input(cs.ns(|| "Generator"), || {
+            f().map(|pp| pp.borrow().generator)
+        })?;
+        Ok(Self {
+            generator,
+            _group: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+}
+
+impl<G, ConstraintF> AllocGadget<ElGamalRandomness<G>, ConstraintF> for ElGamalRandomnessGadget
+where
+    G: Group,
+    ConstraintF: Field,
+{
+    fn alloc_constant<T, CS: ConstraintSystem<ConstraintF>>(
+        _cs: CS,
+        val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<ElGamalRandomness<G>>,
+    {
+        let randomness = scalar_bytes::<G>(Some(val.borrow().0));
+        Ok(ElGamalRandomnessGadget(
+            randomness
+                .into_iter()
+                .map(|byte| UInt8::constant(byte.unwrap()))
+                .collect(),
+        ))
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(cs: CS, f: F) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<ElGamalRandomness<G>>,
+    {
+        let randomness = scalar_bytes::<G>(f().map(|r| r.borrow().0).ok());
+        Ok(ElGamalRandomnessGadget(UInt8::alloc_vec(cs, &randomness)?))
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<ElGamalRandomness<G>>,
+    {
+        let randomness = scalar_bytes::<G>(f().map(|r| r.borrow().0).ok());
+        Ok(ElGamalRandomnessGadget(alloc_input_bytes(cs, &randomness)?))
+    }
+}
+
+impl<G, ConstraintF, GG> AllocGadget<ElGamalCiphertext<G>, ConstraintF>
+    for ElGamalCiphertextGadget<G, ConstraintF, GG>
+where
+    G: Group,
+    ConstraintF: Field,
+    GG: GroupGadget<G, ConstraintF>,
+{
+    fn alloc_constant<T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<ElGamalCiphertext<G>>,
+    {
+        let ciphertext = val.borrow();
+        let c1 = GG::alloc_constant(cs.ns(|| "c1"), ciphertext.c1)?;
+        let c2 = GG::alloc_constant(cs.ns(|| "c2"), ciphertext.c2)?;
+        Ok(Self {
+            c1,
+            c2,
+            _group: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<ElGamalCiphertext<G>>,
+    {
+        let ciphertext = f().map(|c| c.borrow().clone()).ok();
+        let c1 = GG::alloc(cs.ns(|| "c1"), || ciphertext.as_ref().map(|c| c.c1).get())?;
+        let c2 = GG::alloc(cs.ns(|| "c2"), || ciphertext.as_ref().map(|c| c.c2).get())?;
+        Ok(Self {
+            c1,
+            c2,
+            _group: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<ElGamalCiphertext<G>>,
+    {
+        let ciphertext = f().map(|c| c.borrow().clone()).ok();
+        let c1 = GG::alloc_input(cs.ns(|| "c1"), || ciphertext.as_ref().map(|c| c.c1).get())?;
+        let c2 = GG::alloc_input(cs.ns(|| "c2"), || ciphertext.as_ref().map(|c| c.c2).get())?;
+        Ok(Self {
+            c1,
+            c2,
+            _group: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+}
+
+impl<G, ConstraintF, GG> ConditionalEqGadget<ConstraintF>
+    for ElGamalCiphertextGadget<G, ConstraintF, GG>
+where
+    G: Group,
+    ConstraintF: Field,
+    GG: GroupGadget<G, ConstraintF>,
+{
+    #[inline]
+    fn conditional_enforce_equal<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+        condition: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        self.c1
+            .conditional_enforce_equal(cs.ns(|| "c1"), &other.c1, condition)?;
+        self.c2
+            .conditional_enforce_equal(cs.ns(|| "c2"), &other.c2, condition)?;
+        Ok(())
+    }
+
+    fn cost() -> usize {
+        2 * <GG as ConditionalEqGadget<ConstraintF>>::cost()
+    }
+}
+
+impl<G, ConstraintF, GG> EqGadget<ConstraintF> for ElGamalCiphertextGadget<G, ConstraintF, GG>
+where
+    G: Group,
+    ConstraintF: Field,
+    GG: GroupGadget<G, ConstraintF>,
+{
+}
+
+impl<G, ConstraintF, GG> ToBytesGadget<ConstraintF> for ElGamalCiphertextGadget<G, ConstraintF, GG>
+where
+    G: Group,
+    ConstraintF: Field,
+    GG: GroupGadget<G, ConstraintF>,
+{
+    fn to_bytes<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        let mut bytes = self.c1.to_bytes(cs.ns(|| "c1 to bytes"))?;
+        bytes.extend(self.c2.to_bytes(cs.ns(|| "c2 to bytes"))?);
+        Ok(bytes)
+    }
+
+    fn to_non_unique_bytes<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        let mut bytes = self.c1.to_non_unique_bytes(cs.ns(|| "c1 to bytes"))?;
+        bytes.extend(self.c2.to_non_unique_bytes(cs.ns(|| "c2 to bytes"))?);
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        commitment::{
+            pedersen::{constraints::PedersenCommitmentGadget, PedersenCommitment},
+            CommitmentGadget, CommitmentScheme,
+        },
+        crh::pedersen::PedersenWindow,
+        encryption::EncryptionScheme,
+    };
+    use algebra::{
+        ed_on_bls12_381::{EdwardsAffine as JubJub, Fq},
+        test_rng, to_bytes, ToBytes, UniformRand,
+    };
+    use r1cs_std::{ed_on_bls12_381::EdwardsGadget, test_constraint_system::TestConstraintSystem};
+
+    type TestEnc = ElGamal<JubJub>;
+    type TestEncGadget = ElGamalEncryptionGadget<JubJub, Fq, EdwardsGadget>;
+
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    struct Window;
+
+    impl PedersenWindow for Window {
+        const WINDOW_SIZE: usize = 4;
+        const NUM_WINDOWS: usize = 128;
+    }
+
+    type TestComm = PedersenCommitment<JubJub, Window>;
+    type TestCommGadget = PedersenCommitmentGadget<JubJub, Fq, EdwardsGadget>;
+
+    #[test]
+    fn elgamal_encryption_gadget_test() {
+        let rng = &mut test_rng();
+        let parameters = TestEnc::setup(rng).unwrap();
+        let (pk, _) = TestEnc::keygen(&parameters, rng).unwrap();
+        let plaintext = JubJub::rand(rng);
+        let randomness = ElGamalRandomness(UniformRand::rand(rng));
+        let ciphertext = TestEnc::encrypt(&parameters, &pk, &plaintext, &randomness).unwrap();
+
+        // The plaintext is bound by a public commitment.
+        let comm_parameters = TestComm::setup(rng).unwrap();
+        let comm_randomness = UniformRand::rand(rng);
+        let commitment = TestComm::commit(
+            &comm_parameters,
+            &to_bytes![plaintext].unwrap(),
+            &comm_randomness,
+        )
+        .unwrap();
+
+        let other_plaintext = JubJub::rand(rng);
+        for (plaintext, satisfied) in &[(plaintext, true), (other_plaintext, false)] {
+            let mut cs = TestConstraintSystem::<Fq>::new();
+            let parameters_var =
+                <TestEncGadget as EncryptionGadget<TestEnc, Fq>>::ParametersGadget::alloc_constant(
+                    cs.ns(|| "Parameters"),
+                    &parameters,
+                )
+                .unwrap();
+            let pk_var = EdwardsGadget::alloc_input(cs.ns(|| "Public key"), || Ok(pk)).unwrap();
+            let plaintext_var =
+                EdwardsGadget::alloc(cs.ns(|| "Plaintext"), || Ok(*plaintext)).unwrap();
+            let randomness_var =
+                <TestEncGadget as EncryptionGadget<TestEnc, Fq>>::RandomnessGadget::alloc(
+                    cs.ns(|| "Randomness"),
+                    || Ok(&randomness),
+                )
+                .unwrap();
+            let ciphertext_var =
+                ElGamalCiphertextGadget::alloc_input(cs.ns(|| "Ciphertext"), || Ok(&ciphertext))
+                    .unwrap();
+
+            let result_var = TestEncGadget::check_encryption_gadget(
+                cs.ns(|| "Encrypt"),
+                &parameters_var,
+                &pk_var,
+                &plaintext_var,
+                &randomness_var,
+            )
+            .unwrap();
+            result_var
+                .enforce_equal(cs.ns(|| "Check ciphertext"), &ciphertext_var)
+                .unwrap();
+
+            let comm_parameters_var = <TestCommGadget as CommitmentGadget<TestComm, Fq>>::ParametersGadget::alloc_constant(
+                cs.ns(|| "Commitment parameters"),
+                &comm_parameters,
+            )
+            .unwrap();
+            let comm_randomness_var =
+                <TestCommGadget as CommitmentGadget<TestComm, Fq>>::RandomnessGadget::alloc(
+                    cs.ns(|| "Commitment randomness"),
+                    || Ok(&comm_randomness),
+                )
+                .unwrap();
+            let plaintext_bytes = plaintext_var
+                .to_bytes(cs.ns(|| "Plaintext to bytes"))
+                .unwrap();
+            let commitment_var = TestCommGadget::check_commitment_gadget(
+                cs.ns(|| "Commit"),
+                &comm_parameters_var,
+                &plaintext_bytes,
+                &comm_randomness_var,
+            )
+            .unwrap();
+            let expected_commitment_var =
+                EdwardsGadget::alloc_input(cs.ns(|| "Commitment"), || Ok(commitment)).unwrap();
+            commitment_var
+                .enforce_equal(cs.ns(|| "Check commitment"), &expected_commitment_var)
+                .unwrap();
+
+            assert_eq!(
+                GroupGadget::<JubJub, Fq>::get_value(&result_var.c1),
+                Some(ciphertext.c1)
+            );
+            assert_eq!(cs.is_satisfied(), *satisfied);
+        }
+    }
+}
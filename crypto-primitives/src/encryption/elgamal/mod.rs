@@ -0,0 +1,149 @@
+use crate::{encryption::EncryptionScheme, Error};
+use algebra_core::{
+    bytes::ToBytes,
+    groups::Group,
+    io::{Result as IoResult, Write},
+    UniformRand,
+};
+use core::marker::PhantomData;
+use rand::Rng;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// ElGamal encryption of elements of the group `G`:
+/// * the public key is `pk := sk · g` for a random secret key `sk`;
+/// * the ciphertext of `m` is `(c1, c2) := (r · g, m + r · pk)` for random `r`;
+/// * decryption computes `m = c2 - sk · c1`.
+///
+/// Messages are group elements, so callers must encode their plaintexts into
+/// `G`, e.g. as `m · h` for a second generator `h` when `m` is small.
+pub struct ElGamal<G: Group> {
+    _group: PhantomData<G>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "G: Group"), Debug(bound = "G: Group"))]
+pub struct ElGamalParameters<G: Group> {
+    pub generator: G,
+}
+
+pub type ElGamalPublicKey<G> = G;
+
+pub type ElGamalPlaintext<G> = G;
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "G: Group"), Default(bound = "G: Group"))]
+pub struct ElGamalSecretKey<G: Group>(pub G::ScalarField);
+
+impl<G: Group> ToBytes for ElGamalSecretKey<G> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "G: Group"),
+    PartialEq(bound = "G: Group"),
+    Debug(bound = "G: Group"),
+    Eq(bound = "G: Group"),
+    Default(bound = "G: Group")
+)]
+pub struct ElGamalRandomness<G: Group>(pub G::ScalarField);
+
+impl<G: Group> UniformRand for ElGamalRandomness<G> {
+    #[inline]
+    fn rand<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        ElGamalRandomness(UniformRand::rand(rng))
+    }
+}
+
+impl<G: Group> ToBytes for ElGamalRandomness<G> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "G: Group"),
+    PartialEq(bound = "G: Group"),
+    Debug(bound = "G: Group"),
+    Eq(bound = "G: Group"),
+    Hash(bound = "G: Group"),
+    Default(bound = "G: Group")
+)]
+pub struct ElGamalCiphertext<G: Group> {
+    pub c1: G,
+    pub c2: G,
+}
+
+impl<G: Group> ToBytes for ElGamalCiphertext<G> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.c1.write(&mut writer)?;
+        self.c2.write(&mut writer)
+    }
+}
+
+impl<G: Group> EncryptionScheme for ElGamal<G> {
+    type Parameters = ElGamalParameters<G>;
+    type PublicKey = ElGamalPublicKey<G>;
+    type SecretKey = ElGamalSecretKey<G>;
+    type Randomness = ElGamalRandomness<G>;
+    type Plaintext = ElGamalPlaintext<G>;
+    type Ciphertext = ElGamalCiphertext<G>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        let setup_time = start_timer!(|| "ElGamal::Setup");
+        let generator = G::rand(rng);
+        end_timer!(setup_time);
+        Ok(ElGamalParameters { generator })
+    }
+
+    fn keygen<R: Rng>(
+        parameters: &Self::Parameters,
+        rng: &mut R,
+    ) -> Result<(Self::PublicKey, Self::SecretKey), Error> {
+        let keygen_time = start_timer!(|| "ElGamal::KeyGen");
+
+        let secret_key = G::ScalarField::rand(rng);
+        let public_key = parameters.generator.mul(&secret_key);
+
+        end_timer!(keygen_time);
+        Ok((public_key, ElGamalSecretKey(secret_key)))
+    }
+
+    fn encrypt(
+        parameters: &Self::Parameters,
+        pk: &Self::PublicKey,
+        message: &Self::Plaintext,
+        r: &Self::Randomness,
+    ) -> Result<Self::Ciphertext, Error> {
+        let encrypt_time = start_timer!(|| "ElGamal::Encrypt");
+
+        // (c1, c2) := (r · g, m + r · pk)
+        let c1 = parameters.generator.mul(&r.0);
+        let c2 = *message + &pk.mul(&r.0);
+
+        end_timer!(encrypt_time);
+        Ok(ElGamalCiphertext { c1, c2 })
+    }
+
+    fn decrypt(
+        _parameters: &Self::Parameters,
+        sk: &Self::SecretKey,
+        ciphertext: &Self::Ciphertext,
+    ) -> Result<Self::Plaintext, Error> {
+        let decrypt_time = start_timer!(|| "ElGamal::Decrypt");
+
+        // m := c2 - sk · c1
+        let message = ciphertext.c2 - &ciphertext.c1.mul(&sk.0);
+
+        end_timer!(decrypt_time);
+        Ok(message)
+    }
+}
@@ -0,0 +1,76 @@
+use algebra_core::{bytes::ToBytes, UniformRand};
+use core::fmt::Debug;
+use rand::Rng;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+#[cfg(feature = "r1cs")]
+pub use constraints::*;
+
+pub mod elgamal;
+
+use crate::Error;
+
+pub trait EncryptionScheme {
+    type Parameters: Clone;
+    type PublicKey: ToBytes + Clone + Default + Eq;
+    type SecretKey: ToBytes + Clone + Default;
+    type Randomness: ToBytes + Clone + Default + UniformRand;
+    type Plaintext: Clone + Default + Eq + Debug;
+    type Ciphertext: ToBytes + Clone + Default + Eq + Debug;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error>;
+
+    fn keygen<R: Rng>(
+        pp: &Self::Parameters,
+        rng: &mut R,
+    ) -> Result<(Self::PublicKey, Self::SecretKey), Error>;
+
+    fn encrypt(
+        pp: &Self::Parameters,
+        pk: &Self::PublicKey,
+        message: &Self::Plaintext,
+        r: &Self::Randomness,
+    ) -> Result<Self::Ciphertext, Error>;
+
+    fn decrypt(
+        pp: &Self::Parameters,
+        sk: &Self::SecretKey,
+        ciphertext: &Self::Ciphertext,
+    ) -> Result<Self::Plaintext, Error>;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::encryption::{elgamal::ElGamal, EncryptionScheme};
+    use algebra::{ed_on_bls12_381::EdwardsAffine as JubJub, test_rng, UniformRand};
+
+    fn encrypt_and_decrypt<E: EncryptionScheme>(message: &E::Plaintext) {
+        let rng = &mut test_rng();
+        let parameters = E::setup(rng).unwrap();
+        let (pk, sk) = E::keygen(&parameters, rng).unwrap();
+        let r = E::Randomness::rand(rng);
+        let ciphertext = E::encrypt(&parameters, &pk, message, &r).unwrap();
+        assert_eq!(&E::decrypt(&parameters, &sk, &ciphertext).unwrap(), message);
+
+        let other_r = E::Randomness::rand(rng);
+        let other_ciphertext = E::encrypt(&parameters, &pk, message, &other_r).unwrap();
+        assert_ne!(ciphertext, other_ciphertext);
+        assert_eq!(
+            &E::decrypt(&parameters, &sk, &other_ciphertext).unwrap(),
+            message
+        );
+
+        let (_, other_sk) = E::keygen(&parameters, rng).unwrap();
+        assert_ne!(
+            &E::decrypt(&parameters, &other_sk, &ciphertext).unwrap(),
+            message
+        );
+    }
+
+    #[test]
+    fn elgamal_encryption_test() {
+        let rng = &mut test_rng();
+        encrypt_and_decrypt::<ElGamal<JubJub>>(&JubJub::rand(rng));
+    }
+}
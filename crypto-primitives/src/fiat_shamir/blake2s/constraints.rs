@@ -0,0 +1,124 @@
+use crate::{
+    crh::{
+        blake2s::constraints::Blake2sCRHParametersGadget,
+        utils::constraints::bytes_to_field_elements_gadget,
+    },
+    fiat_shamir::{
+        blake2s::{bytes_per_challenge, Blake2sFiatShamirRng},
+        FiatShamirRngGadget, BYTES_TAG, FIELD_ELEMENTS_TAG,
+    },
+    prf::blake2s::constraints::blake2s_gadget,
+    Vec,
+};
+use algebra_core::PrimeField;
+use blake2::Blake2s;
+use digest::Digest;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+/// The transcript of `Blake2sFiatShamirRng`, which costs one Blake2s
+/// evaluation per squeeze.
+#[derive(Clone)]
+pub struct Blake2sFiatShamirRngGadget {
+    seed: Vec<UInt8>,
+    absorbed: Vec<UInt8>,
+}
+
+impl Blake2sFiatShamirRngGadget {
+    fn squeeze_seed<F: PrimeField, CS: ConstraintSystem<F>>(
+        &mut self,
+        mut cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        let input = self
+            .seed
+            .iter()
+            .chain(&self.absorbed)
+            .flat_map(|byte| byte.into_bits_le())
+            .collect::<Vec<_>>();
+        let mut seed = Vec::with_capacity(32);
+        for (i, int) in blake2s_gadget(cs.ns(|| "Hash"), &input)?
+            .into_iter()
+            .enumerate()
+        {
+            seed.extend(int.to_bytes(cs.ns(|| format!("Digest to bytes {}", i)))?);
+        }
+        self.seed = seed;
+        self.absorbed.clear();
+        Ok(self.seed.clone())
+    }
+
+    fn absorb_frame(&mut self, tag: u8, length: usize) {
+        self.absorbed.push(UInt8::constant(tag));
+        self.absorbed
+            .extend(UInt8::constant_vec(&(length as u64).to_le_bytes()));
+    }
+}
+
+impl<F: PrimeField> FiatShamirRngGadget<F, Blake2sFiatShamirRng> for Blake2sFiatShamirRngGadget {
+    type ParametersGadget = Blake2sCRHParametersGadget;
+
+    fn new<CS: ConstraintSystem<F>>(
+        _cs: CS,
+        _parameters: &Self::ParametersGadget,
+        domain: &[u8],
+    ) -> Result<Self, SynthesisError> {
+        Ok(Self {
+            seed: UInt8::constant_vec(&Blake2s::digest(domain)),
+            absorbed: Vec::new(),
+        })
+    }
+
+    fn absorb_bytes<CS: ConstraintSystem<F>>(
+        &mut self,
+        _cs: CS,
+        bytes: &[UInt8],
+    ) -> Result<(), SynthesisError> {
+        self.absorb_frame(BYTES_TAG, bytes.len());
+        self.absorbed.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn absorb_field_elements<CS: ConstraintSystem<F>>(
+        &mut self,
+        mut cs: CS,
+        elements: &[FpGadget<F>],
+    ) -> Result<(), SynthesisError> {
+        self.absorb_frame(FIELD_ELEMENTS_TAG, elements.len());
+        for (i, element) in elements.iter().enumerate() {
+            let bytes = element.to_bytes(cs.ns(|| format!("Element {} to bytes", i)))?;
+            self.absorbed.extend(bytes);
+        }
+        Ok(())
+    }
+
+    fn squeeze_field_elements<CS: ConstraintSystem<F>>(
+        &mut self,
+        mut cs: CS,
+        num_elements: usize,
+    ) -> Result<Vec<FpGadget<F>>, SynthesisError> {
+        let mut elements = Vec::with_capacity(num_elements);
+        for i in 0..num_elements {
+            let seed = self.squeeze_seed(cs.ns(|| format!("Squeeze {}", i)))?;
+            elements.extend(bytes_to_field_elements_gadget::<F, CS>(
+                &seed[..bytes_per_challenge::<F>()],
+            ));
+        }
+        Ok(elements)
+    }
+
+    fn squeeze_bits<CS: ConstraintSystem<F>>(
+        &mut self,
+        mut cs: CS,
+        num_bits: usize,
+    ) -> Result<Vec<Boolean>, SynthesisError> {
+        let mut bits = Vec::with_capacity(num_bits);
+        let mut i = 0;
+        while bits.len() < num_bits {
+            let seed = self.squeeze_seed(cs.ns(|| format!("Squeeze {}", i)))?;
+            bits.extend(seed.iter().flat_map(|byte| byte.into_bits_le()));
+            i += 1;
+        }
+        bits.truncate(num_bits);
+        Ok(bits)
+    }
+}
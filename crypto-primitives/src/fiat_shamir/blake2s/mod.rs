@@ -0,0 +1,97 @@
+use crate::{
+    crh::utils::{bytes_per_element, bytes_to_field_elements},
+    fiat_shamir::{FiatShamirRng, BYTES_TAG, FIELD_ELEMENTS_TAG},
+    Vec,
+};
+use algebra_core::{to_bytes, PrimeField, ToBytes};
+use blake2::Blake2s;
+use core::cmp::min;
+use digest::Digest;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// A transcript that chains Blake2s digests:
+/// * the initial seed is `H(domain)`;
+/// * each message is absorbed as its tag byte, its length as a little-endian
+///   `u64`, and then its bytes or the canonical little-endian encoding of its
+///   field elements;
+/// * each squeeze replaces the seed with `H(seed || absorbed)`, and outputs the
+///   low `min(CAPACITY, 256)` bits of the new seed, rounded down to whole
+///   bytes, as a field element.
+#[derive(Clone)]
+pub struct Blake2sFiatShamirRng {
+    seed: [u8; 32],
+    absorbed: Vec<u8>,
+}
+
+impl Blake2sFiatShamirRng {
+    fn squeeze_seed(&mut self) -> [u8; 32] {
+        let mut h = Blake2s::new();
+        h.input(&self.seed);
+        h.input(&self.absorbed);
+        self.seed.copy_from_slice(&h.result());
+        self.absorbed.clear();
+        self.seed
+    }
+
+    fn absorb_frame(&mut self, tag: u8, length: usize) {
+        self.absorbed.push(tag);
+        self.absorbed
+            .extend_from_slice(&(length as u64).to_le_bytes());
+    }
+}
+
+/// The number of digest bytes packed into each squeezed element of `F`.
+pub(crate) fn bytes_per_challenge<F: PrimeField>() -> usize {
+    min(bytes_per_element::<F>(), 32)
+}
+
+impl<F: PrimeField> FiatShamirRng<F> for Blake2sFiatShamirRng {
+    type Parameters = ();
+
+    fn setup() -> Self::Parameters {}
+
+    fn new(_parameters: &Self::Parameters, domain: &[u8]) -> Self {
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&Blake2s::digest(domain));
+        Self {
+            seed,
+            absorbed: Vec::new(),
+        }
+    }
+
+    fn absorb_bytes(&mut self, bytes: &[u8]) {
+        self.absorb_frame(BYTES_TAG, bytes.len());
+        self.absorbed.extend_from_slice(bytes);
+    }
+
+    fn absorb_field_elements(&mut self, elements: &[F]) {
+        self.absorb_frame(FIELD_ELEMENTS_TAG, elements.len());
+        for element in elements {
+            self.absorbed.extend(to_bytes![element].unwrap());
+        }
+    }
+
+    fn squeeze_field_elements(&mut self, num_elements: usize) -> Vec<F> {
+        (0..num_elements)
+            .map(|_| {
+                let seed = self.squeeze_seed();
+                bytes_to_field_elements(&seed[..bytes_per_challenge::<F>()])[0]
+            })
+            .collect()
+    }
+
+    fn squeeze_bits(&mut self, num_bits: usize) -> Vec<bool> {
+        let mut bits = Vec::with_capacity(num_bits);
+        while bits.len() < num_bits {
+            let seed = self.squeeze_seed();
+            bits.extend(
+                seed.iter()
+                    .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1 == 1)),
+            );
+        }
+        bits.truncate(num_bits);
+        bits
+    }
+}
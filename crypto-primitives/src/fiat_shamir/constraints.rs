@@ -0,0 +1,138 @@
+use crate::{fiat_shamir::FiatShamirRng, Vec};
+use algebra_core::PrimeField;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::{fields::ToConstraintFieldGadget, prelude::*};
+
+/// The in-circuit counterpart of the transcript `R`: given the same domain
+/// and messages, it derives the same challenges.
+pub trait FiatShamirRngGadget<F: PrimeField, R: FiatShamirRng<F>>: Sized + Clone {
+    type ParametersGadget: AllocGadget<R::Parameters, F> + Clone;
+
+    fn new<CS: ConstraintSystem<F>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        domain: &[u8],
+    ) -> Result<Self, SynthesisError>;
+
+    fn absorb_bytes<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: CS,
+        bytes: &[UInt8],
+    ) -> Result<(), SynthesisError>;
+
+    fn absorb_field_elements<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: CS,
+        elements: &[FpGadget<F>],
+    ) -> Result<(), SynthesisError>;
+
+    fn absorb<CS: ConstraintSystem<F>, T: ToConstraintFieldGadget<F>>(
+        &mut self,
+        mut cs: CS,
+        value: &T,
+    ) -> Result<(), SynthesisError> {
+        let elements = value.to_constraint_field(cs.ns(|| "To field elements"))?;
+        self.absorb_field_elements(cs.ns(|| "Absorb"), &elements)
+    }
+
+    fn squeeze_field_elements<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: CS,
+        num_elements: usize,
+    ) -> Result<Vec<FpGadget<F>>, SynthesisError>;
+
+    fn squeeze_bits<CS: ConstraintSystem<F>>(
+        &mut self,
+        cs: CS,
+        num_bits: usize,
+    ) -> Result<Vec<Boolean>, SynthesisError>;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        crh::poseidon::PoseidonRounds128,
+        fiat_shamir::{
+            blake2s::{constraints::Blake2sFiatShamirRngGadget, Blake2sFiatShamirRng},
+            poseidon::{constraints::PoseidonSpongeGadget, PoseidonSponge},
+            FiatShamirRng, FiatShamirRngGadget,
+        },
+    };
+    use algebra::{
+        ed_on_bls12_381::{EdwardsAffine as JubJub, Fq},
+        test_rng, UniformRand,
+    };
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::{
+        ed_on_bls12_381::EdwardsGadget, prelude::*, test_constraint_system::TestConstraintSystem,
+    };
+
+    fn transcript_gadget_test<R: FiatShamirRng<Fq>, RG: FiatShamirRngGadget<Fq, R>>() {
+        let rng = &mut test_rng();
+        let parameters = R::setup();
+        let message = b"Hi, I am a transcript!";
+        let point = JubJub::rand(rng);
+        let element = Fq::rand(rng);
+
+        let mut transcript = R::new(&parameters, b"protocol");
+        transcript.absorb_bytes(message);
+        transcript.absorb(&point).unwrap();
+        let challenges = transcript.squeeze_field_elements(2);
+        transcript.absorb_field_elements(&[element]);
+        let bits = transcript.squeeze_bits(300);
+
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let parameters_var =
+            RG::ParametersGadget::alloc(cs.ns(|| "Parameters"), || Ok(&parameters)).unwrap();
+        let message_var = UInt8::alloc_vec(cs.ns(|| "Message"), message).unwrap();
+        let point_var = EdwardsGadget::alloc(cs.ns(|| "Point"), || Ok(point)).unwrap();
+        let element_var = FpGadget::alloc(cs.ns(|| "Element"), || Ok(element)).unwrap();
+
+        let mut transcript_var = RG::new(cs.ns(|| "New"), &parameters_var, b"protocol").unwrap();
+        transcript_var
+            .absorb_bytes(cs.ns(|| "Absorb message"), &message_var)
+            .unwrap();
+        transcript_var
+            .absorb(cs.ns(|| "Absorb point"), &point_var)
+            .unwrap();
+        let challenges_var = transcript_var
+            .squeeze_field_elements(cs.ns(|| "Squeeze elements"), 2)
+            .unwrap();
+        transcript_var
+            .absorb_field_elements(cs.ns(|| "Absorb element"), &[element_var])
+            .unwrap();
+        let bits_var = transcript_var
+            .squeeze_bits(cs.ns(|| "Squeeze bits"), 300)
+            .unwrap();
+
+        assert_eq!(
+            challenges,
+            challenges_var
+                .iter()
+                .map(|c| c.get_value().unwrap())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            bits,
+            bits_var
+                .iter()
+                .map(|b| b.get_value().unwrap())
+                .collect::<Vec<_>>()
+        );
+        assert!(cs.is_satisfied());
+        println!("number of constraints: {}", cs.num_constraints());
+    }
+
+    #[test]
+    fn blake2s_transcript_gadget_test() {
+        transcript_gadget_test::<Blake2sFiatShamirRng, Blake2sFiatShamirRngGadget>();
+    }
+
+    #[test]
+    fn poseidon_transcript_gadget_test() {
+        transcript_gadget_test::<
+            PoseidonSponge<Fq, PoseidonRounds128>,
+            PoseidonSpongeGadget<Fq, PoseidonRounds128>,
+        >();
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{Error, Vec};
+use algebra_core::{PrimeField, ToConstraintField};
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+#[cfg(feature = "r1cs")]
+pub use constraints::*;
+
+pub mod blake2s;
+pub mod poseidon;
+
+/// Every absorbed message is preceded by the tag of its kind and by its
+/// length, so that distinct sequences of messages are never absorbed as the
+/// same input, e.g. an empty byte string and a zero field element.
+pub(crate) const BYTES_TAG: u8 = 0;
+pub(crate) const FIELD_ELEMENTS_TAG: u8 = 1;
+
+/// A transcript of the messages of an interactive protocol, from which the
+/// verifier challenges are derived as in the Fiat-Shamir transform.
+///
+/// Challenges are elements of `F`, the field over which a recursive
+/// verifier is expressed, and `FiatShamirRngGadget` derives the same
+/// challenges from the same messages in-circuit.
+pub trait FiatShamirRng<F: PrimeField>: Clone {
+    type Parameters: Clone;
+
+    fn setup() -> Self::Parameters;
+
+    /// Starts a transcript for the protocol identified by `domain`.
+    fn new(parameters: &Self::Parameters, domain: &[u8]) -> Self;
+
+    /// Absorbs `bytes` as one message.
+    fn absorb_bytes(&mut self, bytes: &[u8]);
+
+    /// Absorbs `elements` as one message.
+    fn absorb_field_elements(&mut self, elements: &[F]);
+
+    /// Absorbs the field elements of `value`, e.g. the coordinates of a curve
+    /// point over `F`.
+    fn absorb<T: ToConstraintField<F> + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.absorb_field_elements(&value.to_field_elements()?);
+        Ok(())
+    }
+
+    fn squeeze_field_elements(&mut self, num_elements: usize) -> Vec<F>;
+
+    /// Squeezes `num_bits` little-endian bits, e.g. for a challenge in a
+    /// scalar field other than `F`.
+    fn squeeze_bits(&mut self, num_bits: usize) -> Vec<bool>;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        crh::poseidon::PoseidonRounds128,
+        fiat_shamir::{blake2s::Blake2sFiatShamirRng, poseidon::PoseidonSponge, FiatShamirRng},
+    };
+    use algebra::{
+        ed_on_bls12_381::{EdwardsAffine as JubJub, Fq},
+        test_rng, UniformRand, Zero,
+    };
+
+    fn transcript_test<R: FiatShamirRng<Fq>>() {
+        let rng = &mut test_rng();
+        let parameters = R::setup();
+        let point = JubJub::rand(rng);
+        let element = Fq::rand(rng);
+
+        let mut transcript = R::new(&parameters, b"protocol");
+        transcript.absorb_bytes(b"message");
+        transcript.absorb(&point).unwrap();
+        transcript.absorb_field_elements(&[element]);
+        let mut other_transcript = transcript.clone();
+        let challenges = transcript.squeeze_field_elements(2);
+        assert_ne!(challenges[0], challenges[1]);
+        assert_eq!(challenges, other_transcript.squeeze_field_elements(2));
+        assert_eq!(
+            transcript.squeeze_bits(300),
+            other_transcript.squeeze_bits(300)
+        );
+
+        // Challenges depend on the domain, the messages and their framing.
+        let mut transcript = R::new(&parameters, b"other protocol");
+        transcript.absorb_bytes(b"message");
+        transcript.absorb(&point).unwrap();
+        transcript.absorb_field_elements(&[element]);
+        assert_ne!(challenges, transcript.squeeze_field_elements(2));
+
+        let mut transcript = R::new(&parameters, b"protocol");
+        transcript.absorb_bytes(b"message");
+        transcript.absorb(&point).unwrap();
+        transcript.absorb_field_elements(&[element + &Fq::from(1u64)]);
+        assert_ne!(challenges, transcript.squeeze_field_elements(2));
+
+        let mut transcript = R::new(&parameters, b"protocol");
+        transcript.absorb_bytes(b"mess");
+        transcript.absorb_bytes(b"age");
+        transcript.absorb(&point).unwrap();
+        transcript.absorb_field_elements(&[element]);
+        assert_ne!(challenges, transcript.squeeze_field_elements(2));
+
+        // Messages of different kinds are told apart.
+        let mut transcript = R::new(&parameters, b"protocol");
+        transcript.absorb_bytes(b"");
+        let mut other_transcript = R::new(&parameters, b"protocol");
+        other_transcript.absorb_field_elements(&[Fq::zero()]);
+        assert_ne!(
+            transcript.squeeze_field_elements(1),
+            other_transcript.squeeze_field_elements(1)
+        );
+
+        let mut transcript = R::new(&parameters, b"protocol");
+        transcript.absorb_field_elements(&[]);
+        let mut other_transcript = R::new(&parameters, b"protocol");
+        other_transcript.absorb_bytes(&[]);
+        assert_ne!(
+            transcript.squeeze_field_elements(1),
+            other_transcript.squeeze_field_elements(1)
+        );
+    }
+
+    #[test]
+    fn blake2s_transcript_test() {
+        transcript_test::<Blake2sFiatShamirRng>();
+    }
+
+    #[test]
+    fn poseidon_transcript_test() {
+        transcript_test::<PoseidonSponge<Fq, PoseidonRounds128>>();
+    }
+}
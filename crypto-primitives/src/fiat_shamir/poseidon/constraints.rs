@@ -0,0 +1,153 @@
+use crate::{
+    crh::{
+        poseidon::{
+            constraints::{poseidon_permutation_gadget, PoseidonParametersGadget},
+            PoseidonParameters, PoseidonRoundParams, FIAT_SHAMIR_DOMAIN,
+        },
+        utils::constraints::bytes_to_field_elements_gadget,
+    },
+    fiat_shamir::{poseidon::PoseidonSponge, FiatShamirRngGadget, BYTES_TAG, FIELD_ELEMENTS_TAG},
+    Vec,
+};
+use algebra_core::{FpParameters, PrimeField};
+use core::{cmp::min, marker::PhantomData};
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+/// The sponge of `PoseidonSponge`, in which absorbing field elements is
+/// free and each squeeze costs one permutation.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "F: PrimeField, P: PoseidonRoundParams"))]
+pub struct PoseidonSpongeGadget<F: PrimeField, P: PoseidonRoundParams> {
+    parameters: PoseidonParameters<F>,
+    state: Vec<FpGadget<F>>,
+    position: usize,
+    /// The number of permutations so far, which namespaces them.
+    num_permutations: usize,
+    #[doc(hidden)]
+    _params: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: PoseidonRoundParams> PoseidonSpongeGadget<F, P> {
+    fn permute<CS: ConstraintSystem<F>>(&mut self, mut cs: CS) -> Result<(), SynthesisError> {
+        poseidon_permutation_gadget::<F, P, _>(
+            cs.ns(|| format!("Permute {}", self.num_permutations)),
+            &self.parameters,
+            &mut self.state,
+        )?;
+        self.num_permutations += 1;
+        self.position = 0;
+        Ok(())
+    }
+
+    fn squeeze<CS: ConstraintSystem<F>>(&mut self, cs: CS) -> Result<FpGadget<F>, SynthesisError> {
+        self.permute(cs)?;
+        Ok(self.state[1].clone())
+    }
+
+    fn absorb_frame<CS: ConstraintSystem<F>>(
+        &mut self,
+        mut cs: CS,
+        tag: u8,
+        length: usize,
+    ) -> Result<(), SynthesisError> {
+        let zero = FpGadget::zero(cs.ns(|| "zero"))?;
+        let frame = [
+            zero.add_constant(cs.ns(|| "tag"), &F::from(tag))?,
+            zero.add_constant(cs.ns(|| "length"), &F::from(length as u64))?,
+        ];
+        self.absorb_elements(cs.ns(|| "Absorb frame"), &frame)
+    }
+
+    fn absorb_elements<CS: ConstraintSystem<F>>(
+        &mut self,
+        mut cs: CS,
+        elements: &[FpGadget<F>],
+    ) -> Result<(), SynthesisError> {
+        for (i, element) in elements.iter().enumerate() {
+            self.state[1 + self.position].add_in_place(cs.ns(|| format!("Add {}", i)), element)?;
+            self.position += 1;
+            if self.position == P::WIDTH - 1 {
+                self.permute(&mut cs)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<F, P> FiatShamirRngGadget<F, PoseidonSponge<F, P>> for PoseidonSpongeGadget<F, P>
+where
+    F: PrimeField,
+    P: PoseidonRoundParams,
+{
+    type ParametersGadget = PoseidonParametersGadget<F>;
+
+    fn new<CS: ConstraintSystem<F>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        domain: &[u8],
+    ) -> Result<Self, SynthesisError> {
+        let mut state = Vec::with_capacity(P::WIDTH);
+        state.push(
+            FpGadget::zero(cs.ns(|| "capacity"))?
+                .add_constant(cs.ns(|| "domain"), &F::from(FIAT_SHAMIR_DOMAIN))?,
+        );
+        for i in 1..P::WIDTH {
+            state.push(FpGadget::zero(cs.ns(|| format!("rate {}", i)))?);
+        }
+        let mut sponge = Self {
+            parameters: parameters.parameters.clone(),
+            state,
+            position: 0,
+            num_permutations: 0,
+            _params: PhantomData,
+        };
+        sponge.absorb_bytes(cs.ns(|| "Absorb domain"), &UInt8::constant_vec(domain))?;
+        Ok(sponge)
+    }
+
+    fn absorb_bytes<CS: ConstraintSystem<F>>(
+        &mut self,
+        mut cs: CS,
+        bytes: &[UInt8],
+    ) -> Result<(), SynthesisError> {
+        self.absorb_frame(cs.ns(|| "Frame"), BYTES_TAG, bytes.len())?;
+        let elements = bytes_to_field_elements_gadget::<F, CS>(bytes);
+        self.absorb_elements(cs.ns(|| "Absorb bytes"), &elements)
+    }
+
+    fn absorb_field_elements<CS: ConstraintSystem<F>>(
+        &mut self,
+        mut cs: CS,
+        elements: &[FpGadget<F>],
+    ) -> Result<(), SynthesisError> {
+        self.absorb_frame(cs.ns(|| "Frame"), FIELD_ELEMENTS_TAG, elements.len())?;
+        self.absorb_elements(cs.ns(|| "Absorb elements"), elements)
+    }
+
+    fn squeeze_field_elements<CS: ConstraintSystem<F>>(
+        &mut self,
+        mut cs: CS,
+        num_elements: usize,
+    ) -> Result<Vec<FpGadget<F>>, SynthesisError> {
+        (0..num_elements).map(|_| self.squeeze(&mut cs)).collect()
+    }
+
+    fn squeeze_bits<CS: ConstraintSystem<F>>(
+        &mut self,
+        mut cs: CS,
+        num_bits: usize,
+    ) -> Result<Vec<Boolean>, SynthesisError> {
+        let capacity = <F::Params as FpParameters>::CAPACITY as usize;
+        let mut bits = Vec::with_capacity(num_bits);
+        let mut i = 0;
+        while bits.len() < num_bits {
+            let element = self.squeeze(&mut cs)?;
+            let element_bits = element.to_bits(cs.ns(|| format!("Element {} to bits", i)))?;
+            let num_element_bits = min(capacity, num_bits - bits.len());
+            bits.extend(element_bits.into_iter().rev().take(num_element_bits));
+            i += 1;
+        }
+        Ok(bits)
+    }
+}
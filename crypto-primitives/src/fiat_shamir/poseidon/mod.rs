@@ -0,0 +1,105 @@
+use crate::{
+    crh::{
+        poseidon::{
+            poseidon_permutation, PoseidonParameters, PoseidonRoundParams, FIAT_SHAMIR_DOMAIN,
+        },
+        utils::bytes_to_field_elements,
+    },
+    fiat_shamir::{FiatShamirRng, BYTES_TAG, FIELD_ELEMENTS_TAG},
+    Vec,
+};
+use algebra_core::{biginteger::BigInteger, FpParameters, PrimeField};
+use core::{cmp::min, marker::PhantomData};
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// A duplex Poseidon sponge, whose capacity element is initialized to
+/// `FIAT_SHAMIR_DOMAIN`:
+/// * field elements are added to the rate in turn, with a permutation whenever
+///   the rate is full;
+/// * each message is absorbed as its tag and its length, followed by its field
+///   elements, or the packing of its bytes with `bytes_to_field_elements`;
+/// * the domain is absorbed as the first message;
+/// * each squeezed element is the first rate element after a permutation.
+///
+/// Unlike `Blake2sFiatShamirRng`, absorbing field elements costs no
+/// constraints in-circuit.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "F: PrimeField, P: PoseidonRoundParams"))]
+pub struct PoseidonSponge<F: PrimeField, P: PoseidonRoundParams> {
+    parameters: PoseidonParameters<F>,
+    state: Vec<F>,
+    position: usize,
+    _params: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: PoseidonRoundParams> PoseidonSponge<F, P> {
+    fn squeeze(&mut self) -> F {
+        poseidon_permutation::<F, P>(&self.parameters, &mut self.state);
+        self.position = 0;
+        self.state[1]
+    }
+
+    fn absorb_frame(&mut self, tag: u8, length: usize) {
+        self.absorb_elements(&[F::from(tag), F::from(length as u64)]);
+    }
+
+    fn absorb_elements(&mut self, elements: &[F]) {
+        for element in elements {
+            self.state[1 + self.position] += element;
+            self.position += 1;
+            if self.position == P::WIDTH - 1 {
+                poseidon_permutation::<F, P>(&self.parameters, &mut self.state);
+                self.position = 0;
+            }
+        }
+    }
+}
+
+impl<F: PrimeField, P: PoseidonRoundParams> FiatShamirRng<F> for PoseidonSponge<F, P> {
+    type Parameters = PoseidonParameters<F>;
+
+    fn setup() -> Self::Parameters {
+        PoseidonParameters::new::<P>()
+    }
+
+    fn new(parameters: &Self::Parameters, domain: &[u8]) -> Self {
+        let mut state = vec![F::zero(); P::WIDTH];
+        state[0] = F::from(FIAT_SHAMIR_DOMAIN);
+        let mut sponge = Self {
+            parameters: parameters.clone(),
+            state,
+            position: 0,
+            _params: PhantomData,
+        };
+        sponge.absorb_bytes(domain);
+        sponge
+    }
+
+    fn absorb_bytes(&mut self, bytes: &[u8]) {
+        self.absorb_frame(BYTES_TAG, bytes.len());
+        self.absorb_elements(&bytes_to_field_elements(bytes));
+    }
+
+    fn absorb_field_elements(&mut self, elements: &[F]) {
+        self.absorb_frame(FIELD_ELEMENTS_TAG, elements.len());
+        self.absorb_elements(elements);
+    }
+
+    fn squeeze_field_elements(&mut self, num_elements: usize) -> Vec<F> {
+        (0..num_elements).map(|_| self.squeeze()).collect()
+    }
+
+    /// Takes the low `CAPACITY` bits of as many squeezed elements as needed.
+    fn squeeze_bits(&mut self, num_bits: usize) -> Vec<bool> {
+        let capacity = <F::Params as FpParameters>::CAPACITY as usize;
+        let mut bits = Vec::with_capacity(num_bits);
+        while bits.len() < num_bits {
+            let element = self.squeeze().into_repr();
+            let num_element_bits = min(capacity, num_bits - bits.len());
+            bits.extend((0..num_element_bits).map(|i| element.get_bit(i)));
+        }
+        bits
+    }
+}
@@ -17,25 +17,36 @@ pub(crate) use std::{borrow::ToOwned, boxed::Box, vec::Vec};
 
 pub mod commitment;
 pub mod crh;
+pub mod encryption;
+pub mod fiat_shamir;
 pub mod merkle_tree;
 pub mod nizk;
 pub mod prf;
+pub mod sigma;
 pub mod signature;
 
 pub use self::{
     commitment::CommitmentScheme,
-    crh::FixedLengthCRH,
+    crh::{FixedLengthCRH, CRH},
+    encryption::EncryptionScheme,
+    fiat_shamir::FiatShamirRng,
     merkle_tree::{MerkleHashTree, MerkleTreePath},
     nizk::NIZK,
     prf::PRF,
+    sigma::SigmaProtocol,
     signature::SignatureScheme,
 };
 
 #[cfg(feature = "r1cs")]
 pub use self::{
-    commitment::CommitmentGadget, crh::FixedLengthCRHGadget,
-    merkle_tree::constraints::MerkleTreePathGadget, nizk::NIZKVerifierGadget, prf::PRFGadget,
-    signature::SigRandomizePkGadget,
+    commitment::CommitmentGadget,
+    crh::{CRHGadget, FixedLengthCRHGadget},
+    encryption::EncryptionGadget,
+    fiat_shamir::FiatShamirRngGadget,
+    merkle_tree::constraints::MerkleTreePathGadget,
+    nizk::NIZKVerifierGadget,
+    prf::PRFGadget,
+    signature::{SigRandomizePkGadget, SigVerifyGadget},
 };
 
 pub type Error = Box<dyn algebra_core::Error>;
@@ -44,6 +55,8 @@ pub type Error = Box<dyn algebra_core::Error>;
 pub enum CryptoError {
     IncorrectInputLength(usize),
     NotPrimeOrder,
+    UnsupportedOperation,
+    MismatchedProverState,
 }
 
 impl core::fmt::Display for CryptoError {
@@ -51,6 +64,10 @@ impl core::fmt::Display for CryptoError {
         let msg = match self {
             CryptoError::IncorrectInputLength(len) => format!("input length is wrong: {}", len),
             CryptoError::NotPrimeOrder => "element is not prime order".to_owned(),
+            CryptoError::UnsupportedOperation => "operation is not supported".to_owned(),
+            CryptoError::MismatchedProverState => {
+                "prover state does not match the witness".to_owned()
+            },
         };
         write!(f, "{}", msg)
     }
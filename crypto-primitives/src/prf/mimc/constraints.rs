@@ -0,0 +1,142 @@
+use crate::{
+    crh::{
+        mimc::{
+            constraints::{mimc_feistel_sponge_gadget, mimc_hash_gadget},
+            MiMCParameters, MiMCRoundParams, PRF_DOMAIN,
+        },
+        utils::constraints::bytes_to_field_elements_gadget,
+    },
+    prf::{
+        mimc::{MiMCFeistelPRF, MiMCPRF},
+        PRFGadget,
+    },
+    Vec,
+};
+use algebra_core::PrimeField;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+use core::marker::PhantomData;
+
+fn pack_seed_and_input_gadget<F: PrimeField, CS: ConstraintSystem<F>>(
+    seed: &[UInt8],
+    input: &[UInt8],
+) -> Vec<FpGadget<F>> {
+    assert_eq!(seed.len(), 32);
+    assert_eq!(input.len(), 32);
+    let mut elements = bytes_to_field_elements_gadget::<F, CS>(seed);
+    elements.extend(bytes_to_field_elements_gadget::<F, CS>(input));
+    elements
+}
+
+pub struct MiMCPRFGadget<F: PrimeField, P: MiMCRoundParams> {
+    #[doc(hidden)]
+    _field: PhantomData<F>,
+    #[doc(hidden)]
+    _params: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: MiMCRoundParams> PRFGadget<MiMCPRF<F, P>, F> for MiMCPRFGadget<F, P> {
+    type OutputGadget = FpGadget<F>;
+
+    fn new_seed<CS: ConstraintSystem<F>>(mut cs: CS, seed: &[u8; 32]) -> Vec<UInt8> {
+        UInt8::alloc_vec(&mut cs.ns(|| "alloc_seed"), seed).unwrap()
+    }
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<F>>(
+        cs: CS,
+        seed: &[UInt8],
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        let parameters = MiMCParameters::cached::<P>();
+        let elements = pack_seed_and_input_gadget::<F, CS>(seed, input);
+        mimc_hash_gadget::<F, P, _>(cs, &parameters, PRF_DOMAIN, &elements)
+    }
+}
+
+pub struct MiMCFeistelPRFGadget<F: PrimeField, P: MiMCRoundParams> {
+    #[doc(hidden)]
+    _field: PhantomData<F>,
+    #[doc(hidden)]
+    _params: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: MiMCRoundParams> PRFGadget<MiMCFeistelPRF<F, P>, F>
+    for MiMCFeistelPRFGadget<F, P>
+{
+    type OutputGadget = FpGadget<F>;
+
+    fn new_seed<CS: ConstraintSystem<F>>(mut cs: CS, seed: &[u8; 32]) -> Vec<UInt8> {
+        UInt8::alloc_vec(&mut cs.ns(|| "alloc_seed"), seed).unwrap()
+    }
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<F>>(
+        cs: CS,
+        seed: &[UInt8],
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        let parameters = MiMCParameters::cached::<P>();
+        let elements = pack_seed_and_input_gadget::<F, CS>(seed, input);
+        mimc_feistel_sponge_gadget::<F, P, _>(cs, &parameters, PRF_DOMAIN, &elements)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        crh::mimc::MiMCRoundParams,
+        prf::{
+            mimc::{
+                constraints::{MiMCFeistelPRFGadget, MiMCPRFGadget},
+                MiMCFeistelPRF, MiMCPRF,
+            },
+            PRFGadget, PRF,
+        },
+    };
+    use algebra::{ed_on_bls12_381::Fq as Fr, test_rng};
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::{prelude::*, test_constraint_system::TestConstraintSystem};
+    use rand::Rng;
+
+    #[derive(Clone)]
+    struct Rounds;
+
+    impl MiMCRoundParams for Rounds {
+        const ROUNDS: usize = 110;
+        const EXPONENT: u64 = 5;
+        const INPUT_SIZE_BITS: usize = 256;
+    }
+
+    fn prf_primitive_gadget_test<P, PG>()
+    where
+        P: PRF<Seed = [u8; 32], Input = [u8; 32], Output = Fr>,
+        PG: PRFGadget<P, Fr, OutputGadget = FpGadget<Fr>>,
+    {
+        let rng = &mut test_rng();
+        let mut cs = TestConstraintSystem::<Fr>::new();
+
+        let seed: [u8; 32] = rng.gen();
+        let input: [u8; 32] = rng.gen();
+        let primitive_result = P::evaluate(&seed, &input).unwrap();
+        assert_ne!(primitive_result, P::evaluate(&input, &seed).unwrap());
+
+        let seed_bytes = PG::new_seed(cs.ns(|| "seed"), &seed);
+        let input_bytes = UInt8::alloc_vec(cs.ns(|| "input"), &input).unwrap();
+        let gadget_result =
+            PG::check_evaluation_gadget(cs.ns(|| "gadget_evaluation"), &seed_bytes, &input_bytes)
+                .unwrap();
+
+        assert_eq!(primitive_result, gadget_result.value.unwrap());
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn mimc_prf_primitive_gadget_test() {
+        prf_primitive_gadget_test::<MiMCPRF<Fr, Rounds>, MiMCPRFGadget<Fr, Rounds>>();
+    }
+
+    #[test]
+    fn mimc_feistel_prf_primitive_gadget_test() {
+        prf_primitive_gadget_test::<MiMCFeistelPRF<Fr, Rounds>, MiMCFeistelPRFGadget<Fr, Rounds>>();
+    }
+}
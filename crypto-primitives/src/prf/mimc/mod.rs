@@ -0,0 +1,65 @@
+use core::marker::PhantomData;
+
+use super::PRF;
+use crate::{
+    crh::{
+        mimc::{mimc_feistel_sponge, mimc_hash, MiMCParameters, MiMCRoundParams, PRF_DOMAIN},
+        utils::bytes_to_field_elements,
+    },
+    CryptoError, Vec,
+};
+use algebra_core::PrimeField;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+fn pack_seed_and_input<F: PrimeField>(seed: &[u8; 32], input: &[u8; 32]) -> Vec<F> {
+    let mut elements = bytes_to_field_elements::<F>(seed);
+    elements.extend(bytes_to_field_elements::<F>(input));
+    elements
+}
+
+/// The PRF `(seed, input) -> MiMC(seed || input)`, with MiMC-n/n in the
+/// Miyaguchi-Preneel mode and the parameters of
+/// `MiMCParameters::cached::<P>()`.
+pub struct MiMCPRF<F: PrimeField, P: MiMCRoundParams> {
+    field: PhantomData<F>,
+    params: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: MiMCRoundParams> PRF for MiMCPRF<F, P> {
+    type Input = [u8; 32];
+    type Output = F;
+    type Seed = [u8; 32];
+
+    fn evaluate(seed: &Self::Seed, input: &Self::Input) -> Result<Self::Output, CryptoError> {
+        let eval_time = start_timer!(|| "MiMCPRF::Eval");
+        let parameters = MiMCParameters::cached::<P>();
+        let elements = pack_seed_and_input(seed, input);
+        let result = mimc_hash::<F, P>(&parameters, PRF_DOMAIN, &elements);
+        end_timer!(eval_time);
+        Ok(result)
+    }
+}
+
+/// The PRF `(seed, input) -> MiMC(seed || input)`, with the sponge over the
+/// MiMC-2n/n Feistel permutation.
+pub struct MiMCFeistelPRF<F: PrimeField, P: MiMCRoundParams> {
+    field: PhantomData<F>,
+    params: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: MiMCRoundParams> PRF for MiMCFeistelPRF<F, P> {
+    type Input = [u8; 32];
+    type Output = F;
+    type Seed = [u8; 32];
+
+    fn evaluate(seed: &Self::Seed, input: &Self::Input) -> Result<Self::Output, CryptoError> {
+        let eval_time = start_timer!(|| "MiMCFeistelPRF::Eval");
+        let parameters = MiMCParameters::cached::<P>();
+        let elements = pack_seed_and_input(seed, input);
+        let result = mimc_feistel_sponge::<F, P>(&parameters, PRF_DOMAIN, &elements);
+        end_timer!(eval_time);
+        Ok(result)
+    }
+}
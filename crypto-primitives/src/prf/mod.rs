@@ -11,6 +11,12 @@ pub use constraints::*;
 pub mod blake2s;
 pub use self::blake2s::*;
 
+pub mod mimc;
+pub use self::mimc::*;
+
+pub mod poseidon;
+pub use self::poseidon::*;
+
 pub trait PRF {
     type Input: FromBytes + Default;
     type Output: ToBytes + Eq + Clone + Default + Hash;
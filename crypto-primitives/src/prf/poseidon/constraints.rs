@@ -0,0 +1,87 @@
+use crate::{
+    crh::{
+        poseidon::{
+            constraints::poseidon_sponge_gadget, PoseidonParameters, PoseidonRoundParams,
+            PRF_DOMAIN,
+        },
+        utils::constraints::bytes_to_field_elements_gadget,
+    },
+    prf::{poseidon::PoseidonPRF, PRFGadget},
+    Vec,
+};
+use algebra_core::PrimeField;
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::prelude::*;
+
+use core::marker::PhantomData;
+
+pub struct PoseidonPRFGadget<F: PrimeField, P: PoseidonRoundParams> {
+    #[doc(hidden)]
+    _field: PhantomData<F>,
+    #[doc(hidden)]
+    _params: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: PoseidonRoundParams> PRFGadget<PoseidonPRF<F, P>, F>
+    for PoseidonPRFGadget<F, P>
+{
+    type OutputGadget = FpGadget<F>;
+
+    fn new_seed<CS: ConstraintSystem<F>>(mut cs: CS, seed: &[u8; 32]) -> Vec<UInt8> {
+        UInt8::alloc_vec(&mut cs.ns(|| "alloc_seed"), seed).unwrap()
+    }
+
+    fn check_evaluation_gadget<CS: ConstraintSystem<F>>(
+        cs: CS,
+        seed: &[UInt8],
+        input: &[UInt8],
+    ) -> Result<Self::OutputGadget, SynthesisError> {
+        assert_eq!(seed.len(), 32);
+        assert_eq!(input.len(), 32);
+        let parameters = PoseidonParameters::cached::<P>();
+        let mut elements = bytes_to_field_elements_gadget::<F, CS>(seed);
+        elements.extend(bytes_to_field_elements_gadget::<F, CS>(input));
+        poseidon_sponge_gadget::<F, P, _>(cs, &parameters, PRF_DOMAIN, &elements)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        crh::poseidon::PoseidonRounds128,
+        prf::{
+            poseidon::{constraints::PoseidonPRFGadget, PoseidonPRF},
+            PRFGadget, PRF,
+        },
+    };
+    use algebra::{ed_on_bls12_381::Fq as Fr, test_rng};
+    use r1cs_core::ConstraintSystem;
+    use r1cs_std::{prelude::*, test_constraint_system::TestConstraintSystem};
+    use rand::Rng;
+
+    type TestPRF = PoseidonPRF<Fr, PoseidonRounds128>;
+    type TestPRFGadget = PoseidonPRFGadget<Fr, PoseidonRounds128>;
+
+    #[test]
+    fn prf_primitive_gadget_test() {
+        let rng = &mut test_rng();
+        let mut cs = TestConstraintSystem::<Fr>::new();
+
+        let seed: [u8; 32] = rng.gen();
+        let input: [u8; 32] = rng.gen();
+        let primitive_result = TestPRF::evaluate(&seed, &input).unwrap();
+        assert_ne!(primitive_result, TestPRF::evaluate(&input, &seed).unwrap());
+
+        let seed_bytes = TestPRFGadget::new_seed(cs.ns(|| "seed"), &seed);
+        let input_bytes = UInt8::alloc_vec(cs.ns(|| "input"), &input).unwrap();
+        let gadget_result = TestPRFGadget::check_evaluation_gadget(
+            cs.ns(|| "gadget_evaluation"),
+            &seed_bytes,
+            &input_bytes,
+        )
+        .unwrap();
+
+        assert_eq!(primitive_result, gadget_result.value.unwrap());
+        assert!(cs.is_satisfied());
+    }
+}
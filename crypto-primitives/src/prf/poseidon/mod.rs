@@ -0,0 +1,37 @@
+use core::marker::PhantomData;
+
+use super::PRF;
+use crate::{
+    crh::{
+        poseidon::{poseidon_sponge, PoseidonParameters, PoseidonRoundParams, PRF_DOMAIN},
+        utils::bytes_to_field_elements,
+    },
+    CryptoError,
+};
+use algebra_core::PrimeField;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// The PRF `(seed, input) -> Poseidon(seed || input)`, with the parameters
+/// of `PoseidonParameters::cached::<P>()`.
+pub struct PoseidonPRF<F: PrimeField, P: PoseidonRoundParams> {
+    field: PhantomData<F>,
+    params: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: PoseidonRoundParams> PRF for PoseidonPRF<F, P> {
+    type Input = [u8; 32];
+    type Output = F;
+    type Seed = [u8; 32];
+
+    fn evaluate(seed: &Self::Seed, input: &Self::Input) -> Result<Self::Output, CryptoError> {
+        let eval_time = start_timer!(|| "PoseidonPRF::Eval");
+        let parameters = PoseidonParameters::cached::<P>();
+        let mut elements = bytes_to_field_elements::<F>(seed);
+        elements.extend(bytes_to_field_elements::<F>(input));
+        let result = poseidon_sponge::<F, P>(&parameters, PRF_DOMAIN, &elements);
+        end_timer!(eval_time);
+        Ok(result)
+    }
+}
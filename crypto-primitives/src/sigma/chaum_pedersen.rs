@@ -0,0 +1,110 @@
+use crate::{sigma::SigmaProtocol, Error};
+use algebra_core::{
+    bytes::ToBytes,
+    groups::Group,
+    io::{Result as IoResult, Write},
+    UniformRand,
+};
+use core::marker::PhantomData;
+use rand::Rng;
+
+/// The Chaum-Pedersen proof that `log_g(a) == log_h(b)`, i.e. of knowledge
+/// of `x` such that `a = x · g` and `b = x · h`:
+/// * the commitment is `(t1, t2) := (r · g, r · h)` for random `r`;
+/// * the response to `c` is `z := r + c · x`;
+/// * the verifier checks that `z · g == t1 + c · a` and `z · h == t2 + c · b`.
+pub struct ChaumPedersen<G: Group> {
+    _group: PhantomData<G>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "G: Group"), Debug(bound = "G: Group"))]
+pub struct ChaumPedersenStatement<G: Group> {
+    pub g: G,
+    pub h: G,
+    pub a: G,
+    pub b: G,
+}
+
+impl<G: Group> ToBytes for ChaumPedersenStatement<G> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.g.write(&mut writer)?;
+        self.h.write(&mut writer)?;
+        self.a.write(&mut writer)?;
+        self.b.write(&mut writer)
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "G: Group"), Debug(bound = "G: Group"))]
+pub struct ChaumPedersenCommitment<G: Group> {
+    pub t1: G,
+    pub t2: G,
+}
+
+impl<G: Group> ToBytes for ChaumPedersenCommitment<G> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.t1.write(&mut writer)?;
+        self.t2.write(&mut writer)
+    }
+}
+
+impl<G: Group> SigmaProtocol for ChaumPedersen<G> {
+    type Challenge = G::ScalarField;
+    type Statement = ChaumPedersenStatement<G>;
+    type Witness = G::ScalarField;
+    type Commitment = ChaumPedersenCommitment<G>;
+    type ProverState = G::ScalarField;
+    type Response = G::ScalarField;
+
+    const PROTOCOL_NAME: &'static [u8] = b"ChaumPedersen";
+
+    fn prover_commit<R: Rng>(
+        statement: &Self::Statement,
+        _witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::ProverState), Error> {
+        let r = G::ScalarField::rand(rng);
+        let commitment = ChaumPedersenCommitment {
+            t1: statement.g.mul(&r),
+            t2: statement.h.mul(&r),
+        };
+        Ok((commitment, r))
+    }
+
+    fn prover_respond(
+        _statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: &Self::ProverState,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Error> {
+        Ok(*state + &(*challenge * witness))
+    }
+
+    fn verify(
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Error> {
+        Ok(
+            statement.g.mul(response) == commitment.t1 + &statement.a.mul(challenge)
+                && statement.h.mul(response) == commitment.t2 + &statement.b.mul(challenge),
+        )
+    }
+
+    fn simulate<R: Rng>(
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Error> {
+        let response = G::ScalarField::rand(rng);
+        let commitment = ChaumPedersenCommitment {
+            t1: statement.g.mul(&response) - &statement.a.mul(challenge),
+            t2: statement.h.mul(&response) - &statement.b.mul(challenge),
+        };
+        Ok((commitment, response))
+    }
+}
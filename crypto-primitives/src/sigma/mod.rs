@@ -0,0 +1,304 @@
+use crate::{fiat_shamir::FiatShamirRng, Error};
+use algebra_core::{bytes::ToBytes, to_bytes, PrimeField};
+use rand::Rng;
+
+pub mod chaum_pedersen;
+pub mod or;
+pub mod pedersen;
+pub mod schnorr;
+
+pub use self::{
+    chaum_pedersen::ChaumPedersen, or::OrProof, pedersen::PedersenOpening, schnorr::SchnorrPoK,
+};
+
+/// A three-move public-coin protocol, in which the prover sends a
+/// commitment, the verifier replies with a random challenge, and the prover
+/// answers with a response.
+///
+/// The protocol must be special honest-verifier zero-knowledge: `simulate`
+/// outputs accepting transcripts for any given challenge, and these are what
+/// `OrProof` uses for the branch whose witness the prover does not know.
+pub trait SigmaProtocol {
+    type Challenge: PrimeField;
+    type Statement: ToBytes + Clone;
+    type Witness: Clone;
+    type Commitment: ToBytes + Clone;
+    type ProverState: Clone;
+    type Response: Clone;
+
+    /// The domain of the Fiat-Shamir transcripts of the protocol.
+    const PROTOCOL_NAME: &'static [u8];
+
+    fn prover_commit<R: Rng>(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::ProverState), Error>;
+
+    fn prover_respond(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: &Self::ProverState,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Error>;
+
+    fn verify(
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Error>;
+
+    /// Outputs a commitment and response that are accepted with `challenge`,
+    /// without a witness.
+    fn simulate<R: Rng>(
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Error>;
+
+    /// Derives the challenge from the statement and the commitment with a
+    /// transcript whose domain is `PROTOCOL_NAME`.
+    fn fiat_shamir_challenge<FS: FiatShamirRng<Self::Challenge>>(
+        fs_parameters: &FS::Parameters,
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+    ) -> Result<Self::Challenge, Error> {
+        let mut transcript = FS::new(fs_parameters, Self::PROTOCOL_NAME);
+        transcript.absorb_bytes(&to_bytes![statement]?);
+        transcript.absorb_bytes(&to_bytes![commitment]?);
+        Ok(transcript.squeeze_field_elements(1)[0])
+    }
+
+    /// Proves knowledge of `witness` non-interactively, with the challenge
+    /// given by `fiat_shamir_challenge`.
+    fn prove<FS: FiatShamirRng<Self::Challenge>, R: Rng>(
+        fs_parameters: &FS::Parameters,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<SigmaProof<Self>, Error> {
+        let prove_time = start_timer!(|| "SigmaProtocol::Prove");
+        let (commitment, state) = Self::prover_commit(statement, witness, rng)?;
+        let challenge = Self::fiat_shamir_challenge::<FS>(fs_parameters, statement, &commitment)?;
+        let response = Self::prover_respond(statement, witness, &state, &challenge)?;
+        end_timer!(prove_time);
+        Ok(SigmaProof {
+            commitment,
+            response,
+        })
+    }
+
+    fn verify_proof<FS: FiatShamirRng<Self::Challenge>>(
+        fs_parameters: &FS::Parameters,
+        statement: &Self::Statement,
+        proof: &SigmaProof<Self>,
+    ) -> Result<bool, Error> {
+        let verify_time = start_timer!(|| "SigmaProtocol::Verify");
+        let challenge =
+            Self::fiat_shamir_challenge::<FS>(fs_parameters, statement, &proof.commitment)?;
+        let result = Self::verify(statement, &proof.commitment, &challenge, &proof.response)?;
+        end_timer!(verify_time);
+        Ok(result)
+    }
+}
+
+/// A non-interactive proof, whose challenge is recomputed by the verifier.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "S: SigmaProtocol"))]
+pub struct SigmaProof<S: SigmaProtocol + ?Sized> {
+    pub commitment: S::Commitment,
+    pub response: S::Response,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        commitment::{
+            pedersen::{PedersenCommitment, PedersenRandomness, PedersenWindow},
+            CommitmentScheme,
+        },
+        fiat_shamir::{blake2s::Blake2sFiatShamirRng, FiatShamirRng},
+        sigma::{
+            chaum_pedersen::ChaumPedersenStatement,
+            or::{OrStatement, OrWitness},
+            pedersen::{PedersenOpeningStatement, PedersenOpeningWitness},
+            schnorr::SchnorrStatement,
+            ChaumPedersen, OrProof, PedersenOpening, SchnorrPoK, SigmaProtocol,
+        },
+    };
+    use algebra::{
+        ed_on_bls12_381::{EdwardsProjective as JubJub, Fr},
+        groups::Group,
+        test_rng, One, UniformRand,
+    };
+    use rand::Rng;
+
+    fn sigma_protocol_test<S: SigmaProtocol>(
+        statement: &S::Statement,
+        witness: &S::Witness,
+        bad_statement: &S::Statement,
+    ) {
+        let rng = &mut test_rng();
+
+        // Interactive.
+        let (commitment, state) = S::prover_commit(statement, witness, rng).unwrap();
+        let challenge = S::Challenge::rand(rng);
+        let response = S::prover_respond(statement, witness, &state, &challenge).unwrap();
+        assert!(S::verify(statement, &commitment, &challenge, &response).unwrap());
+        let other_challenge = challenge + &S::Challenge::one();
+        assert!(!S::verify(statement, &commitment, &other_challenge, &response).unwrap());
+        assert!(!S::verify(bad_statement, &commitment, &challenge, &response).unwrap());
+
+        // Simulated.
+        let (commitment, response) = S::simulate(statement, &challenge, rng).unwrap();
+        assert!(S::verify(statement, &commitment, &challenge, &response).unwrap());
+
+        // Non-interactive.
+        let fs_parameters = <Blake2sFiatShamirRng as FiatShamirRng<S::Challenge>>::setup();
+        let proof =
+            S::prove::<Blake2sFiatShamirRng, _>(&fs_parameters, statement, witness, rng).unwrap();
+        assert!(
+            S::verify_proof::<Blake2sFiatShamirRng>(&fs_parameters, statement, &proof).unwrap()
+        );
+        assert!(
+            !S::verify_proof::<Blake2sFiatShamirRng>(&fs_parameters, bad_statement, &proof)
+                .unwrap()
+        );
+        // Simulated transcripts do not pass as proofs.
+        let challenge = S::fiat_shamir_challenge::<Blake2sFiatShamirRng>(
+            &fs_parameters,
+            statement,
+            &proof.commitment,
+        )
+        .unwrap();
+        let (_, response) = S::simulate(statement, &challenge, rng).unwrap();
+        let mut forged_proof = proof;
+        forged_proof.response = response;
+        assert!(
+            !S::verify_proof::<Blake2sFiatShamirRng>(&fs_parameters, statement, &forged_proof)
+                .unwrap()
+        );
+    }
+
+    fn schnorr_statement<R: Rng>(rng: &mut R) -> (SchnorrStatement<JubJub>, Fr) {
+        let generator = JubJub::rand(rng);
+        let x = Fr::rand(rng);
+        let statement = SchnorrStatement {
+            generator,
+            public: generator.mul(&x),
+        };
+        (statement, x)
+    }
+
+    #[test]
+    fn schnorr_pok_test() {
+        let rng = &mut test_rng();
+        let (statement, x) = schnorr_statement(rng);
+        let (bad_statement, _) = schnorr_statement(rng);
+        sigma_protocol_test::<SchnorrPoK<JubJub>>(&statement, &x, &bad_statement);
+    }
+
+    #[test]
+    fn chaum_pedersen_test() {
+        let rng = &mut test_rng();
+        let (g, h) = (JubJub::rand(rng), JubJub::rand(rng));
+        let x = Fr::rand(rng);
+        let statement = ChaumPedersenStatement {
+            g,
+            h,
+            a: g.mul(&x),
+            b: h.mul(&x),
+        };
+        let mut bad_statement = statement.clone();
+        bad_statement.b = h.mul(&(x + &Fr::one()));
+        sigma_protocol_test::<ChaumPedersen<JubJub>>(&statement, &x, &bad_statement);
+    }
+
+    #[test]
+    fn pedersen_opening_test() {
+        #[derive(Clone, PartialEq, Eq, Hash)]
+        struct Window;
+
+        impl PedersenWindow for Window {
+            const WINDOW_SIZE: usize = 4;
+            const NUM_WINDOWS: usize = 8;
+        }
+
+        type Commitment = PedersenCommitment<JubJub, Window>;
+
+        let rng = &mut test_rng();
+        let parameters = Commitment::setup(rng).unwrap();
+        let input = vec![0x2a, 0xff, 0x01];
+        let randomness = PedersenRandomness(Fr::rand(rng));
+        let commitment = Commitment::commit(&parameters, &input, &randomness).unwrap();
+        let statement = PedersenOpeningStatement {
+            parameters,
+            commitment,
+        };
+        let witness = PedersenOpeningWitness { input, randomness };
+        let mut bad_statement = statement.clone();
+        bad_statement.commitment += &statement.parameters.randomness_generator[0];
+        sigma_protocol_test::<PedersenOpening<JubJub, Window>>(
+            &statement,
+            &witness,
+            &bad_statement,
+        );
+
+        let too_long = PedersenOpeningWitness {
+            input: vec![0u8; 5],
+            randomness: PedersenRandomness(Fr::rand(rng)),
+        };
+        assert!(
+            PedersenOpening::<JubJub, Window>::prove::<Blake2sFiatShamirRng, _>(
+                &(),
+                &statement,
+                &too_long,
+                rng,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn or_proof_test() {
+        type Or = OrProof<SchnorrPoK<JubJub>, ChaumPedersen<JubJub>>;
+
+        let rng = &mut test_rng();
+        let (left, x) = schnorr_statement(rng);
+        let (g, h) = (JubJub::rand(rng), JubJub::rand(rng));
+        let y = Fr::rand(rng);
+        let right = ChaumPedersenStatement {
+            g,
+            h,
+            a: g.mul(&y),
+            b: h.mul(&y),
+        };
+        let (unknown_left, _) = schnorr_statement(rng);
+        let mut unknown_right = right.clone();
+        unknown_right.b = h.mul(&(y + &Fr::one()));
+
+        // Either witness proves the disjunction, but not one of two false
+        // statements.
+        let statement = OrStatement::<SchnorrPoK<JubJub>, ChaumPedersen<JubJub>> {
+            left,
+            right: unknown_right.clone(),
+        };
+        let bad_statement = OrStatement {
+            left: unknown_left.clone(),
+            right: unknown_right,
+        };
+        sigma_protocol_test::<Or>(&statement, &OrWitness::Left(x), &bad_statement);
+
+        let statement = OrStatement {
+            left: unknown_left,
+            right,
+        };
+        sigma_protocol_test::<Or>(&statement, &OrWitness::Right(y), &bad_statement);
+
+        // Responding with the other witness than the one committed to fails.
+        let (_, state) = Or::prover_commit(&statement, &OrWitness::Right(y), rng).unwrap();
+        let challenge = Fr::rand(rng);
+        assert!(Or::prover_respond(&statement, &OrWitness::Left(x), &state, &challenge).is_err());
+    }
+}
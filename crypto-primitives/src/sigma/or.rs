@@ -0,0 +1,183 @@
+use crate::{sigma::SigmaProtocol, Box, CryptoError, Error};
+use algebra_core::{
+    bytes::ToBytes,
+    io::{Result as IoResult, Write},
+    UniformRand,
+};
+use core::marker::PhantomData;
+use rand::Rng;
+
+/// The disjunction of the statements of `A` and `B`, as in Cramer, Damgård
+/// and Schoenmakers: the prover simulates the branch whose witness it does
+/// not know with a challenge of its choice, and the verifier checks that the
+/// challenges of both branches sum to its own.
+pub struct OrProof<A: SigmaProtocol, B: SigmaProtocol<Challenge = A::Challenge>> {
+    _left: PhantomData<A>,
+    _right: PhantomData<B>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "A: SigmaProtocol, B: SigmaProtocol"))]
+pub struct OrStatement<A: SigmaProtocol, B: SigmaProtocol> {
+    pub left: A::Statement,
+    pub right: B::Statement,
+}
+
+impl<A: SigmaProtocol, B: SigmaProtocol> ToBytes for OrStatement<A, B> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.left.write(&mut writer)?;
+        self.right.write(&mut writer)
+    }
+}
+
+/// A witness for either branch.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "A: SigmaProtocol, B: SigmaProtocol"))]
+pub enum OrWitness<A: SigmaProtocol, B: SigmaProtocol> {
+    Left(A::Witness),
+    Right(B::Witness),
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "A: SigmaProtocol, B: SigmaProtocol"))]
+pub struct OrCommitment<A: SigmaProtocol, B: SigmaProtocol> {
+    pub left: A::Commitment,
+    pub right: B::Commitment,
+}
+
+impl<A: SigmaProtocol, B: SigmaProtocol> ToBytes for OrCommitment<A, B> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.left.write(&mut writer)?;
+        self.right.write(&mut writer)
+    }
+}
+
+/// The state of the known branch, together with the simulated challenge and
+/// response of the other one.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "A: SigmaProtocol, B: SigmaProtocol<Challenge = A::Challenge>"))]
+pub enum OrProverState<A: SigmaProtocol, B: SigmaProtocol<Challenge = A::Challenge>> {
+    Left(A::ProverState, A::Challenge, B::Response),
+    Right(B::ProverState, A::Challenge, A::Response),
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "A: SigmaProtocol, B: SigmaProtocol"))]
+pub struct OrResponse<A: SigmaProtocol, B: SigmaProtocol> {
+    /// The challenge of the left branch; that of the right one is the
+    /// difference with the verifier challenge.
+    pub left_challenge: A::Challenge,
+    pub left: A::Response,
+    pub right: B::Response,
+}
+
+impl<A, B> SigmaProtocol for OrProof<A, B>
+where
+    A: SigmaProtocol,
+    B: SigmaProtocol<Challenge = A::Challenge>,
+{
+    type Challenge = A::Challenge;
+    type Statement = OrStatement<A, B>;
+    type Witness = OrWitness<A, B>;
+    type Commitment = OrCommitment<A, B>;
+    type ProverState = OrProverState<A, B>;
+    type Response = OrResponse<A, B>;
+
+    const PROTOCOL_NAME: &'static [u8] = b"OrProof";
+
+    fn prover_commit<R: Rng>(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::ProverState), Error> {
+        let simulated_challenge = A::Challenge::rand(rng);
+        Ok(match witness {
+            OrWitness::Left(witness) => {
+                let (left, state) = A::prover_commit(&statement.left, witness, rng)?;
+                let (right, response) = B::simulate(&statement.right, &simulated_challenge, rng)?;
+                (
+                    OrCommitment { left, right },
+                    OrProverState::Left(state, simulated_challenge, response),
+                )
+            },
+            OrWitness::Right(witness) => {
+                let (left, response) = A::simulate(&statement.left, &simulated_challenge, rng)?;
+                let (right, state) = B::prover_commit(&statement.right, witness, rng)?;
+                (
+                    OrCommitment { left, right },
+                    OrProverState::Right(state, simulated_challenge, response),
+                )
+            },
+        })
+    }
+
+    fn prover_respond(
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: &Self::ProverState,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Error> {
+        Ok(match (witness, state) {
+            (OrWitness::Left(witness), OrProverState::Left(state, right_challenge, right)) => {
+                let left_challenge = *challenge - right_challenge;
+                let left = A::prover_respond(&statement.left, witness, state, &left_challenge)?;
+                OrResponse {
+                    left_challenge,
+                    left,
+                    right: right.clone(),
+                }
+            },
+            (OrWitness::Right(witness), OrProverState::Right(state, left_challenge, left)) => {
+                let right_challenge = *challenge - left_challenge;
+                let right = B::prover_respond(&statement.right, witness, state, &right_challenge)?;
+                OrResponse {
+                    left_challenge: *left_challenge,
+                    left: left.clone(),
+                    right,
+                }
+            },
+            _ => return Err(Box::new(CryptoError::MismatchedProverState)),
+        })
+    }
+
+    fn verify(
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Error> {
+        let right_challenge = *challenge - &response.left_challenge;
+        Ok(A::verify(
+            &statement.left,
+            &commitment.left,
+            &response.left_challenge,
+            &response.left,
+        )? && B::verify(
+            &statement.right,
+            &commitment.right,
+            &right_challenge,
+            &response.right,
+        )?)
+    }
+
+    fn simulate<R: Rng>(
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Error> {
+        let left_challenge = A::Challenge::rand(rng);
+        let right_challenge = *challenge - &left_challenge;
+        let (left, left_response) = A::simulate(&statement.left, &left_challenge, rng)?;
+        let (right, right_response) = B::simulate(&statement.right, &right_challenge, rng)?;
+        Ok((
+            OrCommitment { left, right },
+            OrResponse {
+                left_challenge,
+                left: left_response,
+                right: right_response,
+            },
+        ))
+    }
+}
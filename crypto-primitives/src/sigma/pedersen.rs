@@ -0,0 +1,169 @@
+use crate::{
+    commitment::pedersen::{PedersenParameters, PedersenRandomness, PedersenWindow},
+    crh::pedersen::bytes_to_bits,
+    sigma::SigmaProtocol,
+    Box, CryptoError, Error, Vec,
+};
+use algebra_core::{
+    bytes::ToBytes,
+    groups::Group,
+    io::{Result as IoResult, Write},
+    Field, One, UniformRand, Zero,
+};
+use core::marker::PhantomData;
+use rand::Rng;
+
+/// Proof of knowledge of an opening of a `PedersenCommitment<G, W>`.
+///
+/// With `g_i` the first generator of window `i` and `h` the first randomness
+/// generator, a commitment to an input whose windows are the integers `m_i`
+/// is `C = sum_i m_i · g_i + r · h`. The protocol proves knowledge of such a
+/// representation of `C`:
+/// * the commitment is `t := sum_i s_i · g_i + s · h` for random `s_i`, `s`;
+/// * the response to `c` is `(z_i, z) := (s_i + c · m_i, s + c · r)`;
+/// * the verifier checks that `sum_i z_i · g_i + z · h == t + c · C`.
+///
+/// The windows are not shown to be in range, so this attests to knowledge
+/// of an opening only as far as Pedersen commitments are binding for
+/// arbitrary scalars.
+pub struct PedersenOpening<G: Group, W: PedersenWindow> {
+    _group: PhantomData<G>,
+    _window: PhantomData<W>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "G: Group"))]
+pub struct PedersenOpeningStatement<G: Group> {
+    pub parameters: PedersenParameters<G>,
+    pub commitment: G,
+}
+
+impl<G: Group> PedersenOpeningStatement<G> {
+    /// The bases `g_i` followed by `h`.
+    fn bases(&self) -> Vec<G> {
+        self.parameters
+            .generators
+            .iter()
+            .map(|powers| powers[0])
+            .chain(Some(self.parameters.randomness_generator[0]))
+            .collect()
+    }
+}
+
+impl<G: Group> ToBytes for PedersenOpeningStatement<G> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.bases().write(&mut writer)?;
+        self.commitment.write(&mut writer)
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "G: Group"))]
+pub struct PedersenOpeningWitness<G: Group> {
+    pub input: Vec<u8>,
+    pub randomness: PedersenRandomness<G>,
+}
+
+impl<G: Group, W: PedersenWindow> PedersenOpening<G, W> {
+    /// The scalars `m_i` followed by `r`.
+    fn exponents(witness: &PedersenOpeningWitness<G>) -> Result<Vec<G::ScalarField>, Error> {
+        if witness.input.len() * 8 > W::WINDOW_SIZE * W::NUM_WINDOWS {
+            return Err(Box::new(CryptoError::IncorrectInputLength(
+                witness.input.len(),
+            )));
+        }
+        let mut bits = bytes_to_bits(&witness.input);
+        bits.resize(W::WINDOW_SIZE * W::NUM_WINDOWS, false);
+        let mut exponents = bits
+            .chunks(W::WINDOW_SIZE)
+            .map(|window| {
+                window
+                    .iter()
+                    .rev()
+                    .fold(G::ScalarField::zero(), |acc, bit| {
+                        let acc = acc.double();
+                        if *bit {
+                            acc + &G::ScalarField::one()
+                        } else {
+                            acc
+                        }
+                    })
+            })
+            .collect::<Vec<_>>();
+        exponents.push(witness.randomness.0);
+        Ok(exponents)
+    }
+
+    fn linear_combination(bases: &[G], scalars: &[G::ScalarField]) -> G {
+        bases
+            .iter()
+            .zip(scalars)
+            .map(|(base, scalar)| base.mul(scalar))
+            .sum()
+    }
+}
+
+impl<G: Group, W: PedersenWindow> SigmaProtocol for PedersenOpening<G, W> {
+    type Challenge = G::ScalarField;
+    type Statement = PedersenOpeningStatement<G>;
+    type Witness = PedersenOpeningWitness<G>;
+    type Commitment = G;
+    type ProverState = Vec<G::ScalarField>;
+    type Response = Vec<G::ScalarField>;
+
+    const PROTOCOL_NAME: &'static [u8] = b"PedersenOpening";
+
+    fn prover_commit<R: Rng>(
+        statement: &Self::Statement,
+        _witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::ProverState), Error> {
+        let bases = statement.bases();
+        let state = (0..bases.len())
+            .map(|_| G::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        Ok((Self::linear_combination(&bases, &state), state))
+    }
+
+    fn prover_respond(
+        _statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: &Self::ProverState,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Error> {
+        Ok(Self::exponents(witness)?
+            .into_iter()
+            .zip(state)
+            .map(|(exponent, s)| *s + &(*challenge * &exponent))
+            .collect())
+    }
+
+    fn verify(
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Error> {
+        let bases = statement.bases();
+        if response.len() != bases.len() {
+            return Ok(false);
+        }
+        Ok(Self::linear_combination(&bases, response)
+            == *commitment + &statement.commitment.mul(challenge))
+    }
+
+    fn simulate<R: Rng>(
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Error> {
+        let bases = statement.bases();
+        let response = (0..bases.len())
+            .map(|_| G::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let commitment =
+            Self::linear_combination(&bases, &response) - &statement.commitment.mul(challenge);
+        Ok((commitment, response))
+    }
+}
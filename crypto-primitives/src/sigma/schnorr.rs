@@ -0,0 +1,80 @@
+use crate::{sigma::SigmaProtocol, Error};
+use algebra_core::{
+    bytes::ToBytes,
+    groups::Group,
+    io::{Result as IoResult, Write},
+    UniformRand,
+};
+use core::marker::PhantomData;
+use rand::Rng;
+
+/// Proof of knowledge of `x` such that `public = x · generator`:
+/// * the commitment is `t := r · generator` for random `r`;
+/// * the response to `c` is `z := r + c · x`;
+/// * the verifier checks that `z · generator == t + c · public`.
+pub struct SchnorrPoK<G: Group> {
+    _group: PhantomData<G>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "G: Group"), Debug(bound = "G: Group"))]
+pub struct SchnorrStatement<G: Group> {
+    pub generator: G,
+    pub public: G,
+}
+
+impl<G: Group> ToBytes for SchnorrStatement<G> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.generator.write(&mut writer)?;
+        self.public.write(&mut writer)
+    }
+}
+
+impl<G: Group> SigmaProtocol for SchnorrPoK<G> {
+    type Challenge = G::ScalarField;
+    type Statement = SchnorrStatement<G>;
+    type Witness = G::ScalarField;
+    type Commitment = G;
+    type ProverState = G::ScalarField;
+    type Response = G::ScalarField;
+
+    const PROTOCOL_NAME: &'static [u8] = b"SchnorrPoK";
+
+    fn prover_commit<R: Rng>(
+        statement: &Self::Statement,
+        _witness: &Self::Witness,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::ProverState), Error> {
+        let r = G::ScalarField::rand(rng);
+        Ok((statement.generator.mul(&r), r))
+    }
+
+    fn prover_respond(
+        _statement: &Self::Statement,
+        witness: &Self::Witness,
+        state: &Self::ProverState,
+        challenge: &Self::Challenge,
+    ) -> Result<Self::Response, Error> {
+        Ok(*state + &(*challenge * witness))
+    }
+
+    fn verify(
+        statement: &Self::Statement,
+        commitment: &Self::Commitment,
+        challenge: &Self::Challenge,
+        response: &Self::Response,
+    ) -> Result<bool, Error> {
+        Ok(statement.generator.mul(response) == *commitment + &statement.public.mul(challenge))
+    }
+
+    fn simulate<R: Rng>(
+        statement: &Self::Statement,
+        challenge: &Self::Challenge,
+        rng: &mut R,
+    ) -> Result<(Self::Commitment, Self::Response), Error> {
+        let response = G::ScalarField::rand(rng);
+        let commitment = statement.generator.mul(&response) - &statement.public.mul(challenge);
+        Ok((commitment, response))
+    }
+}
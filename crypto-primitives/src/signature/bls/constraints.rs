@@ -0,0 +1,641 @@
+use crate::Vec;
+use algebra_core::{
+    curves::bls12::{Bls12, Bls12Parameters, G1Affine, G2Affine},
+    hash_to_curve::{HashToField, SWUParameters},
+    AffineCurve, BitIterator, Field, One, PrimeField, SquareRootField,
+};
+use r1cs_core::{ConstraintSystem, ConstraintVar, SynthesisError};
+use r1cs_std::{
+    fields::fp::FpGadget,
+    groups::{
+        bls12::{G1Gadget, G2Gadget},
+        curves::short_weierstrass::AffineGadget,
+    },
+    pairing::bls12::PairingGadget as Bls12PairingGadget,
+    prelude::*,
+    Assignment,
+};
+
+use crate::{
+    prf::blake2s::constraints::blake2s_gadget,
+    signature::{
+        bls::{signature_dst, BlsPublicKey, BlsSig, BlsSigParameters, BlsSignature},
+        SigVerifyGadget,
+    },
+};
+
+use blake2::Blake2s;
+use core::{borrow::Borrow, marker::PhantomData};
+
+/// Verification of `BlsSignature<Bls12<P>, Blake2s>` signatures in circuits
+/// over the base field of `Bls12<P>`, e.g. verification of BLS12-377
+/// signatures over BW6-761.
+///
+/// Since `G1` is defined over the constraint field, the message is hashed to
+/// `G1` entirely in the circuit: `expand_message_xmd` is computed with the
+/// Blake2s gadget, and the simplified SWU map, the isogeny and the cofactor
+/// clearing with native field arithmetic. The map is incomplete on a
+/// negligible fraction of messages, for which the constraints are
+/// unsatisfiable.
+pub struct BlsSigVerifyGadget<P: Bls12Parameters> {
+    #[doc(hidden)]
+    _params: PhantomData<P>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "P: Bls12Parameters"))]
+pub struct BlsSigGadgetParameters<P: Bls12Parameters> {
+    generator: G2Gadget<P>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Debug(bound = "P: Bls12Parameters"),
+    Clone(bound = "P: Bls12Parameters"),
+    PartialEq(bound = "P: Bls12Parameters"),
+    Eq(bound = "P: Bls12Parameters")
+)]
+pub struct BlsSigGadgetPk<P: Bls12Parameters> {
+    pub_key: G2Gadget<P>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Debug(bound = "P: Bls12Parameters"),
+    Clone(bound = "P: Bls12Parameters")
+)]
+pub struct BlsSigGadgetSig<P: Bls12Parameters> {
+    signature: G1Gadget<P>,
+}
+
+impl<P: Bls12Parameters> SigVerifyGadget<BlsSignature<Bls12<P>, Blake2s>, P::Fp>
+    for BlsSigVerifyGadget<P>
+where
+    P::G1Parameters: SWUParameters,
+{
+    type ParametersGadget = BlsSigGadgetParameters<P>;
+    type PublicKeyGadget = BlsSigGadgetPk<P>;
+    type SignatureGadget = BlsSigGadgetSig<P>;
+
+    fn check_verify<CS: ConstraintSystem<P::Fp>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        public_key: &Self::PublicKeyGadget,
+        message: &[UInt8],
+        signature: &Self::SignatureGadget,
+    ) -> Result<(), SynthesisError> {
+        let hash = Self::hash_to_g1(cs.ns(|| "Hash message"), message)?;
+        let neg_generator = parameters.generator.negate(cs.ns(|| "Negate generator"))?;
+
+        // e(sigma, -g) · e(H(msg), pk) == 1
+        let g1s = [
+            Bls12PairingGadget::<P>::prepare_g1(
+                cs.ns(|| "Prepare signature"),
+                &signature.signature,
+            )?,
+            Bls12PairingGadget::<P>::prepare_g1(cs.ns(|| "Prepare hash"), &hash)?,
+        ];
+        let g2s = [
+            Bls12PairingGadget::<P>::prepare_g2(cs.ns(|| "Prepare generator"), &neg_generator)?,
+            Bls12PairingGadget::<P>::prepare_g2(
+                cs.ns(|| "Prepare public key"),
+                &public_key.pub_key,
+            )?,
+        ];
+        let result = Bls12PairingGadget::<P>::product_of_pairings(
+            cs.ns(|| "Product of pairings"),
+            &g1s,
+            &g2s,
+        )?;
+
+        let one = <Bls12PairingGadget<P> as PairingGadget<Bls12<P>, P::Fp>>::GTGadget::one(
+            cs.ns(|| "One"),
+        )?;
+        result.enforce_equal(cs.ns(|| "Check pairings"), &one)
+    }
+}
+
+impl<P: Bls12Parameters> BlsSigVerifyGadget<P>
+where
+    P::G1Parameters: SWUParameters,
+{
+    /// Sums public keys, for verifying a signature on a single message that
+    /// is aggregated from signatures by each of them. The public keys must be
+    /// pairwise distinct and not opposite, which holds for keys that come
+    /// with proofs of possession.
+    ///
+    /// Returns `SynthesisError::Unsatisfiable` if `public_keys` is empty:
+    /// unlike `BlsSignature::aggregate_public_keys`, which returns the
+    /// identity, there is then no key against which a signature could be
+    /// verified.
+    pub fn aggregate_public_keys<CS: ConstraintSystem<P::Fp>>(
+        mut cs: CS,
+        public_keys: &[BlsSigGadgetPk<P>],
+    ) -> Result<BlsSigGadgetPk<P>, SynthesisError> {
+        let (first, rest) = public_keys
+            .split_first()
+            .ok_or(SynthesisError::Unsatisfiable)?;
+        let mut pub_key = first.pub_key.clone();
+        for (i, pk) in rest.iter().enumerate() {
+            pub_key = pub_key.add(cs.ns(|| format!("Add public key {}", i)), &pk.pub_key)?;
+        }
+        Ok(BlsSigGadgetPk { pub_key })
+    }
+
+    /// Hashes `message` to `G1`, with the same output as
+    /// `HashToCurve::hash_to_curve::<Blake2s>` under `signature_dst`.
+    pub fn hash_to_g1<CS: ConstraintSystem<P::Fp>>(
+        mut cs: CS,
+        message: &[UInt8],
+    ) -> Result<G1Gadget<P>, SynthesisError> {
+        let len = <P::Fp as HashToField>::UNIFORM_BYTES_LEN;
+        let dst = signature_dst::<G1Affine<P>, Blake2s>();
+        let uniform_bytes = expand_message_xmd(cs.ns(|| "Expand message"), message, &dst, 2 * len)?;
+        let u0 = field_from_uniform_bytes::<_, CS>(&uniform_bytes[..len]);
+        let u1 = field_from_uniform_bytes::<_, CS>(&uniform_bytes[len..]);
+
+        let q0 = map_to_curve::<P::G1Parameters, _>(cs.ns(|| "Map u0"), &u0)?;
+        let q1 = map_to_curve::<P::G1Parameters, _>(cs.ns(|| "Map u1"), &u1)?;
+        let r = q0.add(cs.ns(|| "Add"), &q1)?;
+
+        // Clear the cofactor by double-and-add, which is incomplete only for
+        // points of order dividing `H_EFF`.
+        let mut seen_one = false;
+        let mut result = r.clone();
+        for (i, b) in BitIterator::new(<P::G1Parameters as SWUParameters>::H_EFF).enumerate() {
+            let mut cs = cs.ns(|| format!("Clear cofactor {}", i));
+
+            let old_seen_one = seen_one;
+            if seen_one {
+                result.double_in_place(cs.ns(|| "Double"))?;
+            } else {
+                seen_one = b;
+            }
+
+            if b && old_seen_one {
+                result = result.add(cs.ns(|| "Add"), &r)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// `expand_message_xmd` with Blake2s, for tags of at most 255 bytes.
+fn expand_message_xmd<ConstraintF: PrimeField, CS: ConstraintSystem<ConstraintF>>(
+    mut cs: CS,
+    msg: &[UInt8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Result<Vec<UInt8>, SynthesisError> {
+    const B_IN_BYTES: usize = 32;
+    const R_IN_BYTES: usize = 64;
+    let ell = (len_in_bytes + B_IN_BYTES - 1) / B_IN_BYTES;
+    assert!(ell <= 255 && dst.len() <= 255);
+
+    let mut dst_prime = dst.to_vec();
+    dst_prime.push(dst.len() as u8);
+    let dst_prime = UInt8::constant_vec(&dst_prime);
+
+    let hash = |cs: &mut CS, i: usize, input: Vec<UInt8>| -> Result<Vec<UInt8>, SynthesisError> {
+        let input = input
+            .iter()
+            .chain(&dst_prime)
+            .flat_map(|byte| byte.into_bits_le())
+            .collect::<Vec<_>>();
+        let output = blake2s_gadget(cs.ns(|| format!("b_{}", i)), &input)?;
+        Ok(output
+            .iter()
+            .flat_map(|word| word.to_bits_le())
+            .collect::<Vec<_>>()
+            .chunks(8)
+            .map(UInt8::from_bits_le)
+            .collect())
+    };
+
+    let mut b_0_input = UInt8::constant_vec(&[0u8; R_IN_BYTES]);
+    b_0_input.extend_from_slice(msg);
+    b_0_input.extend(UInt8::constant_vec(&(len_in_bytes as u16).to_be_bytes()));
+    b_0_input.push(UInt8::constant(0));
+    let b_0 = hash(&mut cs, 0, b_0_input)?;
+
+    let mut b_i_input = b_0.clone();
+    b_i_input.push(UInt8::constant(1));
+    let mut b_i = hash(&mut cs, 1, b_i_input)?;
+
+    let mut uniform_bytes = Vec::with_capacity(ell * B_IN_BYTES);
+    uniform_bytes.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let mut b_i_input = b_0
+            .iter()
+            .zip(&b_i)
+            .enumerate()
+            .map(|(j, (a, b))| a.xor(cs.ns(|| format!("b_0 xor b_{} {}", i - 1, j)), b))
+            .collect::<Result<Vec<_>, _>>()?;
+        b_i_input.push(UInt8::constant(i as u8));
+        b_i = hash(&mut cs, i, b_i_input)?;
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    Ok(uniform_bytes)
+}
+
+/// Interprets `bytes` as a big-endian integer, reduced modulo the
+/// characteristic. This is a linear combination of the bits, and so costs no
+/// constraints.
+fn field_from_uniform_bytes<F: PrimeField, CS: ConstraintSystem<F>>(
+    bytes: &[UInt8],
+) -> FpGadget<F> {
+    let mut lc = ConstraintVar::zero();
+    let mut value = Some(F::zero());
+    let mut coeff = F::one();
+    for bit in bytes.iter().rev().flat_map(|byte| byte.into_bits_le()) {
+        lc = ConstraintVar::LC(lc + bit.lc(CS::one(), coeff));
+        value = match (value, bit.get_value()) {
+            (Some(value), Some(true)) => Some(value + &coeff),
+            (Some(value), Some(false)) => Some(value),
+            _ => None,
+        };
+        coeff.double_in_place();
+    }
+    FpGadget {
+        value,
+        variable: lc,
+    }
+}
+
+/// Maps `u` to a point of the curve with the simplified SWU map and the
+/// isogeny, as `algebra_core::hash_to_curve::map_to_curve` does.
+fn map_to_curve<P, CS>(
+    mut cs: CS,
+    u: &FpGadget<P::BaseField>,
+) -> Result<AffineGadget<P, P::BaseField, FpGadget<P::BaseField>>, SynthesisError>
+where
+    P: SWUParameters,
+    P::BaseField: PrimeField,
+    CS: ConstraintSystem<P::BaseField>,
+{
+    let a = P::ISO_COEFF_A;
+    let b = P::ISO_COEFF_B;
+    let g = |cs: &mut CS, name: &str, x: &FpGadget<P::BaseField>| {
+        let mut cs = cs.ns(|| name);
+        x.square(cs.ns(|| "x^2"))?
+            .add_constant(cs.ns(|| "x^2 + A"), &a)?
+            .mul(cs.ns(|| "x^3 + A * x"), x)?
+            .add_constant(cs.ns(|| "x^3 + A * x + B"), &b)
+    };
+
+    // x1 = (-B / A) * (1 + 1 / tv1) with tv1 = Z^2 * u^4 + Z * u^2, or
+    // B / (Z * A) if tv1 is zero. As x1 * A * tv1 = -B * (tv1 + 1) in the
+    // first case, x1 = num / den with
+    // den = A * tv1 + Z * A * [tv1 == 0] and
+    // num = -B * (tv1 + 1) + 2 * B * [tv1 == 0].
+    let z_u2 = u
+        .square(cs.ns(|| "u^2"))?
+        .mul_by_constant(cs.ns(|| "Z * u^2"), &P::ZETA)?;
+    let tv1 = z_u2
+        .square(cs.ns(|| "Z^2 * u^4"))?
+        .add(cs.ns(|| "tv1"), &z_u2)?;
+    let tv1_is_zero = is_zero(cs.ns(|| "tv1 == 0"), &tv1)?;
+    let den = tv1.mul_by_constant(cs.ns(|| "A * tv1"), &a)?.add(
+        cs.ns(|| "den"),
+        &boolean_times_constant::<_, CS>(&tv1_is_zero, P::ZETA * &a),
+    )?;
+    let num = tv1
+        .add_constant(cs.ns(|| "tv1 + 1"), &P::BaseField::one())?
+        .mul_by_constant(cs.ns(|| "-B * (tv1 + 1)"), &-b)?
+        .add(
+            cs.ns(|| "num"),
+            &boolean_times_constant::<_, CS>(&tv1_is_zero, b.double()),
+        )?;
+    let x1 = FpGadget::alloc(cs.ns(|| "x1"), || {
+        Ok(num.get_value().get()? * &den.get_value().get()?.inverse().get()?)
+    })?;
+    x1.mul_equals(cs.ns(|| "Check x1"), &den, &num)?;
+    let gx1 = g(&mut cs, "g(x1)", &x1)?;
+
+    // Exactly one of g(x1) and g(Z * u^2 * x1) = Z^3 * u^6 * g(x1) is a
+    // square (unless u = 0 or g(x1) = 0), so the constraint y^2 = g(x) holds
+    // for a single choice of x.
+    let x2 = x1.mul(cs.ns(|| "x2"), &z_u2)?;
+    let gx2 = g(&mut cs, "g(x2)", &x2)?;
+    let gx1_is_square = Boolean::alloc(cs.ns(|| "g(x1) is square"), || {
+        Ok(gx1.get_value().get()?.legendre().is_qr())
+    })?;
+    let x = FpGadget::conditionally_select(cs.ns(|| "x"), &gx1_is_square, &x1, &x2)?;
+    let gx = FpGadget::conditionally_select(cs.ns(|| "g(x)"), &gx1_is_square, &gx1, &gx2)?;
+
+    // The sign of y matches that of u.
+    let y = FpGadget::alloc(cs.ns(|| "y"), || {
+        let y = gx.get_value().get()?.sqrt().get()?;
+        let u = u.get_value().get()?;
+        Ok(if u.sgn0() != y.sgn0() { -y } else { y })
+    })?;
+    y.mul_equals(cs.ns(|| "Check y"), &y, &gx)?;
+    let u_bits = u.to_bits(cs.ns(|| "u to bits"))?;
+    let y_bits = y.to_bits(cs.ns(|| "y to bits"))?;
+    u_bits
+        .last()
+        .unwrap()
+        .enforce_equal(cs.ns(|| "Check sign"), y_bits.last().unwrap())?;
+
+    iso_map::<P, _>(cs.ns(|| "Isogeny"), &x, &y)
+}
+
+/// The isogeny to the curve, whose constraints are unsatisfiable at the
+/// points where it is not defined.
+fn iso_map<P, CS>(
+    mut cs: CS,
+    x: &FpGadget<P::BaseField>,
+    y: &FpGadget<P::BaseField>,
+) -> Result<AffineGadget<P, P::BaseField, FpGadget<P::BaseField>>, SynthesisError>
+where
+    P: SWUParameters,
+    P::BaseField: PrimeField,
+    CS: ConstraintSystem<P::BaseField>,
+{
+    let x_num = evaluate(cs.ns(|| "x_num"), P::ISOGENY_X_NUM, x)?;
+    let x_den = evaluate(cs.ns(|| "x_den"), P::ISOGENY_X_DEN, x)?;
+    let y_num = evaluate(cs.ns(|| "y_num"), P::ISOGENY_Y_NUM, x)?.mul(cs.ns(|| "y * y_num"), y)?;
+    let y_den = evaluate(cs.ns(|| "y_den"), P::ISOGENY_Y_DEN, x)?;
+
+    let x = FpGadget::alloc(cs.ns(|| "x"), || {
+        Ok(x_num.get_value().get()? * &x_den.get_value().get()?.inverse().get()?)
+    })?;
+    x.mul_equals(cs.ns(|| "Check x"), &x_den, &x_num)?;
+    let y = FpGadget::alloc(cs.ns(|| "y"), || {
+        Ok(y_num.get_value().get()? * &y_den.get_value().get()?.inverse().get()?)
+    })?;
+    y.mul_equals(cs.ns(|| "Check y"), &y_den, &y_num)?;
+
+    Ok(AffineGadget::new(x, y, Boolean::constant(false)))
+}
+
+/// Evaluates the polynomial with coefficients `coeffs`, in increasing order
+/// of degree, at `x` with Horner's rule.
+fn evaluate<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    coeffs: &[F],
+    x: &FpGadget<F>,
+) -> Result<FpGadget<F>, SynthesisError> {
+    let (leading, rest) = coeffs.split_last().unwrap();
+    let mut result = FpGadget::zero(cs.ns(|| "zero"))?.add_constant(cs.ns(|| "c_n"), leading)?;
+    for (i, c) in rest.iter().rev().enumerate() {
+        result = if i == 0 {
+            x.mul_by_constant(cs.ns(|| "c_n * x"), leading)?
+        } else {
+            result.mul(cs.ns(|| format!("Multiply {}", i)), x)?
+        };
+        result.add_constant_in_place(cs.ns(|| format!("Add {}", i)), c)?;
+    }
+    Ok(result)
+}
+
+fn is_zero<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    x: &FpGadget<F>,
+) -> Result<Boolean, SynthesisError> {
+    let is_zero = Boolean::alloc(cs.ns(|| "is zero"), || Ok(x.get_value().get()?.is_zero()))?;
+    let inv = FpGadget::alloc(cs.ns(|| "inverse"), || {
+        Ok(x.get_value().get()?.inverse().unwrap_or_else(F::zero))
+    })?;
+    // x * inv = 1 - is_zero and x * is_zero = 0
+    let one = CS::one();
+    cs.enforce(
+        || "x * inv = 1 - is_zero",
+        |lc| &x.variable + lc,
+        |lc| &inv.variable + lc,
+        |_| is_zero.not().lc(one, F::one()),
+    );
+    cs.enforce(
+        || "x * is_zero = 0",
+        |lc| &x.variable + lc,
+        |_| is_zero.lc(one, F::one()),
+        |lc| lc,
+    );
+    Ok(is_zero)
+}
+
+fn boolean_times_constant<F: PrimeField, CS: ConstraintSystem<F>>(
+    bit: &Boolean,
+    coeff: F,
+) -> FpGadget<F> {
+    FpGadget {
+        value: bit
+            .get_value()
+            .map(|bit| if bit { coeff } else { F::zero() }),
+        variable: ConstraintVar::LC(bit.lc(CS::one(), coeff)),
+    }
+}
+
+impl<P: Bls12Parameters> AllocGadget<BlsSigParameters<Bls12<P>>, P::Fp>
+    for BlsSigGadgetParameters<P>
+{
+    fn alloc_constant<T, CS: ConstraintSystem<P::Fp>>(
+        cs: CS,
+        val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<BlsSigParameters<Bls12<P>>>,
+    {
+        let generator =
+            G2Gadget::<P>::alloc_constant(cs, val.borrow().generator.into_projective())?;
+        Ok(Self { generator })
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<P::Fp>>(cs: CS, f: F) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<BlsSigParameters<Bls12<P>>>,
+    {
+        let generator = G2Gadget::<P>::alloc_checked(cs, || {
+            f().map(|pp| pp.borrow().generator.into_projective())
+        })?;
+        Ok(Self { generator })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<P::Fp>>(cs: CS, f: F) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<BlsSigParameters<Bls12<P>>>,
+    {
+        let generator = G2Gadget::<P>::alloc_input(cs, || {
+            f().map(|pp| pp.borrow().generator.into_projective())
+        })?;
+        Ok(Self { generator })
+    }
+}
+
+impl<P: Bls12Parameters> AllocGadget<BlsPublicKey<Bls12<P>>, P::Fp> for BlsSigGadgetPk<P> {
+    fn alloc_constant<T, CS: ConstraintSystem<P::Fp>>(
+        cs: CS,
+        val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<G2Affine<P>>,
+    {
+        let pub_key = G2Gadget::<P>::alloc_constant(cs, val.borrow().into_projective())?;
+        Ok(Self { pub_key })
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<P::Fp>>(cs: CS, f: F) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<G2Affine<P>>,
+    {
+        let pub_key =
+            G2Gadget::<P>::alloc_checked(cs, || f().map(|pk| pk.borrow().into_projective()))?;
+        Ok(Self { pub_key })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<P::Fp>>(cs: CS, f: F) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<G2Affine<P>>,
+    {
+        let pub_key =
+            G2Gadget::<P>::alloc_input(cs, || f().map(|pk| pk.borrow().into_projective()))?;
+        Ok(Self { pub_key })
+    }
+}
+
+impl<P: Bls12Parameters> AllocGadget<BlsSig<Bls12<P>>, P::Fp> for BlsSigGadgetSig<P> {
+    fn alloc_constant<T, CS: ConstraintSystem<P::Fp>>(
+        cs: CS,
+        val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<G1Affine<P>>,
+    {
+        let signature = G1Gadget::<P>::alloc_constant(cs, val.borrow().into_projective())?;
+        Ok(Self { signature })
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<P::Fp>>(cs: CS, f: F) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<G1Affine<P>>,
+    {
+        let signature =
+            G1Gadget::<P>::alloc_checked(cs, || f().map(|sig| sig.borrow().into_projective()))?;
+        Ok(Self { signature })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<P::Fp>>(cs: CS, f: F) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<G1Affine<P>>,
+    {
+        let signature =
+            G1Gadget::<P>::alloc_input(cs, || f().map(|sig| sig.borrow().into_projective()))?;
+        Ok(Self { signature })
+    }
+}
+
+impl<P: Bls12Parameters> ConditionalEqGadget<P::Fp> for BlsSigGadgetPk<P> {
+    #[inline]
+    fn conditional_enforce_equal<CS: ConstraintSystem<P::Fp>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+        condition: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        self.pub_key.conditional_enforce_equal(
+            &mut cs.ns(|| "PubKey equality"),
+            &other.pub_key,
+            condition,
+        )
+    }
+
+    fn cost() -> usize {
+        <G2Gadget<P> as ConditionalEqGadget<P::Fp>>::cost()
+    }
+}
+
+impl<P: Bls12Parameters> EqGadget<P::Fp> for BlsSigGadgetPk<P> {}
+
+impl<P: Bls12Parameters> ToBytesGadget<P::Fp> for BlsSigGadgetPk<P> {
+    fn to_bytes<CS: ConstraintSystem<P::Fp>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        self.pub_key.to_bytes(&mut cs.ns(|| "PubKey To Bytes"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::signature::SignatureScheme;
+    use algebra::{
+        bls12_377::{Bls12_377, Fq, G1Affine, Parameters},
+        hash_to_curve::HashToCurve,
+        test_rng, ProjectiveCurve,
+    };
+    use r1cs_std::test_constraint_system::TestConstraintSystem;
+
+    type Bls = BlsSignature<Bls12_377, Blake2s>;
+    type BlsGadget = BlsSigVerifyGadget<Parameters>;
+
+    #[test]
+    fn hash_to_g1_test() {
+        for message in &["", "abc", "Hi, I am a BLS signature!"] {
+            let mut cs = TestConstraintSystem::<Fq>::new();
+            let message_var = UInt8::alloc_vec(cs.ns(|| "Message"), message.as_bytes()).unwrap();
+            let hash = BlsGadget::hash_to_g1(cs.ns(|| "Hash"), &message_var).unwrap();
+            assert!(cs.is_satisfied());
+
+            let dst = signature_dst::<G1Affine, Blake2s>();
+            let expected = G1Affine::hash_to_curve::<Blake2s>(message.as_bytes(), &dst);
+            assert_eq!(hash.get_value().unwrap().into_affine(), expected);
+        }
+    }
+
+    #[test]
+    fn check_verify_test() {
+        let rng = &mut test_rng();
+        let message = "Hi, I am a BLS signature!".as_bytes();
+        let parameters = Bls::setup(rng).unwrap();
+        let (pks, sks): (Vec<_>, Vec<_>) = (0..3)
+            .map(|_| Bls::keygen(&parameters, rng).unwrap())
+            .unzip();
+        let sigs = sks
+            .iter()
+            .map(|sk| Bls::sign(&parameters, sk, message, rng).unwrap())
+            .collect::<Vec<_>>();
+        let sig = Bls::aggregate_signatures(&sigs);
+
+        for (message, satisfied) in &[(message, true), ("Bad message".as_bytes(), false)] {
+            let mut cs = TestConstraintSystem::<Fq>::new();
+            let parameters_var =
+                BlsSigGadgetParameters::alloc_constant(cs.ns(|| "Parameters"), &parameters)
+                    .unwrap();
+            let pk_vars = pks
+                .iter()
+                .enumerate()
+                .map(|(i, pk)| {
+                    BlsSigGadgetPk::alloc(cs.ns(|| format!("Public key {}", i)), || Ok(pk)).unwrap()
+                })
+                .collect::<Vec<_>>();
+            assert!(matches!(
+                BlsGadget::aggregate_public_keys(cs.ns(|| "Aggregate none"), &[]),
+                Err(SynthesisError::Unsatisfiable)
+            ));
+            let pk_var = BlsGadget::aggregate_public_keys(cs.ns(|| "Aggregate"), &pk_vars).unwrap();
+            assert_eq!(
+                pk_var.pub_key.get_value().unwrap().into_affine(),
+                Bls::aggregate_public_keys(&pks)
+            );
+            let sig_var = BlsSigGadgetSig::alloc(cs.ns(|| "Signature"), || Ok(sig)).unwrap();
+            let message_var = UInt8::alloc_vec(cs.ns(|| "Message"), message).unwrap();
+            BlsGadget::check_verify(
+                cs.ns(|| "Verify"),
+                &parameters_var,
+                &pk_var,
+                &message_var,
+                &sig_var,
+            )
+            .unwrap();
+            assert_eq!(cs.is_satisfied(), *satisfied);
+        }
+    }
+}
@@ -0,0 +1,336 @@
+use crate::{Box, CryptoError, Error, SignatureScheme, Vec};
+use algebra_core::{
+    bytes::ToBytes,
+    hash_to_curve::HashToCurve,
+    io::{Result as IoResult, Write},
+    AffineCurve, Field, Flags, One, PairingEngine, ProjectiveCurve, SWFlags, UniformRand, Zero,
+};
+use blake2::Blake2s;
+use core::{marker::PhantomData, ops::Neg};
+use digest::{BlockInput, Digest};
+use rand::Rng;
+use sha2::{Sha256, Sha512};
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// Hash functions with which messages are hashed to the curve, along with
+/// their names in the suite IDs of the hash-to-curve draft.
+pub trait BlsHash: Digest + BlockInput {
+    /// The name of the hash function, such as `SHA-256`.
+    const NAME: &'static str;
+}
+
+impl BlsHash for Sha256 {
+    const NAME: &'static str = "SHA-256";
+}
+
+impl BlsHash for Sha512 {
+    const NAME: &'static str = "SHA-512";
+}
+
+impl BlsHash for Blake2s {
+    const NAME: &'static str = "BLAKE2S";
+}
+
+/// The ciphersuite ID of the proof of possession scheme of the BLS signature
+/// draft (draft-irtf-cfrg-bls-signature) for hashing to the curve `C` with
+/// `H`, such as `BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_`. It is the
+/// domain separation tag used when hashing messages to be signed.
+pub fn signature_dst<C: HashToCurve, H: BlsHash>() -> Vec<u8> {
+    ciphersuite_tag::<C, H>(b"BLS_SIG_")
+}
+
+/// The domain separation tag used when hashing public keys in proofs of
+/// possession, such as `BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_`.
+pub fn pop_dst<C: HashToCurve, H: BlsHash>() -> Vec<u8> {
+    ciphersuite_tag::<C, H>(b"BLS_POP_")
+}
+
+fn ciphersuite_tag<C: HashToCurve, H: BlsHash>(prefix: &[u8]) -> Vec<u8> {
+    [
+        prefix,
+        C::CURVE_ID.as_bytes(),
+        b"_XMD:",
+        H::NAME.as_bytes(),
+        b"_SSWU_RO_POP_",
+    ]
+    .concat()
+}
+
+/// The compressed encoding `point_to_octets` of the BLS signature draft,
+/// which the proof of possession signs: `x` in big-endian, with the
+/// coordinates of `Fp2` elements in the order `c1 || c0`, and the three most
+/// significant bits of the first byte set to the compression flag, the
+/// infinity flag and the sign of `y`, which is set if `y` is the
+/// lexicographically largest of `y` and `-y`.
+///
+/// This is `CanonicalSerialize::serialize` in reverse, with the flags moved
+/// to where the draft expects them. It fails on curves whose base field has
+/// fewer than three spare bits to hold the flags.
+pub fn point_to_octets<G: AffineCurve>(point: &G) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::with_capacity(point.serialized_size());
+    point.serialize(&mut bytes)?;
+    let last = bytes.last_mut().ok_or(CryptoError::UnsupportedOperation)?;
+    let flags = SWFlags::from_u8_remove_flags(last);
+    if *last & 0xE0 != 0 {
+        return Err(Box::new(CryptoError::UnsupportedOperation));
+    }
+    *last |= match flags {
+        SWFlags::Infinity => 0xC0,
+        SWFlags::PositiveY => 0xA0,
+        SWFlags::NegativeY => 0x80,
+    };
+    bytes.reverse();
+    Ok(bytes)
+}
+
+/// The BLS signature scheme of Boneh, Lynn and Shacham, with public keys in
+/// `G2` and signatures in `G1`. Messages are hashed to `G1` with
+/// `HashToCurve`, using the digest `H`.
+///
+/// Signatures on any messages can be aggregated into a single signature,
+/// which is verified with a single product of pairings. To prevent rogue-key
+/// attacks, the public keys involved in an aggregate verification must come
+/// with a proof of possession of the matching secret key, checked once with
+/// `verify_possession`.
+pub struct BlsSignature<E: PairingEngine, H: BlsHash> {
+    _engine: PhantomData<E>,
+    _hash: PhantomData<H>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "E: PairingEngine"))]
+pub struct BlsSigParameters<E: PairingEngine> {
+    pub generator: E::G2Affine,
+}
+
+pub type BlsPublicKey<E> = <E as PairingEngine>::G2Affine;
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "E: PairingEngine"), Default(bound = "E: PairingEngine"))]
+pub struct BlsSecretKey<E: PairingEngine>(pub E::Fr);
+
+impl<E: PairingEngine> ToBytes for BlsSecretKey<E> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+pub type BlsSig<E> = <E as PairingEngine>::G1Affine;
+
+impl<E: PairingEngine, H: BlsHash> SignatureScheme for BlsSignature<E, H>
+where
+    E::G1Affine: HashToCurve,
+{
+    type Parameters = BlsSigParameters<E>;
+    type PublicKey = BlsPublicKey<E>;
+    type SecretKey = BlsSecretKey<E>;
+    type Signature = BlsSig<E>;
+
+    fn setup<R: Rng>(_rng: &mut R) -> Result<Self::Parameters, Error> {
+        Ok(BlsSigParameters {
+            generator: E::G2Affine::prime_subgroup_generator(),
+        })
+    }
+
+    fn keygen<R: Rng>(
+        parameters: &Self::Parameters,
+        rng: &mut R,
+    ) -> Result<(Self::PublicKey, Self::SecretKey), Error> {
+        let keygen_time = start_timer!(|| "BlsSig::KeyGen");
+
+        let secret_key = loop {
+            let secret_key = E::Fr::rand(rng);
+            if !secret_key.is_zero() {
+                break secret_key;
+            }
+        };
+        let public_key = parameters.generator.mul(secret_key).into_affine();
+
+        end_timer!(keygen_time);
+        Ok((public_key, BlsSecretKey(secret_key)))
+    }
+
+    fn sign<R: Rng>(
+        _parameters: &Self::Parameters,
+        sk: &Self::SecretKey,
+        message: &[u8],
+        _rng: &mut R,
+    ) -> Result<Self::Signature, Error> {
+        let sign_time = start_timer!(|| "BlsSig::Sign");
+
+        // sigma := sk · H(msg)
+        let signature =
+            E::G1Affine::hash_to_curve::<H>(message, &signature_dst::<E::G1Affine, H>())
+                .mul(sk.0)
+                .into_affine();
+
+        end_timer!(sign_time);
+        Ok(signature)
+    }
+
+    fn verify(
+        parameters: &Self::Parameters,
+        pk: &Self::PublicKey,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<bool, Error> {
+        let verify_time = start_timer!(|| "BlsSig::Verify");
+        let result =
+            Self::aggregate_verify(parameters, core::slice::from_ref(pk), &[message], signature);
+        end_timer!(verify_time);
+        result
+    }
+
+    fn randomize_public_key(
+        _parameters: &Self::Parameters,
+        public_key: &Self::PublicKey,
+        randomness: &[u8],
+    ) -> Result<Self::PublicKey, Error> {
+        let rand_pk_time = start_timer!(|| "BlsSig::RandomizePubKey");
+        let randomized_pk = public_key
+            .mul(scalar_from_bytes::<E>(randomness))
+            .into_affine();
+        end_timer!(rand_pk_time);
+        Ok(randomized_pk)
+    }
+
+    fn randomize_signature(
+        _parameters: &Self::Parameters,
+        signature: &Self::Signature,
+        randomness: &[u8],
+    ) -> Result<Self::Signature, Error> {
+        let rand_signature_time = start_timer!(|| "BlsSig::RandomizeSig");
+        let randomized_signature = signature
+            .mul(scalar_from_bytes::<E>(randomness))
+            .into_affine();
+        end_timer!(rand_signature_time);
+        Ok(randomized_signature)
+    }
+}
+
+impl<E: PairingEngine, H: BlsHash> BlsSignature<E, H>
+where
+    E::G1Affine: HashToCurve,
+{
+    /// Aggregates signatures, on the same or on different messages, into a
+    /// single signature.
+    pub fn aggregate_signatures(signatures: &[BlsSig<E>]) -> BlsSig<E> {
+        signatures
+            .iter()
+            .fold(E::G1Projective::zero(), |acc, s| acc.add_mixed(s))
+            .into_affine()
+    }
+
+    /// Aggregates public keys into a single one, for verifying an aggregate
+    /// signature on a single message with `verify`.
+    pub fn aggregate_public_keys(public_keys: &[BlsPublicKey<E>]) -> BlsPublicKey<E> {
+        public_keys
+            .iter()
+            .fold(E::G2Projective::zero(), |acc, pk| acc.add_mixed(pk))
+            .into_affine()
+    }
+
+    /// Aggregates the signatures of `secret_keys` on the respective
+    /// `messages`.
+    pub fn aggregate_sign<R: Rng>(
+        parameters: &BlsSigParameters<E>,
+        secret_keys: &[BlsSecretKey<E>],
+        messages: &[&[u8]],
+        rng: &mut R,
+    ) -> Result<BlsSig<E>, Error> {
+        if secret_keys.len() != messages.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength(messages.len())));
+        }
+        let signatures = secret_keys
+            .iter()
+            .zip(messages)
+            .map(|(sk, message)| Self::sign(parameters, sk, message, rng))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::aggregate_signatures(&signatures))
+    }
+
+    /// Verifies an aggregate signature by `public_keys` on the respective
+    /// `messages`, with a single product of pairings:
+    /// `e(sigma, -g) · prod_i e(H(m_i), pk_i) == 1`.
+    ///
+    /// The public keys must have been checked with `verify_possession`
+    /// beforehand.
+    pub fn aggregate_verify(
+        parameters: &BlsSigParameters<E>,
+        public_keys: &[BlsPublicKey<E>],
+        messages: &[&[u8]],
+        signature: &BlsSig<E>,
+    ) -> Result<bool, Error> {
+        let aggregate_verify_time = start_timer!(|| "BlsSig::AggregateVerify");
+        if public_keys.len() != messages.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength(messages.len())));
+        }
+        if public_keys.is_empty() || public_keys.iter().any(|pk| pk.is_zero()) {
+            return Ok(false);
+        }
+
+        let mut pairs = Vec::with_capacity(public_keys.len() + 1);
+        pairs.push((
+            E::G1Prepared::from(*signature),
+            E::G2Prepared::from(parameters.generator.neg()),
+        ));
+        for (pk, message) in public_keys.iter().zip(messages) {
+            let hash = E::G1Affine::hash_to_curve::<H>(message, &signature_dst::<E::G1Affine, H>());
+            pairs.push((E::G1Prepared::from(hash), E::G2Prepared::from(*pk)));
+        }
+        let result = E::product_of_pairings(&pairs).is_one();
+
+        end_timer!(aggregate_verify_time);
+        Ok(result)
+    }
+
+    /// Proves possession of the secret key `sk` of `pk`, by signing
+    /// `point_to_octets(pk)` under a dedicated domain separation tag.
+    pub fn prove_possession(
+        sk: &BlsSecretKey<E>,
+        pk: &BlsPublicKey<E>,
+    ) -> Result<BlsSig<E>, Error> {
+        let message = point_to_octets(pk)?;
+        let proof = E::G1Affine::hash_to_curve::<H>(&message, &pop_dst::<E::G1Affine, H>())
+            .mul(sk.0)
+            .into_affine();
+        Ok(proof)
+    }
+
+    /// Checks a proof of possession produced by `prove_possession`.
+    pub fn verify_possession(
+        parameters: &BlsSigParameters<E>,
+        pk: &BlsPublicKey<E>,
+        proof: &BlsSig<E>,
+    ) -> Result<bool, Error> {
+        if pk.is_zero() {
+            return Ok(false);
+        }
+        let message = point_to_octets(pk)?;
+        let hash = E::G1Affine::hash_to_curve::<H>(&message, &pop_dst::<E::G1Affine, H>());
+        let result = E::product_of_pairings(&[
+            (
+                E::G1Prepared::from(*proof),
+                E::G2Prepared::from(parameters.generator.neg()),
+            ),
+            (E::G1Prepared::from(hash), E::G2Prepared::from(*pk)),
+        ])
+        .is_one();
+        Ok(result)
+    }
+}
+
+fn scalar_from_bytes<E: PairingEngine>(bytes: &[u8]) -> E::Fr {
+    let mut base = E::Fr::one();
+    let mut scalar = E::Fr::zero();
+    for bit in super::schnorr::bytes_to_bits(bytes) {
+        if bit {
+            scalar += &base;
+        }
+        base.double_in_place();
+    }
+    scalar
+}
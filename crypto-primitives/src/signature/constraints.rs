@@ -19,3 +19,24 @@ pub trait SigRandomizePkGadget<S: SignatureScheme, ConstraintF: Field> {
         randomness: &[UInt8],
     ) -> Result<Self::PublicKeyGadget, SynthesisError>;
 }
+
+pub trait SigVerifyGadget<S: SignatureScheme, ConstraintF: Field> {
+    type ParametersGadget: AllocGadget<S::Parameters, ConstraintF> + Clone;
+
+    type PublicKeyGadget: ToBytesGadget<ConstraintF>
+        + EqGadget<ConstraintF>
+        + AllocGadget<S::PublicKey, ConstraintF>
+        + Clone;
+
+    type SignatureGadget: AllocGadget<S::Signature, ConstraintF> + Clone;
+
+    /// Enforces that `signature` is a valid signature on `message` under
+    /// `public_key`.
+    fn check_verify<CS: ConstraintSystem<ConstraintF>>(
+        cs: CS,
+        parameters: &Self::ParametersGadget,
+        public_key: &Self::PublicKeyGadget,
+        message: &[UInt8],
+        signature: &Self::SignatureGadget,
+    ) -> Result<(), SynthesisError>;
+}
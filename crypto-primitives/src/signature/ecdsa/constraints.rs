@@ -0,0 +1,1099 @@
+use crate::{
+    crh::{CRHGadget, CRH},
+    signature::{
+        ecdsa::{Ecdsa, EcdsaParameters, EcdsaSig},
+        SigVerifyGadget,
+    },
+    Vec,
+};
+use algebra_core::{
+    curves::{
+        models::{ModelParameters, SWModelParameters},
+        short_weierstrass_jacobian::GroupAffine,
+        AffineCurve,
+    },
+    BigInteger, Field, FpParameters, One, PrimeField, ProjectiveCurve, Zero,
+};
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::{fields::nonnative::NonNativeFieldGadget, prelude::*, Assignment};
+
+use blake2::Blake2s;
+use core::{borrow::Borrow, marker::PhantomData};
+use digest::Digest;
+
+type BaseFieldGadget<P, ConstraintF> =
+    NonNativeFieldGadget<<P as ModelParameters>::BaseField, ConstraintF>;
+type ScalarFieldGadget<P, ConstraintF> =
+    NonNativeFieldGadget<<P as ModelParameters>::ScalarField, ConstraintF>;
+
+/// The number of scalar bits processed per addition in scalar
+/// multiplications.
+const WINDOW_SIZE: usize = 4;
+
+/// Verification of `Ecdsa<P, H>` signatures in circuits over any prime field
+/// `ConstraintF` that is large enough for non-native arithmetic, such as the
+/// scalar fields of BN254 and BLS12-381.
+///
+/// Arithmetic modulo the base field and the group order of `P` is emulated
+/// with `NonNativeFieldGadget`, and scalar multiplications use incomplete
+/// affine formulas. All accumulators are offset by a point `H` whose discrete
+/// logarithm is unknown, which makes exceptional cases unlikely for honest
+/// signatures. Additions involving the public key enforce that their operands
+/// have distinct x-coordinates, so that a malicious prover cannot abuse an
+/// exceptional case; in the fixed-base multiplication by a constant generator,
+/// reaching one would require knowing the discrete logarithm of `H`, so that
+/// check is skipped there. If `P` has a GLV endomorphism, the multiplication
+/// of the public key uses it to halve the number of doublings.
+///
+/// Like `Ecdsa::verify`, the gadget only accepts signatures with
+/// `s <= (n - 1) / 2`. The curve must have prime order, as secp256k1 has.
+pub struct EcdsaSigVerifyGadget<P: SWModelParameters, H: CRH, HG> {
+    #[doc(hidden)]
+    _params: PhantomData<P>,
+    #[doc(hidden)]
+    _hash: PhantomData<H>,
+    #[doc(hidden)]
+    _hash_gadget: PhantomData<HG>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(
+    bound = "P: SWModelParameters, H: CRH, HG: CRHGadget<H, ConstraintF>, ConstraintF: PrimeField"
+))]
+pub struct EcdsaSigGadgetParameters<P, H, HG, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    H: CRH,
+    HG: CRHGadget<H, ConstraintF>,
+    ConstraintF: PrimeField,
+{
+    generator: GeneratorGadget<P, ConstraintF>,
+    crh_parameters: HG::ParametersGadget,
+}
+
+/// A generator allocated with `alloc_constant` is kept as a constant, since
+/// fixed-base scalar multiplication precomputes its multiples.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "P: SWModelParameters, ConstraintF: PrimeField"))]
+enum GeneratorGadget<P: SWModelParameters, ConstraintF: PrimeField>
+where
+    P::BaseField: PrimeField,
+{
+    Constant(GroupAffine<P>),
+    Variable(NonNativeAffineGadget<P, ConstraintF>),
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "P: SWModelParameters, ConstraintF: PrimeField"))]
+pub struct EcdsaSigGadget<P: SWModelParameters, ConstraintF: PrimeField> {
+    r: ScalarFieldGadget<P, ConstraintF>,
+    s: ScalarFieldGadget<P, ConstraintF>,
+}
+
+/// An affine point on the curve of `P`, other than the point at infinity,
+/// with coordinates held in non-native arithmetic.
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "P: SWModelParameters, ConstraintF: PrimeField"),
+    Debug(bound = "P: SWModelParameters, ConstraintF: PrimeField")
+)]
+pub struct NonNativeAffineGadget<P: SWModelParameters, ConstraintF: PrimeField>
+where
+    P::BaseField: PrimeField,
+{
+    pub x: BaseFieldGadget<P, ConstraintF>,
+    pub y: BaseFieldGadget<P, ConstraintF>,
+}
+
+impl<P, ConstraintF> NonNativeAffineGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    ConstraintF: PrimeField,
+{
+    /// Enforces `y^2 = x^3 + a * x + b`.
+    pub fn enforce_on_curve<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<(), SynthesisError> {
+        let x_squared = self.x.square(cs.ns(|| "x^2"))?;
+        let a = BaseFieldGadget::<P, ConstraintF>::alloc_constant(cs.ns(|| "a"), &P::COEFF_A)?;
+        let b = BaseFieldGadget::<P, ConstraintF>::alloc_constant(cs.ns(|| "b"), &P::COEFF_B)?;
+        let mut rhs = vec![(&x_squared, &self.x)];
+        if !P::COEFF_A.is_zero() {
+            rhs.push((&a, &self.x));
+        }
+        BaseFieldGadget::<P, ConstraintF>::enforce_sum_of_products_equal(
+            cs.ns(|| "Check equation"),
+            &[(&self.y, &self.y)],
+            &rhs,
+            &b,
+        )
+    }
+
+    /// Adds `other`, which must have a different x-coordinate.
+    pub fn add<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let dx = other.x.sub(cs.ns(|| "x2 - x1"), &self.x)?;
+        let _ = dx.inverse(cs.ns(|| "Enforce distinct x"))?;
+        self.add_unchecked(cs.ns(|| "Add"), other)
+    }
+
+    /// Adds `other` without enforcing that it has a different x-coordinate;
+    /// if it does not, the result is unconstrained.
+    fn add_unchecked<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let dx = other.x.sub(cs.ns(|| "x2 - x1"), &self.x)?;
+        let dy = other.y.sub(cs.ns(|| "y2 - y1"), &self.y)?;
+        let lambda = BaseFieldGadget::<P, ConstraintF>::alloc(cs.ns(|| "Lambda"), || {
+            let dx = dx.get_value().get()?;
+            let dy = dy.get_value().get()?;
+            Ok(dy * &dx.inverse().unwrap_or_else(P::BaseField::zero))
+        })?;
+        lambda.mul_equals(cs.ns(|| "Check lambda"), &dx, &dy)?;
+        self.finish(cs.ns(|| "Finish"), &lambda, &other.x)
+    }
+
+    pub fn double<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Self, SynthesisError> {
+        // lambda = (3 * x^2 + a) / (2 * y); there is no point with y = 0 on a
+        // curve of odd order.
+        let lambda = BaseFieldGadget::<P, ConstraintF>::alloc(cs.ns(|| "Lambda"), || {
+            let x = self.x.get_value().get()?;
+            let y = self.y.get_value().get()?;
+            let numerator = x.square() * &P::BaseField::from(3u64) + &P::COEFF_A;
+            Ok(numerator * &y.double().inverse().unwrap_or_else(P::BaseField::zero))
+        })?;
+        let two_y = self.y.double(cs.ns(|| "2 * y"))?;
+        let three_x = self
+            .x
+            .double(cs.ns(|| "2 * x"))?
+            .add(cs.ns(|| "3 * x"), &self.x)?;
+        let a = BaseFieldGadget::<P, ConstraintF>::alloc_constant(cs.ns(|| "a"), &P::COEFF_A)?;
+        BaseFieldGadget::<P, ConstraintF>::enforce_sum_of_products_equal(
+            cs.ns(|| "Check lambda"),
+            &[(&lambda, &two_y)],
+            &[(&self.x, &three_x)],
+            &a,
+        )?;
+        self.finish(cs.ns(|| "Finish"), &lambda, &self.x)
+    }
+
+    /// Allocates `x3 = lambda^2 - x1 - x2` and `y3 = lambda * (x1 - x3) - y1`,
+    /// and checks them as `lambda^2 = x1 + x2 + x3` and
+    /// `lambda * (x1 - x3) = y1 + y3`.
+    fn finish<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        lambda: &BaseFieldGadget<P, ConstraintF>,
+        other_x: &BaseFieldGadget<P, ConstraintF>,
+    ) -> Result<Self, SynthesisError> {
+        let x3_value = lambda.get_value().and_then(|lambda| {
+            Some(lambda.square() - &self.x.get_value()? - &other_x.get_value()?)
+        });
+        let y3_value = lambda.get_value().and_then(|lambda| {
+            Some(lambda * &(self.x.get_value()? - &x3_value?) - &self.y.get_value()?)
+        });
+        let x3 = BaseFieldGadget::<P, ConstraintF>::alloc(cs.ns(|| "x3"), || x3_value.get())?;
+        let y3 = BaseFieldGadget::<P, ConstraintF>::alloc(cs.ns(|| "y3"), || y3_value.get())?;
+
+        let x_sum = self
+            .x
+            .add(cs.ns(|| "x1 + x2"), other_x)?
+            .add(cs.ns(|| "x1 + x2 + x3"), &x3)?;
+        lambda.mul_equals(cs.ns(|| "Check x3"), lambda, &x_sum)?;
+        let dx = self.x.sub(cs.ns(|| "x1 - x3"), &x3)?;
+        let y_sum = self.y.add(cs.ns(|| "y1 + y3"), &y3)?;
+        lambda.mul_equals(cs.ns(|| "Check y3"), &dx, &y_sum)?;
+        Ok(Self { x: x3, y: y3 })
+    }
+
+    /// Returns `-self` if `condition` holds, and `self` otherwise.
+    fn conditional_negate<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        condition: &Boolean,
+    ) -> Result<Self, SynthesisError> {
+        let negated = self.y.negate(cs.ns(|| "Negate y"))?;
+        Ok(Self {
+            x: self.x.clone(),
+            y: BaseFieldGadget::<P, ConstraintF>::conditionally_select(
+                cs.ns(|| "Select y"),
+                condition,
+                &negated,
+                &self.y,
+            )?,
+        })
+    }
+
+    /// Selects `table[index]`, where `index` is given by little-endian `bits`.
+    fn lookup<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        bits: &[Boolean],
+        table: &[Self],
+    ) -> Result<Self, SynthesisError> {
+        let mut table = table.to_vec();
+        for (i, bit) in bits.iter().enumerate() {
+            let mut cs = cs.ns(|| format!("Bit {}", i));
+            table = table
+                .chunks(2)
+                .enumerate()
+                .map(|(j, pair)| {
+                    Self::conditionally_select(
+                        cs.ns(|| format!("Select {}", j)),
+                        bit,
+                        &pair[1],
+                        &pair[0],
+                    )
+                })
+                .collect::<Result<_, _>>()?;
+        }
+        Ok(table.remove(0))
+    }
+
+    /// Selects `table[index]` from constants, where `index` is given by two
+    /// little-endian `bits`.
+    fn two_bit_lookup_constant<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        bits: &[Boolean],
+        table: &[GroupAffine<P>],
+    ) -> Result<Self, SynthesisError> {
+        let xs: Vec<_> = table.iter().map(|p| p.x).collect();
+        let ys: Vec<_> = table.iter().map(|p| p.y).collect();
+        Ok(Self {
+            x: TwoBitLookupGadget::two_bit_lookup(cs.ns(|| "x"), bits, &xs)?,
+            y: TwoBitLookupGadget::two_bit_lookup(cs.ns(|| "y"), bits, &ys)?,
+        })
+    }
+
+    /// Selects `table[index]` from constants, where `index` is given by
+    /// `WINDOW_SIZE` little-endian `bits`.
+    fn lookup_constant<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        bits: &[Boolean],
+        table: &[GroupAffine<P>],
+    ) -> Result<Self, SynthesisError> {
+        debug_assert_eq!(bits.len(), WINDOW_SIZE);
+        let mut quarters = Vec::with_capacity(4);
+        for (i, quarter) in table.chunks(4).enumerate() {
+            quarters.push(Self::two_bit_lookup_constant(
+                cs.ns(|| format!("Quarter {}", i)),
+                &bits[..2],
+                quarter,
+            )?);
+        }
+        Self::lookup(cs.ns(|| "Select quarter"), &bits[2..], &quarters)
+    }
+
+    /// Computes `scalar * base + (2^n - 1) * offset`, where `scalar` is given
+    /// by `n` little-endian windows, with precomputed tables.
+    fn fixed_base_mul<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        base: &GroupAffine<P>,
+        offset: &GroupAffine<P>,
+        windows: &[Vec<Boolean>],
+    ) -> Result<Self, SynthesisError> {
+        // Window `i` adds `digit * 16^i * base + 2^i * offset`. The
+        // accumulator before window `i` is `a * base + (2^i - 1) * offset` for
+        // some `a`, so it can only share its x-coordinate with a table entry
+        // if the discrete logarithm of `offset` is known.
+        let mut base = base.into_projective();
+        let mut offset = offset.into_projective();
+        let mut result: Option<Self> = None;
+        for (i, window) in windows.iter().enumerate() {
+            let mut cs = cs.ns(|| format!("Window {}", i));
+            let mut entry = offset;
+            let mut table = Vec::with_capacity(1 << WINDOW_SIZE);
+            for _ in 0..1 << WINDOW_SIZE {
+                table.push(entry);
+                entry += &base;
+            }
+            ProjectiveCurve::batch_normalization(&mut table);
+            let table: Vec<_> = table.into_iter().map(|p| p.into_affine()).collect();
+            let point = Self::lookup_constant(cs.ns(|| "Lookup"), window, &table)?;
+            result = Some(match result {
+                Some(result) => result.add_unchecked(cs.ns(|| "Add"), &point)?,
+                None => point,
+            });
+            for _ in 0..WINDOW_SIZE {
+                base.double_in_place();
+            }
+            offset.double_in_place();
+        }
+        Ok(result.unwrap())
+    }
+
+    /// Returns `[offset, offset + self, ..., offset + 15 * self]`.
+    fn table<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        offset: &GroupAffine<P>,
+    ) -> Result<Vec<Self>, SynthesisError> {
+        let mut table = vec![Self::alloc_constant(cs.ns(|| "Offset"), offset)?];
+        for i in 1..1 << WINDOW_SIZE {
+            let entry = table[i - 1].add(cs.ns(|| format!("Entry {}", i)), self)?;
+            table.push(entry);
+        }
+        Ok(table)
+    }
+
+    /// Computes `scalar * self + (16^0 + ... + 16^(n - 1)) * offset`, where
+    /// `scalar` is given by `n` little-endian windows.
+    fn variable_base_mul<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        offset: &GroupAffine<P>,
+        windows: &[Vec<Boolean>],
+    ) -> Result<Self, SynthesisError> {
+        let table = self.table(cs.ns(|| "Table"), offset)?;
+        let mut result: Option<Self> = None;
+        for (i, window) in windows.iter().enumerate().rev() {
+            let mut cs = cs.ns(|| format!("Window {}", i));
+            let point = Self::lookup(cs.ns(|| "Lookup"), window, &table)?;
+            result = Some(match result {
+                Some(mut result) => {
+                    for j in 0..WINDOW_SIZE {
+                        result = result.double(cs.ns(|| format!("Double {}", j)))?;
+                    }
+                    result.add(cs.ns(|| "Add"), &point)?
+                },
+                None => point,
+            });
+        }
+        Ok(result.unwrap())
+    }
+
+    /// Computes `k1 * self + k2 * phi(self)`, where `phi` is the GLV
+    /// endomorphism `(x, y) -> (omega * x, y)` and each `ki` is given by a
+    /// sign bit and `n` little-endian windows of its absolute value.
+    ///
+    /// The result is offset by `sign(k1) * S * offset + sign(k2) * S *
+    /// phi(offset)`, where `S = 16^0 + ... + 16^(n - 1)`.
+    fn glv_mul<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        omega: &P::BaseField,
+        offset: &GroupAffine<P>,
+        k1: (&Boolean, &[Vec<Boolean>]),
+        k2: (&Boolean, &[Vec<Boolean>]),
+    ) -> Result<Self, SynthesisError> {
+        let table = self.table(cs.ns(|| "Table"), offset)?;
+        let endomorphism = |p: &GroupAffine<P>| GroupAffine::<P>::new(p.x * omega, p.y, false);
+        let mut endomorphism_table = vec![Self::alloc_constant(
+            cs.ns(|| "Endomorphism of offset"),
+            endomorphism(offset),
+        )?];
+        for (i, entry) in table.iter().enumerate().skip(1) {
+            endomorphism_table.push(Self {
+                x: entry
+                    .x
+                    .mul_by_constant(cs.ns(|| format!("Endomorphism {}", i)), omega)?,
+                y: entry.y.clone(),
+            });
+        }
+
+        let mut result: Option<Self> = None;
+        for (i, (w1, w2)) in k1.1.iter().zip(k2.1).enumerate().rev() {
+            let mut cs = cs.ns(|| format!("Window {}", i));
+            let p1 = Self::lookup(cs.ns(|| "Lookup k1"), w1, &table)?
+                .conditional_negate(cs.ns(|| "Sign of k1"), k1.0)?;
+            let p2 = Self::lookup(cs.ns(|| "Lookup k2"), w2, &endomorphism_table)?
+                .conditional_negate(cs.ns(|| "Sign of k2"), k2.0)?;
+            result = Some(match result {
+                Some(mut result) => {
+                    for j in 0..WINDOW_SIZE {
+                        result = result.double(cs.ns(|| format!("Double {}", j)))?;
+                    }
+                    result
+                        .add(cs.ns(|| "Add k1"), &p1)?
+                        .add(cs.ns(|| "Add k2"), &p2)?
+                },
+                None => p1.add(cs.ns(|| "Add"), &p2)?,
+            });
+        }
+        Ok(result.unwrap())
+    }
+}
+
+/// Returns a point of the curve of `P` whose discrete logarithm is unknown,
+/// by hashing to an x-coordinate until one is on the curve.
+fn offset_point<P: SWModelParameters>() -> GroupAffine<P>
+where
+    P::BaseField: PrimeField,
+{
+    let mut counter = 0u64;
+    loop {
+        let hash = Blake2s::new()
+            .chain(b"ECDSA gadget offset")
+            .chain(&counter.to_le_bytes())
+            .result();
+        if let Some(point) = P::BaseField::from_random_bytes(&hash)
+            .and_then(|x| GroupAffine::<P>::get_point_from_x(x, false))
+        {
+            return point;
+        }
+        counter += 1;
+    }
+}
+
+/// Allocates the `num_bits` low bits of `value`, most significant first.
+fn alloc_bits<B: BigInteger, ConstraintF: PrimeField, CS: ConstraintSystem<ConstraintF>>(
+    mut cs: CS,
+    value: Option<B>,
+    num_bits: usize,
+) -> Result<Vec<Boolean>, SynthesisError> {
+    (0..num_bits)
+        .rev()
+        .map(|i| {
+            Boolean::alloc(cs.ns(|| format!("Bit {}", i)), || {
+                value.map(|v| v.get_bit(i)).get()
+            })
+        })
+        .collect()
+}
+
+/// Splits big-endian `bits` into little-endian windows of `WINDOW_SIZE` bits.
+fn windows(bits: &[Boolean]) -> Vec<Vec<Boolean>> {
+    let mut bits: Vec<_> = bits.iter().rev().cloned().collect();
+    let num_windows = (bits.len() + WINDOW_SIZE - 1) / WINDOW_SIZE;
+    bits.resize(num_windows * WINDOW_SIZE, Boolean::constant(false));
+    bits.chunks(WINDOW_SIZE).map(|w| w.to_vec()).collect()
+}
+
+/// Returns `16^0 + ... + 16^(n - 1)`.
+fn window_sum<F: PrimeField>(n: usize) -> F {
+    let sixteen = F::from(1u64 << WINDOW_SIZE);
+    let mut sum = F::zero();
+    let mut power = F::one();
+    for _ in 0..n {
+        sum += &power;
+        power *= &sixteen;
+    }
+    sum
+}
+
+impl<P, H, HG, ConstraintF> SigVerifyGadget<Ecdsa<P, H>, ConstraintF>
+    for EcdsaSigVerifyGadget<P, H, HG>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    H: CRH,
+    H::Parameters: Send + Sync,
+    HG: CRHGadget<H, ConstraintF>,
+    ConstraintF: PrimeField,
+{
+    type ParametersGadget = EcdsaSigGadgetParameters<P, H, HG, ConstraintF>;
+    type PublicKeyGadget = NonNativeAffineGadget<P, ConstraintF>;
+    type SignatureGadget = EcdsaSigGadget<P, ConstraintF>;
+
+    fn check_verify<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        public_key: &Self::PublicKeyGadget,
+        message: &[UInt8],
+        signature: &Self::SignatureGadget,
+    ) -> Result<(), SynthesisError> {
+        assert_eq!(P::COFACTOR, &[1], "the curve must have prime order");
+        let scalar_bits = <P::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+        public_key.enforce_on_curve(cs.ns(|| "Public key on curve"))?;
+
+        // z := the leftmost bits of H(msg)
+        let digest_bits = HG::check_evaluation_gadget(
+            cs.ns(|| "Hash message"),
+            &parameters.crh_parameters,
+            message,
+        )?
+        .to_bytes(cs.ns(|| "Digest to bytes"))?
+        .iter()
+        .flat_map(|byte| byte.into_bits_le().into_iter().rev())
+        .take(scalar_bits)
+        .collect::<Vec<_>>();
+        let digest =
+            ScalarFieldGadget::<P, ConstraintF>::from_bits(cs.ns(|| "Digest"), &digest_bits)?;
+
+        // This also enforces that r and s are non-zero.
+        let _ = signature.r.inverse(cs.ns(|| "Enforce r is non-zero"))?;
+        let s_inverse = signature.s.inverse(cs.ns(|| "Invert s"))?;
+
+        // s <= (n - 1) / 2
+        let s_bits = alloc_bits(
+            cs.ns(|| "s bits"),
+            signature.s.get_value().map(|s| s.into_repr()),
+            scalar_bits,
+        )?;
+        Boolean::enforce_smaller_or_equal_than::<_, _, P::ScalarField, _>(
+            cs.ns(|| "Enforce low s"),
+            &s_bits,
+            <P::ScalarField as PrimeField>::Params::MODULUS_MINUS_ONE_DIV_TWO,
+        )?;
+        ScalarFieldGadget::<P, ConstraintF>::from_bits(cs.ns(|| "s from bits"), &s_bits)?
+            .enforce_equal(cs.ns(|| "Check s bits"), &signature.s)?;
+
+        // u1 := z / s, given by the bits of any integer that is congruent to
+        // it, since the generator has order n.
+        let u1_value = digest
+            .get_value()
+            .and_then(|z| Some(z * &s_inverse.get_value()?));
+        let u1_bits = alloc_bits(
+            cs.ns(|| "u1 bits"),
+            u1_value.map(|u| u.into_repr()),
+            scalar_bits,
+        )?;
+        let u1 = ScalarFieldGadget::<P, ConstraintF>::from_bits(cs.ns(|| "u1"), &u1_bits)?;
+        ScalarFieldGadget::<P, ConstraintF>::enforce_sum_of_products_equal(
+            cs.ns(|| "Check u1"),
+            &[(&digest, &s_inverse)],
+            &[],
+            &u1,
+        )?;
+        let u1_windows = windows(&u1_bits);
+
+        // R := u1 * G + u2 * Q, where u2 := r / s, computed with offsets that
+        // are removed at the end.
+        let offset = offset_point::<P>();
+        let (u1_g, u1_offset) = match &parameters.generator {
+            GeneratorGadget::Constant(generator) => {
+                let u1_g = NonNativeAffineGadget::fixed_base_mul(
+                    cs.ns(|| "u1 * G"),
+                    generator,
+                    &offset,
+                    &u1_windows,
+                )?;
+                let u1_offset = <P::ScalarField as From<u64>>::from(2u64)
+                    .pow(&[u1_windows.len() as u64])
+                    - &P::ScalarField::one();
+                (u1_g, u1_offset)
+            },
+            GeneratorGadget::Variable(generator) => {
+                generator.enforce_on_curve(cs.ns(|| "Generator on curve"))?;
+                let u1_g = generator.variable_base_mul(cs.ns(|| "u1 * G"), &offset, &u1_windows)?;
+                (u1_g, window_sum::<P::ScalarField>(u1_windows.len()))
+            },
+        };
+
+        let u2_value = signature
+            .r
+            .get_value()
+            .and_then(|r| Some(r * &s_inverse.get_value()?));
+        let (u2_q, negated_offset) = match P::GLV {
+            Some(glv) => {
+                // u2 = k1 + lambda * k2, where k1 and k2 are short.
+                let half_bits = scalar_bits / 2 + 2;
+                let decomposition = u2_value.map(|u| glv.decompose(&u.into_repr()).unwrap());
+                let mut signs = Vec::with_capacity(2);
+                let mut halves = Vec::with_capacity(2);
+                let mut half_windows = Vec::with_capacity(2);
+                for i in 0..2 {
+                    let mut cs = cs.ns(|| format!("k{}", i + 1));
+                    let half = decomposition.map(|d| if i == 0 { d.0 } else { d.1 });
+                    let sign = Boolean::alloc(cs.ns(|| "Sign"), || half.map(|h| h.0).get())?;
+                    let bits = alloc_bits(cs.ns(|| "Bits"), half.map(|h| h.1), half_bits)?;
+                    let absolute = ScalarFieldGadget::<P, ConstraintF>::from_bits(
+                        cs.ns(|| "Absolute value"),
+                        &bits,
+                    )?;
+                    let negated = absolute.negate(cs.ns(|| "Negate"))?;
+                    halves.push(ScalarFieldGadget::<P, ConstraintF>::conditionally_select(
+                        cs.ns(|| "Apply sign"),
+                        &sign,
+                        &negated,
+                        &absolute,
+                    )?);
+                    signs.push(sign);
+                    half_windows.push(windows(&bits));
+                }
+                let lambda = ScalarFieldGadget::<P, ConstraintF>::alloc_constant(
+                    cs.ns(|| "lambda"),
+                    &glv.lambda,
+                )?;
+                ScalarFieldGadget::<P, ConstraintF>::enforce_sum_of_products_equal(
+                    cs.ns(|| "Check decomposition of u2"),
+                    &[(&signature.r, &s_inverse)],
+                    &[(&lambda, &halves[1])],
+                    &halves[0],
+                )?;
+
+                let u2_q = public_key.glv_mul(
+                    cs.ns(|| "u2 * Q"),
+                    &glv.omega,
+                    &offset,
+                    (&signs[0], &half_windows[0]),
+                    (&signs[1], &half_windows[1]),
+                )?;
+
+                // The offset depends on the signs of k1 and k2, and
+                // phi(offset) = lambda * offset.
+                let sum = window_sum::<P::ScalarField>(half_windows[0].len());
+                let mut negated_offsets = Vec::with_capacity(4);
+                for i in 0..4 {
+                    let k1_offset = if i & 1 == 0 { sum } else { -sum };
+                    let k2_offset = if i & 2 == 0 { sum } else { -sum };
+                    let total = u1_offset + &k1_offset + &(k2_offset * &glv.lambda);
+                    negated_offsets.push((-offset.mul(total)).into_affine());
+                }
+                let negated_offset = NonNativeAffineGadget::two_bit_lookup_constant(
+                    cs.ns(|| "Total offset"),
+                    &signs,
+                    &negated_offsets,
+                )?;
+                (u2_q, negated_offset)
+            },
+            None => {
+                let u2_bits = alloc_bits(
+                    cs.ns(|| "u2 bits"),
+                    u2_value.map(|u| u.into_repr()),
+                    scalar_bits,
+                )?;
+                let u2 = ScalarFieldGadget::<P, ConstraintF>::from_bits(cs.ns(|| "u2"), &u2_bits)?;
+                ScalarFieldGadget::<P, ConstraintF>::enforce_sum_of_products_equal(
+                    cs.ns(|| "Check u2"),
+                    &[(&signature.r, &s_inverse)],
+                    &[],
+                    &u2,
+                )?;
+                let u2_windows = windows(&u2_bits);
+                let u2_q =
+                    public_key.variable_base_mul(cs.ns(|| "u2 * Q"), &offset, &u2_windows)?;
+
+                let total = u1_offset + &window_sum::<P::ScalarField>(u2_windows.len());
+                let negated_offset = NonNativeAffineGadget::alloc_constant(
+                    cs.ns(|| "Total offset"),
+                    (-offset.mul(total)).into_affine(),
+                )?;
+                (u2_q, negated_offset)
+            },
+        };
+        let sum = u1_g.add(cs.ns(|| "u1 * G + u2 * Q"), &u2_q)?;
+        let point = sum.add(cs.ns(|| "Remove offsets"), &negated_offset)?;
+
+        // x(R) mod n == r
+        let x_bits = point.x.to_bits(cs.ns(|| "x to bits"))?;
+        let x = ScalarFieldGadget::<P, ConstraintF>::from_bits(cs.ns(|| "x mod n"), &x_bits)?;
+        x.enforce_equal(cs.ns(|| "Check r"), &signature.r)
+    }
+}
+
+impl<P, ConstraintF> PartialEq for NonNativeAffineGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    ConstraintF: PrimeField,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl<P, ConstraintF> Eq for NonNativeAffineGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    ConstraintF: PrimeField,
+{
+}
+
+impl<P, ConstraintF> ConditionalEqGadget<ConstraintF> for NonNativeAffineGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    ConstraintF: PrimeField,
+{
+    fn conditional_enforce_equal<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+        condition: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        self.x
+            .conditional_enforce_equal(cs.ns(|| "x"), &other.x, condition)?;
+        self.y
+            .conditional_enforce_equal(cs.ns(|| "y"), &other.y, condition)
+    }
+
+    fn cost() -> usize {
+        2 * <BaseFieldGadget<P, ConstraintF> as ConditionalEqGadget<ConstraintF>>::cost()
+    }
+}
+
+impl<P, ConstraintF> EqGadget<ConstraintF> for NonNativeAffineGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    ConstraintF: PrimeField,
+{
+    fn enforce_equal<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(), SynthesisError> {
+        self.x.enforce_equal(cs.ns(|| "x"), &other.x)?;
+        self.y.enforce_equal(cs.ns(|| "y"), &other.y)
+    }
+}
+
+impl<P, ConstraintF> CondSelectGadget<ConstraintF> for NonNativeAffineGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    ConstraintF: PrimeField,
+{
+    fn conditionally_select<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        cond: &Boolean,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        Ok(Self {
+            x: CondSelectGadget::conditionally_select(
+                cs.ns(|| "x"),
+                cond,
+                &true_value.x,
+                &false_value.x,
+            )?,
+            y: CondSelectGadget::conditionally_select(
+                cs.ns(|| "y"),
+                cond,
+                &true_value.y,
+                &false_value.y,
+            )?,
+        })
+    }
+
+    fn cost() -> usize {
+        2 * <BaseFieldGadget<P, ConstraintF> as CondSelectGadget<ConstraintF>>::cost()
+    }
+}
+
+impl<P, ConstraintF> ToBytesGadget<ConstraintF> for NonNativeAffineGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    ConstraintF: PrimeField,
+{
+    /// Matches the serialization of `GroupAffine<P>`, including the trailing
+    /// infinity flag.
+    fn to_bytes<CS: ConstraintSystem<ConstraintF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        let mut bytes = self.x.to_bytes(cs.ns(|| "x"))?;
+        bytes.extend(self.y.to_bytes(cs.ns(|| "y"))?);
+        bytes.push(UInt8::constant(0));
+        Ok(bytes)
+    }
+}
+
+impl<P, ConstraintF> AllocGadget<GroupAffine<P>, ConstraintF>
+    for NonNativeAffineGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    ConstraintF: PrimeField,
+{
+    fn alloc_constant<T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        t: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<GroupAffine<P>>,
+    {
+        let point = t.borrow();
+        Ok(Self {
+            x: BaseFieldGadget::<P, ConstraintF>::alloc_constant(cs.ns(|| "x"), &point.x)?,
+            y: BaseFieldGadget::<P, ConstraintF>::alloc_constant(cs.ns(|| "y"), &point.y)?,
+        })
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<GroupAffine<P>>,
+    {
+        let point = f().map(|p| *p.borrow());
+        Ok(Self {
+            x: BaseFieldGadget::<P, ConstraintF>::alloc(cs.ns(|| "x"), || {
+                point
+                    .as_ref()
+                    .map(|p| p.x)
+                    .map_err(|_| SynthesisError::AssignmentMissing)
+            })?,
+            y: BaseFieldGadget::<P, ConstraintF>::alloc(cs.ns(|| "y"), || {
+                point
+                    .map(|p| p.y)
+                    .map_err(|_| SynthesisError::AssignmentMissing)
+            })?,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<GroupAffine<P>>,
+    {
+        let point = f().map(|p| *p.borrow());
+        Ok(Self {
+            x: BaseFieldGadget::<P, ConstraintF>::alloc_input(cs.ns(|| "x"), || {
+                point
+                    .as_ref()
+                    .map(|p| p.x)
+                    .map_err(|_| SynthesisError::AssignmentMissing)
+            })?,
+            y: BaseFieldGadget::<P, ConstraintF>::alloc_input(cs.ns(|| "y"), || {
+                point
+                    .map(|p| p.y)
+                    .map_err(|_| SynthesisError::AssignmentMissing)
+            })?,
+        })
+    }
+}
+
+impl<P, H, HG, ConstraintF> AllocGadget<EcdsaParameters<P, H>, ConstraintF>
+    for EcdsaSigGadgetParameters<P, H, HG, ConstraintF>
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    H: CRH,
+    HG: CRHGadget<H, ConstraintF>,
+    ConstraintF: PrimeField,
+{
+    fn alloc_constant<T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<EcdsaParameters<P, H>>,
+    {
+        let parameters = val.borrow();
+        let crh_parameters = HG::ParametersGadget::alloc_constant(
+            cs.ns(|| "CRH parameters"),
+            &parameters.crh_parameters,
+        )?;
+        Ok(Self {
+            generator: GeneratorGadget::Constant(parameters.generator),
+            crh_parameters,
+        })
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<EcdsaParameters<P, H>>,
+    {
+        let parameters = f().map(|pp| pp.borrow().clone());
+        let generator = NonNativeAffineGadget::alloc(cs.ns(|| "Generator"), || {
+            parameters
+                .as_ref()
+                .map(|pp| pp.generator)
+                .map_err(|_| SynthesisError::AssignmentMissing)
+        })?;
+        let crh_parameters = HG::ParametersGadget::alloc(cs.ns(|| "CRH parameters"), || {
+            parameters.map(|pp| pp.crh_parameters)
+        })?;
+        Ok(Self {
+            generator: GeneratorGadget::Variable(generator),
+            crh_parameters,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<EcdsaParameters<P, H>>,
+    {
+        let parameters = f().map(|pp| pp.borrow().clone());
+        let generator = NonNativeAffineGadget::alloc_input(cs.ns(|| "Generator"), || {
+            parameters
+                .as_ref()
+                .map(|pp| pp.generator)
+                .map_err(|_| SynthesisError::AssignmentMissing)
+        })?;
+        let crh_parameters = HG::ParametersGadget::alloc_input(cs.ns(|| "CRH parameters"), || {
+            parameters.map(|pp| pp.crh_parameters)
+        })?;
+        Ok(Self {
+            generator: GeneratorGadget::Variable(generator),
+            crh_parameters,
+        })
+    }
+}
+
+impl<P, ConstraintF> AllocGadget<EcdsaSig<P>, ConstraintF> for EcdsaSigGadget<P, ConstraintF>
+where
+    P: SWModelParameters,
+    ConstraintF: PrimeField,
+{
+    fn alloc_constant<T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<EcdsaSig<P>>,
+    {
+        let signature = val.borrow();
+        Ok(Self {
+            r: ScalarFieldGadget::<P, ConstraintF>::alloc_constant(cs.ns(|| "r"), &signature.r)?,
+            s: ScalarFieldGadget::<P, ConstraintF>::alloc_constant(cs.ns(|| "s"), &signature.s)?,
+        })
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<EcdsaSig<P>>,
+    {
+        let signature = f().map(|sig| sig.borrow().clone());
+        Ok(Self {
+            r: ScalarFieldGadget::<P, ConstraintF>::alloc(cs.ns(|| "r"), || {
+                signature
+                    .as_ref()
+                    .map(|sig| sig.r)
+                    .map_err(|_| SynthesisError::AssignmentMissing)
+            })?,
+            s: ScalarFieldGadget::<P, ConstraintF>::alloc(cs.ns(|| "s"), || {
+                signature
+                    .map(|sig| sig.s)
+                    .map_err(|_| SynthesisError::AssignmentMissing)
+            })?,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<EcdsaSig<P>>,
+    {
+        let signature = f().map(|sig| sig.borrow().clone());
+        Ok(Self {
+            r: ScalarFieldGadget::<P, ConstraintF>::alloc_input(cs.ns(|| "r"), || {
+                signature
+                    .as_ref()
+                    .map(|sig| sig.r)
+                    .map_err(|_| SynthesisError::AssignmentMissing)
+            })?,
+            s: ScalarFieldGadget::<P, ConstraintF>::alloc_input(cs.ns(|| "s"), || {
+                signature
+                    .map(|sig| sig.s)
+                    .map_err(|_| SynthesisError::AssignmentMissing)
+            })?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        crh::sha256::{constraints::Sha256Gadget, Sha256},
+        signature::SignatureScheme,
+    };
+    use algebra::{
+        ed_on_bls12_381::Fq as Bls12_381Fr, ed_on_bn254::Fq as Bn254Fr,
+        secp256k1::Secp256k1Parameters, test_rng,
+    };
+    use r1cs_std::test_constraint_system::TestConstraintSystem;
+
+    type Scheme = Ecdsa<Secp256k1Parameters, Sha256>;
+
+    fn check_verify<ConstraintF: PrimeField>(
+        parameters: &EcdsaParameters<Secp256k1Parameters, Sha256>,
+        constant_parameters: bool,
+        pk: &GroupAffine<Secp256k1Parameters>,
+        message: &[u8],
+        sig: &EcdsaSig<Secp256k1Parameters>,
+    ) -> bool {
+        type Gadget = EcdsaSigVerifyGadget<Secp256k1Parameters, Sha256, Sha256Gadget>;
+
+        let mut cs = TestConstraintSystem::<ConstraintF>::new();
+        let parameters_var = if constant_parameters {
+            EcdsaSigGadgetParameters::<_, _, Sha256Gadget, _>::alloc_constant(
+                cs.ns(|| "Parameters"),
+                parameters,
+            )
+        } else {
+            EcdsaSigGadgetParameters::<_, _, Sha256Gadget, _>::alloc(cs.ns(|| "Parameters"), || {
+                Ok(parameters)
+            })
+        }
+        .unwrap();
+        let pk_var = NonNativeAffineGadget::alloc_input(cs.ns(|| "Public key"), || Ok(pk)).unwrap();
+        let sig_var = EcdsaSigGadget::alloc(cs.ns(|| "Signature"), || Ok(sig)).unwrap();
+        let message_var = UInt8::alloc_vec(cs.ns(|| "Message"), message).unwrap();
+        Gadget::check_verify(
+            cs.ns(|| "Verify"),
+            &parameters_var,
+            &pk_var,
+            &message_var,
+            &sig_var,
+        )
+        .unwrap();
+        cs.is_satisfied()
+    }
+
+    #[test]
+    fn secp256k1_check_verify_test() {
+        let rng = &mut test_rng();
+        let message = "Hi, I am an ECDSA signature!".as_bytes();
+        let parameters = Scheme::setup(rng).unwrap();
+        let (pk, sk) = Scheme::keygen(&parameters, rng).unwrap();
+        let sig = Scheme::sign(&parameters, &sk, message, rng).unwrap();
+        let high_sig = EcdsaSig {
+            r: sig.r,
+            s: -sig.s,
+        };
+
+        let bad_message: &[u8] = b"Bad message";
+        for (message, sig, satisfied) in &[
+            (message, &sig, true),
+            (bad_message, &sig, false),
+            (message, &high_sig, false),
+        ] {
+            let result = check_verify::<Bn254Fr>(&parameters, true, &pk, message, sig);
+            assert_eq!(result, *satisfied);
+        }
+        let result = check_verify::<Bls12_381Fr>(&parameters, true, &pk, message, &sig);
+        assert!(result);
+    }
+
+    #[test]
+    fn secp256k1_check_verify_variable_generator_test() {
+        let rng = &mut test_rng();
+        let message = "Hi, I am an ECDSA signature!".as_bytes();
+        let mut parameters = Scheme::setup(rng).unwrap();
+        parameters.generator = parameters.generator.mul(2u64).into_affine();
+        let (pk, sk) = Scheme::keygen(&parameters, rng).unwrap();
+        let sig = Scheme::sign(&parameters, &sk, message, rng).unwrap();
+
+        let bad_message: &[u8] = b"Bad message";
+        for (message, satisfied) in &[(message, true), (bad_message, false)] {
+            let result = check_verify::<Bn254Fr>(&parameters, false, &pk, message, &sig);
+            assert_eq!(result, *satisfied);
+        }
+    }
+}
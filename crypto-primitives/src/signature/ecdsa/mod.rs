@@ -0,0 +1,217 @@
+use crate::{
+    crh::CRH, signature::eddsa::scalar_from_le_bytes, Box, CryptoError, Error, SignatureScheme,
+};
+use algebra_core::{
+    bytes::ToBytes,
+    curves::{models::SWModelParameters, short_weierstrass_jacobian::GroupAffine, AffineCurve},
+    fields::{Field, FpParameters, PrimeField},
+    io::{Result as IoResult, Write},
+    to_bytes, ProjectiveCurve, UniformRand, Zero,
+};
+use core::marker::PhantomData;
+use rand::Rng;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// ECDSA over the short Weierstrass curve of `P`, as in SEC 1:
+/// * the message digest `z` consists of the leftmost bits of `H(msg)`, as many
+///   as the group order has;
+/// * the signature is `(r, s) := (x(k · G) mod n, k⁻¹ (z + r · d))` for a
+///   random nonce `k` and secret key `d`;
+/// * verification checks that `x(z s⁻¹ · G + r s⁻¹ · Q) mod n == r`.
+///
+/// Since `(r, -s)` is valid whenever `(r, s)` is, signatures are made
+/// non-malleable as in Bitcoin (BIP 146) and Ethereum (EIP-2): `sign` outputs
+/// the one with `s <= (n - 1) / 2`, and `verify` rejects the other one.
+///
+/// With `P` set to secp256k1 and `H` to SHA-256 or Keccak-256, this verifies
+/// Bitcoin and Ethereum signatures respectively, where the message is the
+/// single SHA-256 hash of the data that Bitcoin signs, or the data that
+/// Ethereum signs, such as an RLP-encoded transaction.
+pub struct Ecdsa<P: SWModelParameters, H: CRH> {
+    _params: PhantomData<P>,
+    _hash: PhantomData<H>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "P: SWModelParameters, H: CRH"))]
+pub struct EcdsaParameters<P: SWModelParameters, H: CRH> {
+    pub generator: GroupAffine<P>,
+    pub crh_parameters: H::Parameters,
+}
+
+pub type EcdsaPublicKey<P> = GroupAffine<P>;
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "P: SWModelParameters"),
+    Default(bound = "P: SWModelParameters")
+)]
+pub struct EcdsaSecretKey<P: SWModelParameters>(pub P::ScalarField);
+
+impl<P: SWModelParameters> ToBytes for EcdsaSecretKey<P> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "P: SWModelParameters"),
+    Default(bound = "P: SWModelParameters")
+)]
+pub struct EcdsaSig<P: SWModelParameters> {
+    pub r: P::ScalarField,
+    pub s: P::ScalarField,
+}
+
+impl<P: SWModelParameters, H: CRH> Ecdsa<P, H> {
+    /// Computes the message digest `z`.
+    fn digest(parameters: &EcdsaParameters<P, H>, message: &[u8]) -> Result<P::ScalarField, Error> {
+        let digest = H::evaluate(&parameters.crh_parameters, message)?;
+        Ok(scalar_from_digest(&to_bytes![digest]?))
+    }
+
+    /// Reduces the x-coordinate of `point` modulo the group order.
+    fn x_coordinate(point: &GroupAffine<P>) -> Result<P::ScalarField, Error> {
+        Ok(scalar_from_le_bytes(&to_bytes![point.x]?))
+    }
+}
+
+impl<P: SWModelParameters, H: CRH> SignatureScheme for Ecdsa<P, H>
+where
+    H::Parameters: Send + Sync,
+{
+    type Parameters = EcdsaParameters<P, H>;
+    type PublicKey = EcdsaPublicKey<P>;
+    type SecretKey = EcdsaSecretKey<P>;
+    type Signature = EcdsaSig<P>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        let setup_time = start_timer!(|| "Ecdsa::Setup");
+
+        let generator = GroupAffine::prime_subgroup_generator();
+        let crh_parameters = H::setup(rng)?;
+
+        end_timer!(setup_time);
+        Ok(EcdsaParameters {
+            generator,
+            crh_parameters,
+        })
+    }
+
+    fn keygen<R: Rng>(
+        parameters: &Self::Parameters,
+        rng: &mut R,
+    ) -> Result<(Self::PublicKey, Self::SecretKey), Error> {
+        let keygen_time = start_timer!(|| "Ecdsa::KeyGen");
+
+        let mut secret_key = P::ScalarField::rand(rng);
+        while secret_key.is_zero() {
+            secret_key = P::ScalarField::rand(rng);
+        }
+        let public_key = parameters.generator.mul(secret_key).into_affine();
+
+        end_timer!(keygen_time);
+        Ok((public_key, EcdsaSecretKey(secret_key)))
+    }
+
+    fn sign<R: Rng>(
+        parameters: &Self::Parameters,
+        sk: &Self::SecretKey,
+        message: &[u8],
+        rng: &mut R,
+    ) -> Result<Self::Signature, Error> {
+        let sign_time = start_timer!(|| "Ecdsa::Sign");
+
+        let digest = Self::digest(parameters, message)?;
+        let signature = loop {
+            let nonce = P::ScalarField::rand(rng);
+            let nonce_inverse = match nonce.inverse() {
+                Some(inverse) => inverse,
+                None => continue,
+            };
+            let r = Self::x_coordinate(&parameters.generator.mul(nonce).into_affine())?;
+            if r.is_zero() {
+                continue;
+            }
+            // s := k⁻¹ (z + r · d)
+            let s = nonce_inverse * &(digest + &(r * &sk.0));
+            if !s.is_zero() {
+                break EcdsaSig {
+                    r,
+                    s: if is_low(&s) { s } else { -s },
+                };
+            }
+        };
+
+        end_timer!(sign_time);
+        Ok(signature)
+    }
+
+    fn verify(
+        parameters: &Self::Parameters,
+        pk: &Self::PublicKey,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<bool, Error> {
+        let verify_time = start_timer!(|| "Ecdsa::Verify");
+
+        let EcdsaSig { r, s } = signature;
+        let s_inverse = match s.inverse() {
+            Some(inverse) if !r.is_zero() && is_low(s) => inverse,
+            _ => return Ok(false),
+        };
+        let digest = Self::digest(parameters, message)?;
+
+        // R := z s⁻¹ · G + r s⁻¹ · Q
+        let mut point = parameters.generator.mul(digest * &s_inverse);
+        point += &pk.mul(*r * &s_inverse);
+        let result = !point.is_zero() && Self::x_coordinate(&point.into_affine())? == *r;
+
+        end_timer!(verify_time);
+        Ok(result)
+    }
+
+    /// Randomization is unsupported.
+    fn randomize_public_key(
+        _parameters: &Self::Parameters,
+        _public_key: &Self::PublicKey,
+        _randomness: &[u8],
+    ) -> Result<Self::PublicKey, Error> {
+        Err(Box::new(CryptoError::UnsupportedOperation))
+    }
+
+    /// Randomization is unsupported.
+    fn randomize_signature(
+        _parameters: &Self::Parameters,
+        _signature: &Self::Signature,
+        _randomness: &[u8],
+    ) -> Result<Self::Signature, Error> {
+        Err(Box::new(CryptoError::UnsupportedOperation))
+    }
+}
+
+/// Returns whether `s <= (n - 1) / 2`, where `n` is the characteristic of `F`.
+fn is_low<F: PrimeField>(s: &F) -> bool {
+    s.into_repr() <= F::Params::MODULUS_MINUS_ONE_DIV_TWO
+}
+
+/// Interprets the leftmost `MODULUS_BITS` bits of `digest` as a big-endian
+/// integer, reduced modulo the characteristic of `F`.
+pub fn scalar_from_digest<F: PrimeField>(digest: &[u8]) -> F {
+    digest
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+        .take(F::Params::MODULUS_BITS as usize)
+        .fold(F::zero(), |acc, bit| {
+            let acc = acc.double();
+            if bit {
+                acc + &F::one()
+            } else {
+                acc
+            }
+        })
+}
@@ -0,0 +1,352 @@
+use crate::{
+    crh::{CRHGadget, CRH},
+    signature::{
+        eddsa::{EdDSA, EdDSAParameters, EdDSASig},
+        schnorr::constraints::{alloc_input_bytes, scalar_bytes},
+        SigVerifyGadget,
+    },
+    Vec,
+};
+use algebra_core::{
+    curves::{
+        models::{ModelParameters, TEModelParameters},
+        twisted_edwards_extended::GroupAffine,
+    },
+    to_bytes, BitIterator, PrimeField, ToBytes,
+};
+use r1cs_core::{ConstraintSystem, SynthesisError};
+use r1cs_std::{groups::curves::twisted_edwards::AffineGadget, prelude::*};
+
+use core::{borrow::Borrow, marker::PhantomData};
+
+type EdwardsGadget<P> =
+    AffineGadget<P, <P as ModelParameters>::BaseField, FpGadget<<P as ModelParameters>::BaseField>>;
+
+/// Verification of `EdDSA<P, H>` signatures in circuits over the base field
+/// of `P`, with the challenge computed by `HG`.
+///
+/// The challenge is used as an integer, without reduction modulo the group
+/// order, which is sound since both sides of the verification equation are
+/// multiplied by the cofactor.
+pub struct EdDSASigVerifyGadget<P: TEModelParameters, H: CRH, HG: CRHGadget<H, P::BaseField>>
+where
+    P::BaseField: PrimeField,
+{
+    #[doc(hidden)]
+    _params: PhantomData<P>,
+    #[doc(hidden)]
+    _hash: PhantomData<H>,
+    #[doc(hidden)]
+    _hash_gadget: PhantomData<HG>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(
+    bound = "P: TEModelParameters, H: CRH, HG: CRHGadget<H, P::BaseField>, P::BaseField: PrimeField"
+))]
+pub struct EdDSASigGadgetParameters<P: TEModelParameters, H: CRH, HG: CRHGadget<H, P::BaseField>>
+where
+    P::BaseField: PrimeField,
+{
+    generator: EdwardsGadget<P>,
+    crh_parameters: HG::ParametersGadget,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "P: TEModelParameters, P::BaseField: PrimeField"))]
+pub struct EdDSASigGadgetSig<P: TEModelParameters>
+where
+    P::BaseField: PrimeField,
+{
+    prover_commitment: EdwardsGadget<P>,
+    prover_response: Vec<UInt8>,
+}
+
+impl<P, H, HG> SigVerifyGadget<EdDSA<P, H>, P::BaseField> for EdDSASigVerifyGadget<P, H, HG>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+    H: CRH,
+    H::Parameters: Send + Sync,
+    HG: CRHGadget<H, P::BaseField>,
+{
+    type ParametersGadget = EdDSASigGadgetParameters<P, H, HG>;
+    type PublicKeyGadget = EdwardsGadget<P>;
+    type SignatureGadget = EdDSASigGadgetSig<P>;
+
+    fn check_verify<CS: ConstraintSystem<P::BaseField>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        public_key: &Self::PublicKeyGadget,
+        message: &[UInt8],
+        signature: &Self::SignatureGadget,
+    ) -> Result<(), SynthesisError> {
+        let prover_response = signature
+            .prover_response
+            .iter()
+            .flat_map(|b| b.into_bits_le())
+            .collect::<Vec<_>>();
+        let prover_response_be = prover_response.iter().rev().cloned().collect::<Vec<_>>();
+        Boolean::enforce_in_field::<_, _, P::ScalarField>(
+            cs.ns(|| "Response in field"),
+            &prover_response_be,
+        )?;
+
+        // k := H(R || A || msg)
+        let mut hash_input = signature
+            .prover_commitment
+            .to_bytes(cs.ns(|| "Commitment to bytes"))?;
+        hash_input.extend(public_key.to_bytes(cs.ns(|| "Public key to bytes"))?);
+        hash_input.extend_from_slice(message);
+        let challenge = HG::check_evaluation_gadget(
+            cs.ns(|| "Challenge"),
+            &parameters.crh_parameters,
+            &hash_input,
+        )?
+        .to_bytes(cs.ns(|| "Challenge to bytes"))?
+        .iter()
+        .flat_map(|b| b.into_bits_le())
+        .collect::<Vec<_>>();
+
+        // S · B - R - k · A
+        let zero = <EdwardsGadget<P> as GroupGadget<GroupAffine<P>, _>>::zero(cs.ns(|| "Zero"))?;
+        let lhs = GroupGadget::<GroupAffine<P>, _>::mul_bits(
+            &parameters.generator,
+            cs.ns(|| "Multiply generator"),
+            &zero,
+            prover_response.iter(),
+        )?;
+        let rhs = GroupGadget::<GroupAffine<P>, _>::mul_bits(
+            public_key,
+            cs.ns(|| "Multiply public key"),
+            &signature.prover_commitment,
+            challenge.iter(),
+        )?;
+        let difference = GroupGadget::<GroupAffine<P>, _>::sub(&lhs, cs.ns(|| "Difference"), &rhs)?;
+
+        // [h] (S · B - R - k · A) == 0
+        let mut seen_one = false;
+        let mut result = zero.clone();
+        for (i, b) in BitIterator::new(P::COFACTOR).enumerate() {
+            let mut cs = cs.ns(|| format!("Cofactor iteration {}", i));
+            if seen_one {
+                GroupGadget::<GroupAffine<P>, _>::double_in_place(&mut result, cs.ns(|| "Double"))?;
+            }
+            if b {
+                result =
+                    GroupGadget::<GroupAffine<P>, _>::add(&result, cs.ns(|| "Add"), &difference)?;
+                seen_one = true;
+            }
+        }
+        result.enforce_equal(cs.ns(|| "Check equation"), &zero)
+    }
+}
+
+impl<P, H, HG> AllocGadget<EdDSAParameters<P, H>, P::BaseField>
+    for EdDSASigGadgetParameters<P, H, HG>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+    H: CRH,
+    HG: CRHGadget<H, P::BaseField>,
+{
+    fn alloc_constant<T, CS: ConstraintSystem<P::BaseField>>(
+        mut cs: CS,
+        val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<EdDSAParameters<P, H>>,
+    {
+        let parameters = val.borrow();
+        let generator =
+            EdwardsGadget::<P>::alloc_constant(cs.ns(|| "Generator"), parameters.generator)?;
+        let crh_parameters = HG::ParametersGadget::alloc_constant(
+            cs.ns(|| "CRH parameters"),
+            &parameters.crh_parameters,
+        )?;
+        Ok(Self {
+            generator,
+            crh_parameters,
+        })
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<P::BaseField>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<EdDSAParameters<P, H>>,
+    {
+        let parameters = f().map(|pp| pp.borrow().clone());
+        let generator = EdwardsGadget::<P>::alloc_checked(cs.ns(|| "Generator"), || {
+            parameters
+                .as_ref()
+                .map(|pp| pp.generator)
+                .map_err(|_| SynthesisError::AssignmentMissing)
+        })?;
+        let crh_parameters = HG::ParametersGadget::alloc(cs.ns(|| "CRH parameters"), || {
+            parameters.map(|pp| pp.crh_parameters)
+        })?;
+        Ok(Self {
+            generator,
+            crh_parameters,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<P::BaseField>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<EdDSAParameters<P, H>>,
+    {
+        let parameters = f().map(|pp| pp.borrow().clone());
+        let generator = EdwardsGadget::<P>::alloc_input(cs.ns(|| "Generator"), || {
+            parameters
+                .as_ref()
+                .map(|pp| pp.generator)
+                .map_err(|_| SynthesisError::AssignmentMissing)
+        })?;
+        let crh_parameters = HG::ParametersGadget::alloc_input(cs.ns(|| "CRH parameters"), || {
+            parameters.map(|pp| pp.crh_parameters)
+        })?;
+        Ok(Self {
+            generator,
+            crh_parameters,
+        })
+    }
+}
+
+impl<P> AllocGadget<EdDSASig<P>, P::BaseField> for EdDSASigGadgetSig<P>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+{
+    fn alloc_constant<T, CS: ConstraintSystem<P::BaseField>>(
+        mut cs: CS,
+        val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<EdDSASig<P>>,
+    {
+        let signature = val.borrow();
+        let prover_commitment = EdwardsGadget::<P>::alloc_constant(
+            cs.ns(|| "Prover commitment"),
+            signature.prover_commitment,
+        )?;
+        let prover_response = UInt8::constant_vec(&to_bytes![signature.prover_response].unwrap());
+        Ok(Self {
+            prover_commitment,
+            prover_response,
+        })
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<P::BaseField>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<EdDSASig<P>>,
+    {
+        let signature = f().map(|sig| sig.borrow().clone());
+        let prover_commitment = EdwardsGadget::<P>::alloc(cs.ns(|| "Prover commitment"), || {
+            signature
+                .as_ref()
+                .map(|sig| sig.prover_commitment)
+                .map_err(|_| SynthesisError::AssignmentMissing)
+        })?;
+        let prover_response = UInt8::alloc_vec(
+            cs.ns(|| "Prover response"),
+            &scalar_bytes::<GroupAffine<P>>(signature.ok().map(|sig| sig.prover_response)),
+        )?;
+        Ok(Self {
+            prover_commitment,
+            prover_response,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<P::BaseField>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<EdDSASig<P>>,
+    {
+        let signature = f().map(|sig| sig.borrow().clone());
+        let prover_commitment =
+            EdwardsGadget::<P>::alloc_input(cs.ns(|| "Prover commitment"), || {
+                signature
+                    .as_ref()
+                    .map(|sig| sig.prover_commitment)
+                    .map_err(|_| SynthesisError::AssignmentMissing)
+            })?;
+        let prover_response = alloc_input_bytes(
+            cs.ns(|| "Prover response"),
+            &scalar_bytes::<GroupAffine<P>>(signature.ok().map(|sig| sig.prover_response)),
+        )?;
+        Ok(Self {
+            prover_commitment,
+            prover_response,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        crh::blake2s::{constraints::Blake2sCRHGadget, Blake2sCRH},
+        signature::SignatureScheme,
+    };
+    use algebra::{ed_on_bls12_381, ed_on_bn254, test_rng};
+    use r1cs_std::test_constraint_system::TestConstraintSystem;
+
+    fn check_verify_test<P: TEModelParameters>()
+    where
+        P::BaseField: PrimeField,
+    {
+        type Gadget<P> = EdDSASigVerifyGadget<P, Blake2sCRH, Blake2sCRHGadget>;
+
+        let rng = &mut test_rng();
+        let message = "Hi, I am an EdDSA signature!".as_bytes();
+        let parameters = EdDSA::<P, Blake2sCRH>::setup(rng).unwrap();
+        let (pk, sk) = EdDSA::<P, Blake2sCRH>::keygen(&parameters, rng).unwrap();
+        let sig = EdDSA::<P, Blake2sCRH>::sign(&parameters, &sk, message, rng).unwrap();
+
+        for (message, satisfied) in &[(message, true), ("Bad message".as_bytes(), false)] {
+            let mut cs = TestConstraintSystem::<P::BaseField>::new();
+            let parameters_var =
+                EdDSASigGadgetParameters::<P, Blake2sCRH, Blake2sCRHGadget>::alloc(
+                    cs.ns(|| "Parameters"),
+                    || Ok(&parameters),
+                )
+                .unwrap();
+            let pk_var = EdwardsGadget::<P>::alloc(cs.ns(|| "Public key"), || Ok(pk)).unwrap();
+            let sig_var = EdDSASigGadgetSig::alloc(cs.ns(|| "Signature"), || Ok(&sig)).unwrap();
+            let message_var = UInt8::alloc_vec(cs.ns(|| "Message"), message).unwrap();
+            Gadget::<P>::check_verify(
+                cs.ns(|| "Verify"),
+                &parameters_var,
+                &pk_var,
+                &message_var,
+                &sig_var,
+            )
+            .unwrap();
+            assert_eq!(cs.is_satisfied(), *satisfied);
+        }
+    }
+
+    #[test]
+    fn baby_jubjub_check_verify_test() {
+        check_verify_test::<ed_on_bn254::EdwardsParameters>();
+    }
+
+    #[test]
+    fn jubjub_check_verify_test() {
+        check_verify_test::<ed_on_bls12_381::EdwardsParameters>();
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{crh::CRH, Box, CryptoError, Error, SignatureScheme};
+use algebra_core::{
+    bytes::ToBytes,
+    curves::{models::TEModelParameters, twisted_edwards_extended::GroupAffine, AffineCurve},
+    fields::PrimeField,
+    io::{Result as IoResult, Write},
+    to_bytes, ProjectiveCurve, Zero,
+};
+use core::marker::PhantomData;
+use digest::Digest;
+use rand::Rng;
+use sha2::Sha512;
+
+#[cfg(feature = "r1cs")]
+pub mod constraints;
+
+/// Deterministic EdDSA over the twisted Edwards curve of `P`, in the style of
+/// RFC 8032:
+/// * the secret key is a 32-byte seed `sk`, and `SHA-512(sk)` is split into the
+///   secret scalar `s` (reduced modulo the group order instead of being
+///   clamped) and a nonce prefix;
+/// * the nonce is `r := SHA-512(prefix || msg)`, and the signature is `(R, S)
+///   := (r · B, r + k · s)` with the challenge `k := H(R || A || msg)`;
+/// * verification is cofactor-aware, i.e. it checks `[h] (S · B) == [h] R + [h]
+///   (k · A)`.
+///
+/// The challenge hash `H` is any `CRH`, so that a hash with a cheap gadget
+/// can be chosen for verification in circuits.
+pub struct EdDSA<P: TEModelParameters, H: CRH> {
+    _params: PhantomData<P>,
+    _hash: PhantomData<H>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "P: TEModelParameters, H: CRH"))]
+pub struct EdDSAParameters<P: TEModelParameters, H: CRH> {
+    pub generator: GroupAffine<P>,
+    pub crh_parameters: H::Parameters,
+}
+
+pub type EdDSAPublicKey<P> = GroupAffine<P>;
+
+#[derive(Derivative)]
+#[derivative(Clone, Default)]
+pub struct EdDSASecretKey(pub [u8; 32]);
+
+impl ToBytes for EdDSASecretKey {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.0.write(writer)
+    }
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "P: TEModelParameters"),
+    Default(bound = "P: TEModelParameters")
+)]
+pub struct EdDSASig<P: TEModelParameters> {
+    pub prover_commitment: GroupAffine<P>,
+    pub prover_response: P::ScalarField,
+}
+
+impl<P: TEModelParameters, H: CRH> EdDSA<P, H> {
+    /// Splits `SHA-512(sk)` into the secret scalar and the nonce prefix.
+    fn expand_secret_key(sk: &EdDSASecretKey) -> (P::ScalarField, [u8; 32]) {
+        let digest = Sha512::digest(&sk.0);
+        let mut prefix = [0u8; 32];
+        prefix.copy_from_slice(&digest[32..]);
+        (scalar_from_le_bytes(&digest[..32]), prefix)
+    }
+
+    /// Computes the challenge `k := H(R || A || msg)`.
+    fn challenge(
+        parameters: &EdDSAParameters<P, H>,
+        prover_commitment: &GroupAffine<P>,
+        pk: &GroupAffine<P>,
+        message: &[u8],
+    ) -> Result<P::ScalarField, Error> {
+        let mut hash_input = to_bytes![prover_commitment, pk]?;
+        hash_input.extend_from_slice(message);
+        let digest = H::evaluate(&parameters.crh_parameters, &hash_input)?;
+        Ok(scalar_from_le_bytes(&to_bytes![digest]?))
+    }
+}
+
+impl<P: TEModelParameters, H: CRH> SignatureScheme for EdDSA<P, H>
+where
+    H::Parameters: Send + Sync,
+{
+    type Parameters = EdDSAParameters<P, H>;
+    type PublicKey = EdDSAPublicKey<P>;
+    type SecretKey = EdDSASecretKey;
+    type Signature = EdDSASig<P>;
+
+    fn setup<R: Rng>(rng: &mut R) -> Result<Self::Parameters, Error> {
+        let setup_time = start_timer!(|| "EdDSA::Setup");
+
+        let generator = GroupAffine::prime_subgroup_generator();
+        let crh_parameters = H::setup(rng)?;
+
+        end_timer!(setup_time);
+        Ok(EdDSAParameters {
+            generator,
+            crh_parameters,
+        })
+    }
+
+    fn keygen<R: Rng>(
+        parameters: &Self::Parameters,
+        rng: &mut R,
+    ) -> Result<(Self::PublicKey, Self::SecretKey), Error> {
+        let keygen_time = start_timer!(|| "EdDSA::KeyGen");
+
+        let mut secret_key = EdDSASecretKey::default();
+        rng.fill_bytes(&mut secret_key.0);
+        let (secret_scalar, _) = Self::expand_secret_key(&secret_key);
+        let public_key = parameters.generator.mul(secret_scalar).into_affine();
+
+        end_timer!(keygen_time);
+        Ok((public_key, secret_key))
+    }
+
+    fn sign<R: Rng>(
+        parameters: &Self::Parameters,
+        sk: &Self::SecretKey,
+        message: &[u8],
+        _rng: &mut R,
+    ) -> Result<Self::Signature, Error> {
+        let sign_time = start_timer!(|| "EdDSA::Sign");
+
+        let (secret_scalar, prefix) = Self::expand_secret_key(sk);
+        let public_key = parameters.generator.mul(secret_scalar).into_affine();
+
+        // r := SHA-512(prefix || msg)
+        let mut nonce_input = prefix.to_vec();
+        nonce_input.extend_from_slice(message);
+        let nonce: P::ScalarField = scalar_from_le_bytes(&Sha512::digest(&nonce_input));
+        let prover_commitment = parameters.generator.mul(nonce).into_affine();
+
+        // S := r + k · s
+        let challenge = Self::challenge(parameters, &prover_commitment, &public_key, message)?;
+        let prover_response = nonce + &(challenge * &secret_scalar);
+
+        end_timer!(sign_time);
+        Ok(EdDSASig {
+            prover_commitment,
+            prover_response,
+        })
+    }
+
+    fn verify(
+        parameters: &Self::Parameters,
+        pk: &Self::PublicKey,
+        message: &[u8],
+        signature: &Self::Signature,
+    ) -> Result<bool, Error> {
+        let verify_time = start_timer!(|| "EdDSA::Verify");
+
+        let EdDSASig {
+            prover_commitment,
+            prover_response,
+        } = signature;
+        let challenge = Self::challenge(parameters, prover_commitment, pk, message)?;
+
+        // [h] (S · B - R - k · A) == 0
+        let mut difference = parameters.generator.mul(*prover_response);
+        difference -= &prover_commitment.into_projective();
+        difference -= &pk.mul(challenge);
+        let result = difference.into_affine().scale_by_cofactor().is_zero();
+
+        end_timer!(verify_time);
+        Ok(result)
+    }
+
+    /// Randomization is unsupported, since the challenge binds the public key.
+    fn randomize_public_key(
+        _parameters: &Self::Parameters,
+        _public_key: &Self::PublicKey,
+        _randomness: &[u8],
+    ) -> Result<Self::PublicKey, Error> {
+        Err(Box::new(CryptoError::UnsupportedOperation))
+    }
+
+    /// Randomization is unsupported, since the challenge binds the public key.
+    fn randomize_signature(
+        _parameters: &Self::Parameters,
+        _signature: &Self::Signature,
+        _randomness: &[u8],
+    ) -> Result<Self::Signature, Error> {
+        Err(Box::new(CryptoError::UnsupportedOperation))
+    }
+}
+
+/// Interprets `bytes` as a little-endian integer, reduced modulo the
+/// characteristic of `F`.
+pub fn scalar_from_le_bytes<F: PrimeField>(bytes: &[u8]) -> F {
+    let base = F::from(256u64);
+    bytes
+        .iter()
+        .rev()
+        .fold(F::zero(), |acc, byte| acc * &base + &F::from(*byte))
+}
@@ -8,6 +8,9 @@ pub mod constraints;
 #[cfg(feature = "r1cs")]
 pub use constraints::*;
 
+pub mod bls;
+pub mod ecdsa;
+pub mod eddsa;
 pub mod schnorr;
 
 pub trait SignatureScheme {
@@ -52,12 +55,22 @@ pub trait SignatureScheme {
 
 #[cfg(test)]
 mod test {
-    use crate::{signature::schnorr::SchnorrSignature, SignatureScheme};
+    use crate::{
+        crh::{blake2s::Blake2sCRH, keccak::Keccak256, sha256::Sha256, CRH},
+        signature::{
+            bls::{point_to_octets, pop_dst, signature_dst, BlsSignature},
+            ecdsa::{Ecdsa, EcdsaParameters, EcdsaSig},
+            eddsa::EdDSA,
+            schnorr::{SchnorrBatchSig, SchnorrSig, SchnorrSignature},
+        },
+        SignatureScheme,
+    };
     use algebra::{
-        ed_on_bls12_381::EdwardsAffine as JubJub, groups::Group, test_rng, to_bytes, ToBytes,
-        UniformRand,
+        bls12_377::Bls12_377, ed_on_bls12_381, ed_on_bls12_381::EdwardsAffine as JubJub,
+        ed_on_bn254, groups::Group, secp256k1::Secp256k1Parameters, test_rng, to_bytes, Field, One,
+        PairingEngine, ToBytes, UniformRand, Zero,
     };
-    use blake2::Blake2s;
+    use blake2::{Blake2s, Digest};
 
     fn sign_and_verify<S: SignatureScheme>(message: &[u8]) {
         let rng = &mut test_rng();
@@ -101,4 +114,310 @@ mod test {
             &random_scalar.as_slice(),
         );
     }
+
+    #[test]
+    fn schnorr_batch_verify_test() {
+        type Schnorr = SchnorrSignature<JubJub, Blake2s>;
+
+        let rng = &mut test_rng();
+        let parameters = Schnorr::setup(rng).unwrap();
+        let messages: Vec<&[u8]> = vec![b"zero", b"one", b"two", b"three"];
+        let (pks, sigs): (Vec<_>, Vec<_>) = messages
+            .iter()
+            .map(|message| {
+                let (pk, sk) = Schnorr::keygen(&parameters, rng).unwrap();
+                let sig = Schnorr::sign_batchable(&parameters, &sk, message, rng).unwrap();
+                (pk, sig)
+            })
+            .unzip();
+        assert!(Schnorr::batch_verify(&parameters, &pks, &messages, &sigs, rng).unwrap());
+        assert!(Schnorr::batch_verify(&parameters, &[], &[], &[], rng).unwrap());
+
+        let mut bad_messages = messages.clone();
+        bad_messages.swap(0, 1);
+        assert!(!Schnorr::batch_verify(&parameters, &pks, &bad_messages, &sigs, rng).unwrap());
+        let mut bad_sigs = sigs.clone();
+        bad_sigs[2].signature.prover_response += &<JubJub as Group>::ScalarField::one();
+        assert!(!Schnorr::batch_verify(&parameters, &pks, &messages, &bad_sigs, rng).unwrap());
+        assert!(Schnorr::batch_verify(&parameters, &pks[1..], &messages, &sigs, rng).is_err());
+    }
+
+    #[test]
+    fn schnorr_batch_verify_torsion_test() {
+        type Schnorr = SchnorrSignature<JubJub, Blake2s>;
+        type Fr = <JubJub as Group>::ScalarField;
+
+        let rng = &mut test_rng();
+        let parameters = Schnorr::setup(rng).unwrap();
+        let (pk, sk) = Schnorr::keygen(&parameters, rng).unwrap();
+        let message: &[u8] = b"message";
+
+        // Sign with the commitment `r + T` for a point `T` of order two, so
+        // that `s · g + e · pk - (r + T) == -T`, which vanishes in the
+        // combined equation whenever the random scalar is even, and always
+        // once it is multiplied by the cofactor.
+        let torsion = JubJub::new(Zero::zero(), -ed_on_bls12_381::Fq::one());
+        let (random_scalar, verifier_challenge, prover_commitment) = loop {
+            let random_scalar = Fr::rand(rng);
+            let mut prover_commitment = parameters.generator.mul(&random_scalar);
+            prover_commitment += &torsion;
+            let mut hash_input = parameters.salt.to_vec();
+            hash_input.extend_from_slice(&to_bytes![prover_commitment].unwrap());
+            hash_input.extend_from_slice(message);
+            if let Some(verifier_challenge) = Fr::from_random_bytes(&Blake2s::digest(&hash_input)) {
+                break (random_scalar, verifier_challenge, prover_commitment);
+            }
+        };
+        let forged_sig = SchnorrBatchSig {
+            signature: SchnorrSig {
+                prover_response: random_scalar - &(verifier_challenge * &sk.0),
+                verifier_challenge,
+            },
+            prover_commitment,
+        };
+
+        assert!(!Schnorr::verify(&parameters, &pk, message, &forged_sig.signature).unwrap());
+        for _ in 0..16 {
+            assert!(Schnorr::batch_verify(
+                &parameters,
+                &[pk],
+                &[message],
+                &[forged_sig.clone()],
+                rng
+            )
+            .unwrap());
+        }
+    }
+
+    fn eddsa_signature_test<S: SignatureScheme>() {
+        let message = "Hi, I am an EdDSA signature!";
+        sign_and_verify::<S>(message.as_bytes());
+        failed_verification::<S>(message.as_bytes(), "Bad message".as_bytes());
+
+        let rng = &mut test_rng();
+        let parameters = S::setup(rng).unwrap();
+        let (pk, sk) = S::keygen(&parameters, rng).unwrap();
+        let sig = S::sign(&parameters, &sk, message.as_bytes(), rng).unwrap();
+        let other_sig = S::sign(&parameters, &sk, message.as_bytes(), rng).unwrap();
+        assert!(S::verify(&parameters, &pk, message.as_bytes(), &other_sig).unwrap());
+        assert!(S::randomize_public_key(&parameters, &pk, &[1u8; 32]).is_err());
+        assert!(S::randomize_signature(&parameters, &sig, &[1u8; 32]).is_err());
+    }
+
+    #[test]
+    fn baby_jubjub_eddsa_signature_test() {
+        type BabyJubJubEdDSA = EdDSA<ed_on_bn254::EdwardsParameters, Blake2sCRH>;
+
+        eddsa_signature_test::<BabyJubJubEdDSA>();
+        let rng = &mut test_rng();
+        let parameters = BabyJubJubEdDSA::setup(rng).unwrap();
+        let (_, sk) = BabyJubJubEdDSA::keygen(&parameters, rng).unwrap();
+        let sig = BabyJubJubEdDSA::sign(&parameters, &sk, b"message", rng).unwrap();
+        let other_sig = BabyJubJubEdDSA::sign(&parameters, &sk, b"message", rng).unwrap();
+        assert_eq!(sig.prover_commitment, other_sig.prover_commitment);
+        assert_eq!(sig.prover_response, other_sig.prover_response);
+    }
+
+    #[test]
+    fn jubjub_eddsa_signature_test() {
+        eddsa_signature_test::<EdDSA<ed_on_bls12_381::EdwardsParameters, Blake2sCRH>>();
+    }
+
+    #[test]
+    fn secp256k1_ecdsa_signature_test() {
+        type BitcoinEcdsa = Ecdsa<Secp256k1Parameters, Sha256>;
+
+        let message = "Hi, I am an ECDSA signature!";
+        sign_and_verify::<BitcoinEcdsa>(message.as_bytes());
+        failed_verification::<BitcoinEcdsa>(message.as_bytes(), "Bad message".as_bytes());
+        sign_and_verify::<Ecdsa<Secp256k1Parameters, Keccak256>>(message.as_bytes());
+
+        let rng = &mut test_rng();
+        let parameters = BitcoinEcdsa::setup(rng).unwrap();
+        let (pk, sk) = BitcoinEcdsa::keygen(&parameters, rng).unwrap();
+        let (other_pk, _) = BitcoinEcdsa::keygen(&parameters, rng).unwrap();
+        let sig = BitcoinEcdsa::sign(&parameters, &sk, message.as_bytes(), rng).unwrap();
+        assert!(!BitcoinEcdsa::verify(&parameters, &other_pk, message.as_bytes(), &sig).unwrap());
+
+        // `(r, -s)` satisfies the verification equation as well, but only the
+        // signature with the lower `s` is accepted.
+        for bad_sig in &[
+            EcdsaSig {
+                r: sig.r,
+                s: -sig.s,
+            },
+            EcdsaSig {
+                r: Zero::zero(),
+                s: sig.s,
+            },
+            EcdsaSig {
+                r: sig.r,
+                s: Zero::zero(),
+            },
+            EcdsaSig {
+                r: sig.r + &One::one(),
+                s: sig.s,
+            },
+        ] {
+            assert!(!BitcoinEcdsa::verify(&parameters, &pk, message.as_bytes(), bad_sig).unwrap());
+        }
+        assert!(BitcoinEcdsa::randomize_public_key(&parameters, &pk, &[1u8; 32]).is_err());
+        assert!(BitcoinEcdsa::randomize_signature(&parameters, &sig, &[1u8; 32]).is_err());
+    }
+
+    #[test]
+    fn secp256k1_ecdsa_known_answer_test() {
+        use algebra::{
+            secp256k1::{Fq, Fr, Secp256k1Affine},
+            AffineCurve,
+        };
+        use core::str::FromStr;
+
+        fn check<H: CRH<Parameters = ()>>(pk: (&str, &str), message: &[u8], r: &str, s: &str) {
+            type Parameters<H> = EcdsaParameters<Secp256k1Parameters, H>;
+
+            let parameters = Parameters::<H> {
+                generator: Secp256k1Affine::prime_subgroup_generator(),
+                crh_parameters: (),
+            };
+            let pk = Secp256k1Affine::new(
+                Fq::from_str(pk.0).unwrap(),
+                Fq::from_str(pk.1).unwrap(),
+                false,
+            );
+            let sig = EcdsaSig {
+                r: Fr::from_str(r).unwrap(),
+                s: Fr::from_str(s).unwrap(),
+            };
+            assert!(Ecdsa::<_, H>::verify(&parameters, &pk, message, &sig).unwrap());
+            assert!(!Ecdsa::<_, H>::verify(&parameters, &pk, b"Bad message", &sig).unwrap());
+        }
+
+        // Deterministic (RFC 6979) signatures with the secret key 1, from the
+        // vectors used across Bitcoin libraries, e.g. the ECDSA fixtures of
+        // bitcoinjs-lib, and cross-checked with OpenSSL.
+        let generator = (
+            "55066263022277343669578718895168534326250603453777594175500187360389116729240",
+            "32670510020758816978083085130507043184471273380659243275938904335757337482424",
+        );
+        check::<Sha256>(
+            generator,
+            b"Satoshi Nakamoto",
+            "66622713665624427733710315200720396955896638749566533714623508373930515555288",
+            "16401300452320261922100688354512281705028622471755817586694009013603023182309",
+        );
+        check::<Sha256>(
+            generator,
+            b"All those moments will be lost in time, like tears in rain. Time to die...",
+            "60611438911297328155264284136120185534961497394771823148199442423678177430379",
+            "38220258097294752743694075290588542165294424403629339082999643400395224316961",
+        );
+
+        // The example transaction of EIP-155, signed with the secret key
+        // 0x4646...46; the message is its RLP encoding for signing.
+        let transaction = [
+            0xec, 0x09, 0x85, 0x04, 0xa8, 0x17, 0xc8, 0x00, 0x82, 0x52, 0x08, 0x94, 0x35, 0x35,
+            0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35,
+            0x35, 0x35, 0x35, 0x35, 0x88, 0x0d, 0xe0, 0xb6, 0xb3, 0xa7, 0x64, 0x00, 0x00, 0x80,
+            0x01, 0x80, 0x80,
+        ];
+        check::<Keccak256>(
+            (
+                "34267357454890834202699501369117337915298038816505545536566299989069368210306",
+                "93248519793482870262716478842254439831529833249880237208181267626996973249322",
+            ),
+            &transaction,
+            "18515461264373351373200002665853028612451056578545711640558177340181847433846",
+            "46948507304638947509940763649030358759909902576025900602547168820602576006531",
+        );
+    }
+
+    #[test]
+    fn bls_signature_test() {
+        type Bls = BlsSignature<Bls12_377, Blake2s>;
+
+        let message = "Hi, I am a BLS signature!";
+        let rng = &mut test_rng();
+        sign_and_verify::<Bls>(message.as_bytes());
+        failed_verification::<Bls>(message.as_bytes(), "Bad message".as_bytes());
+        let random_scalar = to_bytes!(<Bls12_377 as PairingEngine>::Fr::rand(rng)).unwrap();
+        randomize_and_verify::<Bls>(message.as_bytes(), &random_scalar.as_slice());
+    }
+
+    #[test]
+    fn bls_ciphersuite_test() {
+        use algebra::{
+            bls12_381::{Bls12_381, G1Affine, G2Affine},
+            AffineCurve,
+        };
+
+        assert_eq!(
+            signature_dst::<G1Affine, sha2::Sha256>(),
+            b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_".to_vec()
+        );
+        assert_eq!(
+            pop_dst::<G1Affine, sha2::Sha256>(),
+            b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_".to_vec()
+        );
+
+        // The compressed encoding of the generator of G2.
+        let expected = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11\
+                        213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b\
+                        02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+        let expected = (0..expected.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&expected[i..i + 2], 16).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            point_to_octets(&G2Affine::prime_subgroup_generator()).unwrap(),
+            expected
+        );
+        let zero = point_to_octets(&G2Affine::zero()).unwrap();
+        assert_eq!(zero[0], 0xC0);
+        assert!(zero[1..].iter().all(|b| *b == 0));
+
+        type Bls = BlsSignature<Bls12_381, sha2::Sha256>;
+        let message = "Hi, I am a BLS signature!";
+        sign_and_verify::<Bls>(message.as_bytes());
+        failed_verification::<Bls>(message.as_bytes(), "Bad message".as_bytes());
+    }
+
+    #[test]
+    fn bls_aggregate_signature_test() {
+        type Bls = BlsSignature<Bls12_377, Blake2s>;
+
+        let rng = &mut test_rng();
+        let parameters = Bls::setup(rng).unwrap();
+        let (pks, sks): (Vec<_>, Vec<_>) = (0..4)
+            .map(|_| Bls::keygen(&parameters, rng).unwrap())
+            .unzip();
+        for (pk, sk) in pks.iter().zip(&sks) {
+            let proof = Bls::prove_possession(sk, pk).unwrap();
+            assert!(Bls::verify_possession(&parameters, pk, &proof).unwrap());
+            let sig = Bls::sign(&parameters, sk, "Bad message".as_bytes(), rng).unwrap();
+            assert!(!Bls::verify_possession(&parameters, pk, &sig).unwrap());
+        }
+
+        // Distinct messages.
+        let messages: Vec<&[u8]> = vec![b"zero", b"one", b"two", b"three"];
+        let sig = Bls::aggregate_sign(&parameters, &sks, &messages, rng).unwrap();
+        assert!(Bls::aggregate_verify(&parameters, &pks, &messages, &sig).unwrap());
+        let mut bad_messages = messages.clone();
+        bad_messages.swap(0, 1);
+        assert!(!Bls::aggregate_verify(&parameters, &pks, &bad_messages, &sig).unwrap());
+        assert!(!Bls::aggregate_verify(&parameters, &pks[1..], &messages[1..], &sig).unwrap());
+        assert!(Bls::aggregate_verify(&parameters, &pks, &messages[1..], &sig).is_err());
+
+        // A single message, verified against the aggregate public key.
+        let message: &[u8] = b"Hi, we are BLS signatures!";
+        let sigs = sks
+            .iter()
+            .map(|sk| Bls::sign(&parameters, sk, message, rng).unwrap())
+            .collect::<Vec<_>>();
+        let sig = Bls::aggregate_signatures(&sigs);
+        let pk = Bls::aggregate_public_keys(&pks);
+        assert!(Bls::verify(&parameters, &pk, message, &sig).unwrap());
+        assert!(!Bls::verify(&parameters, &pk, b"Bad message", &sig).unwrap());
+        assert!(!Bls::verify(&parameters, &pks[0], message, &sig).unwrap());
+    }
 }
@@ -1,18 +1,25 @@
 use crate::Vec;
-use algebra_core::{groups::Group, Field};
+use algebra_core::{groups::Group, to_bytes, Field, FpParameters, PrimeField, ToBytes, Zero};
 use r1cs_core::{ConstraintSystem, SynthesisError};
-use r1cs_std::prelude::*;
+use r1cs_std::{prelude::*, Assignment};
 
-use crate::signature::SigRandomizePkGadget;
+use crate::{
+    prf::blake2s::constraints::blake2s_gadget,
+    signature::{SigRandomizePkGadget, SigVerifyGadget},
+};
 
-use core::{borrow::Borrow, marker::PhantomData};
+use core::{borrow::Borrow, hash::Hash, iter, marker::PhantomData};
 
-use crate::signature::schnorr::{SchnorrPublicKey, SchnorrSigParameters, SchnorrSignature};
+use crate::signature::schnorr::{
+    SchnorrPublicKey, SchnorrSig, SchnorrSigParameters, SchnorrSignature,
+};
+use blake2::Blake2s;
 use digest::Digest;
 
 pub struct SchnorrSigGadgetParameters<G: Group, ConstraintF: Field, GG: GroupGadget<G, ConstraintF>>
 {
     generator: GG,
+    salt: Vec<UInt8>,
     _group: PhantomData<*const G>,
     _engine: PhantomData<*const ConstraintF>,
 }
@@ -23,6 +30,7 @@ impl<G: Group, ConstraintF: Field, GG: GroupGadget<G, ConstraintF>> Clone
     fn clone(&self) -> Self {
         Self {
             generator: self.generator.clone(),
+            salt: self.salt.clone(),
             _group: PhantomData,
             _engine: PhantomData,
         }
@@ -44,6 +52,17 @@ pub struct SchnorrSigGadgetPk<G: Group, ConstraintF: Field, GG: GroupGadget<G, C
     _engine: PhantomData<*const ConstraintF>,
 }
 
+#[derive(Derivative)]
+#[derivative(Clone(bound = "G: Group, ConstraintF: Field"))]
+pub struct SchnorrSigGadgetSig<G: Group, ConstraintF: Field> {
+    prover_response: Vec<UInt8>,
+    verifier_challenge: Vec<UInt8>,
+    #[doc(hidden)]
+    _group: PhantomData<*const G>,
+    #[doc(hidden)]
+    _engine: PhantomData<*const ConstraintF>,
+}
+
 pub struct SchnorrRandomizePkGadget<G: Group, ConstraintF: Field, GG: GroupGadget<G, ConstraintF>> {
     #[doc(hidden)]
     _group: PhantomData<*const G>,
@@ -88,6 +107,105 @@ where
     }
 }
 
+/// Verification of `SchnorrSignature<G, Blake2s>` signatures, where the
+/// verifier challenge is recomputed in the circuit with the Blake2s gadget.
+///
+/// The bytes of the claimed prover commitment are those of
+/// `GG::to_bytes`, which must agree with `G::write`; this is the case for
+/// the affine twisted Edwards gadgets.
+pub struct SchnorrSigVerifyGadget<G: Group, ConstraintF: Field, GG: GroupGadget<G, ConstraintF>> {
+    #[doc(hidden)]
+    _group: PhantomData<*const G>,
+    #[doc(hidden)]
+    _group_gadget: PhantomData<*const GG>,
+    #[doc(hidden)]
+    _engine: PhantomData<*const ConstraintF>,
+}
+
+impl<G, GG, ConstraintF> SigVerifyGadget<SchnorrSignature<G, Blake2s>, ConstraintF>
+    for SchnorrSigVerifyGadget<G, ConstraintF, GG>
+where
+    G: Group + Hash,
+    G::ScalarField: PrimeField,
+    GG: GroupGadget<G, ConstraintF>,
+    ConstraintF: PrimeField,
+{
+    type ParametersGadget = SchnorrSigGadgetParameters<G, ConstraintF, GG>;
+    type PublicKeyGadget = SchnorrSigGadgetPk<G, ConstraintF, GG>;
+    type SignatureGadget = SchnorrSigGadgetSig<G, ConstraintF>;
+
+    fn check_verify<CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        parameters: &Self::ParametersGadget,
+        public_key: &Self::PublicKeyGadget,
+        message: &[UInt8],
+        signature: &Self::SignatureGadget,
+    ) -> Result<(), SynthesisError> {
+        let prover_response = signature
+            .prover_response
+            .iter()
+            .flat_map(|b| b.into_bits_le())
+            .collect::<Vec<_>>();
+        let verifier_challenge = signature
+            .verifier_challenge
+            .iter()
+            .flat_map(|b| b.into_bits_le())
+            .collect::<Vec<_>>();
+
+        // r := s · g + e · pk
+        let zero = GG::zero(cs.ns(|| "Zero"))?;
+        let claimed_prover_commitment = parameters.generator.mul_bits(
+            cs.ns(|| "Multiply generator"),
+            &zero,
+            prover_response.iter(),
+        )?;
+        let claimed_prover_commitment = public_key.pub_key.mul_bits(
+            cs.ns(|| "Multiply public key"),
+            &claimed_prover_commitment,
+            verifier_challenge.iter(),
+        )?;
+
+        // e' := H(salt || r || msg)
+        let mut hash_input = parameters.salt.clone();
+        hash_input.extend(claimed_prover_commitment.to_bytes(cs.ns(|| "Commitment to bytes"))?);
+        hash_input.extend_from_slice(message);
+        let hash_input = hash_input
+            .iter()
+            .flat_map(|b| b.into_bits_le())
+            .collect::<Vec<_>>();
+        let mut digest = Vec::with_capacity(256);
+        for (i, int) in blake2s_gadget(cs.ns(|| "Hash"), &hash_input)?
+            .into_iter()
+            .enumerate()
+        {
+            let bytes = int.to_bytes(cs.ns(|| format!("Digest to bytes {}", i)))?;
+            digest.extend(bytes.iter().flat_map(|b| b.into_bits_le()));
+        }
+
+        // `from_random_bytes` keeps the low `MODULUS_BITS` bits of the digest,
+        // and fails if they do not encode an element of the scalar field.
+        digest.truncate(<G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize);
+        let digest_be = digest.iter().rev().cloned().collect::<Vec<_>>();
+        Boolean::enforce_in_field::<_, _, G::ScalarField>(
+            cs.ns(|| "Challenge in field"),
+            &digest_be,
+        )?;
+
+        // e == e'
+        let obtained_verifier_challenge = digest
+            .into_iter()
+            .chain(iter::repeat(Boolean::constant(false)));
+        for (i, (bit, obtained_bit)) in verifier_challenge
+            .iter()
+            .zip(obtained_verifier_challenge)
+            .enumerate()
+        {
+            bit.enforce_equal(cs.ns(|| format!("Challenge bit {}", i)), &obtained_bit)?;
+        }
+        Ok(())
+    }
+}
+
 impl<G, ConstraintF, GG, D> AllocGadget<SchnorrSigParameters<G, D>, ConstraintF>
     for SchnorrSigGadgetParameters<G, ConstraintF, GG>
 where
@@ -97,44 +215,74 @@ where
     D: Digest,
 {
     fn alloc_constant<T, CS: ConstraintSystem<ConstraintF>>(
-        cs: CS,
+        mut cs: CS,
         val: T,
     ) -> Result<Self, SynthesisError>
     where
         T: Borrow<SchnorrSigParameters<G, D>>,
     {
-        let generator = GG::alloc_constant(cs, val.borrow().generator)?;
+        let parameters = val.borrow();
+        let generator = GG::alloc_constant(cs.ns(|| "Generator"), parameters.generator)?;
+        let salt = UInt8::constant_vec(&parameters.salt);
         Ok(Self {
             generator,
+            salt,
             _engine: PhantomData,
             _group: PhantomData,
         })
     }
 
-    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(cs: CS, f: F) -> Result<Self, SynthesisError>
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
     where
         F: FnOnce() -> Result<T, SynthesisError>,
         T: Borrow<SchnorrSigParameters<G, D>>,
     {
-        let generator = GG::alloc_checked(cs, || f().map(|pp| pp.borrow().generator))?;
+        let parameters = f().map(|pp| {
+            let pp = pp.borrow();
+            (pp.generator, pp.salt)
+        });
+        let salt = match parameters {
+            Ok((_, salt)) => salt.iter().map(|b| Some(*b)).collect(),
+            Err(_) => vec![None; 32],
+        };
+        let generator = GG::alloc_checked(cs.ns(|| "Generator"), || {
+            parameters.map(|(generator, _)| generator)
+        })?;
+        let salt = UInt8::alloc_vec(cs.ns(|| "Salt"), &salt)?;
         Ok(Self {
             generator,
+            salt,
             _engine: PhantomData,
             _group: PhantomData,
         })
     }
 
     fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
-        cs: CS,
+        mut cs: CS,
         f: F,
     ) -> Result<Self, SynthesisError>
     where
         F: FnOnce() -> Result<T, SynthesisError>,
         T: Borrow<SchnorrSigParameters<G, D>>,
     {
-        let generator = GG::alloc_input(cs, || f().map(|pp| pp.borrow().generator))?;
+        let parameters = f().map(|pp| {
+            let pp = pp.borrow();
+            (pp.generator, pp.salt)
+        });
+        let salt = match parameters {
+            Ok((_, salt)) => salt.iter().map(|b| Some(*b)).collect(),
+            Err(_) => vec![None; 32],
+        };
+        let generator = GG::alloc_input(cs.ns(|| "Generator"), || {
+            parameters.map(|(generator, _)| generator)
+        })?;
+        let salt = alloc_input_bytes(cs.ns(|| "Salt"), &salt)?;
         Ok(Self {
             generator,
+            salt,
             _engine: PhantomData,
             _group: PhantomData,
         })
@@ -193,6 +341,99 @@ where
     }
 }
 
+pub(crate) fn alloc_input_bytes<ConstraintF: Field, CS: ConstraintSystem<ConstraintF>>(
+    mut cs: CS,
+    values: &[Option<u8>],
+) -> Result<Vec<UInt8>, SynthesisError> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| UInt8::alloc_input(cs.ns(|| format!("byte_{}", i)), || value.get()))
+        .collect()
+}
+
+pub(crate) fn scalar_bytes<G: Group>(scalar: Option<G::ScalarField>) -> Vec<Option<u8>> {
+    match scalar {
+        Some(scalar) => to_bytes![scalar].unwrap().into_iter().map(Some).collect(),
+        None => vec![None; to_bytes![G::ScalarField::zero()].unwrap().len()],
+    }
+}
+
+impl<G, ConstraintF> AllocGadget<SchnorrSig<G>, ConstraintF> for SchnorrSigGadgetSig<G, ConstraintF>
+where
+    G: Group,
+    ConstraintF: Field,
+{
+    fn alloc_constant<T, CS: ConstraintSystem<ConstraintF>>(
+        _cs: CS,
+        val: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<SchnorrSig<G>>,
+    {
+        let signature = val.borrow();
+        let prover_response = UInt8::constant_vec(&to_bytes![signature.prover_response].unwrap());
+        let verifier_challenge =
+            UInt8::constant_vec(&to_bytes![signature.verifier_challenge].unwrap());
+        Ok(Self {
+            prover_response,
+            verifier_challenge,
+            _group: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<SchnorrSig<G>>,
+    {
+        let signature = f().map(|sig| sig.borrow().clone()).ok();
+        let prover_response = UInt8::alloc_vec(
+            cs.ns(|| "Prover response"),
+            &scalar_bytes::<G>(signature.as_ref().map(|sig| sig.prover_response)),
+        )?;
+        let verifier_challenge = UInt8::alloc_vec(
+            cs.ns(|| "Verifier challenge"),
+            &scalar_bytes::<G>(signature.as_ref().map(|sig| sig.verifier_challenge)),
+        )?;
+        Ok(Self {
+            prover_response,
+            verifier_challenge,
+            _group: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<ConstraintF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<SchnorrSig<G>>,
+    {
+        let signature = f().map(|sig| sig.borrow().clone()).ok();
+        let prover_response = alloc_input_bytes(
+            cs.ns(|| "Prover response"),
+            &scalar_bytes::<G>(signature.as_ref().map(|sig| sig.prover_response)),
+        )?;
+        let verifier_challenge = alloc_input_bytes(
+            cs.ns(|| "Verifier challenge"),
+            &scalar_bytes::<G>(signature.as_ref().map(|sig| sig.verifier_challenge)),
+        )?;
+        Ok(Self {
+            prover_response,
+            verifier_challenge,
+            _group: PhantomData,
+            _engine: PhantomData,
+        })
+    }
+}
+
 impl<G, ConstraintF, GG> ConditionalEqGadget<ConstraintF> for SchnorrSigGadgetPk<G, ConstraintF, GG>
 where
     G: Group,
@@ -240,3 +481,45 @@ where
         self.pub_key.to_bytes(&mut cs.ns(|| "PubKey To Bytes"))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::signature::SignatureScheme;
+    use algebra::{
+        ed_on_bls12_381::{EdwardsAffine as JubJub, Fq},
+        test_rng,
+    };
+    use r1cs_std::{ed_on_bls12_381::EdwardsGadget, test_constraint_system::TestConstraintSystem};
+
+    type Schnorr = SchnorrSignature<JubJub, Blake2s>;
+    type SchnorrGadget = SchnorrSigVerifyGadget<JubJub, Fq, EdwardsGadget>;
+
+    #[test]
+    fn check_verify_test() {
+        let rng = &mut test_rng();
+        let message = "Hi, I am a Schnorr signature!".as_bytes();
+        let parameters = Schnorr::setup(rng).unwrap();
+        let (pk, sk) = Schnorr::keygen(&parameters, rng).unwrap();
+        let sig = Schnorr::sign(&parameters, &sk, message, rng).unwrap();
+
+        for (message, satisfied) in &[(message, true), ("Bad message".as_bytes(), false)] {
+            let mut cs = TestConstraintSystem::<Fq>::new();
+            let parameters_var =
+                SchnorrSigGadgetParameters::alloc(cs.ns(|| "Parameters"), || Ok(&parameters))
+                    .unwrap();
+            let pk_var = SchnorrSigGadgetPk::alloc(cs.ns(|| "Public key"), || Ok(pk)).unwrap();
+            let sig_var = SchnorrSigGadgetSig::alloc(cs.ns(|| "Signature"), || Ok(&sig)).unwrap();
+            let message_var = UInt8::alloc_vec(cs.ns(|| "Message"), message).unwrap();
+            SchnorrGadget::check_verify(
+                cs.ns(|| "Verify"),
+                &parameters_var,
+                &pk_var,
+                &message_var,
+                &sig_var,
+            )
+            .unwrap();
+            assert_eq!(cs.is_satisfied(), *satisfied);
+        }
+    }
+}
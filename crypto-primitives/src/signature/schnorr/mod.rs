@@ -1,9 +1,11 @@
-use crate::{Error, SignatureScheme, Vec};
+use crate::{Box, CryptoError, Error, SignatureScheme, Vec};
 use algebra_core::{
     bytes::ToBytes,
+    curves::{AffineCurve, ProjectiveCurve},
     fields::{Field, PrimeField},
     groups::Group,
     io::{Result as IoResult, Write},
+    msm::VariableBaseMSM,
     to_bytes, One, ToConstraintField, UniformRand, Zero,
 };
 use core::{hash::Hash, marker::PhantomData};
@@ -46,6 +48,140 @@ pub struct SchnorrSig<G: Group> {
     pub verifier_challenge: G::ScalarField,
 }
 
+/// A `SchnorrSig` together with its prover commitment `r`, as produced by
+/// `SchnorrSignature::sign_batchable`. `verify` recomputes `r`, so it is
+/// left out of `SchnorrSig`, but `batch_verify` needs it; the signature is
+/// therefore one group element larger.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "G: Group"), Default(bound = "G: Group"))]
+pub struct SchnorrBatchSig<G: Group> {
+    pub signature: SchnorrSig<G>,
+    pub prover_commitment: G,
+}
+
+impl<G: Group + Hash, D: Digest + Send + Sync> SchnorrSignature<G, D>
+where
+    G::ScalarField: PrimeField,
+{
+    /// Computes the verifier challenge `e := H(salt || r || msg)`, or `None`
+    /// if the digest does not encode a scalar.
+    fn verifier_challenge(
+        parameters: &SchnorrSigParameters<G, D>,
+        prover_commitment: &G,
+        message: &[u8],
+    ) -> Result<Option<G::ScalarField>, Error> {
+        let mut hash_input = Vec::new();
+        hash_input.extend_from_slice(&parameters.salt);
+        hash_input.extend_from_slice(&to_bytes![prover_commitment]?);
+        hash_input.extend_from_slice(message);
+        Ok(G::ScalarField::from_random_bytes(&D::digest(&hash_input)))
+    }
+
+    /// Signs `message` like `SignatureScheme::sign`, but also returns the
+    /// prover commitment `r`, for verification with `batch_verify`.
+    pub fn sign_batchable<R: Rng>(
+        parameters: &SchnorrSigParameters<G, D>,
+        sk: &SchnorrSecretKey<G>,
+        message: &[u8],
+        rng: &mut R,
+    ) -> Result<SchnorrBatchSig<G>, Error> {
+        let sign_time = start_timer!(|| "SchnorrSig::Sign");
+        // (k, e, r);
+        let (random_scalar, verifier_challenge, prover_commitment) = loop {
+            // Sample a random scalar `k` from the prime scalar field.
+            let random_scalar: G::ScalarField = G::ScalarField::rand(rng);
+            // Commit to the random scalar via r := k · g.
+            // This is the prover's first msg in the Sigma protocol.
+            let prover_commitment: G = parameters.generator.mul(&random_scalar);
+
+            // Compute the supposed verifier response: e := H(salt || r || msg);
+            if let Some(verifier_challenge) =
+                Self::verifier_challenge(parameters, &prover_commitment, message)?
+            {
+                break (random_scalar, verifier_challenge, prover_commitment);
+            };
+        };
+
+        // k - xe;
+        let prover_response = random_scalar - &(verifier_challenge * &sk.0);
+        let signature = SchnorrSig {
+            prover_response,
+            verifier_challenge,
+        };
+
+        end_timer!(sign_time);
+        Ok(SchnorrBatchSig {
+            signature,
+            prover_commitment,
+        })
+    }
+}
+
+impl<G, D> SchnorrSignature<G, D>
+where
+    G: AffineCurve + Group<ScalarField = <G as AffineCurve>::ScalarField>,
+    D: Digest + Send + Sync,
+{
+    /// Verifies the `signatures` by `public_keys` on the respective
+    /// `messages` at once: after checking every challenge, the equations
+    /// `s_i · g + e_i · pk_i == r_i` are combined with random scalars `z_i`
+    /// into a single multi-scalar multiplication
+    /// `(sum_i z_i s_i) · g + sum_i (z_i e_i) · pk_i - sum_i z_i · r_i == 0`.
+    ///
+    /// On curves with a cofactor, the combined equation would hold with
+    /// noticeable probability for signatures that are off by a small order
+    /// point. The result of the multi-scalar multiplication is therefore
+    /// multiplied by the cofactor, so that such signatures are always
+    /// accepted, as in cofactored EdDSA verification, even though `verify`
+    /// rejects them.
+    pub fn batch_verify<R: Rng>(
+        parameters: &SchnorrSigParameters<G, D>,
+        public_keys: &[SchnorrPublicKey<G>],
+        messages: &[&[u8]],
+        signatures: &[SchnorrBatchSig<G>],
+        rng: &mut R,
+    ) -> Result<bool, Error> {
+        let batch_verify_time = start_timer!(|| "SchnorrSig::BatchVerify");
+        if public_keys.len() != messages.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength(messages.len())));
+        }
+        if public_keys.len() != signatures.len() {
+            return Err(Box::new(CryptoError::IncorrectInputLength(
+                signatures.len(),
+            )));
+        }
+
+        let mut bases = Vec::with_capacity(2 * signatures.len() + 1);
+        let mut scalars = Vec::with_capacity(2 * signatures.len() + 1);
+        let mut generator_scalar = <G as Group>::ScalarField::zero();
+        bases.push(parameters.generator);
+        for ((pk, message), batch_sig) in public_keys.iter().zip(messages).zip(signatures) {
+            let SchnorrBatchSig {
+                signature,
+                prover_commitment,
+            } = batch_sig;
+            let obtained_verifier_challenge =
+                Self::verifier_challenge(parameters, prover_commitment, message)?;
+            if obtained_verifier_challenge != Some(signature.verifier_challenge) {
+                return Ok(false);
+            }
+
+            let z = <G as Group>::ScalarField::rand(rng);
+            generator_scalar += &(z * &signature.prover_response);
+            bases.push(*pk);
+            scalars.push((z * &signature.verifier_challenge).into_repr());
+            bases.push(*prover_commitment);
+            scalars.push((-z).into_repr());
+        }
+        scalars.insert(0, generator_scalar.into_repr());
+
+        let combined = VariableBaseMSM::multi_scalar_mul(&bases, &scalars).into_affine();
+        let result = combined.mul_by_cofactor_to_projective().is_zero();
+        end_timer!(batch_verify_time);
+        Ok(result)
+    }
+}
+
 impl<G: Group + Hash, D: Digest + Send + Sync> SignatureScheme for SchnorrSignature<G, D>
 where
     G::ScalarField: PrimeField,
@@ -89,38 +225,7 @@ where
         message: &[u8],
         rng: &mut R,
     ) -> Result<Self::Signature, Error> {
-        let sign_time = start_timer!(|| "SchnorrSig::Sign");
-        // (k, e);
-        let (random_scalar, verifier_challenge) = loop {
-            // Sample a random scalar `k` from the prime scalar field.
-            let random_scalar: G::ScalarField = G::ScalarField::rand(rng);
-            // Commit to the random scalar via r := k · g.
-            // This is the prover's first msg in the Sigma protocol.
-            let prover_commitment: G = parameters.generator.mul(&random_scalar);
-
-            // Hash everything to get verifier challenge.
-            let mut hash_input = Vec::new();
-            hash_input.extend_from_slice(&parameters.salt);
-            hash_input.extend_from_slice(&to_bytes![prover_commitment]?);
-            hash_input.extend_from_slice(message);
-
-            // Compute the supposed verifier response: e := H(salt || r || msg);
-            if let Some(verifier_challenge) =
-                G::ScalarField::from_random_bytes(&D::digest(&hash_input))
-            {
-                break (random_scalar, verifier_challenge);
-            };
-        };
-
-        // k - xe;
-        let prover_response = random_scalar - &(verifier_challenge * &sk.0);
-        let signature = SchnorrSig {
-            prover_response,
-            verifier_challenge,
-        };
-
-        end_timer!(sign_time);
-        Ok(signature)
+        Ok(Self::sign_batchable(parameters, sk, message, rng)?.signature)
     }
 
     fn verify(
@@ -139,13 +244,8 @@ where
         let public_key_times_verifier_challenge = pk.mul(verifier_challenge);
         claimed_prover_commitment += &public_key_times_verifier_challenge;
 
-        let mut hash_input = Vec::new();
-        hash_input.extend_from_slice(&parameters.salt);
-        hash_input.extend_from_slice(&to_bytes![claimed_prover_commitment]?);
-        hash_input.extend_from_slice(&message);
-
         let obtained_verifier_challenge = if let Some(obtained_verifier_challenge) =
-            G::ScalarField::from_random_bytes(&D::digest(&hash_input))
+            Self::verifier_challenge(parameters, &claimed_prover_commitment, message)?
         {
             obtained_verifier_challenge
         } else {
@@ -110,3 +110,95 @@ mod cp6_782 {
         assert!(!verify_proof(&pvk, &proof, &[Fr::zero()]).unwrap());
     }
 }
+
+mod serialization {
+    use super::*;
+    use crate::{
+        create_random_proof, generate_random_parameters, Parameters, Proof, VerifyingKey,
+        SERIALIZATION_VERSION,
+    };
+    use algebra_core::{test_rng, PairingEngine, UniformRand};
+
+    fn round_trip<E: PairingEngine>() {
+        let rng = &mut test_rng();
+
+        let params =
+            generate_random_parameters::<E, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+        let proof = create_random_proof(
+            MySillyCircuit {
+                a: Some(E::Fr::rand(rng)),
+                b: Some(E::Fr::rand(rng)),
+            },
+            &params,
+            rng,
+        )
+        .unwrap();
+
+        let mut bytes = Vec::new();
+        proof.write(&mut bytes).unwrap();
+        assert_eq!(bytes[0], SERIALIZATION_VERSION);
+        assert!(Proof::<E>::read(&bytes[..]).unwrap() == proof);
+
+        let mut bytes = Vec::new();
+        params.vk.write(&mut bytes).unwrap();
+        assert_eq!(bytes[0], SERIALIZATION_VERSION);
+        assert!(VerifyingKey::<E>::read(&bytes[..]).unwrap() == params.vk);
+
+        let mut bytes = Vec::new();
+        params.write(&mut bytes).unwrap();
+        assert_eq!(bytes[0], SERIALIZATION_VERSION);
+        assert!(Parameters::<E>::read(&bytes[..], true).unwrap() == params);
+        assert!(Parameters::<E>::read(&bytes[..], false).unwrap() == params);
+
+        // Truncated encodings and unknown versions are rejected.
+        assert!(Parameters::<E>::read(&bytes[..bytes.len() - 1], false).is_err());
+        bytes[0] = SERIALIZATION_VERSION + 1;
+        assert!(Parameters::<E>::read(&bytes[..], true).is_err());
+    }
+
+    #[test]
+    fn bn254() {
+        round_trip::<algebra::bn254::Bn254>();
+    }
+
+    #[test]
+    fn bls12_377() {
+        round_trip::<algebra::bls12_377::Bls12_377>();
+    }
+
+    #[test]
+    fn bls12_381() {
+        round_trip::<algebra::bls12_381::Bls12_381>();
+    }
+
+    #[test]
+    fn cp6_782() {
+        round_trip::<algebra::cp6_782::CP6_782>();
+    }
+
+    #[test]
+    fn bw6_761() {
+        round_trip::<algebra::bw6_761::BW6_761>();
+    }
+
+    #[test]
+    fn mnt4_298() {
+        round_trip::<algebra::mnt4_298::MNT4_298>();
+    }
+
+    #[test]
+    fn mnt4_753() {
+        round_trip::<algebra::mnt4_753::MNT4_753>();
+    }
+
+    #[test]
+    fn mnt6_298() {
+        round_trip::<algebra::mnt6_298::MNT6_298>();
+    }
+
+    #[test]
+    fn mnt6_753() {
+        round_trip::<algebra::mnt6_753::MNT6_753>();
+    }
+}
@@ -0,0 +1,89 @@
+use crate::same_ratio;
+use algebra_core::{
+    hash_to_curve::HashToCurve, serialize::*, AffineCurve, PairingEngine, PrimeField,
+    ProjectiveCurve, UniformRand, Zero,
+};
+use blake2::Blake2s;
+use rand::Rng;
+
+/// The domain separation tag with which `ProofOfKnowledge` hashes to `G2`.
+const POK_DST: &[u8] = b"ZEXE_GROTH16_SETUP_XMD:BLAKE2S_SSWU_RO_POK_";
+
+/// A proof of knowledge of a secret `x`, for a random `s`: the elements
+/// `sG` and `xsG` of `G1`, and `xH` for the point `H` of `G2` obtained by
+/// hashing them along with the digest of the transcript. Since `H` depends
+/// on the transcript, a proof cannot be replayed in another contribution.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = "E: PairingEngine"),
+    Debug(bound = "E: PairingEngine"),
+    PartialEq(bound = "E: PairingEngine")
+)]
+pub struct ProofOfKnowledge<E: PairingEngine> {
+    pub g1_s: E::G1Affine,
+    pub g1_s_x: E::G1Affine,
+    pub g2_s_x: E::G2Affine,
+}
+
+impl<E: PairingEngine> ProofOfKnowledge<E>
+where
+    E::G2Affine: HashToCurve,
+{
+    /// Proves the knowledge of `x` for the transcript with the digest
+    /// `digest`. The `personalization` distinguishes the several secrets of
+    /// a contribution.
+    pub(crate) fn new<R: Rng>(x: E::Fr, digest: &[u8], personalization: u8, rng: &mut R) -> Self {
+        let g1_s = E::G1Projective::rand(rng).into_affine();
+        let g1_s_x = g1_s.mul(x.into_repr()).into_affine();
+        let g2_s = Self::hash_to_g2(g1_s, g1_s_x, digest, personalization);
+        Self {
+            g1_s,
+            g1_s_x,
+            g2_s_x: g2_s.mul(x.into_repr()).into_affine(),
+        }
+    }
+
+    fn hash_to_g2(
+        g1_s: E::G1Affine,
+        g1_s_x: E::G1Affine,
+        digest: &[u8],
+        personalization: u8,
+    ) -> E::G2Affine {
+        let mut msg = digest.to_vec();
+        msg.push(personalization);
+        (g1_s, g1_s_x)
+            .serialize(&mut msg)
+            .expect("serializing to a vector does not fail");
+        E::G2Affine::hash_to_curve::<Blake2s>(&msg, POK_DST)
+    }
+
+    /// The point `H` of `G2`, such that the proof holds `xH`.
+    pub(crate) fn g2_s(&self, digest: &[u8], personalization: u8) -> E::G2Affine {
+        Self::hash_to_g2(self.g1_s, self.g1_s_x, digest, personalization)
+    }
+
+    /// Checks the proof for the transcript with the digest `digest`.
+    pub fn verify(&self, digest: &[u8], personalization: u8) -> bool {
+        !self.g1_s.is_zero()
+            && !self.g1_s_x.is_zero()
+            && same_ratio::<E>(
+                (self.g1_s, self.g1_s_x),
+                (self.g2_s(digest, personalization), self.g2_s_x),
+            )
+    }
+
+    /// Checks the proof, and that `after` is `x` times `before`.
+    pub(crate) fn verify_update(
+        &self,
+        before: E::G1Affine,
+        after: E::G1Affine,
+        digest: &[u8],
+        personalization: u8,
+    ) -> bool {
+        self.verify(digest, personalization)
+            && same_ratio::<E>(
+                (before, after),
+                (self.g2_s(digest, personalization), self.g2_s_x),
+            )
+    }
+}
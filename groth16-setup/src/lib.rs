@@ -0,0 +1,163 @@
+//! A multi-party trusted setup ceremony for Groth16 parameters, after
+//! [`BGM17`], in which the parameters are secure as long as one of the
+//! participants discards their share of the toxic waste.
+//!
+//! The ceremony has two phases. In the first one, the participants take
+//! turns to update an [`Accumulator`] of the powers `τ^i`, `ατ^i` and `βτ^i`
+//! in the exponent, which does not depend on any circuit. In the second one,
+//! the accumulator is specialized into the [`MPCParameters`] of a circuit,
+//! and the participants take turns to update `δ`. Each contribution comes
+//! with proofs of knowledge of the participant's secrets, bound to the
+//! transcript so far, which the transcript verifiers check along with the
+//! consistency of the updated elements.
+//!
+//! Every step reads its input from a file and writes its output to another
+//! one, with [`read_from_file`] and [`write_to_file`], so that participants
+//! can contribute on machines that stay offline.
+//!
+//! [`BGM17`]: https://eprint.iacr.org/2017/1050
+#![deny(unused_import_braces, unused_qualifications, trivial_casts)]
+#![deny(trivial_numeric_casts, private_in_public, variant_size_differences)]
+#![deny(stable_features, unreachable_pub, non_shorthand_field_patterns)]
+#![deny(unused_attributes, unused_imports, unused_mut)]
+#![deny(renamed_and_removed_lints, stable_features, unused_allocation)]
+#![deny(unused_comparisons, bare_trait_objects, unused_must_use, const_err)]
+#![forbid(unsafe_code)]
+
+#[macro_use]
+extern crate bench_utils;
+
+#[macro_use]
+extern crate derivative;
+
+use algebra_core::{
+    msm::VariableBaseMSM, serialize::*, AffineCurve, One, PairingEngine, PrimeField,
+    ProjectiveCurve, UniformRand,
+};
+use blake2::{Blake2s, Digest};
+use ff_fft::cfg_iter;
+use r1cs_core::SynthesisError;
+use rand::Rng;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+mod keypair;
+pub use keypair::ProofOfKnowledge;
+
+/// The first phase of the ceremony, which does not depend on the circuit.
+pub mod phase1;
+pub use phase1::Accumulator;
+
+/// The second phase of the ceremony, for a given circuit.
+pub mod phase2;
+pub use phase2::MPCParameters;
+
+#[cfg(test)]
+mod test;
+
+/// The errors of the setup ceremony.
+#[derive(Debug)]
+pub enum Error {
+    /// An accumulator must hold at least one power of `τ`.
+    InvalidDegree(usize),
+    /// The circuit needs an evaluation domain larger than the degree of the
+    /// accumulator.
+    TooManyConstraints { domain_size: usize, degree: usize },
+    /// The contribution with the given index is invalid.
+    InvalidContribution(usize),
+    /// The accumulator is not consistent with its contributions.
+    InvalidAccumulator,
+    /// The parameters are not consistent with the circuit or with their
+    /// contributions.
+    InvalidParameters,
+    /// Reading or writing a file failed.
+    SerializationError(SerializationError),
+    /// Synthesizing the circuit failed.
+    SynthesisError(SynthesisError),
+}
+
+impl From<SerializationError> for Error {
+    fn from(error: SerializationError) -> Self {
+        Error::SerializationError(error)
+    }
+}
+
+impl From<SynthesisError> for Error {
+    fn from(error: SynthesisError) -> Self {
+        Error::SynthesisError(error)
+    }
+}
+
+/// Reads a value, such as an [`Accumulator`] or [`MPCParameters`], from the
+/// file at `path`. All group elements are checked to lie in the prime order
+/// subgroup.
+pub fn read_from_file<T: CanonicalDeserialize, P: AsRef<Path>>(path: P) -> Result<T, Error> {
+    let file = File::open(path).map_err(SerializationError::IoError)?;
+    Ok(T::deserialize(&mut BufReader::new(file))?)
+}
+
+/// Writes a value, such as an [`Accumulator`] or [`MPCParameters`], to the
+/// file at `path`, replacing its contents.
+pub fn write_to_file<T: CanonicalSerialize, P: AsRef<Path>>(
+    value: &T,
+    path: P,
+) -> Result<(), Error> {
+    let file = File::create(path).map_err(SerializationError::IoError)?;
+    Ok(value.serialize(&mut BufWriter::new(file))?)
+}
+
+/// The digest of the transcript after `message` is appended to the
+/// transcript with the digest `previous`.
+pub(crate) fn next_digest<T: CanonicalSerialize>(previous: &[u8], message: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(message.serialized_size());
+    message
+        .serialize(&mut bytes)
+        .expect("serializing to a vector does not fail");
+    let mut hasher = Blake2s::new();
+    hasher.input(previous);
+    hasher.input(&bytes);
+    hasher.result().to_vec()
+}
+
+/// Checks that `g1.1 / g1.0 == g2.1 / g2.0`, that is
+/// `e(g1.0, g2.1) == e(g1.1, g2.0)`.
+pub(crate) fn same_ratio<E: PairingEngine>(
+    g1: (E::G1Affine, E::G1Affine),
+    g2: (E::G2Affine, E::G2Affine),
+) -> bool {
+    E::product_of_pairings(&[(g1.0.into(), g2.1.into()), ((-g1.1).into(), g2.0.into())]).is_one()
+}
+
+/// Computes `(sum_i r_i a_i, sum_i r_i b_i)` for random `r_i`, which have
+/// the same ratio as every pair `(a_i, b_i)` when those all do, and
+/// otherwise only with negligible probability.
+pub(crate) fn merge_pairs<G: AffineCurve, R: Rng>(a: &[G], b: &[G], rng: &mut R) -> (G, G) {
+    let randomizers = (0..a.len())
+        .map(|_| G::ScalarField::rand(rng).into_repr())
+        .collect::<Vec<_>>();
+    let a = VariableBaseMSM::multi_scalar_mul(a, &randomizers);
+    let b = VariableBaseMSM::multi_scalar_mul(b, &randomizers);
+    (a.into_affine(), b.into_affine())
+}
+
+/// Computes `merge_pairs` for the consecutive elements of `v`, which have
+/// the same ratio as `(G, xG)` when `v` holds the successive powers of `x`
+/// in the exponent.
+pub(crate) fn power_pairs<G: AffineCurve, R: Rng>(v: &[G], rng: &mut R) -> (G, G) {
+    merge_pairs(&v[..v.len() - 1], &v[1..], rng)
+}
+
+/// Multiplies `v_i` by `scalars_i`.
+pub(crate) fn scale<G: AffineCurve>(v: &[G], scalars: &[G::ScalarField]) -> Vec<G> {
+    let scaled = cfg_iter!(v)
+        .zip(scalars)
+        .map(|(g, s)| g.mul(s.into_repr()))
+        .collect::<Vec<_>>();
+    G::Projective::batch_normalization_into_affine(&scaled)
+}
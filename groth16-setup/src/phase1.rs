@@ -0,0 +1,274 @@
+//! The accumulator of the first phase holds, for the combined secrets `τ`,
+//! `α` and `β` of all participants so far, the powers `τ^i G` for
+//! `i < 2m - 1`, `τ^i H`, `ατ^i G` and `βτ^i G` for `i < m`, and `βH`,
+//! where `m` is its degree. A participant multiplies all of them by the
+//! matching powers of their own secrets.
+use crate::{next_digest, power_pairs, same_ratio, scale, Error, ProofOfKnowledge};
+use algebra_core::{
+    hash_to_curve::HashToCurve, serialize::*, AffineCurve, One, PairingEngine, PrimeField,
+    ProjectiveCurve, UniformRand, Zero,
+};
+use rand::Rng;
+
+const TAU: u8 = 0;
+const ALPHA: u8 = 1;
+const BETA: u8 = 2;
+
+/// The contribution of one participant: the values `τG`, `αG` and `βG` of
+/// the accumulator after it, and the proofs of knowledge of the factors by
+/// which the participant multiplied them.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = "E: PairingEngine"),
+    Debug(bound = "E: PairingEngine"),
+    PartialEq(bound = "E: PairingEngine")
+)]
+pub struct PublicKey<E: PairingEngine> {
+    pub tau_g1: E::G1Affine,
+    pub alpha_g1: E::G1Affine,
+    pub beta_g1: E::G1Affine,
+    pub tau_pok: ProofOfKnowledge<E>,
+    pub alpha_pok: ProofOfKnowledge<E>,
+    pub beta_pok: ProofOfKnowledge<E>,
+}
+
+/// The accumulator of the powers of `τ`, along with the contributions that
+/// produced it.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Accumulator<E: PairingEngine> {
+    /// `τ^i G` for `i < 2m - 1`.
+    pub tau_powers_g1: Vec<E::G1Affine>,
+    /// `τ^i H` for `i < m`.
+    pub tau_powers_g2: Vec<E::G2Affine>,
+    /// `ατ^i G` for `i < m`.
+    pub alpha_tau_powers_g1: Vec<E::G1Affine>,
+    /// `βτ^i G` for `i < m`.
+    pub beta_tau_powers_g1: Vec<E::G1Affine>,
+    /// `βH`.
+    pub beta_g2: E::G2Affine,
+    pub contributions: Vec<PublicKey<E>>,
+}
+
+impl<E: PairingEngine> Accumulator<E>
+where
+    E::G2Affine: HashToCurve,
+{
+    /// The accumulator of degree `degree` before any contribution, for
+    /// which all secrets are `1`. It supports circuits whose evaluation
+    /// domain has at most `degree` elements, and `degree` must be at least
+    /// `2`.
+    pub fn new(degree: usize) -> Result<Self, Error> {
+        if degree < 2 {
+            return Err(Error::InvalidDegree(degree));
+        }
+        let g1 = E::G1Affine::prime_subgroup_generator();
+        let g2 = E::G2Affine::prime_subgroup_generator();
+        Ok(Self {
+            tau_powers_g1: vec![g1; 2 * degree - 1],
+            tau_powers_g2: vec![g2; degree],
+            alpha_tau_powers_g1: vec![g1; degree],
+            beta_tau_powers_g1: vec![g1; degree],
+            beta_g2: g2,
+            contributions: Vec::new(),
+        })
+    }
+
+    /// The number `m` of powers of `τ` in `G2`.
+    pub fn degree(&self) -> usize {
+        self.tau_powers_g2.len()
+    }
+
+    fn initial_digest(degree: usize) -> Vec<u8> {
+        next_digest(b"groth16-setup-phase1", &degree)
+    }
+
+    /// The digest of the transcript, to which the next contribution is
+    /// bound.
+    pub fn digest(&self) -> Vec<u8> {
+        self.contributions
+            .iter()
+            .fold(Self::initial_digest(self.degree()), |digest, key| {
+                next_digest(&digest, key)
+            })
+    }
+
+    /// Multiplies the accumulator by fresh secrets, which are dropped
+    /// afterwards, and records the contribution. Returns the digest of the
+    /// transcript after the contribution, which the participant publishes
+    /// to find their contribution in the verified transcript.
+    pub fn contribute<R: Rng>(&mut self, rng: &mut R) -> Vec<u8> {
+        let contribute_time = start_timer!(|| "Phase 1 contribution");
+        let digest = self.digest();
+        let tau = nonzero_scalar::<E, _>(rng);
+        let alpha = nonzero_scalar::<E, _>(rng);
+        let beta = nonzero_scalar::<E, _>(rng);
+
+        let mut tau_powers = Vec::with_capacity(self.tau_powers_g1.len());
+        let mut power = E::Fr::one();
+        for _ in 0..self.tau_powers_g1.len() {
+            tau_powers.push(power);
+            power *= &tau;
+        }
+        let degree = self.degree();
+        let alpha_tau_powers = tau_powers[..degree]
+            .iter()
+            .map(|p| *p * &alpha)
+            .collect::<Vec<_>>();
+        let beta_tau_powers = tau_powers[..degree]
+            .iter()
+            .map(|p| *p * &beta)
+            .collect::<Vec<_>>();
+
+        self.tau_powers_g1 = scale(&self.tau_powers_g1, &tau_powers);
+        self.tau_powers_g2 = scale(&self.tau_powers_g2, &tau_powers[..degree]);
+        self.alpha_tau_powers_g1 = scale(&self.alpha_tau_powers_g1, &alpha_tau_powers);
+        self.beta_tau_powers_g1 = scale(&self.beta_tau_powers_g1, &beta_tau_powers);
+        self.beta_g2 = self.beta_g2.mul(beta.into_repr()).into_affine();
+
+        let key = PublicKey {
+            tau_g1: self.tau_g1(),
+            alpha_g1: self.alpha_tau_powers_g1[0],
+            beta_g1: self.beta_tau_powers_g1[0],
+            tau_pok: ProofOfKnowledge::new(tau, &digest, TAU, rng),
+            alpha_pok: ProofOfKnowledge::new(alpha, &digest, ALPHA, rng),
+            beta_pok: ProofOfKnowledge::new(beta, &digest, BETA, rng),
+        };
+        let digest = next_digest(&digest, &key);
+        self.contributions.push(key);
+        end_timer!(contribute_time);
+        digest
+    }
+
+    /// Verifies the whole transcript: every contribution, and that the
+    /// accumulator holds the powers of the secrets that they combine into.
+    /// Returns the digests of the transcript after each contribution.
+    pub fn verify<R: Rng>(&self, rng: &mut R) -> Result<Vec<Vec<u8>>, Error> {
+        let verify_time = start_timer!(|| "Phase 1 transcript verification");
+        let degree = self.degree();
+        if !self.has_valid_lengths() {
+            return Err(Error::InvalidAccumulator);
+        }
+        let g1 = E::G1Affine::prime_subgroup_generator();
+        let mut before = (g1, g1, g1);
+        let mut digest = Self::initial_digest(degree);
+        let mut digests = Vec::with_capacity(self.contributions.len());
+        for (i, key) in self.contributions.iter().enumerate() {
+            if !key.verify(before, &digest) {
+                return Err(Error::InvalidContribution(i));
+            }
+            before = (key.tau_g1, key.alpha_g1, key.beta_g1);
+            digest = next_digest(&digest, key);
+            digests.push(digest.clone());
+        }
+        if before
+            != (
+                self.tau_g1(),
+                self.alpha_tau_powers_g1[0],
+                self.beta_tau_powers_g1[0],
+            )
+            || !self.is_well_formed(rng)
+        {
+            return Err(Error::InvalidAccumulator);
+        }
+        end_timer!(verify_time);
+        Ok(digests)
+    }
+
+    /// Verifies that `after` is `before` with one more valid contribution,
+    /// assuming that `before` is valid. Returns the digest of the
+    /// transcript after the contribution.
+    pub fn verify_contribution<R: Rng>(
+        before: &Self,
+        after: &Self,
+        rng: &mut R,
+    ) -> Result<Vec<u8>, Error> {
+        let num_contributions = before.contributions.len();
+        if !before.has_valid_lengths()
+            || !after.has_valid_lengths()
+            || after.degree() != before.degree()
+            || after.contributions.len() != num_contributions + 1
+            || after.contributions[..num_contributions] != before.contributions[..]
+        {
+            return Err(Error::InvalidAccumulator);
+        }
+        let key = &after.contributions[num_contributions];
+        let digest = before.digest();
+        let previous = (
+            before.tau_g1(),
+            before.alpha_tau_powers_g1[0],
+            before.beta_tau_powers_g1[0],
+        );
+        if !key.verify(previous, &digest) {
+            return Err(Error::InvalidContribution(num_contributions));
+        }
+        if (key.tau_g1, key.alpha_g1, key.beta_g1)
+            != (
+                after.tau_g1(),
+                after.alpha_tau_powers_g1[0],
+                after.beta_tau_powers_g1[0],
+            )
+            || !after.is_well_formed(rng)
+        {
+            return Err(Error::InvalidAccumulator);
+        }
+        Ok(next_digest(&digest, key))
+    }
+
+    fn tau_g1(&self) -> E::G1Affine {
+        self.tau_powers_g1[1]
+    }
+
+    fn has_valid_lengths(&self) -> bool {
+        let degree = self.degree();
+        degree >= 2
+            && self.tau_powers_g1.len() == 2 * degree - 1
+            && self.alpha_tau_powers_g1.len() == degree
+            && self.beta_tau_powers_g1.len() == degree
+    }
+
+    /// Checks that the accumulator, whose lengths are valid, holds the
+    /// powers of some `τ`, `α` and `β` in the exponent.
+    fn is_well_formed<R: Rng>(&self, rng: &mut R) -> bool {
+        let g1 = E::G1Affine::prime_subgroup_generator();
+        let g2 = E::G2Affine::prime_subgroup_generator();
+        if self.tau_powers_g1[0] != g1 || self.tau_powers_g2[0] != g2 {
+            return false;
+        }
+        let tau_g1 = (g1, self.tau_powers_g1[1]);
+        let tau_g2 = (g2, self.tau_powers_g2[1]);
+        same_ratio::<E>(tau_g1, tau_g2)
+            && same_ratio::<E>((g1, self.beta_tau_powers_g1[0]), (g2, self.beta_g2))
+            && same_ratio::<E>(power_pairs(&self.tau_powers_g1, rng), tau_g2)
+            && same_ratio::<E>(tau_g1, power_pairs(&self.tau_powers_g2, rng))
+            && same_ratio::<E>(power_pairs(&self.alpha_tau_powers_g1, rng), tau_g2)
+            && same_ratio::<E>(power_pairs(&self.beta_tau_powers_g1, rng), tau_g2)
+    }
+}
+
+impl<E: PairingEngine> PublicKey<E>
+where
+    E::G2Affine: HashToCurve,
+{
+    /// Checks the proofs of knowledge, and that the values `τG`, `αG` and
+    /// `βG` were multiplied by the proven secrets.
+    fn verify(&self, before: (E::G1Affine, E::G1Affine, E::G1Affine), digest: &[u8]) -> bool {
+        self.tau_pok
+            .verify_update(before.0, self.tau_g1, digest, TAU)
+            && self
+                .alpha_pok
+                .verify_update(before.1, self.alpha_g1, digest, ALPHA)
+            && self
+                .beta_pok
+                .verify_update(before.2, self.beta_g1, digest, BETA)
+    }
+}
+
+/// Samples a nonzero secret.
+pub(crate) fn nonzero_scalar<E: PairingEngine, R: Rng>(rng: &mut R) -> E::Fr {
+    loop {
+        let x = E::Fr::rand(rng);
+        if !x.is_zero() {
+            return x;
+        }
+    }
+}
@@ -0,0 +1,365 @@
+//! The parameters of the second phase are the Groth16 parameters of a
+//! circuit for the secrets `τ`, `α` and `β` of the accumulator, with
+//! `γ = 1` and a secret `δ` that starts at `1`. A participant multiplies `δ`
+//! by their own secret `δ'`, which multiplies `δG` and `δH` by `δ'`, and the
+//! elements of the H-query and of the L-query, which are divided by `δ`, by
+//! `1 / δ'`.
+use crate::{
+    merge_pairs, next_digest, phase1::nonzero_scalar, same_ratio, Accumulator, Error,
+    ProofOfKnowledge,
+};
+use algebra_core::{
+    hash_to_curve::HashToCurve, serialize::*, AffineCurve, Field, One, PairingEngine, PrimeField,
+    ProjectiveCurve, Zero,
+};
+use ff_fft::{cfg_chunks_mut, cfg_iter, cfg_iter_mut, EvaluationDomain, Radix2EvaluationDomain};
+use groth16::{KeypairAssembly, Parameters, VerifyingKey};
+use r1cs_core::{ConstraintSynthesizer, ConstraintSystem, Index};
+use rand::Rng;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+const DELTA: u8 = 0;
+
+/// The contribution of one participant: the value `δG` of the parameters
+/// after it, and the proof of knowledge of the factor by which the
+/// participant multiplied it.
+#[derive(Derivative, CanonicalSerialize, CanonicalDeserialize)]
+#[derivative(
+    Clone(bound = "E: PairingEngine"),
+    Debug(bound = "E: PairingEngine"),
+    PartialEq(bound = "E: PairingEngine")
+)]
+pub struct PublicKey<E: PairingEngine> {
+    pub delta_g1: E::G1Affine,
+    pub delta_pok: ProofOfKnowledge<E>,
+}
+
+/// The Groth16 parameters of a circuit, along with the contributions that
+/// produced them.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MPCParameters<E: PairingEngine> {
+    pub params: Parameters<E>,
+    /// The digest of the parameters before any contribution, which binds
+    /// the transcript to the circuit and to the accumulator.
+    pub cs_hash: Vec<u8>,
+    pub contributions: Vec<PublicKey<E>>,
+}
+
+impl<E: PairingEngine> MPCParameters<E>
+where
+    E::G2Affine: HashToCurve,
+{
+    /// Specializes the accumulator, which must have been verified, into the
+    /// parameters of `circuit` before any contribution.
+    pub fn new<C: ConstraintSynthesizer<E::Fr>>(
+        circuit: C,
+        accumulator: &Accumulator<E>,
+    ) -> Result<Self, Error> {
+        let new_time = start_timer!(|| "Phase 2 parameters");
+        let mut assembly = KeypairAssembly::<E> {
+            num_inputs: 0,
+            num_aux: 0,
+            num_constraints: 0,
+            at: vec![],
+            bt: vec![],
+            ct: vec![],
+        };
+
+        // Allocate the "one" input variable
+        assembly.alloc_input(|| "", || Ok(E::Fr::one()))?;
+
+        let synthesis_time = start_timer!(|| "Constraint synthesis");
+        circuit.generate_constraints(&mut assembly)?;
+        end_timer!(synthesis_time);
+
+        // This is the domain that `GeneralEvaluationDomain` picks whenever a
+        // radix-2 domain of that size exists.
+        let domain =
+            Radix2EvaluationDomain::<E::Fr>::new(assembly.num_constraints + assembly.num_inputs)
+                .ok_or(r1cs_core::SynthesisError::PolynomialDegreeTooLarge)?;
+        let domain_size = domain.size();
+        if domain_size > accumulator.degree() {
+            return Err(Error::TooManyConstraints {
+                domain_size,
+                degree: accumulator.degree(),
+            });
+        }
+
+        // The Lagrange polynomials of the domain, evaluated at `τ`.
+        let lagrange_time = start_timer!(|| "Evaluate Lagrange coefficients");
+        let coeffs_g1 = lagrange_coefficients(&accumulator.tau_powers_g1[..domain_size], &domain);
+        let coeffs_g2 = lagrange_coefficients(&accumulator.tau_powers_g2[..domain_size], &domain);
+        let alpha_coeffs_g1 =
+            lagrange_coefficients(&accumulator.alpha_tau_powers_g1[..domain_size], &domain);
+        let beta_coeffs_g1 =
+            lagrange_coefficients(&accumulator.beta_tau_powers_g1[..domain_size], &domain);
+        end_timer!(lagrange_time);
+
+        let num_variables = assembly.num_inputs + assembly.num_aux;
+        let mut a_query = vec![E::G1Projective::zero(); num_variables];
+        let mut b_g1_query = vec![E::G1Projective::zero(); num_variables];
+        let mut b_g2_query = vec![E::G2Projective::zero(); num_variables];
+        let mut ext = vec![E::G1Projective::zero(); num_variables];
+
+        // As in the instance map of the QAP, the input `i` is also the left
+        // input of the constraint `num_constraints + i`.
+        for i in 0..assembly.num_inputs {
+            a_query[i].add_assign_mixed(&coeffs_g1[assembly.num_constraints + i]);
+            ext[i].add_assign_mixed(&beta_coeffs_g1[assembly.num_constraints + i]);
+        }
+
+        let query_time = start_timer!(|| "Calculate queries");
+        let num_inputs = assembly.num_inputs;
+        let variable = |index: Index| match index {
+            Index::Input(i) => i,
+            Index::Aux(i) => num_inputs + i,
+        };
+        for i in 0..assembly.num_constraints {
+            for &(coeff, index) in assembly.at[i].iter() {
+                let coeff = coeff.into_repr();
+                a_query[variable(index)] += &coeffs_g1[i].mul(coeff);
+                ext[variable(index)] += &beta_coeffs_g1[i].mul(coeff);
+            }
+            for &(coeff, index) in assembly.bt[i].iter() {
+                let coeff = coeff.into_repr();
+                b_g1_query[variable(index)] += &coeffs_g1[i].mul(coeff);
+                b_g2_query[variable(index)] += &coeffs_g2[i].mul(coeff);
+                ext[variable(index)] += &alpha_coeffs_g1[i].mul(coeff);
+            }
+            for &(coeff, index) in assembly.ct[i].iter() {
+                let coeff = coeff.into_repr();
+                ext[variable(index)] += &coeffs_g1[i].mul(coeff);
+            }
+        }
+
+        // `τ^i t(τ) G = τ^(i + n) G - τ^i G` for the vanishing polynomial
+        // `t(X) = X^n - 1` of the domain.
+        let h_query = cfg_iter!(accumulator.tau_powers_g1[domain_size..2 * domain_size - 1])
+            .zip(&accumulator.tau_powers_g1[..domain_size - 1])
+            .map(|(high, low)| high.into_projective() - &low.into_projective())
+            .collect::<Vec<_>>();
+        end_timer!(query_time);
+
+        let gamma_abc_g1 = ext[..num_inputs].to_vec();
+        let l_query = ext[num_inputs..].to_vec();
+        let g1 = E::G1Affine::prime_subgroup_generator();
+        let g2 = E::G2Affine::prime_subgroup_generator();
+        let params = Parameters {
+            vk: VerifyingKey {
+                alpha_g1: accumulator.alpha_tau_powers_g1[0],
+                beta_g2: accumulator.beta_g2,
+                gamma_g2: g2,
+                delta_g2: g2,
+                gamma_abc_g1: E::G1Projective::batch_normalization_into_affine(&gamma_abc_g1),
+            },
+            beta_g1: accumulator.beta_tau_powers_g1[0],
+            delta_g1: g1,
+            a_query: E::G1Projective::batch_normalization_into_affine(&a_query),
+            b_g1_query: E::G1Projective::batch_normalization_into_affine(&b_g1_query),
+            b_g2_query: E::G2Projective::batch_normalization_into_affine(&b_g2_query),
+            h_query: E::G1Projective::batch_normalization_into_affine(&h_query),
+            l_query: E::G1Projective::batch_normalization_into_affine(&l_query),
+        };
+        end_timer!(new_time);
+
+        Ok(Self {
+            cs_hash: next_digest(b"groth16-setup-phase2", &params),
+            params,
+            contributions: Vec::new(),
+        })
+    }
+
+    /// The digest of the transcript, to which the next contribution is
+    /// bound.
+    pub fn digest(&self) -> Vec<u8> {
+        self.contributions
+            .iter()
+            .fold(self.cs_hash.clone(), |digest, key| {
+                next_digest(&digest, key)
+            })
+    }
+
+    /// Multiplies `δ` by a fresh secret, which is dropped afterwards, and
+    /// records the contribution. Returns the digest of the transcript after
+    /// the contribution, which the participant publishes to find their
+    /// contribution in the verified transcript.
+    pub fn contribute<R: Rng>(&mut self, rng: &mut R) -> Vec<u8> {
+        let contribute_time = start_timer!(|| "Phase 2 contribution");
+        let digest = self.digest();
+        let delta = nonzero_scalar::<E, _>(rng);
+        let delta_inverse = delta.inverse().unwrap().into_repr();
+
+        let scale = |v: &mut Vec<E::G1Affine>| {
+            let scaled = cfg_iter!(v)
+                .map(|g| g.mul(delta_inverse))
+                .collect::<Vec<_>>();
+            *v = E::G1Projective::batch_normalization_into_affine(&scaled);
+        };
+        scale(&mut self.params.h_query);
+        scale(&mut self.params.l_query);
+        self.params.delta_g1 = self.params.delta_g1.mul(delta.into_repr()).into_affine();
+        self.params.vk.delta_g2 = self.params.vk.delta_g2.mul(delta.into_repr()).into_affine();
+
+        let key = PublicKey {
+            delta_g1: self.params.delta_g1,
+            delta_pok: ProofOfKnowledge::new(delta, &digest, DELTA, rng),
+        };
+        let digest = next_digest(&digest, &key);
+        self.contributions.push(key);
+        end_timer!(contribute_time);
+        digest
+    }
+
+    /// Verifies the whole transcript: that the parameters before any
+    /// contribution are those of `circuit` for the accumulator, which must
+    /// have been verified, every contribution, and that the parameters
+    /// were updated with the secrets that they combine into. Returns the
+    /// digests of the transcript after each contribution.
+    pub fn verify<C: ConstraintSynthesizer<E::Fr>, R: Rng>(
+        &self,
+        circuit: C,
+        accumulator: &Accumulator<E>,
+        rng: &mut R,
+    ) -> Result<Vec<Vec<u8>>, Error> {
+        let verify_time = start_timer!(|| "Phase 2 transcript verification");
+        let initial = Self::new(circuit, accumulator)?;
+        if initial.cs_hash != self.cs_hash {
+            return Err(Error::InvalidParameters);
+        }
+        let mut before = initial.params.delta_g1;
+        let mut digest = initial.cs_hash.clone();
+        let mut digests = Vec::with_capacity(self.contributions.len());
+        for (i, key) in self.contributions.iter().enumerate() {
+            if !key.verify(before, &digest) {
+                return Err(Error::InvalidContribution(i));
+            }
+            before = key.delta_g1;
+            digest = next_digest(&digest, key);
+            digests.push(digest.clone());
+        }
+        if before != self.params.delta_g1 || !is_update(&initial.params, &self.params, rng) {
+            return Err(Error::InvalidParameters);
+        }
+        end_timer!(verify_time);
+        Ok(digests)
+    }
+
+    /// Verifies that `after` is `before` with one more valid contribution,
+    /// assuming that `before` is valid. Returns the digest of the
+    /// transcript after the contribution.
+    pub fn verify_contribution<R: Rng>(
+        before: &Self,
+        after: &Self,
+        rng: &mut R,
+    ) -> Result<Vec<u8>, Error> {
+        let num_contributions = before.contributions.len();
+        if after.cs_hash != before.cs_hash
+            || after.contributions.len() != num_contributions + 1
+            || after.contributions[..num_contributions] != before.contributions[..]
+        {
+            return Err(Error::InvalidParameters);
+        }
+        let key = &after.contributions[num_contributions];
+        let digest = before.digest();
+        if !key.verify(before.params.delta_g1, &digest) {
+            return Err(Error::InvalidContribution(num_contributions));
+        }
+        if key.delta_g1 != after.params.delta_g1 || !is_update(&before.params, &after.params, rng) {
+            return Err(Error::InvalidParameters);
+        }
+        Ok(next_digest(&digest, key))
+    }
+}
+
+impl<E: PairingEngine> PublicKey<E>
+where
+    E::G2Affine: HashToCurve,
+{
+    /// Checks the proof of knowledge, and that `δG` was multiplied by the
+    /// proven secret.
+    fn verify(&self, before: E::G1Affine, digest: &[u8]) -> bool {
+        self.delta_pok
+            .verify_update(before, self.delta_g1, digest, DELTA)
+    }
+}
+
+/// Checks that `after` only differs from `before` by a change of `δ`:
+/// `δG` and `δH` are multiplied by the same factor, the H-query and the
+/// L-query are divided by it, and all other elements are unchanged.
+fn is_update<E: PairingEngine, R: Rng>(
+    before: &Parameters<E>,
+    after: &Parameters<E>,
+    rng: &mut R,
+) -> bool {
+    if before.vk.alpha_g1 != after.vk.alpha_g1
+        || before.vk.beta_g2 != after.vk.beta_g2
+        || before.vk.gamma_g2 != after.vk.gamma_g2
+        || before.vk.gamma_abc_g1 != after.vk.gamma_abc_g1
+        || before.beta_g1 != after.beta_g1
+        || before.a_query != after.a_query
+        || before.b_g1_query != after.b_g1_query
+        || before.b_g2_query != after.b_g2_query
+        || before.h_query.len() != after.h_query.len()
+        || before.l_query.len() != after.l_query.len()
+        || after.delta_g1.is_zero()
+    {
+        return false;
+    }
+    let delta_g2 = (before.vk.delta_g2, after.vk.delta_g2);
+    let (h_after, h_before) = merge_pairs(&after.h_query, &before.h_query, rng);
+    let (l_after, l_before) = merge_pairs(&after.l_query, &before.l_query, rng);
+    same_ratio::<E>((before.delta_g1, after.delta_g1), delta_g2)
+        && same_ratio::<E>((h_after, h_before), delta_g2)
+        && same_ratio::<E>((l_after, l_before), delta_g2)
+}
+
+/// Computes `L_j(τ) P` for the Lagrange polynomials `L_j` of the domain,
+/// given the powers `τ^i P`, with an inverse FFT in the group:
+/// `L_j(τ) = (1 / n) sum_i ω^(-ij) τ^i`.
+fn lagrange_coefficients<G: AffineCurve>(
+    powers: &[G],
+    domain: &Radix2EvaluationDomain<G::ScalarField>,
+) -> Vec<G> {
+    let mut a = powers
+        .iter()
+        .map(AffineCurve::into_projective)
+        .collect::<Vec<_>>();
+    let n = a.len();
+    let log_n = domain.log_size_of_group;
+    for k in 0..n {
+        let rk = bitreverse(k as u32, log_n) as usize;
+        if k < rk {
+            a.swap(k, rk);
+        }
+    }
+
+    let mut m = 1;
+    for _ in 0..log_n {
+        let w_m = domain.group_gen_inv.pow(&[(n / (2 * m)) as u64]);
+        cfg_chunks_mut!(a, 2 * m).for_each(|chunk| {
+            let mut w = G::ScalarField::one();
+            for j in 0..m {
+                let t = chunk[j + m].mul(w.into_repr());
+                chunk[j + m] = chunk[j] - &t;
+                chunk[j] += &t;
+                w *= &w_m;
+            }
+        });
+        m *= 2;
+    }
+
+    let size_inv = domain.size_inv.into_repr();
+    cfg_iter_mut!(a).for_each(|g| *g = g.mul(size_inv));
+    G::Projective::batch_normalization_into_affine(&a)
+}
+
+fn bitreverse(mut n: u32, l: u32) -> u32 {
+    let mut r = 0;
+    for _ in 0..l {
+        r = (r << 1) | (n & 1);
+        n >>= 1;
+    }
+    r
+}
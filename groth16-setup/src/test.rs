@@ -0,0 +1,251 @@
+use algebra_core::Field;
+use r1cs_core::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+
+struct MySillyCircuit<F: Field> {
+    a: Option<F>,
+    b: Option<F>,
+}
+
+impl<ConstraintF: Field> ConstraintSynthesizer<ConstraintF> for MySillyCircuit<ConstraintF> {
+    fn generate_constraints<CS: ConstraintSystem<ConstraintF>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
+        let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
+        let c = cs.alloc_input(
+            || "c",
+            || {
+                let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+                let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
+
+                a.mul_assign(&b);
+                Ok(a)
+            },
+        )?;
+        let d = cs.alloc_input(
+            || "d",
+            || {
+                let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+                let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
+
+                a.add_assign(&b);
+                Ok(a)
+            },
+        )?;
+
+        cs.enforce(|| "a*b=c", |lc| lc + a, |lc| lc + b, |lc| lc + c);
+        cs.enforce(
+            || "(a+b)*1=d",
+            |lc| lc + a + b,
+            |lc| lc + CS::one(),
+            |lc| lc + d,
+        );
+
+        Ok(())
+    }
+}
+
+mod bls12_381 {
+    use super::*;
+    use crate::{read_from_file, write_to_file, Accumulator, Error, MPCParameters};
+    use groth16::{create_random_proof, prepare_verifying_key, verify_proof};
+
+    use algebra::bls12_381::{Bls12_381, Fr};
+    use algebra_core::{test_rng, AffineCurve, UniformRand};
+    use rand::Rng;
+
+    fn circuit() -> MySillyCircuit<Fr> {
+        MySillyCircuit { a: None, b: None }
+    }
+
+    fn accumulator<R: Rng>(num_contributions: usize, rng: &mut R) -> Accumulator<Bls12_381> {
+        let mut accumulator = Accumulator::new(8).unwrap();
+        for _ in 0..num_contributions {
+            accumulator.contribute(rng);
+        }
+        accumulator
+    }
+
+    #[test]
+    fn ceremony() {
+        let rng = &mut test_rng();
+        let dir = std::env::temp_dir();
+
+        let mut accumulator = Accumulator::<Bls12_381>::new(8).unwrap();
+        let mut digests = Vec::new();
+        for i in 0..3 {
+            let path = dir.join(format!("groth16-setup-test-phase1-{}", i));
+            write_to_file(&accumulator, &path).unwrap();
+            let before = read_from_file::<Accumulator<Bls12_381>, _>(&path).unwrap();
+            assert_eq!(before, accumulator);
+
+            accumulator.contribute(rng);
+            let digest = Accumulator::verify_contribution(&before, &accumulator, rng).unwrap();
+            assert_eq!(digest, accumulator.digest());
+            digests.push(digest);
+        }
+        assert_eq!(accumulator.verify(rng).unwrap(), digests);
+
+        let mut params = MPCParameters::new(circuit(), &accumulator).unwrap();
+        let mut digests = Vec::new();
+        for i in 0..2 {
+            let path = dir.join(format!("groth16-setup-test-phase2-{}", i));
+            write_to_file(&params, &path).unwrap();
+            let before = read_from_file::<MPCParameters<Bls12_381>, _>(&path).unwrap();
+            assert_eq!(before, params);
+
+            let digest = params.contribute(rng);
+            assert_eq!(
+                MPCParameters::verify_contribution(&before, &params, rng).unwrap(),
+                digest
+            );
+            digests.push(digest);
+        }
+        assert_eq!(
+            params.verify(circuit(), &accumulator, rng).unwrap(),
+            digests
+        );
+
+        let pvk = prepare_verifying_key(&params.params.vk);
+        for _ in 0..5 {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+            let proof = create_random_proof(
+                MySillyCircuit {
+                    a: Some(a),
+                    b: Some(b),
+                },
+                &params.params,
+                rng,
+            )
+            .unwrap();
+            assert!(verify_proof(&pvk, &proof, &[a * &b, a + &b]).unwrap());
+            assert!(!verify_proof(&pvk, &proof, &[a, b]).unwrap());
+        }
+    }
+
+    #[test]
+    fn reject_invalid_phase1_contributions() {
+        let rng = &mut test_rng();
+
+        let before = accumulator(1, rng);
+        let mut after = before.clone();
+        after.contribute(rng);
+        assert!(Accumulator::verify_contribution(&before, &after, rng).is_ok());
+
+        let mut tampered = after.clone();
+        tampered.tau_powers_g1[3] = tampered.tau_powers_g1[2];
+        assert!(matches!(
+            Accumulator::verify_contribution(&before, &tampered, rng),
+            Err(Error::InvalidAccumulator)
+        ));
+        assert!(matches!(
+            tampered.verify(rng),
+            Err(Error::InvalidAccumulator)
+        ));
+
+        let mut tampered = after.clone();
+        tampered.beta_g2 = tampered.tau_powers_g2[1];
+        assert!(matches!(
+            tampered.verify(rng),
+            Err(Error::InvalidAccumulator)
+        ));
+
+        // A contribution that does not prove the knowledge of its secrets.
+        let mut tampered = after.clone();
+        tampered.contributions[1].alpha_pok = tampered.contributions[1].beta_pok.clone();
+        assert!(matches!(
+            Accumulator::verify_contribution(&before, &tampered, rng),
+            Err(Error::InvalidContribution(1))
+        ));
+        assert!(matches!(
+            tampered.verify(rng),
+            Err(Error::InvalidContribution(1))
+        ));
+
+        // The proofs of knowledge are bound to the transcript.
+        let key = &after.contributions[1];
+        assert!(key.tau_pok.verify(&before.digest(), 0));
+        assert!(!key.tau_pok.verify(&after.digest(), 0));
+        assert!(!key.tau_pok.verify(&before.digest(), 1));
+
+        assert!(matches!(
+            Accumulator::verify_contribution(&before, &before, rng),
+            Err(Error::InvalidAccumulator)
+        ));
+        assert!(matches!(
+            Accumulator::<Bls12_381>::new(1),
+            Err(Error::InvalidDegree(1))
+        ));
+    }
+
+    #[test]
+    fn reject_invalid_phase2_contributions() {
+        let rng = &mut test_rng();
+
+        let accumulator = accumulator(1, rng);
+        let mut before = MPCParameters::new(circuit(), &accumulator).unwrap();
+        before.contribute(rng);
+        let mut after = before.clone();
+        after.contribute(rng);
+        assert!(MPCParameters::verify_contribution(&before, &after, rng).is_ok());
+
+        let mut tampered = after.clone();
+        tampered.params.h_query[0] = tampered.params.h_query[1];
+        assert!(matches!(
+            MPCParameters::verify_contribution(&before, &tampered, rng),
+            Err(Error::InvalidParameters)
+        ));
+        assert!(matches!(
+            tampered.verify(circuit(), &accumulator, rng),
+            Err(Error::InvalidParameters)
+        ));
+
+        let mut tampered = after.clone();
+        tampered.params.a_query[1] = tampered.params.a_query[2];
+        assert!(matches!(
+            tampered.verify(circuit(), &accumulator, rng),
+            Err(Error::InvalidParameters)
+        ));
+
+        // `δ` is changed without a contribution.
+        let mut tampered = after.clone();
+        tampered.params.delta_g1 = tampered.params.delta_g1.mul(Fr::rand(rng)).into();
+        assert!(matches!(
+            tampered.verify(circuit(), &accumulator, rng),
+            Err(Error::InvalidParameters)
+        ));
+
+        let mut tampered = after.clone();
+        tampered.contributions[1].delta_pok.g1_s_x = tampered.contributions[1].delta_pok.g1_s;
+        assert!(matches!(
+            MPCParameters::verify_contribution(&before, &tampered, rng),
+            Err(Error::InvalidContribution(1))
+        ));
+        assert!(matches!(
+            tampered.verify(circuit(), &accumulator, rng),
+            Err(Error::InvalidContribution(1))
+        ));
+
+        // The parameters were specialized from another accumulator.
+        let mut other = accumulator.clone();
+        other.contribute(rng);
+        assert!(matches!(
+            after.verify(circuit(), &other, rng),
+            Err(Error::InvalidParameters)
+        ));
+    }
+
+    #[test]
+    fn accumulator_too_small() {
+        let accumulator = Accumulator::<Bls12_381>::new(4).unwrap();
+        assert!(matches!(
+            MPCParameters::new(circuit(), &accumulator),
+            Err(Error::TooManyConstraints {
+                domain_size: 8,
+                degree: 4
+            })
+        ));
+    }
+}
@@ -48,6 +48,22 @@ mod test;
 
 pub use self::{generator::*, prover::*, verifier::*};
 
+/// The version of the format in which `Proof`, `VerifyingKey` and
+/// `Parameters` are written. It is the first byte of their encodings, and
+/// is checked when they are read.
+pub const SERIALIZATION_VERSION: u8 = 1;
+
+fn write_version<W: Write>(writer: &mut W) -> io::Result<()> {
+    Ok(SERIALIZATION_VERSION.serialize(writer)?)
+}
+
+fn read_version<R: Read>(reader: &mut R) -> io::Result<()> {
+    if u8::deserialize(reader)? != SERIALIZATION_VERSION {
+        return Err(SerializationError::InvalidData.into());
+    }
+    Ok(())
+}
+
 /// A proof in the Groth16 SNARK.
 #[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Proof<E: PairingEngine> {
@@ -77,16 +93,18 @@ impl<E: PairingEngine> Default for Proof<E> {
 
 impl<E: PairingEngine> Proof<E> {
     /// Serialize the proof into bytes, for storage on disk or transmission
-    /// over the network.
-    pub fn write<W: Write>(&self, mut _writer: W) -> io::Result<()> {
-        // TODO: implement serialization
-        unimplemented!()
+    /// over the network: the format version, followed by the compressed
+    /// canonical serialization of the proof.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write_version(&mut writer)?;
+        Ok(self.serialize(&mut writer)?)
     }
 
-    /// Deserialize the proof from bytes.
-    pub fn read<R: Read>(mut _reader: R) -> io::Result<Self> {
-        // TODO: implement serialization
-        unimplemented!()
+    /// Deserialize the proof from bytes, checking that its elements lie in
+    /// the prime order subgroups.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        read_version(&mut reader)?;
+        Ok(Self::deserialize(&mut reader)?)
     }
 }
 
@@ -127,16 +145,18 @@ impl<E: PairingEngine> Default for VerifyingKey<E> {
 
 impl<E: PairingEngine> VerifyingKey<E> {
     /// Serialize the verification key into bytes, for storage on disk
-    /// or transmission over the network.
-    pub fn write<W: Write>(&self, mut _writer: W) -> io::Result<()> {
-        // TODO: implement serialization
-        unimplemented!()
+    /// or transmission over the network: the format version, followed by
+    /// the compressed canonical serialization of the key.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write_version(&mut writer)?;
+        Ok(self.serialize(&mut writer)?)
     }
 
-    /// Deserialize the verification key from bytes.
-    pub fn read<R: Read>(mut _reader: R) -> io::Result<Self> {
-        // TODO: implement serialization
-        unimplemented!()
+    /// Deserialize the verification key from bytes, checking that its
+    /// elements lie in the prime order subgroups.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        read_version(&mut reader)?;
+        Ok(Self::deserialize(&mut reader)?)
     }
 }
 
@@ -154,16 +174,27 @@ pub struct Parameters<E: PairingEngine> {
 }
 
 impl<E: PairingEngine> Parameters<E> {
-    /// Serialize the parameters to bytes.
-    pub fn write<W: Write>(&self, mut _writer: W) -> io::Result<()> {
-        // TODO: implement serialization
-        unimplemented!()
+    /// Serialize the parameters to bytes: the format version, followed by
+    /// the uncompressed canonical serialization of the parameters, which is
+    /// larger than the compressed one but much faster to read.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write_version(&mut writer)?;
+        Ok(self.serialize_uncompressed(&mut writer)?)
     }
 
-    /// Deserialize the public parameters from bytes.
-    pub fn read<R: Read>(mut _reader: R, _checked: bool) -> io::Result<Self> {
-        // TODO: implement serialization
-        unimplemented!()
+    /// Deserialize the public parameters from bytes. If `checked` is set,
+    /// every element is checked to lie in its prime order subgroup, which
+    /// dominates the cost of reading. Only parameters from a trusted source,
+    /// such as ones this process wrote itself, should be read unchecked: the
+    /// prover multiplies them with `mul_subgroup`, which gives wrong proofs
+    /// for points outside the subgroup.
+    pub fn read<R: Read>(mut reader: R, checked: bool) -> io::Result<Self> {
+        read_version(&mut reader)?;
+        if checked {
+            Ok(Self::deserialize_uncompressed(&mut reader)?)
+        } else {
+            Ok(Self::deserialize_unchecked(&mut reader)?)
+        }
     }
 }
 
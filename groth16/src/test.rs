@@ -74,6 +74,132 @@ mod bls12_377 {
             assert!(!verify_proof(&pvk, &proof, &[a]).unwrap());
         }
     }
+
+    #[test]
+    fn verify_proofs_batch() {
+        let rng = &mut test_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_377, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+
+        let pvk = prepare_verifying_key::<Bls12_377>(&params.vk);
+
+        let mut proofs = Vec::new();
+        let mut public_inputs = Vec::new();
+        for _ in 0..10 {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+            let mut c = a;
+            c.mul_assign(&b);
+
+            let proof = create_random_proof(
+                MySillyCircuit {
+                    a: Some(a),
+                    b: Some(b),
+                },
+                &params,
+                rng,
+            )
+            .unwrap();
+            proofs.push(proof);
+            public_inputs.push(vec![c]);
+        }
+
+        let batch = proofs
+            .iter()
+            .zip(&public_inputs)
+            .map(|(proof, inputs)| (proof, inputs.as_slice()))
+            .collect::<Vec<_>>();
+        assert!(crate::verify_proofs_batch(&pvk, &batch, rng).unwrap());
+        assert!(crate::verify_proofs_batch(&pvk, &batch[..1], rng).unwrap());
+        assert!(crate::verify_proofs_batch(&pvk, &[], rng).unwrap());
+
+        let wrong_input = [Fr::rand(rng)];
+        let mut wrong_batch = batch.clone();
+        wrong_batch[3].1 = &wrong_input;
+        assert!(!crate::verify_proofs_batch(&pvk, &wrong_batch, rng).unwrap());
+
+        let mut swapped_batch = batch.clone();
+        swapped_batch[0].0 = batch[1].0;
+        assert!(!crate::verify_proofs_batch(&pvk, &swapped_batch, rng).unwrap());
+
+        let mut short_batch = batch;
+        short_batch[5].1 = &[];
+        assert!(crate::verify_proofs_batch(&pvk, &short_batch, rng).is_err());
+    }
+
+    #[test]
+    fn deserialize_rejects_points_outside_subgroup() {
+        use crate::{Parameters, Proof, VerifyingKey};
+        use algebra::bls12_377::{Fq, G1Affine};
+        use algebra_core::{CanonicalDeserialize, CanonicalSerialize, One, Zero};
+
+        let rng = &mut test_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_377, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let proof = create_random_proof(
+            MySillyCircuit {
+                a: Some(a),
+                b: Some(b),
+            },
+            &params,
+            rng,
+        )
+        .unwrap();
+
+        let mut bytes = Vec::new();
+        proof.serialize(&mut bytes).unwrap();
+        assert_eq!(Proof::deserialize(&mut &bytes[..]).unwrap(), proof);
+
+        let mut bytes = Vec::new();
+        params.vk.serialize(&mut bytes).unwrap();
+        assert_eq!(
+            VerifyingKey::deserialize(&mut &bytes[..]).unwrap(),
+            params.vk
+        );
+
+        // A point on the curve, but outside of the prime order subgroup.
+        let mut x = Fq::zero();
+        let p = loop {
+            if let Some(p) = G1Affine::get_point_from_x(x, true) {
+                break p;
+            }
+            x += &Fq::one();
+        };
+        assert!(!p.is_in_correct_subgroup_assuming_on_curve());
+
+        let mut bad_proof = proof.clone();
+        bad_proof.a = p;
+        let mut bytes = Vec::new();
+        bad_proof.serialize(&mut bytes).unwrap();
+        assert!(Proof::<Bls12_377>::deserialize(&mut &bytes[..]).is_err());
+
+        let mut bytes = Vec::new();
+        bad_proof.serialize_uncompressed(&mut bytes).unwrap();
+        assert!(Proof::<Bls12_377>::deserialize_uncompressed(&mut &bytes[..]).is_err());
+
+        let mut bad_vk = params.vk.clone();
+        bad_vk.gamma_abc_g1[0] = p;
+        let mut bytes = Vec::new();
+        bad_vk.serialize(&mut bytes).unwrap();
+        assert!(VerifyingKey::<Bls12_377>::deserialize(&mut &bytes[..]).is_err());
+
+        // Only checked reads of the parameters reject such points.
+        let mut bad_params = params.clone();
+        bad_params.a_query[0] = p;
+        let mut bytes = Vec::new();
+        bad_params.write(&mut bytes).unwrap();
+        assert!(Parameters::<Bls12_377>::read(&bytes[..], true).is_err());
+        assert_eq!(
+            Parameters::<Bls12_377>::read(&bytes[..], false).unwrap(),
+            bad_params
+        );
+    }
 }
 
 mod cp6_782 {
@@ -113,3 +239,117 @@ mod cp6_782 {
         assert!(!verify_proof(&pvk, &proof, &[Fr::zero()]).unwrap());
     }
 }
+
+mod serialization {
+    use super::*;
+    use crate::{
+        create_random_proof, generate_random_parameters, Parameters, Proof, VerifyingKey,
+        SERIALIZATION_VERSION,
+    };
+    use algebra_core::{test_rng, PairingEngine, UniformRand};
+
+    // Not every engine implements `PartialEq`, which the derived `PartialEq`
+    // of the proof types requires, so values are compared by their encodings.
+    fn round_trip<E: PairingEngine>() {
+        let rng = &mut test_rng();
+
+        let params =
+            generate_random_parameters::<E, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+        let proof = create_random_proof(
+            MySillyCircuit {
+                a: Some(E::Fr::rand(rng)),
+                b: Some(E::Fr::rand(rng)),
+            },
+            &params,
+            rng,
+        )
+        .unwrap();
+
+        let mut bytes = Vec::new();
+        proof.write(&mut bytes).unwrap();
+        assert_eq!(bytes[0], SERIALIZATION_VERSION);
+        let mut again = Vec::new();
+        Proof::<E>::read(&bytes[..])
+            .unwrap()
+            .write(&mut again)
+            .unwrap();
+        assert_eq!(again, bytes);
+
+        let mut bytes = Vec::new();
+        params.vk.write(&mut bytes).unwrap();
+        assert_eq!(bytes[0], SERIALIZATION_VERSION);
+        let mut again = Vec::new();
+        VerifyingKey::<E>::read(&bytes[..])
+            .unwrap()
+            .write(&mut again)
+            .unwrap();
+        assert_eq!(again, bytes);
+
+        let mut bytes = Vec::new();
+        params.write(&mut bytes).unwrap();
+        assert_eq!(bytes[0], SERIALIZATION_VERSION);
+        let mut again = Vec::new();
+        Parameters::<E>::read(&bytes[..], true)
+            .unwrap()
+            .write(&mut again)
+            .unwrap();
+        assert_eq!(again, bytes);
+        let mut again = Vec::new();
+        Parameters::<E>::read(&bytes[..], false)
+            .unwrap()
+            .write(&mut again)
+            .unwrap();
+        assert_eq!(again, bytes);
+
+        // Truncated encodings and unknown versions are rejected.
+        assert!(Parameters::<E>::read(&bytes[..bytes.len() - 1], false).is_err());
+        bytes[0] = SERIALIZATION_VERSION + 1;
+        assert!(Parameters::<E>::read(&bytes[..], true).is_err());
+    }
+
+    #[test]
+    fn bn254() {
+        round_trip::<algebra::bn254::Bn254>();
+    }
+
+    #[test]
+    fn bls12_377() {
+        round_trip::<algebra::bls12_377::Bls12_377>();
+    }
+
+    #[test]
+    fn bls12_381() {
+        round_trip::<algebra::bls12_381::Bls12_381>();
+    }
+
+    #[test]
+    fn cp6_782() {
+        round_trip::<algebra::cp6_782::CP6_782>();
+    }
+
+    #[test]
+    fn bw6_761() {
+        round_trip::<algebra::bw6_761::BW6_761>();
+    }
+
+    #[test]
+    fn mnt4_298() {
+        round_trip::<algebra::mnt4_298::MNT4_298>();
+    }
+
+    #[test]
+    fn mnt4_753() {
+        round_trip::<algebra::mnt4_753::MNT4_753>();
+    }
+
+    #[test]
+    fn mnt6_298() {
+        round_trip::<algebra::mnt6_298::MNT6_298>();
+    }
+
+    #[test]
+    fn mnt6_753() {
+        round_trip::<algebra::mnt6_753::MNT6_753>();
+    }
+}
@@ -1,8 +1,11 @@
-use algebra_core::{AffineCurve, PairingEngine, PrimeField, ProjectiveCurve};
+use algebra_core::{
+    msm::VariableBaseMSM, AffineCurve, Field, PairingEngine, PrimeField, ProjectiveCurve, Zero,
+};
+use rand::Rng;
 
 use super::{PreparedVerifyingKey, Proof, VerifyingKey};
 
-use crate::SynthesisError;
+use crate::{SynthesisError, Vec};
 
 use core::ops::{AddAssign, Neg};
 
@@ -43,3 +46,68 @@ pub fn verify_proof<E: PairingEngine>(
 
     Ok(test == pvk.alpha_g1_beta_g2)
 }
+
+/// Verifies `proofs`, each given with its public inputs, against the same
+/// verifying key at once.
+///
+/// The verification equations `e(A_i, B_i) = e(α, β) e(IC_i, γ) e(C_i, δ)`
+/// are combined with random scalars `r_i` into
+/// `prod_i e(r_i A_i, B_i) e(sum_i r_i IC_i, -γ) e(sum_i r_i C_i, -δ) = e(α,
+/// β)^(sum_i r_i)`, which costs one multi-Miller-loop and one final
+/// exponentiation, while `sum_i r_i IC_i` and `sum_i r_i C_i` are computed with
+/// one MSM each.
+pub fn verify_proofs_batch<E: PairingEngine, R: Rng>(
+    pvk: &PreparedVerifyingKey<E>,
+    proofs: &[(&Proof<E>, &[E::Fr])],
+    rng: &mut R,
+) -> Result<bool, SynthesisError> {
+    if proofs
+        .iter()
+        .any(|(_, public_inputs)| (public_inputs.len() + 1) != pvk.gamma_abc_g1.len())
+    {
+        return Err(SynthesisError::MalformedVerifyingKey);
+    }
+    if proofs.is_empty() {
+        return Ok(true);
+    }
+
+    let batch_time = start_timer!(|| format!("Verify a batch of {} proofs", proofs.len()));
+    let mut randomizer_sum = E::Fr::zero();
+    let mut input_scalars = vec![E::Fr::zero(); pvk.gamma_abc_g1.len()];
+    let mut randomized_a = Vec::with_capacity(proofs.len());
+    let mut c_bases = Vec::with_capacity(proofs.len());
+    let mut c_scalars = Vec::with_capacity(proofs.len());
+    for (proof, public_inputs) in proofs {
+        // 128-bit randomizers give soundness error 2^-128, at half the cost
+        // of full-length scalars in `r_i A_i`.
+        let r = <E::Fr as From<u128>>::from(rng.gen());
+        randomizer_sum += &r;
+        input_scalars[0] += &r;
+        for (scalar, input) in input_scalars[1..].iter_mut().zip(*public_inputs) {
+            *scalar += &(r * input);
+        }
+        randomized_a.push(proof.a.mul(r));
+        c_bases.push(proof.c);
+        c_scalars.push(r.into_repr());
+    }
+    let input_scalars = input_scalars
+        .into_iter()
+        .map(|s| s.into_repr())
+        .collect::<Vec<_>>();
+    let g_ic = VariableBaseMSM::multi_scalar_mul_subgroup(&pvk.gamma_abc_g1, &input_scalars);
+    let c_sum = VariableBaseMSM::multi_scalar_mul(&c_bases, &c_scalars);
+
+    let mut pairs = E::G1Projective::batch_normalization_into_affine(&randomized_a)
+        .into_iter()
+        .zip(proofs)
+        .map(|(a, (proof, _))| (a.into(), proof.b.into()))
+        .collect::<Vec<_>>();
+    pairs.push((g_ic.into_affine().into(), pvk.gamma_g2_neg_pc.clone()));
+    pairs.push((c_sum.into_affine().into(), pvk.delta_g2_neg_pc.clone()));
+    let qap = E::miller_loop(pairs.iter());
+
+    let test = E::final_exponentiation(&qap).ok_or(SynthesisError::UnexpectedIdentity)?;
+    end_timer!(batch_time);
+
+    Ok(test == pvk.alpha_g1_beta_g2.pow(randomizer_sum.into_repr()))
+}
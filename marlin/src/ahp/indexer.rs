@@ -0,0 +1,258 @@
+use crate::{ahp::Domains, data_structures::IndexInfo, Error, String, Vec};
+use algebra_core::{Field, PrimeField};
+use ff_fft::{DensePolynomial, EvaluationDomain, GeneralEvaluationDomain};
+use r1cs_core::{
+    ConstraintSynthesizer, ConstraintSystem, Index as VarIndex, LinearCombination, SynthesisError,
+    Variable,
+};
+
+/// A sparse matrix given by its rows, each of which lists the non-zero
+/// entries as pairs of a coefficient and a column.
+pub type Matrix<F> = Vec<Vec<(F, usize)>>;
+
+/// The polynomials over `K` which encode a matrix `M`: for the `κ`-th
+/// non-zero entry `M[r][c]`, `row(κ)` and `col(κ)` are the elements of `H`
+/// at which the row `r` and the column `c` live, and `val(κ)` is
+/// `M[r][c] / (u_H(row(κ), row(κ)) u_H(col(κ), col(κ)))`.
+#[derive(Clone, Debug)]
+pub struct MatrixArithmetization<F: PrimeField> {
+    pub row: DensePolynomial<F>,
+    pub col: DensePolynomial<F>,
+    pub val: DensePolynomial<F>,
+    /// The evaluations of `row` over `K`.
+    pub row_evals: Vec<F>,
+    /// The evaluations of `col` over `K`.
+    pub col_evals: Vec<F>,
+    /// The evaluations of `val` over `K`.
+    pub val_evals: Vec<F>,
+}
+
+impl<F: PrimeField> MatrixArithmetization<F> {
+    fn new(
+        matrix: &[Vec<(F, usize)>],
+        domains: &Domains<F>,
+        elements_of_h: &[F],
+    ) -> MatrixArithmetization<F> {
+        let num_non_zero = domains.k.size();
+        let mut row_evals = Vec::with_capacity(num_non_zero);
+        let mut col_evals = Vec::with_capacity(num_non_zero);
+        let mut val_evals = Vec::with_capacity(num_non_zero);
+        for (r, row) in matrix.iter().enumerate() {
+            for (coeff, c) in row {
+                row_evals.push(elements_of_h[r]);
+                col_evals.push(elements_of_h[domains.h.reindex_by_subdomain(domains.x, *c)]);
+                val_evals.push(*coeff);
+            }
+        }
+
+        // Since `u_H(a, a) = |H| / a` for `a` in `H`, `val(κ)` is
+        // `M[r][c] row(κ) col(κ) / |H|^2`.
+        let size_squared_inverse = domains
+            .h
+            .size_as_field_element()
+            .square()
+            .inverse()
+            .unwrap();
+        for ((val, row), col) in val_evals.iter_mut().zip(&row_evals).zip(&col_evals) {
+            *val *= &(*row * col * &size_squared_inverse);
+        }
+
+        // Padding entries have `val(κ) = 0`.
+        row_evals.resize(num_non_zero, F::one());
+        col_evals.resize(num_non_zero, F::one());
+        val_evals.resize(num_non_zero, F::zero());
+
+        let interpolate =
+            |evals: &[F]| DensePolynomial::from_coefficients_vec(domains.k.ifft(evals));
+        MatrixArithmetization {
+            row: interpolate(&row_evals),
+            col: interpolate(&col_evals),
+            val: interpolate(&val_evals),
+            row_evals,
+            col_evals,
+            val_evals,
+        }
+    }
+}
+
+/// The index of a constraint system: its matrices, with columns ordered as
+/// `z = (x, w)`, and their arithmetizations.
+#[derive(Clone, Debug)]
+pub struct Index<F: PrimeField> {
+    pub index_info: IndexInfo,
+    pub a: Matrix<F>,
+    pub b: Matrix<F>,
+    pub c: Matrix<F>,
+    pub a_arith: MatrixArithmetization<F>,
+    pub b_arith: MatrixArithmetization<F>,
+    pub c_arith: MatrixArithmetization<F>,
+}
+
+impl<F: PrimeField> Index<F> {
+    /// The index polynomials `row`, `col` and `val` of `A`, `B` and `C`, in
+    /// the order of `IndexVerifierKey::index_commitments`.
+    pub fn polynomials(&self) -> Vec<&DensePolynomial<F>> {
+        [&self.a_arith, &self.b_arith, &self.c_arith]
+            .iter()
+            .flat_map(|arith| vec![&arith.row, &arith.col, &arith.val])
+            .collect()
+    }
+
+    pub(crate) fn domains(&self) -> Result<Domains<F>, Error> {
+        Domains::new(&self.index_info)
+    }
+
+    /// Indexes the constraint system of `circuit`, whose assignment is not
+    /// needed.
+    pub fn new<C: ConstraintSynthesizer<F>>(circuit: C) -> Result<Self, Error> {
+        let index_time = start_timer!(|| "AHP::Index");
+
+        let mut ics = IndexerConstraintSystem::<F>::new();
+        ics.alloc_input(|| "", || Ok(F::one()))?;
+        circuit.generate_constraints(&mut ics)?;
+
+        let num_instance_variables =
+            GeneralEvaluationDomain::<F>::compute_size_of_domain(ics.num_input_variables)
+                .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+        let to_matrix = |rows: Vec<Vec<(F, VarIndex)>>| -> Matrix<F> {
+            rows.into_iter()
+                .map(|row| {
+                    row.into_iter()
+                        .map(|(coeff, index)| match index {
+                            VarIndex::Input(i) => (coeff, i),
+                            VarIndex::Aux(i) => (coeff, num_instance_variables + i),
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+        let a = to_matrix(ics.a);
+        let b = to_matrix(ics.b);
+        let c = to_matrix(ics.c);
+        let num_non_zero = [&a, &b, &c]
+            .iter()
+            .map(|matrix| matrix.iter().map(|row| row.len()).sum::<usize>())
+            .max()
+            .unwrap();
+
+        let index_info = IndexInfo {
+            num_instance_variables,
+            num_variables: num_instance_variables + ics.num_witness_variables,
+            num_constraints: ics.num_constraints,
+            num_non_zero,
+        };
+        let domains = Domains::new(&index_info)?;
+        let elements_of_h = domains.h.elements().collect::<Vec<_>>();
+
+        let arith_time = start_timer!(|| "Arithmetizing matrices");
+        let a_arith = MatrixArithmetization::new(&a, &domains, &elements_of_h);
+        let b_arith = MatrixArithmetization::new(&b, &domains, &elements_of_h);
+        let c_arith = MatrixArithmetization::new(&c, &domains, &elements_of_h);
+        end_timer!(arith_time);
+
+        end_timer!(index_time);
+        Ok(Self {
+            index_info,
+            a,
+            b,
+            c,
+            a_arith,
+            b_arith,
+            c_arith,
+        })
+    }
+}
+
+/// Collects the constraints of a circuit without its assignment.
+pub(crate) struct IndexerConstraintSystem<F: Field> {
+    pub(crate) num_input_variables: usize,
+    pub(crate) num_witness_variables: usize,
+    pub(crate) num_constraints: usize,
+    pub(crate) a: Vec<Vec<(F, VarIndex)>>,
+    pub(crate) b: Vec<Vec<(F, VarIndex)>>,
+    pub(crate) c: Vec<Vec<(F, VarIndex)>>,
+}
+
+impl<F: Field> IndexerConstraintSystem<F> {
+    pub(crate) fn new() -> Self {
+        Self {
+            num_input_variables: 0,
+            num_witness_variables: 0,
+            num_constraints: 0,
+            a: Vec::new(),
+            b: Vec::new(),
+            c: Vec::new(),
+        }
+    }
+}
+
+fn to_row<F: Field>(lc: LinearCombination<F>) -> Vec<(F, VarIndex)> {
+    lc.as_ref()
+        .iter()
+        .map(|(var, coeff)| (*coeff, var.get_unchecked()))
+        .collect()
+}
+
+impl<F: Field> ConstraintSystem<F> for IndexerConstraintSystem<F> {
+    type Root = Self;
+
+    #[inline]
+    fn alloc<FN, A, AR>(&mut self, _: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        // There is no assignment, so we don't invoke the
+        // function for obtaining one.
+        let index = self.num_witness_variables;
+        self.num_witness_variables += 1;
+        Ok(Variable::new_unchecked(VarIndex::Aux(index)))
+    }
+
+    #[inline]
+    fn alloc_input<FN, A, AR>(&mut self, _: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.num_input_variables;
+        self.num_input_variables += 1;
+        Ok(Variable::new_unchecked(VarIndex::Input(index)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        self.a.push(to_row(a(LinearCombination::zero())));
+        self.b.push(to_row(b(LinearCombination::zero())));
+        self.c.push(to_row(c(LinearCombination::zero())));
+        self.num_constraints += 1;
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn pop_namespace(&mut self) {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+}
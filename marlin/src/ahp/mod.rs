@@ -0,0 +1,135 @@
+//! The algebraic holographic proof (AHP) for R1CS of Marlin.
+//!
+//! The indexer encodes the matrices `A`, `B` and `C` as polynomials over a
+//! domain `K`, and the prover convinces the verifier that `Az ∘ Bz = Cz` for
+//! `z = (x, w)` with three univariate sumchecks: one over the domain `H`
+//! of constraints and variables, which reduces the claim to an evaluation of
+//! the bivariate matrix polynomials, and two which reduce that evaluation
+//! to evaluations of the index polynomials.
+use crate::{data_structures::IndexInfo, Error, Vec};
+use algebra_core::FftField;
+use ff_fft::{DensePolynomial, EvaluationDomain, GeneralEvaluationDomain};
+use r1cs_core::SynthesisError;
+
+/// Encoding R1CS instances as polynomials.
+pub mod indexer;
+pub(crate) mod prover;
+pub(crate) mod verifier;
+
+/// The number of queries to each witness-dependent polynomial, which is the
+/// number of random evaluations that mask it.
+pub(crate) const ZK_BOUND: usize = 1;
+
+/// The evaluation domains of an index: `H` for constraints and variables,
+/// `K` for the non-zero matrix entries, and the subgroup `X` of `H` that
+/// holds the instance.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Domains<F: FftField> {
+    pub(crate) h: GeneralEvaluationDomain<F>,
+    pub(crate) k: GeneralEvaluationDomain<F>,
+    pub(crate) x: GeneralEvaluationDomain<F>,
+}
+
+impl<F: FftField> Domains<F> {
+    pub(crate) fn new(index_info: &IndexInfo) -> Result<Self, Error> {
+        let h = GeneralEvaluationDomain::new(
+            index_info
+                .num_constraints
+                .max(index_info.num_variables)
+                .max(2),
+        )
+        .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+        let k = GeneralEvaluationDomain::new(index_info.num_non_zero.max(2))
+            .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+        let x = GeneralEvaluationDomain::new(index_info.num_instance_variables)
+            .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+        if x.size() != index_info.num_instance_variables || h.size() % x.size() != 0 {
+            return Err(Error::InstanceDoesNotMatchIndex);
+        }
+        Ok(Self { h, k, x })
+    }
+
+    /// The degree bound of the polynomials `g_1` and `g_2` of the sumchecks
+    /// over `H`.
+    pub(crate) fn h_degree_bound(&self) -> usize {
+        self.h.size() - 2
+    }
+
+    /// The degree bound of the polynomial `g_3` of the sumcheck over `K`.
+    pub(crate) fn k_degree_bound(&self) -> usize {
+        self.k.size() - 2
+    }
+
+    /// The maximum degree of the polynomials sent by the prover: the mask
+    /// of the first sumcheck has degree `3|H| + 2 ZK_BOUND - 2`, and `h_3`
+    /// has degree below `6|K| - 6`.
+    pub(crate) fn max_degree(&self) -> usize {
+        (3 * self.h.size() + 2 * ZK_BOUND - 2).max(6 * self.k.size() - 6)
+    }
+}
+
+/// The maximum degree of the polynomials of the proofs for a constraint
+/// system of the given size, which bounds the size of the universal SRS.
+pub fn max_degree<F: FftField>(
+    num_constraints: usize,
+    num_variables: usize,
+    num_non_zero: usize,
+) -> Result<usize, Error> {
+    let index_info = IndexInfo {
+        num_instance_variables: 1,
+        num_variables,
+        num_constraints,
+        num_non_zero,
+    };
+    Ok(Domains::<F>::new(&index_info)?.max_degree())
+}
+
+/// The vanishing polynomial `X^|D| - 1` of the multiplicative subgroup `D`.
+pub(crate) fn vanishing_polynomial<F: FftField>(
+    domain: &GeneralEvaluationDomain<F>,
+) -> DensePolynomial<F> {
+    let mut coeffs = vec![F::zero(); domain.size() + 1];
+    coeffs[0] = -F::one();
+    coeffs[domain.size()] = F::one();
+    DensePolynomial::from_coefficients_vec(coeffs)
+}
+
+/// The polynomial `u_H(α, X) = (v_H(X) - v_H(α)) / (X - α)`, which is
+/// `sum_i α^(|H| - 1 - i) X^i`.
+pub(crate) fn bivariate_kernel_polynomial<F: FftField>(
+    domain: &GeneralEvaluationDomain<F>,
+    alpha: F,
+) -> DensePolynomial<F> {
+    let mut coeffs = Vec::with_capacity(domain.size());
+    let mut cur = F::one();
+    for _ in 0..domain.size() {
+        coeffs.push(cur);
+        cur *= &alpha;
+    }
+    coeffs.reverse();
+    DensePolynomial::from_coefficients_vec(coeffs)
+}
+
+/// Evaluates `u_H(α, β) = (v_H(α) - v_H(β)) / (α - β)` for `α != β`.
+pub(crate) fn evaluate_bivariate_kernel<F: FftField>(
+    domain: &GeneralEvaluationDomain<F>,
+    alpha: F,
+    beta: F,
+) -> F {
+    (domain.evaluate_vanishing_polynomial(alpha) - &domain.evaluate_vanishing_polynomial(beta))
+        * &(alpha - &beta).inverse().unwrap()
+}
+
+/// Splits `p = X g + σ / |D|`, the remainder of a sumcheck over `D`, into
+/// `g` and the sum `σ` of `p` over `D`.
+pub(crate) fn split_sumcheck_remainder<F: FftField>(
+    domain: &GeneralEvaluationDomain<F>,
+    remainder: &DensePolynomial<F>,
+) -> (DensePolynomial<F>, F) {
+    if remainder.is_zero() {
+        return (DensePolynomial::zero(), F::zero());
+    }
+    let sum = remainder.coeffs[0] * &domain.size_as_field_element();
+    let g = DensePolynomial::from_coefficients_slice(&remainder.coeffs[1..]);
+    (g, sum)
+}
@@ -0,0 +1,407 @@
+use crate::{
+    ahp::{
+        bivariate_kernel_polynomial, indexer::Index, split_sumcheck_remainder,
+        vanishing_polynomial, Domains, ZK_BOUND,
+    },
+    Error, String, Vec,
+};
+use algebra_core::{batch_inversion, Field, PrimeField};
+use ff_fft::{DensePolynomial, EvaluationDomain, GeneralEvaluationDomain};
+use r1cs_core::{
+    ConstraintSynthesizer, ConstraintSystem, Index as VarIndex, LinearCombination, SynthesisError,
+    Variable,
+};
+use rand::RngCore;
+
+/// Collects the assignment of a circuit without its constraints.
+pub(crate) struct ProverConstraintSystem<F: Field> {
+    pub(crate) input_assignment: Vec<F>,
+    pub(crate) witness_assignment: Vec<F>,
+    pub(crate) num_constraints: usize,
+}
+
+impl<F: Field> ConstraintSystem<F> for ProverConstraintSystem<F> {
+    type Root = Self;
+
+    #[inline]
+    fn alloc<FN, A, AR>(&mut self, _: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.witness_assignment.len();
+        self.witness_assignment.push(f()?);
+        Ok(Variable::new_unchecked(VarIndex::Aux(index)))
+    }
+
+    #[inline]
+    fn alloc_input<FN, A, AR>(&mut self, _: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.input_assignment.len();
+        self.input_assignment.push(f()?);
+        Ok(Variable::new_unchecked(VarIndex::Input(index)))
+    }
+
+    #[inline]
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, _: LA, _: LB, _: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        // The constraints are in the index.
+        self.num_constraints += 1;
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn pop_namespace(&mut self) {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+}
+
+/// The polynomials of the first round: the shifted witness `w`, the
+/// products `z_A` and `z_B` of `A` and `B` with `z`, and the mask of the
+/// first sumcheck.
+pub(crate) struct ProverFirstMessage<F: PrimeField> {
+    pub(crate) w: DensePolynomial<F>,
+    pub(crate) z_a: DensePolynomial<F>,
+    pub(crate) z_b: DensePolynomial<F>,
+    pub(crate) mask: DensePolynomial<F>,
+}
+
+/// The polynomials `t`, `g_1` and `h_1` of the second round.
+pub(crate) struct ProverSecondMessage<F: PrimeField> {
+    pub(crate) t: DensePolynomial<F>,
+    pub(crate) g_1: DensePolynomial<F>,
+    pub(crate) h_1: DensePolynomial<F>,
+}
+
+/// The state of the prover of the AHP.
+pub(crate) struct ProverState<'a, F: PrimeField> {
+    index: &'a Index<F>,
+    domains: Domains<F>,
+    /// The padded instance `x`, starting with the constant one.
+    pub(crate) public_input: Vec<F>,
+    witness: Vec<F>,
+    z: Option<DensePolynomial<F>>,
+    z_a: Option<DensePolynomial<F>>,
+    z_b: Option<DensePolynomial<F>>,
+    mask: Option<DensePolynomial<F>>,
+    /// `u_H(α, X)` and `η_A, η_B, η_C`.
+    alpha_and_etas: Option<(DensePolynomial<F>, [F; 3])>,
+}
+
+impl<'a, F: PrimeField> ProverState<'a, F> {
+    /// Computes the assignment of `circuit`, which must match `index`.
+    pub(crate) fn init<C: ConstraintSynthesizer<F>>(
+        index: &'a Index<F>,
+        circuit: C,
+    ) -> Result<Self, Error> {
+        let init_time = start_timer!(|| "AHP::Prover::Init");
+        let mut pcs = ProverConstraintSystem {
+            input_assignment: Vec::new(),
+            witness_assignment: Vec::new(),
+            num_constraints: 0,
+        };
+        pcs.alloc_input(|| "", || Ok(F::one()))?;
+        circuit.generate_constraints(&mut pcs)?;
+
+        let index_info = &index.index_info;
+        if pcs.num_constraints != index_info.num_constraints
+            || pcs.input_assignment.len() > index_info.num_instance_variables
+            || index_info.num_instance_variables + pcs.witness_assignment.len()
+                != index_info.num_variables
+        {
+            return Err(Error::InstanceDoesNotMatchIndex);
+        }
+        let mut public_input = pcs.input_assignment;
+        public_input.resize(index_info.num_instance_variables, F::zero());
+
+        end_timer!(init_time);
+        Ok(Self {
+            index,
+            domains: index.domains()?,
+            public_input,
+            witness: pcs.witness_assignment,
+            z: None,
+            z_a: None,
+            z_b: None,
+            mask: None,
+            alpha_and_etas: None,
+        })
+    }
+
+    /// Evaluates `M z` over `H`, the rows beyond the constraints being zero.
+    fn matrix_times_z(&self, matrix: &[Vec<(F, usize)>]) -> Vec<F> {
+        let num_instance_variables = self.public_input.len();
+        let mut evals = matrix
+            .iter()
+            .map(|row| {
+                row.iter().fold(F::zero(), |acc, (coeff, c)| {
+                    let value = if *c < num_instance_variables {
+                        self.public_input[*c]
+                    } else {
+                        self.witness[*c - num_instance_variables]
+                    };
+                    acc + &(*coeff * &value)
+                })
+            })
+            .collect::<Vec<_>>();
+        evals.resize(self.domains.h.size(), F::zero());
+        evals
+    }
+
+    /// Computes `w`, `z_A`, `z_B` and the mask, each of which is randomized
+    /// with `ZK_BOUND` random evaluations.
+    pub(crate) fn first_round<R: RngCore>(
+        &mut self,
+        rng: &mut R,
+    ) -> Result<ProverFirstMessage<F>, Error> {
+        let round_time = start_timer!(|| "AHP::Prover::FirstRound");
+        let Domains { h, x, .. } = self.domains;
+
+        let x_poly = DensePolynomial::from_coefficients_vec(x.ifft(&self.public_input));
+        let x_evals = h.fft(&x_poly.coeffs);
+
+        // `w` is `(z - x̂) / v_X`, whose evaluations over `H \ X` are the
+        // witness, shifted by the instance.
+        let ratio = h.size() / x.size();
+        let mut w_evals = vec![F::zero(); h.size()];
+        for (k, w_eval) in w_evals.iter_mut().enumerate() {
+            if k % ratio != 0 {
+                let witness = self
+                    .witness
+                    .get(k - (k / ratio) - 1)
+                    .copied()
+                    .unwrap_or_else(F::zero);
+                *w_eval = witness - &x_evals[k];
+            }
+        }
+        let w_poly = mask_with_vanishing_polynomial(&h, h.ifft(&w_evals), rng);
+        let (w, remainder) = w_poly.divide_by_vanishing_poly(x).unwrap();
+        assert!(remainder.is_zero());
+
+        let z_a_poly =
+            mask_with_vanishing_polynomial(&h, h.ifft(&self.matrix_times_z(&self.index.a)), rng);
+        let z_b_poly =
+            mask_with_vanishing_polynomial(&h, h.ifft(&self.matrix_times_z(&self.index.b)), rng);
+
+        // A random polynomial whose sum over `H` is zero: the sum is `|H|`
+        // times the sum of the coefficients of the powers divisible by `|H|`.
+        let mut mask = DensePolynomial::rand(3 * h.size() + 2 * ZK_BOUND - 2, rng);
+        let sum = mask
+            .coeffs
+            .iter()
+            .step_by(h.size())
+            .skip(1)
+            .fold(F::zero(), |acc, c| acc + c);
+        mask.coeffs[0] = -sum;
+
+        self.z = Some(&(&w * &vanishing_polynomial(&x)) + &x_poly);
+        self.z_a = Some(z_a_poly.clone());
+        self.z_b = Some(z_b_poly.clone());
+        self.mask = Some(mask.clone());
+        end_timer!(round_time);
+
+        Ok(ProverFirstMessage {
+            w,
+            z_a: z_a_poly,
+            z_b: z_b_poly,
+            mask,
+        })
+    }
+
+    /// Computes `t` and the polynomials `g_1` and `h_1` of the first
+    /// sumcheck, `mask + u_H(α, X) (sum_M η_M z_M) - t z = h_1 v_H + X g_1`,
+    /// where `z_C = z_A z_B`.
+    pub(crate) fn second_round(
+        &mut self,
+        alpha: F,
+        etas: [F; 3],
+    ) -> Result<ProverSecondMessage<F>, Error> {
+        let round_time = start_timer!(|| "AHP::Prover::SecondRound");
+        let Domains { h, x, .. } = self.domains;
+
+        // `t(Y) = sum_M η_M sum_{κ ∈ H} u_H(α, κ) M̂(κ, Y)`, where
+        // `u_H(α, κ) = v_H(α) / (α - κ)` over `H`.
+        let v_h_alpha = h.evaluate_vanishing_polynomial(alpha);
+        let mut r_alpha_evals = h.elements().map(|k| alpha - &k).collect::<Vec<_>>();
+        batch_inversion(&mut r_alpha_evals);
+        r_alpha_evals.iter_mut().for_each(|r| *r *= &v_h_alpha);
+
+        let mut t_evals = vec![F::zero(); h.size()];
+        for (matrix, eta) in [&self.index.a, &self.index.b, &self.index.c]
+            .iter()
+            .zip(&etas)
+        {
+            for (row, r_alpha) in matrix.iter().zip(&r_alpha_evals) {
+                let scaled = *eta * r_alpha;
+                for (coeff, c) in row {
+                    t_evals[h.reindex_by_subdomain(x, *c)] += &(scaled * coeff);
+                }
+            }
+        }
+        let t = DensePolynomial::from_coefficients_vec(h.ifft(&t_evals));
+
+        let z_a = self.z_a.as_ref().unwrap();
+        let z_b = self.z_b.as_ref().unwrap();
+        let mut summed_z_m = z_a * z_b;
+        summed_z_m.coeffs.iter_mut().for_each(|c| *c *= &etas[2]);
+        summed_z_m += (etas[0], z_a);
+        summed_z_m += (etas[1], z_b);
+
+        let r_alpha = bivariate_kernel_polynomial(&h, alpha);
+        let mut q_1 = &r_alpha * &summed_z_m;
+        q_1 += self.mask.as_ref().unwrap();
+        q_1 = &q_1 - &(&t * self.z.as_ref().unwrap());
+
+        let (h_1, remainder) = q_1.divide_by_vanishing_poly(h).unwrap();
+        let (g_1, sum) = split_sumcheck_remainder(&h, &remainder);
+        if !sum.is_zero() {
+            return Err(SynthesisError::Unsatisfiable.into());
+        }
+
+        self.alpha_and_etas = Some((r_alpha, etas));
+        end_timer!(round_time);
+        Ok(ProverSecondMessage { t, g_1, h_1 })
+    }
+
+    /// Computes the polynomials `g_2` and `h_2` of the second sumcheck,
+    /// `u_H(α, X) sum_M η_M M̂(X, β_1) = h_2 v_H + X g_2 + t(β_1) / |H|`.
+    pub(crate) fn third_round(
+        &mut self,
+        beta_1: F,
+    ) -> Result<(DensePolynomial<F>, DensePolynomial<F>), Error> {
+        let round_time = start_timer!(|| "AHP::Prover::ThirdRound");
+        let Domains { h, x, .. } = self.domains;
+        let (r_alpha, etas) = self.alpha_and_etas.as_ref().unwrap();
+
+        // `M̂(κ, β_1) = sum_c M[κ][c] L_c(β_1)` over `H`.
+        let lagrange_at_beta_1 = h.evaluate_all_lagrange_coefficients(beta_1);
+        let mut m_evals = vec![F::zero(); h.size()];
+        for (matrix, eta) in [&self.index.a, &self.index.b, &self.index.c]
+            .iter()
+            .zip(etas)
+        {
+            for (row, m_eval) in matrix.iter().zip(&mut m_evals) {
+                for (coeff, c) in row {
+                    *m_eval += &(*eta * coeff * &lagrange_at_beta_1[h.reindex_by_subdomain(x, *c)]);
+                }
+            }
+        }
+        let m_poly = DensePolynomial::from_coefficients_vec(h.ifft(&m_evals));
+
+        let q_2 = r_alpha * &m_poly;
+        let (h_2, remainder) = q_2.divide_by_vanishing_poly(h).unwrap();
+        let (g_2, _) = split_sumcheck_remainder(&h, &remainder);
+
+        end_timer!(round_time);
+        Ok((g_2, h_2))
+    }
+
+    /// Computes the sum `σ_3 = sum_M η_M M̂(β_2, β_1)` and the polynomials
+    /// `g_3` and `h_3` of the third sumcheck, `a - b (X g_3 + σ_3 / |K|) =
+    /// h_3 v_K`, where `f_3 = a / b` over `K` is the summand.
+    pub(crate) fn fourth_round(
+        &mut self,
+        beta_1: F,
+        beta_2: F,
+    ) -> Result<(DensePolynomial<F>, DensePolynomial<F>, F), Error> {
+        let round_time = start_timer!(|| "AHP::Prover::FourthRound");
+        let Domains { h, k, .. } = self.domains;
+        let (_, etas) = self.alpha_and_etas.take().unwrap();
+        let arithmetizations = [
+            &self.index.a_arith,
+            &self.index.b_arith,
+            &self.index.c_arith,
+        ];
+        let v_h_product =
+            h.evaluate_vanishing_polynomial(beta_2) * &h.evaluate_vanishing_polynomial(beta_1);
+
+        let mut f_3_evals = vec![F::zero(); k.size()];
+        for (arith, eta) in arithmetizations.iter().zip(&etas) {
+            let mut inverses = arith
+                .row_evals
+                .iter()
+                .zip(&arith.col_evals)
+                .map(|(row, col)| (beta_2 - row) * &(beta_1 - col))
+                .collect::<Vec<_>>();
+            batch_inversion(&mut inverses);
+            for ((f_3_eval, val), inverse) in
+                f_3_evals.iter_mut().zip(&arith.val_evals).zip(&inverses)
+            {
+                *f_3_eval += &(v_h_product * eta * val * inverse);
+            }
+        }
+        let f_3 = DensePolynomial::from_coefficients_vec(k.ifft(&f_3_evals));
+        let (g_3, sigma_3) = split_sumcheck_remainder(&k, &f_3);
+
+        // `b = prod_M (β_2 - row_M)(β_1 - col_M)`, and
+        // `a = v_H(β_2) v_H(β_1) sum_M η_M val_M prod_{N != M} (β_2 - row_N)(β_1 -
+        // col_N)`.
+        let denominators = arithmetizations
+            .iter()
+            .map(|arith| {
+                let row = &DensePolynomial::from_coefficients_vec(vec![beta_2]) - &arith.row;
+                let col = &DensePolynomial::from_coefficients_vec(vec![beta_1]) - &arith.col;
+                &row * &col
+            })
+            .collect::<Vec<_>>();
+        let mut a = DensePolynomial::zero();
+        for (i, (arith, eta)) in arithmetizations.iter().zip(&etas).enumerate() {
+            let mut term = arith.val.clone();
+            for (j, denominator) in denominators.iter().enumerate() {
+                if j != i {
+                    term = &term * denominator;
+                }
+            }
+            a += (v_h_product * eta, &term);
+        }
+        let b = &(&denominators[0] * &denominators[1]) * &denominators[2];
+
+        let (h_3, remainder) = (&a - &(&b * &f_3)).divide_by_vanishing_poly(k).unwrap();
+        assert!(remainder.is_zero());
+
+        end_timer!(round_time);
+        Ok((g_3, h_3, sigma_3))
+    }
+}
+
+/// Adds `v_H` times a random polynomial of degree `ZK_BOUND - 1` to the
+/// polynomial with coefficients `coeffs`, which leaves it unchanged over `H`.
+fn mask_with_vanishing_polynomial<F: PrimeField, R: RngCore>(
+    domain: &GeneralEvaluationDomain<F>,
+    coeffs: Vec<F>,
+    rng: &mut R,
+) -> DensePolynomial<F> {
+    let mut coeffs = coeffs;
+    coeffs.resize(domain.size() + ZK_BOUND, F::zero());
+    for i in 0..ZK_BOUND {
+        let r = F::rand(rng);
+        coeffs[i] -= &r;
+        coeffs[domain.size() + i] += &r;
+    }
+    DensePolynomial::from_coefficients_vec(coeffs)
+}
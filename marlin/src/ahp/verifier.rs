@@ -0,0 +1,105 @@
+use crate::{
+    ahp::{evaluate_bivariate_kernel, Domains},
+    Vec,
+};
+use algebra_core::PrimeField;
+use ff_fft::EvaluationDomain;
+
+/// The challenges of the verifier of the AHP.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct VerifierChallenges<F: PrimeField> {
+    pub(crate) alpha: F,
+    pub(crate) etas: [F; 3],
+    pub(crate) beta_1: F,
+    pub(crate) beta_2: F,
+    pub(crate) beta_3: F,
+}
+
+/// The evaluations that the verifier of the AHP queries.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct VerifierQueries<F: PrimeField> {
+    pub(crate) w: F,
+    pub(crate) z_a: F,
+    pub(crate) z_b: F,
+    pub(crate) mask: F,
+    pub(crate) t: F,
+    pub(crate) g_1: F,
+    pub(crate) h_1: F,
+    pub(crate) g_2: F,
+    pub(crate) h_2: F,
+    pub(crate) g_3: F,
+    pub(crate) h_3: F,
+    /// `row`, `col` and `val` of `A`, `B` and `C` at `β_3`.
+    pub(crate) index: [[F; 3]; 3],
+}
+
+/// Checks the three sumchecks of the AHP for the padded instance
+/// `public_input` at the queried points.
+pub(crate) fn check_sumchecks<F: PrimeField>(
+    domains: &Domains<F>,
+    public_input: &[F],
+    challenges: &VerifierChallenges<F>,
+    queries: &VerifierQueries<F>,
+    sigma_3: F,
+) -> bool {
+    let check_time = start_timer!(|| "AHP::Verifier::CheckSumchecks");
+    let Domains { h, k, x } = domains;
+    let VerifierChallenges {
+        alpha,
+        etas,
+        beta_1,
+        beta_2,
+        beta_3,
+    } = *challenges;
+    let v_h_at_beta_1 = h.evaluate_vanishing_polynomial(beta_1);
+    let v_h_at_beta_2 = h.evaluate_vanishing_polynomial(beta_2);
+
+    // The first sumcheck, at `β_1`, where `z = w v_X + x̂`.
+    let x_at_beta_1 = x
+        .evaluate_all_lagrange_coefficients(beta_1)
+        .iter()
+        .zip(public_input)
+        .fold(F::zero(), |acc, (l, x_i)| acc + &(*l * x_i));
+    let z_at_beta_1 = queries.w * &x.evaluate_vanishing_polynomial(beta_1) + &x_at_beta_1;
+    let summed_z_m = etas[0] * &queries.z_a
+        + &(etas[1] * &queries.z_b)
+        + &(etas[2] * &queries.z_a * &queries.z_b);
+    let lhs = queries.mask + &(evaluate_bivariate_kernel(h, alpha, beta_1) * &summed_z_m)
+        - &(queries.t * &z_at_beta_1);
+    let rhs = queries.h_1 * &v_h_at_beta_1 + &(beta_1 * &queries.g_1);
+    let first_sumcheck = lhs == rhs;
+
+    // The second sumcheck, at `β_2`, of `u_H(α, X) sum_M η_M M̂(X, β_1)`
+    // to `t(β_1)`.
+    let lhs = evaluate_bivariate_kernel(h, alpha, beta_2) * &sigma_3;
+    let rhs = queries.h_2 * &v_h_at_beta_2
+        + &(beta_2 * &queries.g_2)
+        + &(queries.t * &h.size_as_field_element().inverse().unwrap());
+    let second_sumcheck = lhs == rhs;
+
+    // The third sumcheck, at `β_3`, of `a / b` over `K` to `σ_3`.
+    let denominators = queries
+        .index
+        .iter()
+        .map(|[row, col, _]| (beta_2 - row) * &(beta_1 - col))
+        .collect::<Vec<_>>();
+    let b = denominators.iter().fold(F::one(), |acc, d| acc * d);
+    let a = queries.index.iter().zip(&etas).enumerate().fold(
+        F::zero(),
+        |acc, (i, ([_, _, val], eta))| {
+            let others = denominators
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold(F::one(), |acc, (_, d)| acc * d);
+            acc + &(*eta * val * &others)
+        },
+    ) * &(v_h_at_beta_2 * &v_h_at_beta_1);
+    let lhs = queries.h_3 * &k.evaluate_vanishing_polynomial(beta_3);
+    let rhs = a - &(b * &(beta_3 * &queries.g_3
+        + &(sigma_3 * &k.size_as_field_element().inverse().unwrap())));
+    let third_sumcheck = lhs == rhs;
+
+    end_timer!(check_time);
+    first_sumcheck && second_sumcheck && third_sumcheck
+}
@@ -0,0 +1,95 @@
+use crate::{ahp::indexer::Index, Vec};
+use algebra_core::{
+    bytes::ToBytes,
+    io::{self, Write},
+    serialize::*,
+    AffineCurve, PairingEngine,
+};
+use poly_commit::kzg10;
+
+/// The universal structured reference string, from which the keys of any
+/// index within its size bounds are derived.
+pub type UniversalSRS<E> = kzg10::UniversalParams<E>;
+
+/// A proof that a universal SRS was updated with secrets `s` and `t` known
+/// to the contributor, which multiply the trapdoors `β` and `γ`.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SRSUpdateProof<E: PairingEngine> {
+    /// `sG`, for the generator `G` of the SRS.
+    pub s_g: E::G1Affine,
+    /// `tH`, for the generator `H` of the SRS.
+    pub t_h: E::G2Affine,
+    /// The proof of knowledge of `s`.
+    pub s_proof: KnowledgeProof<E::G1Affine>,
+    /// The proof of knowledge of `t`.
+    pub t_proof: KnowledgeProof<E::G2Affine>,
+}
+
+/// A Schnorr proof of knowledge of `x` such that `X = xP`, which consists of
+/// a commitment `R = kP` and the response `z = k + cx` to the challenge `c`.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KnowledgeProof<G: AffineCurve> {
+    pub commitment: G,
+    pub response: G::ScalarField,
+}
+
+/// The sizes of an indexed constraint system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct IndexInfo {
+    /// The number of instance variables, including the constant one, padded
+    /// to the size of the instance domain.
+    pub num_instance_variables: usize,
+    /// The number of instance and witness variables.
+    pub num_variables: usize,
+    /// The number of constraints.
+    pub num_constraints: usize,
+    /// The maximum number of non-zero entries of the matrices.
+    pub num_non_zero: usize,
+}
+
+impl ToBytes for IndexInfo {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        (self.num_instance_variables as u64).write(&mut writer)?;
+        (self.num_variables as u64).write(&mut writer)?;
+        (self.num_constraints as u64).write(&mut writer)?;
+        (self.num_non_zero as u64).write(&mut writer)
+    }
+}
+
+/// The key used to verify proofs for an index.
+#[derive(Clone, Debug)]
+pub struct IndexVerifierKey<E: PairingEngine> {
+    pub index_info: IndexInfo,
+    /// The commitments to the `row`, `col` and `val` polynomials of `A`, `B`
+    /// and `C`, in that order.
+    pub index_commitments: Vec<kzg10::Commitment<E>>,
+    pub verifier_key: kzg10::VerifierKey<E>,
+    /// The maximum degree of the universal SRS, relative to which degree
+    /// bounds are enforced.
+    pub max_degree: usize,
+}
+
+/// The key used to prove statements about an index.
+#[derive(Clone, Debug)]
+pub struct IndexProverKey<E: PairingEngine> {
+    pub index: Index<E::Fr>,
+    pub index_vk: IndexVerifierKey<E>,
+    /// The powers of the whole universal SRS, which the shifted commitments
+    /// that enforce degree bounds need.
+    pub committer_key: kzg10::Powers<E>,
+}
+
+/// A Marlin proof.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Proof<E: PairingEngine> {
+    /// The commitments sent by the prover in each round.
+    pub commitments: Vec<Vec<kzg10::Commitment<E>>>,
+    /// The evaluations of the committed and index polynomials at the
+    /// verifier's query points.
+    pub evaluations: Vec<E::Fr>,
+    /// The claimed sum `sigma_3` of the last sumcheck.
+    pub sigma3: E::Fr,
+    /// An opening proof for each query point.
+    pub opening_proofs: Vec<kzg10::Proof<E>>,
+}
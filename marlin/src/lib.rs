@@ -0,0 +1,612 @@
+//! An implementation of the [`Marlin`] preprocessing zkSNARK, whose
+//! universal SRS is shared by all constraint systems up to a given size.
+//!
+//! Any `ConstraintSynthesizer` is indexed against the universal SRS without
+//! a circuit-specific setup, and its proofs consist of KZG10 commitments to
+//! the polynomials of the AHP for R1CS, made non-interactive with a
+//! `FiatShamirRng`.
+//!
+//! The universal SRS is updatable: anyone can contribute fresh secrets to it
+//! with `Marlin::update_srs`, and the SRS is sound as long as one of its
+//! contributors was honest.
+//!
+//! [`Marlin`]: https://eprint.iacr.org/2019/1047
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(unused_import_braces, unused_qualifications, trivial_casts)]
+#![deny(trivial_numeric_casts, private_in_public, variant_size_differences)]
+#![deny(stable_features, unreachable_pub, non_shorthand_field_patterns)]
+#![deny(unused_attributes, unused_imports, unused_mut)]
+#![deny(renamed_and_removed_lints, stable_features, unused_allocation)]
+#![deny(unused_comparisons, bare_trait_objects, unused_must_use, const_err)]
+#![forbid(unsafe_code)]
+
+#[macro_use]
+extern crate bench_utils;
+
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "std")]
+pub(crate) use std::{string::String, vec::Vec};
+
+use algebra_core::{
+    to_bytes, AffineCurve, Field, One, PairingEngine, PrimeField, ProjectiveCurve, ToBytes,
+    UniformRand, Zero,
+};
+use core::marker::PhantomData;
+use crypto_primitives::FiatShamirRng;
+use ff_fft::{DensePolynomial, EvaluationDomain, GeneralEvaluationDomain};
+use poly_commit::kzg10::{self, Commitment, Randomness, KZG10};
+use r1cs_core::{ConstraintSynthesizer, SynthesisError};
+use rand::{rngs::mock::StepRng, RngCore};
+
+/// The algebraic holographic proof for R1CS.
+pub mod ahp;
+
+mod data_structures;
+pub use data_structures::*;
+
+#[cfg(test)]
+mod test;
+
+use ahp::{
+    indexer::Index,
+    prover::ProverState,
+    verifier::{check_sumchecks, VerifierChallenges, VerifierQueries},
+    Domains, ZK_BOUND,
+};
+
+/// The errors of the Marlin zkSNARK.
+#[derive(Debug)]
+pub enum Error {
+    /// The index needs a universal SRS of at least the given degree.
+    IndexTooLarge(usize),
+    /// The instance or the assignment does not match the index.
+    InstanceDoesNotMatchIndex,
+    /// Synthesizing the constraint system failed, or its assignment is not
+    /// satisfying.
+    SynthesisError(SynthesisError),
+    /// The polynomial commitment scheme failed.
+    PolynomialCommitmentError(poly_commit::Error),
+}
+
+impl From<SynthesisError> for Error {
+    fn from(error: SynthesisError) -> Self {
+        Error::SynthesisError(error)
+    }
+}
+
+impl From<poly_commit::Error> for Error {
+    fn from(error: poly_commit::Error) -> Self {
+        Error::PolynomialCommitmentError(error)
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::IndexTooLarge(degree) => {
+                write!(f, "the index needs a universal SRS of degree {}", degree)
+            },
+            Error::InstanceDoesNotMatchIndex => write!(f, "the instance does not match the index"),
+            Error::SynthesisError(error) => write!(f, "{}", error),
+            Error::PolynomialCommitmentError(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl algebra_core::Error for Error {}
+
+/// The number of commitments sent by the prover in each round.
+const NUM_ROUND_COMMITMENTS: [usize; 4] = [4, 4, 3, 3];
+/// The number of evaluations at `β_1`, `β_2` and `β_3`.
+const NUM_POINT_EVALUATIONS: [usize; 3] = [8, 3, 12];
+
+/// The Marlin zkSNARK over the pairing `E`, made non-interactive with the
+/// Fiat-Shamir transcript `FS`.
+pub struct Marlin<E: PairingEngine, FS: FiatShamirRng<E::Fr>> {
+    _engine: PhantomData<E>,
+    _fiat_shamir_rng: PhantomData<FS>,
+}
+
+impl<E: PairingEngine, FS: FiatShamirRng<E::Fr>> Marlin<E, FS> {
+    /// The domain of the Fiat-Shamir transcripts of proofs.
+    pub const PROTOCOL_NAME: &'static [u8] = b"MARLIN-2019";
+    /// The domain of the Fiat-Shamir transcripts of SRS updates.
+    pub const SRS_UPDATE_NAME: &'static [u8] = b"MARLIN-2019-SRS-UPDATE";
+
+    /// Samples a universal SRS for constraint systems with up to
+    /// `num_constraints` constraints, `num_variables` variables and
+    /// `num_non_zero` non-zero entries in each matrix.
+    pub fn universal_setup<R: RngCore>(
+        num_constraints: usize,
+        num_variables: usize,
+        num_non_zero: usize,
+        rng: &mut R,
+    ) -> Result<UniversalSRS<E>, Error> {
+        let setup_time = start_timer!(|| "Marlin::UniversalSetup");
+        let max_degree = ahp::max_degree::<E::Fr>(num_constraints, num_variables, num_non_zero)?;
+        let srs = KZG10::setup(max_degree, 1, rng)?;
+        end_timer!(setup_time);
+        Ok(srs)
+    }
+
+    /// Contributes fresh secrets to the universal SRS `srs`, as in
+    /// `KZG10::update`. The updated SRS is sound as long as one of its
+    /// contributors, including the initial setup, discarded their secrets,
+    /// and anyone can check the contribution with `verify_srs_update`.
+    pub fn update_srs<R: RngCore>(
+        srs: &UniversalSRS<E>,
+        rng: &mut R,
+    ) -> Result<(UniversalSRS<E>, SRSUpdateProof<E>), Error> {
+        let update_time = start_timer!(|| "Marlin::UpdateSRS");
+        let s = nonzero_scalar::<E::Fr, _>(rng);
+        let t = nonzero_scalar::<E::Fr, _>(rng);
+        let updated = KZG10::update(srs, s, t);
+
+        let g = srs.powers_of_g[0];
+        let h = srs.powers_of_h[0];
+        let s_g = g.mul(s).into_affine();
+        let t_h = h.mul(t).into_affine();
+        let mut fs = Self::init_update_transcript(srs)?;
+        let s_proof = prove_knowledge(&mut fs, g, s_g, s, rng)?;
+        let t_proof = prove_knowledge(&mut fs, h, t_h, t, rng)?;
+        end_timer!(update_time);
+        Ok((
+            updated,
+            SRSUpdateProof {
+                s_g,
+                t_h,
+                s_proof,
+                t_proof,
+            },
+        ))
+    }
+
+    /// Checks that `updated` extends `previous` with secrets known to the
+    /// contributor of `proof`. The initial SRS can be checked to be well
+    /// formed with `KZG10::check_params`.
+    pub fn verify_srs_update<R: RngCore>(
+        previous: &UniversalSRS<E>,
+        updated: &UniversalSRS<E>,
+        proof: &SRSUpdateProof<E>,
+        rng: &mut R,
+    ) -> Result<bool, Error> {
+        let verify_time = start_timer!(|| "Marlin::VerifySRSUpdate");
+        let result = KZG10::check_update(previous, updated, &proof.s_g, &proof.t_h, rng)? && {
+            let mut fs = Self::init_update_transcript(previous)?;
+            let g = previous.powers_of_g[0];
+            let h = previous.powers_of_h[0];
+            verify_knowledge(&mut fs, g, proof.s_g, &proof.s_proof)?
+                && verify_knowledge(&mut fs, h, proof.t_h, &proof.t_proof)?
+        };
+        end_timer!(verify_time, || format!("Result: {}", result));
+        Ok(result)
+    }
+
+    /// Derives the keys of the constraint system of `circuit` from the
+    /// universal SRS. The assignment of `circuit` is not needed.
+    pub fn index<C: ConstraintSynthesizer<E::Fr>>(
+        srs: &UniversalSRS<E>,
+        circuit: C,
+    ) -> Result<(IndexProverKey<E>, IndexVerifierKey<E>), Error> {
+        let index_time = start_timer!(|| "Marlin::Index");
+        let index = Index::new(circuit)?;
+        let max_degree = index.domains()?.max_degree();
+        if max_degree > srs.max_degree() {
+            return Err(Error::IndexTooLarge(max_degree));
+        }
+
+        let (committer_key, verifier_key) = KZG10::trim(srs, srs.max_degree(), 1)?;
+        let commit_time = start_timer!(|| "Committing to index polynomials");
+        let index_commitments = index
+            .polynomials()
+            .into_iter()
+            .map(|p| Ok(KZG10::commit(&committer_key, p, None, None::<&mut StepRng>)?.0))
+            .collect::<Result<Vec<_>, Error>>()?;
+        end_timer!(commit_time);
+
+        let index_vk = IndexVerifierKey {
+            index_info: index.index_info,
+            index_commitments,
+            verifier_key,
+            max_degree: srs.max_degree(),
+        };
+        let index_pk = IndexProverKey {
+            index,
+            index_vk: index_vk.clone(),
+            committer_key,
+        };
+        end_timer!(index_time);
+        Ok((index_pk, index_vk))
+    }
+
+    /// Proves that the assignment of `circuit` satisfies the constraint
+    /// system of `index_pk`.
+    pub fn prove<C: ConstraintSynthesizer<E::Fr>, R: RngCore>(
+        index_pk: &IndexProverKey<E>,
+        circuit: C,
+        rng: &mut R,
+    ) -> Result<Proof<E>, Error> {
+        let prover_time = start_timer!(|| "Marlin::Prover");
+        let index_vk = &index_pk.index_vk;
+        let ck = &index_pk.committer_key;
+        let domains = index_pk.index.domains()?;
+        let mut state = ProverState::init(&index_pk.index, circuit)?;
+        let mut fs = Self::init_transcript(index_vk, &state.public_input)?;
+
+        let first = state.first_round(rng)?;
+        let first_polys = vec![first.w, first.z_a, first.z_b, first.mask];
+        let first_hiding = [true; 4];
+        let (first_comms, first_rands) = Self::commit(ck, &first_polys, &first_hiding, rng)?;
+        absorb_commitments(&mut fs, &first_comms)?;
+        let alpha = squeeze_challenge(&mut fs, &domains.h, &[]);
+        let etas = fs.squeeze_field_elements(3);
+        let etas = [etas[0], etas[1], etas[2]];
+
+        let second = state.second_round(alpha, etas)?;
+        let g_1_shifted = shift(&second.g_1, index_vk.max_degree - domains.h_degree_bound());
+        let second_polys = vec![second.t, second.g_1, g_1_shifted, second.h_1];
+        let second_hiding = [false, true, true, false];
+        let (second_comms, second_rands) = Self::commit(ck, &second_polys, &second_hiding, rng)?;
+        absorb_commitments(&mut fs, &second_comms)?;
+        let beta_1 = squeeze_challenge(&mut fs, &domains.h, &[alpha]);
+
+        let (g_2, h_2) = state.third_round(beta_1)?;
+        let g_2_shifted = shift(&g_2, index_vk.max_degree - domains.h_degree_bound());
+        let third_polys = vec![g_2, g_2_shifted, h_2];
+        let (third_comms, third_rands) = Self::commit(ck, &third_polys, &[false; 3], rng)?;
+        absorb_commitments(&mut fs, &third_comms)?;
+        let beta_2 = squeeze_challenge(&mut fs, &domains.h, &[alpha]);
+
+        let (g_3, h_3, sigma3) = state.fourth_round(beta_1, beta_2)?;
+        let g_3_shifted = shift(&g_3, index_vk.max_degree - domains.k_degree_bound());
+        let fourth_polys = vec![g_3, g_3_shifted, h_3];
+        let (fourth_comms, fourth_rands) = Self::commit(ck, &fourth_polys, &[false; 3], rng)?;
+        absorb_commitments(&mut fs, &fourth_comms)?;
+        fs.absorb_field_elements(&[sigma3]);
+        let beta_3 = squeeze_challenge(&mut fs, &domains.k, &[]);
+
+        // The polynomials and randomness opened at `β_1`, `β_2` and `β_3`.
+        let empty_randomness = Randomness::empty();
+        let index_polys = index_pk.index.polynomials();
+        let query_sets = [
+            (
+                beta_1,
+                first_polys
+                    .iter()
+                    .chain(&second_polys)
+                    .zip(first_rands.iter().chain(&second_rands))
+                    .collect::<Vec<_>>(),
+            ),
+            (beta_2, third_polys.iter().zip(&third_rands).collect()),
+            (
+                beta_3,
+                fourth_polys
+                    .iter()
+                    .zip(&fourth_rands)
+                    .chain(index_polys.into_iter().map(|p| (p, &empty_randomness)))
+                    .collect(),
+            ),
+        ];
+
+        let eval_time = start_timer!(|| "Evaluating polynomials");
+        let evaluations = query_sets
+            .iter()
+            .flat_map(|(point, polys)| polys.iter().map(move |(p, _)| p.evaluate(*point)))
+            .collect::<Vec<_>>();
+        end_timer!(eval_time);
+        fs.absorb_field_elements(&evaluations);
+        let opening_challenge = fs.squeeze_field_elements(1)[0];
+
+        let open_time = start_timer!(|| "Opening polynomials");
+        let mut opening_proofs = Vec::with_capacity(query_sets.len());
+        for (point, polys) in &query_sets {
+            let mut combined_poly = DensePolynomial::zero();
+            let mut combined_randomness = Randomness::empty();
+            let mut coeff = E::Fr::one();
+            for (p, randomness) in polys {
+                combined_poly += (coeff, *p);
+                combined_randomness += (coeff, *randomness);
+                coeff *= &opening_challenge;
+            }
+            opening_proofs.push(KZG10::open(
+                ck,
+                &combined_poly,
+                *point,
+                &combined_randomness,
+            )?);
+        }
+        end_timer!(open_time);
+
+        end_timer!(prover_time);
+        Ok(Proof {
+            commitments: vec![first_comms, second_comms, third_comms, fourth_comms],
+            evaluations,
+            sigma3,
+            opening_proofs,
+        })
+    }
+
+    /// Verifies that `proof` shows that some witness together with
+    /// `public_input` satisfies the constraint system of `index_vk`. The
+    /// openings are checked in a batch randomized with `rng`.
+    pub fn verify<R: RngCore>(
+        index_vk: &IndexVerifierKey<E>,
+        public_input: &[E::Fr],
+        proof: &Proof<E>,
+        rng: &mut R,
+    ) -> Result<bool, Error> {
+        let verifier_time = start_timer!(|| "Marlin::Verify");
+        let domains = Domains::new(&index_vk.index_info)?;
+        if public_input.len() >= index_vk.index_info.num_instance_variables {
+            return Err(Error::InstanceDoesNotMatchIndex);
+        }
+        let mut padded_public_input = vec![E::Fr::one()];
+        padded_public_input.extend_from_slice(public_input);
+        padded_public_input.resize(index_vk.index_info.num_instance_variables, E::Fr::zero());
+
+        let is_well_formed = proof
+            .commitments
+            .iter()
+            .map(|comms| comms.len())
+            .eq(NUM_ROUND_COMMITMENTS.iter().copied())
+            && proof.evaluations.len() == NUM_POINT_EVALUATIONS.iter().sum::<usize>()
+            && proof.opening_proofs.len() == NUM_POINT_EVALUATIONS.len();
+        if !is_well_formed {
+            return Ok(false);
+        }
+
+        let mut fs = Self::init_transcript(index_vk, &padded_public_input)?;
+        absorb_commitments(&mut fs, &proof.commitments[0])?;
+        let alpha = squeeze_challenge(&mut fs, &domains.h, &[]);
+        let etas = fs.squeeze_field_elements(3);
+        absorb_commitments(&mut fs, &proof.commitments[1])?;
+        let beta_1 = squeeze_challenge(&mut fs, &domains.h, &[alpha]);
+        absorb_commitments(&mut fs, &proof.commitments[2])?;
+        let beta_2 = squeeze_challenge(&mut fs, &domains.h, &[alpha]);
+        absorb_commitments(&mut fs, &proof.commitments[3])?;
+        fs.absorb_field_elements(&[proof.sigma3]);
+        let beta_3 = squeeze_challenge(&mut fs, &domains.k, &[]);
+        fs.absorb_field_elements(&proof.evaluations);
+        let opening_challenge = fs.squeeze_field_elements(1)[0];
+
+        // The evaluations follow the order of the commitments, then of the
+        // index commitments.
+        let e = &proof.evaluations;
+        let challenges = VerifierChallenges {
+            alpha,
+            etas: [etas[0], etas[1], etas[2]],
+            beta_1,
+            beta_2,
+            beta_3,
+        };
+        let queries = VerifierQueries {
+            w: e[0],
+            z_a: e[1],
+            z_b: e[2],
+            mask: e[3],
+            t: e[4],
+            g_1: e[5],
+            h_1: e[7],
+            g_2: e[8],
+            h_2: e[10],
+            g_3: e[11],
+            h_3: e[13],
+            index: [
+                [e[14], e[15], e[16]],
+                [e[17], e[18], e[19]],
+                [e[20], e[21], e[22]],
+            ],
+        };
+
+        // The shifted commitments enforce the degree bounds of `g_1`, `g_2`
+        // and `g_3` relative to the degree of the SRS.
+        let h_shift = (index_vk.max_degree - domains.h_degree_bound()) as u64;
+        let k_shift = (index_vk.max_degree - domains.k_degree_bound()) as u64;
+        let degree_bounds_hold = e[6] == beta_1.pow(&[h_shift]) * &e[5]
+            && e[9] == beta_2.pow(&[h_shift]) * &e[8]
+            && e[12] == beta_3.pow(&[k_shift]) * &e[11];
+        if !degree_bounds_hold
+            || !check_sumchecks(
+                &domains,
+                &padded_public_input,
+                &challenges,
+                &queries,
+                proof.sigma3,
+            )
+        {
+            end_timer!(verifier_time);
+            return Ok(false);
+        }
+
+        let commitments = proof
+            .commitments
+            .iter()
+            .flatten()
+            .chain(&index_vk.index_commitments)
+            .collect::<Vec<_>>();
+        let points = [beta_1, beta_2, beta_3];
+        let mut combined_commitments = Vec::with_capacity(points.len());
+        let mut combined_values = Vec::with_capacity(points.len());
+        let mut offset = 0;
+        for num_evaluations in NUM_POINT_EVALUATIONS.iter() {
+            let mut combined_commitment = Commitment::default();
+            let mut combined_value = E::Fr::zero();
+            let mut coeff = E::Fr::one();
+            for (commitment, value) in commitments[offset..offset + num_evaluations]
+                .iter()
+                .zip(&e[offset..offset + num_evaluations])
+            {
+                combined_commitment += (coeff, *commitment);
+                combined_value += &(coeff * value);
+                coeff *= &opening_challenge;
+            }
+            combined_commitments.push(combined_commitment);
+            combined_values.push(combined_value);
+            offset += num_evaluations;
+        }
+        let result = KZG10::batch_check(
+            &index_vk.verifier_key,
+            &combined_commitments,
+            &points,
+            &combined_values,
+            &proof.opening_proofs,
+            rng,
+        )?;
+
+        end_timer!(verifier_time);
+        Ok(result)
+    }
+
+    /// Starts the transcript of a proof with the index and the padded
+    /// instance.
+    fn init_transcript(
+        index_vk: &IndexVerifierKey<E>,
+        padded_public_input: &[E::Fr],
+    ) -> Result<FS, Error> {
+        let mut fs = FS::new(&FS::setup(), Self::PROTOCOL_NAME);
+        fs.absorb_bytes(&to_bytes![index_vk.index_info].map_err(SynthesisError::from)?);
+        absorb_commitments(&mut fs, &index_vk.index_commitments)?;
+        fs.absorb_field_elements(padded_public_input);
+        Ok(fs)
+    }
+
+    /// Starts the transcript of the proofs of knowledge of an update of
+    /// `srs`, which binds them to the SRS they update.
+    fn init_update_transcript(srs: &UniversalSRS<E>) -> Result<FS, Error> {
+        let mut fs = FS::new(&FS::setup(), Self::SRS_UPDATE_NAME);
+        let bytes = to_bytes![
+            srs.powers_of_g[1],
+            srs.powers_of_gamma_g[0],
+            srs.powers_of_h[1]
+        ]
+        .map_err(SynthesisError::from)?;
+        fs.absorb_bytes(&bytes);
+        Ok(fs)
+    }
+
+    /// Commits to `polys`, of which those marked in `hiding` hide
+    /// `ZK_BOUND` evaluations.
+    fn commit<R: RngCore>(
+        ck: &kzg10::Powers<E>,
+        polys: &[DensePolynomial<E::Fr>],
+        hiding: &[bool],
+        rng: &mut R,
+    ) -> Result<(Vec<Commitment<E>>, Vec<Randomness<E>>), Error> {
+        let commit_time = start_timer!(|| format!("Committing to {} polynomials", polys.len()));
+        let mut commitments = Vec::with_capacity(polys.len());
+        let mut randomness = Vec::with_capacity(polys.len());
+        for (p, hiding) in polys.iter().zip(hiding) {
+            let hiding_bound = if *hiding { Some(ZK_BOUND) } else { None };
+            let (commitment, rand) = KZG10::commit(ck, p, hiding_bound, Some(&mut *rng))?;
+            commitments.push(commitment);
+            randomness.push(rand);
+        }
+        end_timer!(commit_time);
+        Ok((commitments, randomness))
+    }
+}
+
+fn absorb_commitments<F: PrimeField, FS: FiatShamirRng<F>, T: ToBytes>(
+    fs: &mut FS,
+    commitments: &[T],
+) -> Result<(), Error> {
+    for commitment in commitments {
+        fs.absorb_bytes(&to_bytes![commitment].map_err(SynthesisError::from)?);
+    }
+    Ok(())
+}
+
+/// Proves knowledge of `x` such that `public = x · base`, with the challenge
+/// drawn from `fs`.
+fn prove_knowledge<G, FS, R>(
+    fs: &mut FS,
+    base: G,
+    public: G,
+    x: G::ScalarField,
+    rng: &mut R,
+) -> Result<KnowledgeProof<G>, Error>
+where
+    G: AffineCurve,
+    FS: FiatShamirRng<G::ScalarField>,
+    R: RngCore,
+{
+    let k = G::ScalarField::rand(rng);
+    let commitment = base.mul(k).into_affine();
+    let challenge = knowledge_challenge(fs, base, public, commitment)?;
+    Ok(KnowledgeProof {
+        commitment,
+        response: k + &(challenge * &x),
+    })
+}
+
+/// Checks that `z · base == R + c · public` for the proof `(R, z)`.
+fn verify_knowledge<G, FS>(
+    fs: &mut FS,
+    base: G,
+    public: G,
+    proof: &KnowledgeProof<G>,
+) -> Result<bool, Error>
+where
+    G: AffineCurve,
+    FS: FiatShamirRng<G::ScalarField>,
+{
+    let challenge = knowledge_challenge(fs, base, public, proof.commitment)?;
+    let mut rhs = public.mul(challenge);
+    rhs.add_assign_mixed(&proof.commitment);
+    Ok(base.mul(proof.response) == rhs)
+}
+
+fn knowledge_challenge<G, FS>(
+    fs: &mut FS,
+    base: G,
+    public: G,
+    commitment: G,
+) -> Result<G::ScalarField, Error>
+where
+    G: AffineCurve,
+    FS: FiatShamirRng<G::ScalarField>,
+{
+    fs.absorb_bytes(&to_bytes![base, public, commitment].map_err(SynthesisError::from)?);
+    Ok(fs.squeeze_field_elements(1)[0])
+}
+
+fn nonzero_scalar<F: PrimeField, R: RngCore>(rng: &mut R) -> F {
+    loop {
+        let scalar = F::rand(rng);
+        if !scalar.is_zero() {
+            return scalar;
+        }
+    }
+}
+
+/// Squeezes a challenge outside `domain` which differs from `excluded`.
+fn squeeze_challenge<F: PrimeField, FS: FiatShamirRng<F>>(
+    fs: &mut FS,
+    domain: &GeneralEvaluationDomain<F>,
+    excluded: &[F],
+) -> F {
+    loop {
+        let challenge = fs.squeeze_field_elements(1)[0];
+        if !domain.evaluate_vanishing_polynomial(challenge).is_zero()
+            && !excluded.contains(&challenge)
+        {
+            return challenge;
+        }
+    }
+}
+
+/// Computes `X^shift p`.
+fn shift<F: PrimeField>(p: &DensePolynomial<F>, shift: usize) -> DensePolynomial<F> {
+    if p.is_zero() {
+        return DensePolynomial::zero();
+    }
+    let mut coeffs = vec![F::zero(); shift];
+    coeffs.extend_from_slice(&p.coeffs);
+    DensePolynomial::from_coefficients_vec(coeffs)
+}
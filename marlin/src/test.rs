@@ -0,0 +1,291 @@
+use algebra_core::Field;
+use r1cs_core::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+
+struct MySillyCircuit<F: Field> {
+    a: Option<F>,
+    b: Option<F>,
+    num_constraints: usize,
+    num_variables: usize,
+}
+
+impl<ConstraintF: Field> ConstraintSynthesizer<ConstraintF> for MySillyCircuit<ConstraintF> {
+    fn generate_constraints<CS: ConstraintSystem<ConstraintF>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
+        let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
+        let c = cs.alloc_input(
+            || "c",
+            || {
+                let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+                let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
+
+                a.mul_assign(&b);
+                Ok(a)
+            },
+        )?;
+        let d = cs.alloc_input(
+            || "d",
+            || {
+                let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+                let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
+
+                a.mul_assign(&b);
+                a.mul_assign(&b);
+                Ok(a)
+            },
+        )?;
+
+        for i in 0..(self.num_variables - 3) {
+            let _ = cs.alloc(
+                || format!("var {}", i),
+                || self.a.ok_or(SynthesisError::AssignmentMissing),
+            )?;
+        }
+
+        for i in 0..(self.num_constraints - 1) {
+            cs.enforce(
+                || format!("a*b=c {}", i),
+                |lc| lc + a,
+                |lc| lc + b,
+                |lc| lc + c,
+            );
+        }
+        cs.enforce(|| "c*b=d", |lc| lc + c, |lc| lc + b, |lc| lc + d);
+
+        Ok(())
+    }
+}
+
+mod bls12_381 {
+    use super::*;
+    use crate::{Error, Marlin};
+    use crypto_primitives::fiat_shamir::blake2s::Blake2sFiatShamirRng;
+
+    use algebra::bls12_381::{Bls12_381, Fr};
+    use algebra_core::{test_rng, One, UniformRand};
+    use core::ops::MulAssign;
+
+    type MarlinInst = Marlin<Bls12_381, Blake2sFiatShamirRng>;
+
+    fn circuit(a: Option<Fr>, b: Option<Fr>, num_constraints: usize) -> MySillyCircuit<Fr> {
+        MySillyCircuit {
+            a,
+            b,
+            num_constraints,
+            num_variables: num_constraints / 2 + 3,
+        }
+    }
+
+    #[test]
+    fn prove_and_verify() {
+        let rng = &mut test_rng();
+
+        let srs = MarlinInst::universal_setup(100, 100, 100, rng).unwrap();
+
+        for &num_constraints in &[3, 10, 25, 100] {
+            let (pk, vk) = MarlinInst::index(&srs, circuit(None, None, num_constraints)).unwrap();
+
+            for _ in 0..5 {
+                let a = Fr::rand(rng);
+                let b = Fr::rand(rng);
+                let mut c = a;
+                c.mul_assign(&b);
+                let mut d = c;
+                d.mul_assign(&b);
+
+                let proof = MarlinInst::prove(&pk, circuit(Some(a), Some(b), num_constraints), rng)
+                    .unwrap();
+
+                assert!(MarlinInst::verify(&vk, &[c, d], &proof, rng).unwrap());
+                assert!(!MarlinInst::verify(&vk, &[a, d], &proof, rng).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn reject_tampered_proofs() {
+        let rng = &mut test_rng();
+
+        let srs = MarlinInst::universal_setup(10, 10, 10, rng).unwrap();
+        let (pk, vk) = MarlinInst::index(&srs, circuit(None, None, 10)).unwrap();
+
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let mut c = a;
+        c.mul_assign(&b);
+        let mut d = c;
+        d.mul_assign(&b);
+
+        let proof = MarlinInst::prove(&pk, circuit(Some(a), Some(b), 10), rng).unwrap();
+        assert!(MarlinInst::verify(&vk, &[c, d], &proof, rng).unwrap());
+
+        let mut tampered = proof.clone();
+        tampered.sigma3 += &Fr::one();
+        assert!(!MarlinInst::verify(&vk, &[c, d], &tampered, rng).unwrap());
+
+        for i in 0..proof.evaluations.len() {
+            let mut tampered = proof.clone();
+            tampered.evaluations[i] += &Fr::one();
+            assert!(!MarlinInst::verify(&vk, &[c, d], &tampered, rng).unwrap());
+        }
+
+        let mut tampered = proof.clone();
+        tampered.commitments[1].swap(0, 3);
+        assert!(!MarlinInst::verify(&vk, &[c, d], &tampered, rng).unwrap());
+
+        let mut truncated = proof.clone();
+        truncated.evaluations.pop();
+        assert!(!MarlinInst::verify(&vk, &[c, d], &truncated, rng).unwrap());
+
+        let mut truncated = proof;
+        truncated.opening_proofs.pop();
+        assert!(!MarlinInst::verify(&vk, &[c, d], &truncated, rng).unwrap());
+
+        assert!(matches!(
+            MarlinInst::verify(&vk, &[c, d, a, b], &truncated, rng),
+            Err(Error::InstanceDoesNotMatchIndex)
+        ));
+    }
+
+    #[test]
+    fn unsatisfied_assignment_fails() {
+        let rng = &mut test_rng();
+
+        let srs = MarlinInst::universal_setup(10, 10, 10, rng).unwrap();
+        let (pk, _) = MarlinInst::index(&srs, circuit(None, None, 10)).unwrap();
+
+        // `MySillyCircuit` derives `c` and `d` from `a` and `b`, so an
+        // unsatisfying assignment comes from a circuit with other inputs.
+        struct WrongCircuit(MySillyCircuit<Fr>);
+        impl ConstraintSynthesizer<Fr> for WrongCircuit {
+            fn generate_constraints<CS: ConstraintSystem<Fr>>(
+                self,
+                cs: &mut CS,
+            ) -> Result<(), SynthesisError> {
+                let a = self.0.a;
+                let b = self.0.b;
+                let a_var = cs.alloc(|| "a", || a.ok_or(SynthesisError::AssignmentMissing))?;
+                let b_var = cs.alloc(|| "b", || b.ok_or(SynthesisError::AssignmentMissing))?;
+                let c = cs.alloc_input(|| "c", || a.ok_or(SynthesisError::AssignmentMissing))?;
+                let d = cs.alloc_input(|| "d", || b.ok_or(SynthesisError::AssignmentMissing))?;
+                for i in 0..(self.0.num_variables - 3) {
+                    let _ = cs.alloc(
+                        || format!("var {}", i),
+                        || a.ok_or(SynthesisError::AssignmentMissing),
+                    )?;
+                }
+                for i in 0..(self.0.num_constraints - 1) {
+                    cs.enforce(
+                        || format!("a*b=c {}", i),
+                        |lc| lc + a_var,
+                        |lc| lc + b_var,
+                        |lc| lc + c,
+                    );
+                }
+                cs.enforce(|| "c*b=d", |lc| lc + c, |lc| lc + b_var, |lc| lc + d);
+                Ok(())
+            }
+        }
+
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let result = MarlinInst::prove(&pk, WrongCircuit(circuit(Some(a), Some(b), 10)), rng);
+        assert!(matches!(
+            result,
+            Err(Error::SynthesisError(SynthesisError::Unsatisfiable))
+        ));
+    }
+
+    #[test]
+    fn update_srs() {
+        let rng = &mut test_rng();
+
+        let srs = MarlinInst::universal_setup(10, 10, 10, rng).unwrap();
+        let (first, first_proof) = MarlinInst::update_srs(&srs, rng).unwrap();
+        let (second, second_proof) = MarlinInst::update_srs(&first, rng).unwrap();
+        assert!(MarlinInst::verify_srs_update(&srs, &first, &first_proof, rng).unwrap());
+        assert!(MarlinInst::verify_srs_update(&first, &second, &second_proof, rng).unwrap());
+
+        // Proofs do not carry over to other updates.
+        assert!(!MarlinInst::verify_srs_update(&first, &second, &first_proof, rng).unwrap());
+        assert!(!MarlinInst::verify_srs_update(&srs, &second, &second_proof, rng).unwrap());
+        let mut tampered = second_proof.clone();
+        tampered.s_proof.response += &Fr::one();
+        assert!(!MarlinInst::verify_srs_update(&first, &second, &tampered, rng).unwrap());
+        let mut tampered = second_proof;
+        tampered.t_proof = first_proof.t_proof;
+        assert!(!MarlinInst::verify_srs_update(&first, &second, &tampered, rng).unwrap());
+
+        // The updated SRS proves as usual.
+        let (pk, vk) = MarlinInst::index(&second, circuit(None, None, 10)).unwrap();
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let mut c = a;
+        c.mul_assign(&b);
+        let mut d = c;
+        d.mul_assign(&b);
+        let proof = MarlinInst::prove(&pk, circuit(Some(a), Some(b), 10), rng).unwrap();
+        assert!(MarlinInst::verify(&vk, &[c, d], &proof, rng).unwrap());
+    }
+
+    #[test]
+    fn srs_too_small() {
+        let rng = &mut test_rng();
+
+        let srs = MarlinInst::universal_setup(10, 10, 10, rng).unwrap();
+        assert!(matches!(
+            MarlinInst::index(&srs, circuit(None, None, 100)),
+            Err(Error::IndexTooLarge(_))
+        ));
+    }
+}
+
+mod bls12_377 {
+    use super::*;
+    use crate::Marlin;
+    use crypto_primitives::fiat_shamir::blake2s::Blake2sFiatShamirRng;
+
+    use algebra::bls12_377::{Bls12_377, Fr};
+    use algebra_core::{test_rng, UniformRand};
+    use core::ops::MulAssign;
+
+    type MarlinInst = Marlin<Bls12_377, Blake2sFiatShamirRng>;
+
+    #[test]
+    fn prove_and_verify() {
+        let rng = &mut test_rng();
+
+        let srs = MarlinInst::universal_setup(20, 20, 20, rng).unwrap();
+        let (pk, vk) = MarlinInst::index(
+            &srs,
+            MySillyCircuit {
+                a: None,
+                b: None,
+                num_constraints: 20,
+                num_variables: 10,
+            },
+        )
+        .unwrap();
+
+        for _ in 0..5 {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+            let mut c = a;
+            c.mul_assign(&b);
+            let mut d = c;
+            d.mul_assign(&b);
+
+            let circuit = MySillyCircuit {
+                a: Some(a),
+                b: Some(b),
+                num_constraints: 20,
+                num_variables: 10,
+            };
+            let proof = MarlinInst::prove(&pk, circuit, rng).unwrap();
+            assert!(MarlinInst::verify(&vk, &[c, d], &proof, rng).unwrap());
+            assert!(!MarlinInst::verify(&vk, &[c, a], &proof, rng).unwrap());
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::Vec;
+use algebra_core::{
+    bytes::ToBytes,
+    io::{self, Write},
+    serialize::*,
+    AffineCurve, PairingEngine, PrimeField, ProjectiveCurve, Zero,
+};
+use core::ops::{Add, AddAssign};
+use ff_fft::DensePolynomial;
+
+/// The public parameters produced by the trusted setup of `KZG10`, from
+/// which `KZG10::trim` derives committer and verifier keys.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct UniversalParams<E: PairingEngine> {
+    /// `{β^i G}`, for `i` from `0` to the maximum degree.
+    pub powers_of_g: Vec<E::G1Affine>,
+    /// `{β^i γG}`, for `i` from `0` to the maximum degree, which commit to
+    /// the blinding polynomials of hiding commitments.
+    pub powers_of_gamma_g: Vec<E::G1Affine>,
+    /// `{β^i H}`, for `i` from `0` to the maximum number of points of a
+    /// multi-point opening.
+    pub powers_of_h: Vec<E::G2Affine>,
+}
+
+impl<E: PairingEngine> UniversalParams<E> {
+    /// The maximum degree of polynomials that can be committed to.
+    pub fn max_degree(&self) -> usize {
+        self.powers_of_g.len() - 1
+    }
+
+    /// The maximum number of points of a multi-point opening.
+    pub fn max_points(&self) -> usize {
+        self.powers_of_h.len() - 1
+    }
+}
+
+/// The powers used to commit to polynomials and to open them.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Powers<E: PairingEngine> {
+    /// `{β^i G}`, for `i` from `0` to the supported degree.
+    pub powers_of_g: Vec<E::G1Affine>,
+    /// `{β^i γG}`, for `i` from `0` to the supported degree.
+    pub powers_of_gamma_g: Vec<E::G1Affine>,
+}
+
+impl<E: PairingEngine> Powers<E> {
+    /// The number of powers, i.e. one more than the supported degree.
+    pub fn size(&self) -> usize {
+        self.powers_of_g.len()
+    }
+}
+
+/// The key used to check openings.
+#[derive(Clone, Debug)]
+pub struct VerifierKey<E: PairingEngine> {
+    /// The generator `G` of G1.
+    pub g: E::G1Affine,
+    /// The generator `γG` of the blinding terms.
+    pub gamma_g: E::G1Affine,
+    /// The generator `H` of G2.
+    pub h: E::G2Affine,
+    /// `βH`.
+    pub beta_h: E::G2Affine,
+    /// `H`, prepared for pairings.
+    pub prepared_h: E::G2Prepared,
+    /// `βH`, prepared for pairings.
+    pub prepared_beta_h: E::G2Prepared,
+    /// `{β^i G}`, for `i` below the supported number of points, which
+    /// commit to the interpolating polynomials of multi-point openings.
+    pub powers_of_g: Vec<E::G1Affine>,
+    /// `{β^i γG}`, for `i` below the supported number of points.
+    pub powers_of_gamma_g: Vec<E::G1Affine>,
+    /// `{β^i H}`, for `i` up to the supported number of points, which
+    /// commit to the vanishing polynomials of multi-point openings.
+    pub powers_of_h: Vec<E::G2Affine>,
+}
+
+impl<E: PairingEngine> VerifierKey<E> {
+    /// The maximum number of points of a multi-point opening.
+    pub fn max_points(&self) -> usize {
+        self.powers_of_h.len() - 1
+    }
+}
+
+/// A commitment `p(β)G + r(β)γG` to a polynomial `p`, where `r` is the
+/// blinding polynomial of hiding commitments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Commitment<E: PairingEngine>(pub E::G1Affine);
+
+impl<E: PairingEngine> Default for Commitment<E> {
+    fn default() -> Self {
+        Commitment(E::G1Affine::zero())
+    }
+}
+
+impl<E: PairingEngine> ToBytes for Commitment<E> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.0.write(writer)
+    }
+}
+
+impl<'a, E: PairingEngine> AddAssign<(E::Fr, &'a Commitment<E>)> for Commitment<E> {
+    /// Adds `coeff` times `other`, which commits to the corresponding linear
+    /// combination of polynomials.
+    #[inline]
+    fn add_assign(&mut self, (coeff, other): (E::Fr, &'a Commitment<E>)) {
+        let mut result = self.0.into_projective();
+        result += &other.0.mul(coeff.into_repr());
+        self.0 = result.into_affine();
+    }
+}
+
+/// The blinding polynomial of a commitment, which is zero for commitments
+/// that are not hiding.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Randomness<E: PairingEngine> {
+    pub blinding_polynomial: DensePolynomial<E::Fr>,
+}
+
+impl<E: PairingEngine> Randomness<E> {
+    /// The randomness of a commitment that is not hiding.
+    pub fn empty() -> Self {
+        Self {
+            blinding_polynomial: DensePolynomial::zero(),
+        }
+    }
+
+    /// Whether the commitment is hiding.
+    pub fn is_hiding(&self) -> bool {
+        !self.blinding_polynomial.is_zero()
+    }
+}
+
+impl<'a, E: PairingEngine> Add<&'a Randomness<E>> for Randomness<E> {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, other: &'a Self) -> Self {
+        self.blinding_polynomial += &other.blinding_polynomial;
+        self
+    }
+}
+
+impl<'a, E: PairingEngine> AddAssign<(E::Fr, &'a Randomness<E>)> for Randomness<E> {
+    #[inline]
+    fn add_assign(&mut self, (coeff, other): (E::Fr, &'a Randomness<E>)) {
+        self.blinding_polynomial += (coeff, &other.blinding_polynomial);
+    }
+}
+
+/// An opening of a commitment at a single point `z`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Proof<E: PairingEngine> {
+    /// The commitment `w` to the witness `(p(X) - p(z)) / (X - z)`, blinded
+    /// by `(r(X) - r(z)) / (X - z)` for hiding commitments.
+    pub w: E::G1Affine,
+    /// The evaluation `r(z)` of the blinding polynomial of hiding
+    /// commitments.
+    pub random_v: Option<E::Fr>,
+}
+
+impl<E: PairingEngine> ToBytes for Proof<E> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.w.write(&mut writer)?;
+        self.random_v.unwrap_or_else(E::Fr::zero).write(&mut writer)
+    }
+}
+
+/// An opening of a commitment at the points `z_1, ..., z_k`.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultiPointProof<E: PairingEngine> {
+    /// The commitment `w` to the witness `(p(X) - I(X)) / Z(X)`, where `I`
+    /// interpolates `p` at the points and `Z` vanishes on them.
+    pub w: E::G1Affine,
+    /// The evaluations `r(z_i)` of the blinding polynomial of hiding
+    /// commitments.
+    pub random_values: Option<Vec<E::Fr>>,
+}
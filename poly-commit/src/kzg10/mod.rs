@@ -0,0 +1,852 @@
+//! The polynomial commitment scheme of [Kate, Zaverucha and Goldberg][kzg10],
+//! with the hiding variant of [Marlin][marlin].
+//!
+//! A commitment to `p` is `p(β)G`, and an opening at `z` is the commitment
+//! `w` to `(p(X) - p(z)) / (X - z)`, which is checked with the pairing
+//! equation `e(C - p(z)G, H) == e(w, βH - zH)`. Hiding commitments add
+//! `r(β)γG` for a random blinding polynomial `r`.
+//!
+//! [kzg10]: https://www.iacr.org/archive/asiacrypt2010/6477178/6477178.pdf
+//! [marlin]: https://eprint.iacr.org/2019/1047
+use crate::{Error, Vec};
+use algebra_core::{
+    msm::{FixedBaseMSM, VariableBaseMSM},
+    AffineCurve, Field, One, PairingEngine, PrimeField, ProjectiveCurve, UniformRand, Zero,
+};
+use core::marker::PhantomData;
+use ff_fft::{cfg_into_iter, cfg_iter, DensePolynomial};
+use rand::RngCore;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+mod data_structures;
+pub use data_structures::*;
+
+/// The KZG10 polynomial commitment scheme over the pairing `E`.
+pub struct KZG10<E: PairingEngine> {
+    _engine: PhantomData<E>,
+}
+
+impl<E: PairingEngine> KZG10<E> {
+    /// Samples the trusted parameters for polynomials of degree up to
+    /// `max_degree` and multi-point openings at up to `max_points` points,
+    /// at least one of which is needed by single-point openings. The
+    /// trapdoor `β` must be discarded afterwards.
+    pub fn setup<R: RngCore>(
+        max_degree: usize,
+        max_points: usize,
+        rng: &mut R,
+    ) -> Result<UniversalParams<E>, Error> {
+        if max_degree < 1 {
+            return Err(Error::DegreeIsZero);
+        }
+        let setup_time = start_timer!(|| format!(
+            "KZG10::Setup with degree {} and {} points",
+            max_degree, max_points
+        ));
+        let max_points = max_points.max(1);
+        let beta = E::Fr::rand(rng);
+        let g = E::G1Projective::rand(rng);
+        let gamma_g = E::G1Projective::rand(rng);
+        let h = E::G2Projective::rand(rng);
+
+        let mut powers_of_beta = vec![E::Fr::one()];
+        let mut cur = beta;
+        for _ in 0..max_degree.max(max_points) {
+            powers_of_beta.push(cur);
+            cur *= &beta;
+        }
+
+        let scalar_bits = E::Fr::size_in_bits();
+        let window_size = FixedBaseMSM::get_mul_window_size(max_degree + 1);
+
+        let g_time = start_timer!(|| "Generating powers of G");
+        let g_table = FixedBaseMSM::get_window_table(scalar_bits, window_size, g);
+        let powers_of_g = FixedBaseMSM::multi_scalar_mul::<E::G1Projective>(
+            scalar_bits,
+            window_size,
+            &g_table,
+            &powers_of_beta[..=max_degree],
+        );
+        end_timer!(g_time);
+
+        let gamma_g_time = start_timer!(|| "Generating powers of gamma * G");
+        let gamma_g_table = FixedBaseMSM::get_window_table(scalar_bits, window_size, gamma_g);
+        let powers_of_gamma_g = FixedBaseMSM::multi_scalar_mul::<E::G1Projective>(
+            scalar_bits,
+            window_size,
+            &gamma_g_table,
+            &powers_of_beta[..=max_degree],
+        );
+        end_timer!(gamma_g_time);
+
+        let h_time = start_timer!(|| "Generating powers of H");
+        let h_window_size = FixedBaseMSM::get_mul_window_size(max_points + 1);
+        let h_table = FixedBaseMSM::get_window_table(scalar_bits, h_window_size, h);
+        let powers_of_h = FixedBaseMSM::multi_scalar_mul::<E::G2Projective>(
+            scalar_bits,
+            h_window_size,
+            &h_table,
+            &powers_of_beta[..=max_points],
+        );
+        end_timer!(h_time);
+
+        let pp = UniversalParams {
+            powers_of_g: E::G1Projective::batch_normalization_into_affine(&powers_of_g),
+            powers_of_gamma_g: E::G1Projective::batch_normalization_into_affine(&powers_of_gamma_g),
+            powers_of_h: E::G2Projective::batch_normalization_into_affine(&powers_of_h),
+        };
+        end_timer!(setup_time);
+        Ok(pp)
+    }
+
+    /// Specializes the public parameters to polynomials of degree up to
+    /// `supported_degree` and multi-point openings at up to
+    /// `supported_points` points.
+    pub fn trim(
+        pp: &UniversalParams<E>,
+        supported_degree: usize,
+        supported_points: usize,
+    ) -> Result<(Powers<E>, VerifierKey<E>), Error> {
+        if supported_degree < 1 {
+            return Err(Error::DegreeIsZero);
+        }
+        if supported_degree > pp.max_degree() {
+            return Err(Error::UnsupportedDegreeBound(supported_degree));
+        }
+        if supported_points > pp.max_points() {
+            return Err(Error::TooManyPoints {
+                num_points: supported_points,
+                max_points: pp.max_points(),
+            });
+        }
+        let powers = Powers {
+            powers_of_g: pp.powers_of_g[..=supported_degree].to_vec(),
+            powers_of_gamma_g: pp.powers_of_gamma_g[..=supported_degree].to_vec(),
+        };
+        let num_g_powers = supported_points.max(1).min(supported_degree + 1);
+        let num_h_powers = supported_points.max(1) + 1;
+        let vk = VerifierKey {
+            g: pp.powers_of_g[0],
+            gamma_g: pp.powers_of_gamma_g[0],
+            h: pp.powers_of_h[0],
+            beta_h: pp.powers_of_h[1],
+            prepared_h: pp.powers_of_h[0].into(),
+            prepared_beta_h: pp.powers_of_h[1].into(),
+            powers_of_g: pp.powers_of_g[..num_g_powers].to_vec(),
+            powers_of_gamma_g: pp.powers_of_gamma_g[..num_g_powers].to_vec(),
+            powers_of_h: pp.powers_of_h[..num_h_powers].to_vec(),
+        };
+        Ok((powers, vk))
+    }
+
+    /// Updates the public parameters with the non-zero secrets `s` and `t`,
+    /// which replace the trapdoors `β` and `γ` by `βs` and `γt`: the `i`-th
+    /// powers are multiplied by `s^i`, and the powers of `γG` also by `t`.
+    /// The updated parameters are secure if either the previous trapdoors or
+    /// `s` and `t` are discarded, and `check_update` checks them given `sG`
+    /// and `tH`.
+    pub fn update(pp: &UniversalParams<E>, s: E::Fr, t: E::Fr) -> UniversalParams<E> {
+        let update_time = start_timer!(|| "KZG10::Update");
+        let num_powers = pp.powers_of_g.len().max(pp.powers_of_h.len());
+        let mut powers_of_s = vec![E::Fr::one()];
+        let mut cur = s;
+        for _ in 1..num_powers {
+            powers_of_s.push(cur);
+            cur *= &s;
+        }
+        let gamma_scalars = powers_of_s.iter().map(|p| *p * &t).collect::<Vec<_>>();
+
+        let pp = UniversalParams {
+            powers_of_g: scale(&pp.powers_of_g, &powers_of_s),
+            powers_of_gamma_g: scale(&pp.powers_of_gamma_g, &gamma_scalars),
+            powers_of_h: scale(&pp.powers_of_h, &powers_of_s),
+        };
+        end_timer!(update_time);
+        pp
+    }
+
+    /// Checks that `updated` is obtained from `previous` by `update` with
+    /// the secrets `s` and `t` of `s_g = sG` and `t_h = tH`, i.e. that the
+    /// generators `G` and `H` are kept, that `e(βsG, H) == e(sG, βH)` and
+    /// `e(γtG, H) == e(γG, tH)`, and that `updated` is well formed.
+    ///
+    /// This does not check that the contributor knows `s` and `t`, which
+    /// callers must prove separately.
+    pub fn check_update<R: RngCore>(
+        previous: &UniversalParams<E>,
+        updated: &UniversalParams<E>,
+        s_g: &E::G1Affine,
+        t_h: &E::G2Affine,
+        rng: &mut R,
+    ) -> Result<bool, Error> {
+        let check_time = start_timer!(|| "KZG10::CheckUpdate");
+        let same_shape = previous.powers_of_g.len() == updated.powers_of_g.len()
+            && previous.powers_of_gamma_g.len() == updated.powers_of_gamma_g.len()
+            && previous.powers_of_h.len() == updated.powers_of_h.len();
+        if !same_shape || s_g.is_zero() || t_h.is_zero() || !Self::check_params(updated, rng)? {
+            end_timer!(check_time);
+            return Ok(false);
+        }
+        let h = updated.powers_of_h[0];
+        let result = updated.powers_of_g[0] == previous.powers_of_g[0]
+            && h == previous.powers_of_h[0]
+            && E::pairing(updated.powers_of_g[1], h) == E::pairing(*s_g, previous.powers_of_h[1])
+            && E::pairing(updated.powers_of_gamma_g[0], h)
+                == E::pairing(previous.powers_of_gamma_g[0], *t_h);
+        end_timer!(check_time, || format!("Result: {}", result));
+        Ok(result)
+    }
+
+    /// Checks that the public parameters are well formed, i.e. that their
+    /// generators are not zero and that each of their sequences consists of
+    /// the consecutive powers of the same `β`. The equations
+    /// `e(β^{i+1}G, H) == e(β^i G, βH)`, the same for `γG`, and
+    /// `e(G, β^{i+1}H) == e(βG, β^i H)` are combined with random scalars into
+    /// a single product of four pairings.
+    pub fn check_params<R: RngCore>(pp: &UniversalParams<E>, rng: &mut R) -> Result<bool, Error> {
+        if pp.powers_of_g.len() < 2
+            || pp.powers_of_gamma_g.len() != pp.powers_of_g.len()
+            || pp.powers_of_h.len() < 2
+        {
+            return Ok(false);
+        }
+        let (g, beta_g) = (pp.powers_of_g[0], pp.powers_of_g[1]);
+        let (h, beta_h) = (pp.powers_of_h[0], pp.powers_of_h[1]);
+        if g.is_zero() || beta_g.is_zero() || pp.powers_of_gamma_g[0].is_zero() || h.is_zero() {
+            return Ok(false);
+        }
+        let check_time = start_timer!(|| "KZG10::CheckParams");
+
+        let num_g_powers = pp.powers_of_g.len() - 1;
+        let r = (0..2 * num_g_powers)
+            .map(|_| E::Fr::rand(rng))
+            .collect::<Vec<_>>();
+        let (r_g, r_gamma_g) = r.split_at(num_g_powers);
+        let mut g_high = msm(&pp.powers_of_g[1..], r_g);
+        g_high += &msm(&pp.powers_of_gamma_g[1..], r_gamma_g);
+        let mut g_low = msm(&pp.powers_of_g, r_g);
+        g_low += &msm(&pp.powers_of_gamma_g, r_gamma_g);
+
+        let r_h = (1..pp.powers_of_h.len())
+            .map(|_| E::Fr::rand(rng))
+            .collect::<Vec<_>>();
+        let h_high = msm(&pp.powers_of_h[1..], &r_h);
+        let h_low = msm(&pp.powers_of_h, &r_h);
+
+        // The second equation is scaled by a random `c`, so that it cannot
+        // cancel the first.
+        let c = E::Fr::rand(rng).into_repr();
+        let affine_g = E::G1Projective::batch_normalization_into_affine(&[
+            g_high,
+            -g_low,
+            g.mul(c),
+            -beta_g.mul(c),
+        ]);
+        let affine_h = E::G2Projective::batch_normalization_into_affine(&[h_high, h_low]);
+        let result = E::product_of_pairings(&[
+            (affine_g[0].into(), h.into()),
+            (affine_g[1].into(), beta_h.into()),
+            (affine_g[2].into(), affine_h[0].into()),
+            (affine_g[3].into(), affine_h[1].into()),
+        ])
+        .is_one();
+        end_timer!(check_time, || format!("Result: {}", result));
+        Ok(result)
+    }
+
+    /// Commits to `polynomial`. If `hiding_bound` is set, the commitment is
+    /// blinded by a random polynomial of that degree, which hides the
+    /// polynomial from up to `hiding_bound` openings.
+    pub fn commit<R: RngCore>(
+        powers: &Powers<E>,
+        polynomial: &DensePolynomial<E::Fr>,
+        hiding_bound: Option<usize>,
+        rng: Option<&mut R>,
+    ) -> Result<(Commitment<E>, Randomness<E>), Error> {
+        check_degree_is_within_bounds(polynomial.coeffs.len(), powers.size())?;
+        let commit_time = start_timer!(|| format!(
+            "Committing to polynomial of degree {} with hiding_bound: {:?}",
+            polynomial.degree(),
+            hiding_bound,
+        ));
+
+        let mut commitment = msm(&powers.powers_of_g, &polynomial.coeffs);
+
+        let randomness = if let Some(hiding_degree) = hiding_bound {
+            let rng = rng.ok_or(Error::MissingRandomness)?;
+            if hiding_degree + 1 > powers.powers_of_gamma_g.len() {
+                return Err(Error::HidingBoundTooLarge {
+                    hiding_poly_degree: hiding_degree,
+                    num_powers: powers.powers_of_gamma_g.len(),
+                });
+            }
+            let blinding_polynomial = loop {
+                let polynomial = DensePolynomial::rand(hiding_degree, rng);
+                if !polynomial.is_zero() {
+                    break polynomial;
+                }
+            };
+            commitment += &msm(&powers.powers_of_gamma_g, &blinding_polynomial.coeffs);
+            Randomness {
+                blinding_polynomial,
+            }
+        } else {
+            Randomness::empty()
+        };
+
+        end_timer!(commit_time);
+        Ok((Commitment(commitment.into_affine()), randomness))
+    }
+
+    /// Opens the commitment to `polynomial` with `randomness` at `point`.
+    pub fn open(
+        powers: &Powers<E>,
+        polynomial: &DensePolynomial<E::Fr>,
+        point: E::Fr,
+        randomness: &Randomness<E>,
+    ) -> Result<Proof<E>, Error> {
+        check_degree_is_within_bounds(polynomial.coeffs.len(), powers.size())?;
+        let open_time =
+            start_timer!(|| format!("Opening polynomial of degree {}", polynomial.degree()));
+
+        let witness_polynomial = divide_by_linear_factor(polynomial, point);
+        let mut w = msm(&powers.powers_of_g, &witness_polynomial.coeffs);
+
+        let random_v = if randomness.is_hiding() {
+            let blinding_polynomial = &randomness.blinding_polynomial;
+            let random_witness_polynomial = divide_by_linear_factor(blinding_polynomial, point);
+            w += &msm(&powers.powers_of_gamma_g, &random_witness_polynomial.coeffs);
+            Some(blinding_polynomial.evaluate(point))
+        } else {
+            None
+        };
+
+        end_timer!(open_time);
+        Ok(Proof {
+            w: w.into_affine(),
+            random_v,
+        })
+    }
+
+    /// Checks that `proof` opens `commitment` to `value` at `point`, i.e.
+    /// `e(C - vG - r(z)γG, H) == e(w, βH - zH)`.
+    pub fn check(
+        vk: &VerifierKey<E>,
+        commitment: &Commitment<E>,
+        point: E::Fr,
+        value: E::Fr,
+        proof: &Proof<E>,
+    ) -> Result<bool, Error> {
+        let check_time = start_timer!(|| "Checking evaluation");
+        let mut inner = commitment.0.into_projective() - &vk.g.mul(value.into_repr());
+        if let Some(random_v) = proof.random_v {
+            inner -= &vk.gamma_g.mul(random_v.into_repr());
+        }
+        let lhs = E::pairing(inner, vk.h);
+
+        let inner = vk.beta_h.into_projective() - &vk.h.mul(point.into_repr());
+        let rhs = E::pairing(proof.w, inner);
+
+        end_timer!(check_time, || format!("Result: {}", lhs == rhs));
+        Ok(lhs == rhs)
+    }
+
+    /// Checks several single-point openings at once. The equations
+    /// `e(C_i - v_i G - r_i(z_i)γG + z_i w_i, H) == e(w_i, βH)` are combined
+    /// with random scalars into a single product of two pairings.
+    pub fn batch_check<R: RngCore>(
+        vk: &VerifierKey<E>,
+        commitments: &[Commitment<E>],
+        points: &[E::Fr],
+        values: &[E::Fr],
+        proofs: &[Proof<E>],
+        rng: &mut R,
+    ) -> Result<bool, Error> {
+        for len in &[points.len(), values.len(), proofs.len()] {
+            if *len != commitments.len() {
+                return Err(Error::IncorrectInputLength(*len));
+            }
+        }
+        let check_time = start_timer!(|| format!("Checking {} evaluations", commitments.len()));
+
+        let mut total_c = E::G1Projective::zero();
+        let mut total_w = E::G1Projective::zero();
+        let mut g_multiplier = E::Fr::zero();
+        let mut gamma_g_multiplier = E::Fr::zero();
+        for (i, (((c, z), v), proof)) in commitments
+            .iter()
+            .zip(points)
+            .zip(values)
+            .zip(proofs)
+            .enumerate()
+        {
+            // The first randomizer is one, which saves a scalar multiplication.
+            let randomizer = if i == 0 {
+                E::Fr::one()
+            } else {
+                E::Fr::rand(rng)
+            };
+            let mut temp = proof.w.mul(z.into_repr());
+            temp.add_assign_mixed(&c.0);
+            total_c += &temp.mul(randomizer.into_repr());
+            total_w += &proof.w.mul(randomizer.into_repr());
+            g_multiplier += &(randomizer * v);
+            if let Some(random_v) = proof.random_v {
+                gamma_g_multiplier += &(randomizer * &random_v);
+            }
+        }
+        total_c -= &vk.g.mul(g_multiplier.into_repr());
+        total_c -= &vk.gamma_g.mul(gamma_g_multiplier.into_repr());
+
+        let affine_points = E::G1Projective::batch_normalization_into_affine(&[-total_w, total_c]);
+        let (total_w, total_c) = (affine_points[0], affine_points[1]);
+
+        let result = E::product_of_pairings(&[
+            (total_w.into(), vk.prepared_beta_h.clone()),
+            (total_c.into(), vk.prepared_h.clone()),
+        ])
+        .is_one();
+        end_timer!(check_time, || format!("Result: {}", result));
+        Ok(result)
+    }
+
+    /// Opens the commitment to `polynomial` with `randomness` at the
+    /// distinct `points`.
+    pub fn open_multi_point(
+        powers: &Powers<E>,
+        polynomial: &DensePolynomial<E::Fr>,
+        points: &[E::Fr],
+        randomness: &Randomness<E>,
+    ) -> Result<MultiPointProof<E>, Error> {
+        check_degree_is_within_bounds(polynomial.coeffs.len(), powers.size())?;
+        check_points_are_distinct(points)?;
+        let open_time = start_timer!(|| format!(
+            "Opening polynomial of degree {} at {} points",
+            polynomial.degree(),
+            points.len()
+        ));
+
+        let vanishing_polynomial = vanishing_polynomial(points);
+        let values = points
+            .iter()
+            .map(|z| polynomial.evaluate(*z))
+            .collect::<Vec<_>>();
+        let witness_polynomial =
+            &(polynomial - &interpolate(points, &values)) / &vanishing_polynomial;
+        let mut w = msm(&powers.powers_of_g, &witness_polynomial.coeffs);
+
+        let random_values = if randomness.is_hiding() {
+            let blinding_polynomial = &randomness.blinding_polynomial;
+            let random_values = points
+                .iter()
+                .map(|z| blinding_polynomial.evaluate(*z))
+                .collect::<Vec<_>>();
+            let random_witness_polynomial = &(blinding_polynomial
+                - &interpolate(points, &random_values))
+                / &vanishing_polynomial;
+            w += &msm(&powers.powers_of_gamma_g, &random_witness_polynomial.coeffs);
+            Some(random_values)
+        } else {
+            None
+        };
+
+        end_timer!(open_time);
+        Ok(MultiPointProof {
+            w: w.into_affine(),
+            random_values,
+        })
+    }
+
+    /// Checks that `proof` opens `commitment` to `values` at the distinct
+    /// `points`, i.e. `e(C - I(β)G - I_r(β)γG, H) == e(w, Z(β)H)`, where `I`
+    /// and `I_r` interpolate the values and the random values, and `Z`
+    /// vanishes on the points.
+    pub fn check_multi_point(
+        vk: &VerifierKey<E>,
+        commitment: &Commitment<E>,
+        points: &[E::Fr],
+        values: &[E::Fr],
+        proof: &MultiPointProof<E>,
+    ) -> Result<bool, Error> {
+        if points.len() > vk.max_points() {
+            return Err(Error::TooManyPoints {
+                num_points: points.len(),
+                max_points: vk.max_points(),
+            });
+        }
+        if values.len() != points.len() {
+            return Err(Error::IncorrectInputLength(values.len()));
+        }
+        check_points_are_distinct(points)?;
+        let check_time =
+            start_timer!(|| format!("Checking evaluations at {} points", points.len()));
+
+        let interpolation_polynomial = interpolate(points, values);
+        check_degree_is_within_bounds(interpolation_polynomial.coeffs.len(), vk.powers_of_g.len())?;
+        let mut inner = commitment.0.into_projective()
+            - &msm(&vk.powers_of_g, &interpolation_polynomial.coeffs);
+        if let Some(random_values) = &proof.random_values {
+            if random_values.len() != points.len() {
+                return Err(Error::IncorrectInputLength(random_values.len()));
+            }
+            let random_interpolation_polynomial = interpolate(points, random_values);
+            check_degree_is_within_bounds(
+                random_interpolation_polynomial.coeffs.len(),
+                vk.powers_of_gamma_g.len(),
+            )?;
+            inner -= &msm(
+                &vk.powers_of_gamma_g,
+                &random_interpolation_polynomial.coeffs,
+            );
+        }
+        let lhs = E::pairing(inner, vk.h);
+
+        let vanishing_polynomial = vanishing_polynomial(points);
+        let rhs = E::pairing(proof.w, msm(&vk.powers_of_h, &vanishing_polynomial.coeffs));
+
+        end_timer!(check_time, || format!("Result: {}", lhs == rhs));
+        Ok(lhs == rhs)
+    }
+}
+
+fn check_degree_is_within_bounds(num_coefficients: usize, num_powers: usize) -> Result<(), Error> {
+    if num_coefficients > num_powers {
+        Err(Error::TooManyCoefficients {
+            num_coefficients,
+            num_powers,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+fn check_points_are_distinct<F: Field>(points: &[F]) -> Result<(), Error> {
+    for (i, z) in points.iter().enumerate() {
+        if points[..i].contains(z) {
+            return Err(Error::RepeatedPoints);
+        }
+    }
+    Ok(())
+}
+
+/// Computes `sum_i scalars[i] · bases[i]`.
+fn msm<G: AffineCurve>(bases: &[G], scalars: &[G::ScalarField]) -> G::Projective {
+    let msm_time = start_timer!(|| "MSM");
+    let scalars = cfg_into_iter!(scalars)
+        .map(|s| s.into_repr())
+        .collect::<Vec<_>>();
+    let result = VariableBaseMSM::multi_scalar_mul(&bases[..scalars.len()], &scalars);
+    end_timer!(msm_time);
+    result
+}
+
+/// Computes `scalars[i] · bases[i]` for each `i`.
+fn scale<G: AffineCurve>(bases: &[G], scalars: &[G::ScalarField]) -> Vec<G> {
+    let scaled = cfg_iter!(bases)
+        .zip(scalars)
+        .map(|(base, scalar)| base.mul(scalar.into_repr()))
+        .collect::<Vec<_>>();
+    G::Projective::batch_normalization_into_affine(&scaled)
+}
+
+/// Computes `(p(X) - p(z)) / (X - z)` by synthetic division.
+fn divide_by_linear_factor<F: Field>(polynomial: &DensePolynomial<F>, z: F) -> DensePolynomial<F> {
+    if polynomial.coeffs.len() < 2 {
+        return DensePolynomial::zero();
+    }
+    let mut quotient = vec![F::zero(); polynomial.coeffs.len() - 1];
+    let mut carry = F::zero();
+    for (q, coeff) in quotient.iter_mut().zip(&polynomial.coeffs[1..]).rev() {
+        carry = carry * &z + coeff;
+        *q = carry;
+    }
+    DensePolynomial::from_coefficients_vec(quotient)
+}
+
+/// Computes `Z(X) = prod_i (X - points[i])`.
+fn vanishing_polynomial<F: Field>(points: &[F]) -> DensePolynomial<F> {
+    let mut coeffs = vec![F::one()];
+    for z in points {
+        coeffs.insert(0, F::zero());
+        for i in 0..coeffs.len() - 1 {
+            let shifted = coeffs[i + 1] * z;
+            coeffs[i] -= &shifted;
+        }
+    }
+    DensePolynomial::from_coefficients_vec(coeffs)
+}
+
+/// Computes the polynomial of degree less than `points.len()` which
+/// evaluates to `values[i]` at `points[i]`, by Lagrange interpolation.
+fn interpolate<F: Field>(points: &[F], values: &[F]) -> DensePolynomial<F> {
+    let mut coeffs = vec![F::zero(); points.len()];
+    for (i, (z_i, v_i)) in points.iter().zip(values).enumerate() {
+        let others = points
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, z_j)| *z_j)
+            .collect::<Vec<_>>();
+        let denominator = others.iter().fold(F::one(), |acc, z_j| acc * &(*z_i - z_j));
+        let scale = *v_i * &denominator.inverse().unwrap();
+        for (c, b) in coeffs.iter_mut().zip(&vanishing_polynomial(&others).coeffs) {
+            *c += &(scale * b);
+        }
+    }
+    DensePolynomial::from_coefficients_vec(coeffs)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use algebra::{
+        bls12_377::Bls12_377,
+        bls12_381::{Bls12_381, Fr},
+        test_rng,
+    };
+    use ff_fft::DensePolynomial;
+
+    type KZGBls12_381 = KZG10<Bls12_381>;
+
+    fn end_to_end_test_template<E: PairingEngine>(hiding: bool) {
+        let rng = &mut test_rng();
+        for _ in 0..10 {
+            let degree = 1 + usize::from(rand::Rng::gen::<u8>(rng)) % 64;
+            let pp = KZG10::<E>::setup(degree, 1, rng).unwrap();
+            let (powers, vk) = KZG10::trim(&pp, degree, 1).unwrap();
+            let p = DensePolynomial::rand(degree, rng);
+            let hiding_bound = if hiding { Some(1) } else { None };
+            let (comm, rand) = KZG10::commit(&powers, &p, hiding_bound, Some(rng)).unwrap();
+            assert_eq!(rand.is_hiding(), hiding);
+
+            let point = E::Fr::rand(rng);
+            let value = p.evaluate(point);
+            let proof = KZG10::open(&powers, &p, point, &rand).unwrap();
+            assert_eq!(proof.random_v.is_some(), hiding);
+            assert!(KZG10::check(&vk, &comm, point, value, &proof).unwrap());
+            assert!(!KZG10::check(&vk, &comm, point, value + &E::Fr::one(), &proof).unwrap());
+            assert!(!KZG10::check(&vk, &comm, point + &E::Fr::one(), value, &proof).unwrap());
+        }
+    }
+
+    #[test]
+    fn end_to_end_test() {
+        end_to_end_test_template::<Bls12_377>(false);
+        end_to_end_test_template::<Bls12_381>(false);
+    }
+
+    #[test]
+    fn hiding_end_to_end_test() {
+        end_to_end_test_template::<Bls12_377>(true);
+        end_to_end_test_template::<Bls12_381>(true);
+    }
+
+    #[test]
+    fn linear_polynomial_test() {
+        let rng = &mut test_rng();
+        let pp = KZGBls12_381::setup(1, 1, rng).unwrap();
+        let (powers, vk) = KZGBls12_381::trim(&pp, 1, 1).unwrap();
+        let p = DensePolynomial::rand(1, rng);
+        let (comm, rand) = KZGBls12_381::commit(&powers, &p, Some(1), Some(rng)).unwrap();
+        let point = Fr::rand(rng);
+        let proof = KZGBls12_381::open(&powers, &p, point, &rand).unwrap();
+        assert!(KZGBls12_381::check(&vk, &comm, point, p.evaluate(point), &proof).unwrap());
+    }
+
+    #[test]
+    fn linear_combination_test() {
+        let rng = &mut test_rng();
+        let pp = KZGBls12_381::setup(16, 1, rng).unwrap();
+        let (powers, vk) = KZGBls12_381::trim(&pp, 16, 1).unwrap();
+        let p = DensePolynomial::rand(16, rng);
+        let q = DensePolynomial::rand(7, rng);
+        let (p_comm, p_rand) = KZGBls12_381::commit(&powers, &p, Some(2), Some(rng)).unwrap();
+        let (q_comm, q_rand) = KZGBls12_381::commit(&powers, &q, Some(2), Some(rng)).unwrap();
+
+        let coeff = Fr::rand(rng);
+        let mut combination = p.clone();
+        combination += (coeff, &q);
+        let mut combination_comm = p_comm;
+        combination_comm += (coeff, &q_comm);
+        let mut combination_rand = p_rand;
+        combination_rand += (coeff, &q_rand);
+
+        let point = Fr::rand(rng);
+        let proof = KZGBls12_381::open(&powers, &combination, point, &combination_rand).unwrap();
+        let value = p.evaluate(point) + &(coeff * &q.evaluate(point));
+        assert!(KZGBls12_381::check(&vk, &combination_comm, point, value, &proof).unwrap());
+    }
+
+    #[test]
+    fn batch_check_test() {
+        let rng = &mut test_rng();
+        let pp = KZGBls12_381::setup(32, 1, rng).unwrap();
+        let (powers, vk) = KZGBls12_381::trim(&pp, 32, 1).unwrap();
+        let mut commitments = Vec::new();
+        let mut points = Vec::new();
+        let mut values = Vec::new();
+        let mut proofs = Vec::new();
+        for i in 0..10 {
+            let p = DensePolynomial::rand(1 + i * 3, rng);
+            let hiding_bound = if i % 2 == 0 { Some(1) } else { None };
+            let (comm, rand) = KZGBls12_381::commit(&powers, &p, hiding_bound, Some(rng)).unwrap();
+            let point = Fr::rand(rng);
+            commitments.push(comm);
+            values.push(p.evaluate(point));
+            proofs.push(KZGBls12_381::open(&powers, &p, point, &rand).unwrap());
+            points.push(point);
+        }
+        assert!(
+            KZGBls12_381::batch_check(&vk, &commitments, &points, &values, &proofs, rng).unwrap()
+        );
+
+        values[3] += &Fr::one();
+        assert!(
+            !KZGBls12_381::batch_check(&vk, &commitments, &points, &values, &proofs, rng).unwrap()
+        );
+        assert!(
+            KZGBls12_381::batch_check(&vk, &commitments, &points, &values[1..], &proofs, rng)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn multi_point_test() {
+        let rng = &mut test_rng();
+        let pp = KZGBls12_381::setup(20, 5, rng).unwrap();
+        let (powers, vk) = KZGBls12_381::trim(&pp, 20, 5).unwrap();
+        for hiding_bound in &[None, Some(2), Some(7)] {
+            let p = DensePolynomial::rand(20, rng);
+            let (comm, rand) = KZGBls12_381::commit(&powers, &p, *hiding_bound, Some(rng)).unwrap();
+            for num_points in 1..=5 {
+                let points = (0..num_points).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+                let mut values = points.iter().map(|z| p.evaluate(*z)).collect::<Vec<_>>();
+                let proof = KZGBls12_381::open_multi_point(&powers, &p, &points, &rand).unwrap();
+                assert!(
+                    KZGBls12_381::check_multi_point(&vk, &comm, &points, &values, &proof).unwrap()
+                );
+
+                // A single point agrees with the single-point opening.
+                if num_points == 1 {
+                    let single = KZGBls12_381::open(&powers, &p, points[0], &rand).unwrap();
+                    assert_eq!(single.w, proof.w);
+                }
+
+                values[0] += &Fr::one();
+                assert!(
+                    !KZGBls12_381::check_multi_point(&vk, &comm, &points, &values, &proof).unwrap()
+                );
+            }
+        }
+
+        let p = DensePolynomial::rand(20, rng);
+        let z = Fr::rand(rng);
+        assert_eq!(
+            KZGBls12_381::open_multi_point(&powers, &p, &[z, z], &Randomness::empty()),
+            Err(Error::RepeatedPoints)
+        );
+        let points = (0..6).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let values = points.iter().map(|z| p.evaluate(*z)).collect::<Vec<_>>();
+        let proof =
+            KZGBls12_381::open_multi_point(&powers, &p, &points, &Randomness::empty()).unwrap();
+        assert_eq!(
+            KZGBls12_381::check_multi_point(&vk, &Commitment::default(), &points, &values, &proof),
+            Err(Error::TooManyPoints {
+                num_points: 6,
+                max_points: 5
+            })
+        );
+    }
+
+    #[test]
+    fn update_test() {
+        let rng = &mut test_rng();
+        let pp = KZGBls12_381::setup(16, 3, rng).unwrap();
+        assert!(KZGBls12_381::check_params(&pp, rng).unwrap());
+
+        let s = Fr::rand(rng);
+        let t = Fr::rand(rng);
+        let updated = KZGBls12_381::update(&pp, s, t);
+        let s_g = pp.powers_of_g[0].mul(s).into_affine();
+        let t_h = pp.powers_of_h[0].mul(t).into_affine();
+        assert!(KZGBls12_381::check_update(&pp, &updated, &s_g, &t_h, rng).unwrap());
+        assert_eq!(
+            updated.powers_of_gamma_g[2],
+            pp.powers_of_gamma_g[2].mul(t * &s * &s).into_affine()
+        );
+
+        // The updated parameters commit and open as usual.
+        let (powers, vk) = KZGBls12_381::trim(&updated, 16, 3).unwrap();
+        let p = DensePolynomial::rand(16, rng);
+        let (comm, rand) = KZGBls12_381::commit(&powers, &p, Some(2), Some(rng)).unwrap();
+        let points = (0..3).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let values = points.iter().map(|z| p.evaluate(*z)).collect::<Vec<_>>();
+        let proof = KZGBls12_381::open_multi_point(&powers, &p, &points, &rand).unwrap();
+        assert!(KZGBls12_381::check_multi_point(&vk, &comm, &points, &values, &proof).unwrap());
+
+        // The update must match the secrets, and keep the generators.
+        let other_g = pp.powers_of_g[0].mul(t).into_affine();
+        assert!(!KZGBls12_381::check_update(&pp, &updated, &other_g, &t_h, rng).unwrap());
+        let other_h = pp.powers_of_h[0].mul(s).into_affine();
+        assert!(!KZGBls12_381::check_update(&pp, &updated, &s_g, &other_h, rng).unwrap());
+        let zero_g = <Bls12_381 as PairingEngine>::G1Affine::zero();
+        assert!(!KZGBls12_381::check_update(&pp, &updated, &zero_g, &t_h, rng).unwrap());
+        let mut moved = KZGBls12_381::update(&updated, s, Fr::one());
+        moved.powers_of_g[0] = updated.powers_of_g[1];
+        assert!(!KZGBls12_381::check_update(&updated, &moved, &s_g, &t_h, rng).unwrap());
+
+        // Each sequence must consist of consecutive powers of the same β.
+        for i in 0..3 {
+            let mut tampered = updated.clone();
+            match i {
+                0 => tampered.powers_of_g[9] = tampered.powers_of_g[8],
+                1 => tampered.powers_of_gamma_g[16] = tampered.powers_of_g[16],
+                _ => tampered.powers_of_h[3] = tampered.powers_of_h[2],
+            }
+            assert!(!KZGBls12_381::check_params(&tampered, rng).unwrap());
+            assert!(!KZGBls12_381::check_update(&pp, &tampered, &s_g, &t_h, rng).unwrap());
+        }
+        let mut truncated = updated;
+        truncated.powers_of_h.pop();
+        assert!(!KZGBls12_381::check_update(&pp, &truncated, &s_g, &t_h, rng).unwrap());
+    }
+
+    #[test]
+    fn parameter_bounds_test() {
+        let rng = &mut test_rng();
+        assert_eq!(
+            KZGBls12_381::setup(0, 1, rng).unwrap_err(),
+            Error::DegreeIsZero
+        );
+        let pp = KZGBls12_381::setup(8, 2, rng).unwrap();
+        assert_eq!(
+            KZGBls12_381::trim(&pp, 9, 1).unwrap_err(),
+            Error::UnsupportedDegreeBound(9)
+        );
+        let (powers, _) = KZGBls12_381::trim(&pp, 4, 1).unwrap();
+
+        let p = DensePolynomial::<Fr>::rand(5, rng);
+        assert_eq!(
+            KZGBls12_381::commit(&powers, &p, None, Some(rng)).unwrap_err(),
+            Error::TooManyCoefficients {
+                num_coefficients: 6,
+                num_powers: 5
+            }
+        );
+        let p = DensePolynomial::<Fr>::rand(4, rng);
+        assert_eq!(
+            KZGBls12_381::commit(&powers, &p, Some(5), Some(rng)).unwrap_err(),
+            Error::HidingBoundTooLarge {
+                hiding_poly_degree: 5,
+                num_powers: 5
+            }
+        );
+        assert_eq!(
+            KZGBls12_381::commit(&powers, &p, Some(1), None::<&mut rand::rngs::StdRng>)
+                .unwrap_err(),
+            Error::MissingRandomness
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Polynomial commitment schemes over pairing-friendly curves, starting with
+//! the scheme of [Kate, Zaverucha and Goldberg][kzg10].
+//!
+//! [kzg10]: https://www.iacr.org/archive/asiacrypt2010/6477178/6477178.pdf
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(unused_import_braces, unused_qualifications, trivial_casts)]
+#![deny(trivial_numeric_casts, private_in_public, variant_size_differences)]
+#![deny(stable_features, unreachable_pub, non_shorthand_field_patterns)]
+#![deny(unused_attributes, unused_imports, unused_mut)]
+#![deny(renamed_and_removed_lints, stable_features, unused_allocation)]
+#![deny(unused_comparisons, bare_trait_objects, unused_must_use, const_err)]
+#![forbid(unsafe_code)]
+
+#[macro_use]
+extern crate bench_utils;
+
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+pub(crate) use std::vec::Vec;
+
+/// The KZG10 polynomial commitment scheme.
+pub mod kzg10;
+
+pub use self::kzg10::KZG10;
+
+/// The errors of polynomial commitment schemes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The maximum degree of the parameters is zero.
+    DegreeIsZero,
+    /// The requested degree exceeds the maximum degree of the parameters.
+    UnsupportedDegreeBound(usize),
+    /// The polynomial has more coefficients than there are powers.
+    TooManyCoefficients {
+        num_coefficients: usize,
+        num_powers: usize,
+    },
+    /// The degree of the hiding polynomial exceeds the supported one.
+    HidingBoundTooLarge {
+        hiding_poly_degree: usize,
+        num_powers: usize,
+    },
+    /// A hiding commitment or opening lacks its randomness, or a non-hiding
+    /// one has randomness.
+    MissingRandomness,
+    /// There are more evaluation points than the verifier key supports.
+    TooManyPoints {
+        num_points: usize,
+        max_points: usize,
+    },
+    /// The evaluation points of a multi-point opening are not distinct.
+    RepeatedPoints,
+    /// The inputs of a batched operation have different lengths.
+    IncorrectInputLength(usize),
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::DegreeIsZero => write!(f, "the maximum degree is zero"),
+            Error::UnsupportedDegreeBound(degree) => {
+                write!(f, "degree {} exceeds the maximum degree", degree)
+            },
+            Error::TooManyCoefficients {
+                num_coefficients,
+                num_powers,
+            } => write!(
+                f,
+                "the polynomial has {} coefficients, but there are only {} powers",
+                num_coefficients, num_powers
+            ),
+            Error::HidingBoundTooLarge {
+                hiding_poly_degree,
+                num_powers,
+            } => write!(
+                f,
+                "the hiding polynomial has degree {}, but there are only {} powers",
+                hiding_poly_degree, num_powers
+            ),
+            Error::MissingRandomness => write!(f, "the randomness is missing or unexpected"),
+            Error::TooManyPoints {
+                num_points,
+                max_points,
+            } => write!(
+                f,
+                "{} points exceed the supported maximum of {}",
+                num_points, max_points
+            ),
+            Error::RepeatedPoints => write!(f, "the evaluation points are not distinct"),
+            Error::IncorrectInputLength(len) => write!(f, "input length is wrong: {}", len),
+        }
+    }
+}
+
+impl algebra_core::Error for Error {}
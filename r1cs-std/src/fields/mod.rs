@@ -11,6 +11,7 @@ pub mod fp3;
 pub mod fp4;
 pub mod fp6_2over3;
 pub mod fp6_3over2;
+pub mod nonnative;
 
 use crate::fields::fp::FpGadget;
 pub trait ToConstraintFieldGadget<ConstraintF: PrimeField> {
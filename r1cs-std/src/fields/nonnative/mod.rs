@@ -0,0 +1,936 @@
+//! Arithmetic on elements of a prime field `TargetF` inside a constraint
+//! system over a different prime field `BaseF`.
+//!
+//! Elements are held as little-endian limbs of `BITS_PER_LIMB` bits, each in
+//! its own `BaseF` variable. Additions and subtractions are limb-wise and do
+//! not reduce, so limbs may grow beyond `BITS_PER_LIMB` bits; multiplications
+//! exhibit the reduced result together with a quotient, and check the
+//! resulting integer identity limb by limb. `BaseF` must be at least about
+//! 140 bits wide for this to fit.
+//!
+//! `NonNativeFieldGadget` implements `FieldGadget`, so that gadgets which are
+//! generic over their field, such as curve gadgets, can be instantiated over
+//! a foreign field. Several products can also be checked at the cost of one
+//! with `enforce_sum_of_products_equal`.
+use algebra::{FpParameters, PrimeField, ToBytes};
+use core::{borrow::Borrow, cmp::max};
+use r1cs_core::{ConstraintSystem, ConstraintVar, SynthesisError};
+
+use crate::{
+    fields::{fp::FpGadget, FieldGadget},
+    prelude::*,
+    Assignment, Vec,
+};
+
+mod reduce;
+use reduce::{
+    alloc_bits, alloc_limbs, alloc_limbs_cost, ceil_log2, congruence_cost, enforce_congruent,
+    pack_bits, LimbPolynomial,
+};
+
+/// The number of bits in each limb of a reduced element.
+pub const BITS_PER_LIMB: u32 = 64;
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct NonNativeFieldGadget<TargetF: PrimeField, BaseF: PrimeField> {
+    /// The limbs, least significant first.
+    limbs: Vec<FpGadget<BaseF>>,
+    /// An upper bound on the bit length of every limb.
+    limb_bits: u32,
+    value: Option<TargetF>,
+}
+
+impl<TargetF: PrimeField, BaseF: PrimeField> NonNativeFieldGadget<TargetF, BaseF> {
+    /// The number of limbs of a reduced element.
+    pub fn num_limbs() -> usize {
+        ((TargetF::Params::MODULUS_BITS + BITS_PER_LIMB - 1) / BITS_PER_LIMB) as usize
+    }
+
+    /// Limbs are allowed to grow up to this many bits through additions.
+    fn max_limb_bits() -> u32 {
+        BaseF::Params::MODULUS_BITS - 8
+    }
+
+    fn limb_values(value: &TargetF) -> Vec<u64> {
+        let mut limbs = value.into_repr().as_ref().to_vec();
+        limbs.resize(Self::num_limbs(), 0);
+        limbs
+    }
+
+    fn polynomial(&self) -> LimbPolynomial<BaseF> {
+        LimbPolynomial::new(self.limbs.clone(), self.limb_bits)
+    }
+
+    /// Allocates a witness for `value` whose limbs are range-checked to
+    /// `BITS_PER_LIMB` bits.
+    fn alloc_reduced<CS: ConstraintSystem<BaseF>>(
+        cs: CS,
+        value: Option<TargetF>,
+    ) -> Result<Self, SynthesisError> {
+        let limbs = value.map(|v| Self::limb_values(&v));
+        let polynomial = alloc_limbs(
+            cs,
+            limbs.as_deref(),
+            Self::num_limbs() as u32 * BITS_PER_LIMB,
+        )?;
+        Ok(Self {
+            limbs: polynomial.coeffs,
+            limb_bits: BITS_PER_LIMB,
+            value,
+        })
+    }
+
+    /// Interprets big-endian `bits` as an integer and reduces it modulo the
+    /// characteristic of `TargetF`.
+    pub fn from_bits<CS: ConstraintSystem<BaseF>>(
+        mut cs: CS,
+        bits: &[Boolean],
+    ) -> Result<Self, SynthesisError> {
+        assert!(bits.len() <= Self::num_limbs() * BITS_PER_LIMB as usize);
+        let value = bits.iter().try_fold(TargetF::zero(), |acc, bit| {
+            let mut acc = acc.double();
+            if bit.get_value()? {
+                acc += &TargetF::one();
+            }
+            Some(acc)
+        });
+
+        let bits_le: Vec<_> = bits.iter().rev().cloned().collect();
+        let mut limbs = Vec::with_capacity(Self::num_limbs());
+        for (i, chunk) in bits_le.chunks(BITS_PER_LIMB as usize).enumerate() {
+            limbs.push(pack_bits(cs.ns(|| format!("limb {}", i)), chunk)?);
+        }
+        while limbs.len() < Self::num_limbs() {
+            limbs.push(FpGadget::zero(cs.ns(|| format!("limb {}", limbs.len())))?);
+        }
+        Ok(Self {
+            limbs,
+            limb_bits: BITS_PER_LIMB,
+            value,
+        })
+    }
+
+    /// Converts big-endian `bits` into little-endian bytes, padded to the
+    /// serialized size of `TargetF`.
+    fn bits_to_bytes(mut bits: Vec<Boolean>) -> Vec<UInt8> {
+        bits.reverse();
+        let num_bytes = to_bytes![TargetF::default()].unwrap().len();
+        bits.resize(num_bytes * 8, Boolean::constant(false));
+        bits.chunks(8).map(UInt8::from_bits_le).collect()
+    }
+
+    /// Returns an element with the same value whose limbs have
+    /// `BITS_PER_LIMB` bits.
+    pub fn reduce<CS: ConstraintSystem<BaseF>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        if self.limb_bits == BITS_PER_LIMB {
+            return Ok(self.clone());
+        }
+        let result = Self::alloc_reduced(cs.ns(|| "alloc"), self.value)?;
+        result.enforce_equal(cs.ns(|| "check"), self)?;
+        Ok(result)
+    }
+
+    /// A representation of zero whose limbs are all at least `2^bits`.
+    fn pad<CS: ConstraintSystem<BaseF>>(
+        mut cs: CS,
+        bits: u32,
+    ) -> Result<LimbPolynomial<BaseF>, SynthesisError> {
+        let two = TargetF::from(2u64);
+        let shift = two.pow(&[u64::from(BITS_PER_LIMB)]);
+        let mut power = two.pow(&[u64::from(bits)]);
+        let mut sum = TargetF::zero();
+        for _ in 0..Self::num_limbs() {
+            sum += &power;
+            power *= &shift;
+        }
+        let correction = Self::limb_values(&-sum);
+
+        let base_power = BaseF::from(2u64).pow(&[u64::from(bits)]);
+        let mut coeffs = Vec::with_capacity(Self::num_limbs());
+        for (i, limb) in correction.iter().enumerate() {
+            coeffs.push(FpGadget::alloc_constant(
+                cs.ns(|| format!("limb {}", i)),
+                &(base_power + &BaseF::from(*limb)),
+            )?);
+        }
+        Ok(LimbPolynomial::new(coeffs, bits + 1))
+    }
+
+    /// Enforces that the sum of the products of the pairs in `lhs` equals the
+    /// sum of the products of the pairs in `rhs`, plus `result`.
+    ///
+    /// The products are not reduced, so this costs a single congruence check
+    /// however many of them there are.
+    pub fn enforce_sum_of_products_equal<CS: ConstraintSystem<BaseF>>(
+        mut cs: CS,
+        lhs: &[(&Self, &Self)],
+        rhs: &[(&Self, &Self)],
+        result: &Self,
+    ) -> Result<(), SynthesisError> {
+        assert!(!lhs.is_empty(), "the left-hand side must not be empty");
+        // Every sum, plus the slack needed by the congruence check, must fit
+        // into the constraint field.
+        let slack = (lhs.len() + rhs.len()) as u32 + 5;
+        let mut result = result.clone();
+        if result.limb_bits + slack + 1 > BaseF::Params::MODULUS_BITS {
+            result = result.reduce(cs.ns(|| "reduce result"))?;
+        }
+
+        let lhs = Self::sum_of_products(cs.ns(|| "lhs"), lhs, slack)?;
+        let mut rhs_sum = result.polynomial();
+        if !rhs.is_empty() {
+            let products = Self::sum_of_products(cs.ns(|| "rhs"), rhs, slack)?;
+            rhs_sum = rhs_sum.add(cs.ns(|| "add result"), &products)?;
+        }
+        enforce_congruent::<TargetF, _, _>(cs.ns(|| "congruent"), &lhs, &rhs_sum)
+    }
+
+    /// Returns the sum of the products of the non-empty `terms`, reducing
+    /// operands until every product fits into `BaseF` with `slack` bits to
+    /// spare.
+    fn sum_of_products<CS: ConstraintSystem<BaseF>>(
+        mut cs: CS,
+        terms: &[(&Self, &Self)],
+        slack: u32,
+    ) -> Result<LimbPolynomial<BaseF>, SynthesisError> {
+        let log_limbs = ceil_log2(Self::num_limbs() as u64);
+        let mut sum: Option<LimbPolynomial<BaseF>> = None;
+        for (i, (a, b)) in terms.iter().enumerate() {
+            let mut cs = cs.ns(|| format!("product {}", i));
+            let (mut a, mut b) = ((*a).clone(), (*b).clone());
+            let mut j = 0;
+            while a.limb_bits + b.limb_bits + log_limbs + slack > BaseF::Params::MODULUS_BITS {
+                if a.limb_bits >= b.limb_bits {
+                    a = a.reduce(cs.ns(|| format!("reduce lhs {}", j)))?;
+                } else {
+                    b = b.reduce(cs.ns(|| format!("reduce rhs {}", j)))?;
+                }
+                j += 1;
+            }
+            let product = a.polynomial().mul(cs.ns(|| "mul"), &b.polynomial())?;
+            sum = Some(match sum {
+                Some(sum) => sum.add(cs.ns(|| "add"), &product)?,
+                None => product,
+            });
+        }
+        Ok(sum.unwrap())
+    }
+
+    /// The number of constraints used to allocate a reduced element.
+    fn alloc_cost() -> usize {
+        alloc_limbs_cost(Self::num_limbs() as u32 * BITS_PER_LIMB)
+    }
+
+    /// The number of constraints used by `mul_equals` on operands whose limbs
+    /// have `a_bits` and `b_bits` bits, and a reduced result.
+    fn mul_equals_cost(a_bits: u32, b_bits: u32) -> usize {
+        let len = 2 * Self::num_limbs() - 1;
+        let product_bits = a_bits + b_bits + ceil_log2(Self::num_limbs() as u64);
+        len + congruence_cost::<TargetF, BaseF>(
+            (product_bits, len),
+            (BITS_PER_LIMB, Self::num_limbs()),
+        )
+    }
+}
+
+impl<TargetF: PrimeField, BaseF: PrimeField> FieldGadget<TargetF, BaseF>
+    for NonNativeFieldGadget<TargetF, BaseF>
+{
+    type Variable = Vec<ConstraintVar<BaseF>>;
+
+    #[inline]
+    fn get_value(&self) -> Option<TargetF> {
+        self.value
+    }
+
+    #[inline]
+    fn get_variable(&self) -> Self::Variable {
+        self.limbs.iter().map(|limb| limb.get_variable()).collect()
+    }
+
+    #[inline]
+    fn zero<CS: ConstraintSystem<BaseF>>(cs: CS) -> Result<Self, SynthesisError> {
+        Self::alloc_constant(cs, &TargetF::zero())
+    }
+    #[inline]
+    fn one<CS: ConstraintSystem<BaseF>>(cs: CS) -> Result<Self, SynthesisError> {
+        Self::alloc_constant(cs, &TargetF::one())
+    }
+
+    fn conditionally_add_constant<CS: ConstraintSystem<BaseF>>(
+        &self,
+        mut cs: CS,
+        bit: &Boolean,
+        coeff: TargetF,
+    ) -> Result<Self, SynthesisError> {
+        if max(self.limb_bits, BITS_PER_LIMB) + 1 > Self::max_limb_bits() {
+            let reduced = self.reduce(cs.ns(|| "reduce"))?;
+            return reduced.conditionally_add_constant(cs.ns(|| "add reduced"), bit, coeff);
+        }
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        for (i, (limb, c)) in self.limbs.iter().zip(Self::limb_values(&coeff)).enumerate() {
+            limbs.push(limb.conditionally_add_constant(
+                cs.ns(|| format!("limb {}", i)),
+                bit,
+                BaseF::from(c),
+            )?);
+        }
+        let value = match (self.value, bit.get_value()) {
+            (Some(v), Some(b)) => Some(if b { v + &coeff } else { v }),
+            (..) => None,
+        };
+        Ok(Self {
+            limbs,
+            limb_bits: max(self.limb_bits, BITS_PER_LIMB) + 1,
+            value,
+        })
+    }
+
+    fn add<CS: ConstraintSystem<BaseF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        if max(self.limb_bits, other.limb_bits) + 1 > Self::max_limb_bits() {
+            let a = self.reduce(cs.ns(|| "reduce lhs"))?;
+            let b = other.reduce(cs.ns(|| "reduce rhs"))?;
+            return a.add(cs.ns(|| "add reduced"), &b);
+        }
+        let sum = self
+            .polynomial()
+            .add(cs.ns(|| "add"), &other.polynomial())?;
+        Ok(Self {
+            limbs: sum.coeffs,
+            limb_bits: sum.bits,
+            value: self.value.and_then(|a| Some(a + &other.value?)),
+        })
+    }
+    fn double<CS: ConstraintSystem<BaseF>>(&self, cs: CS) -> Result<Self, SynthesisError> {
+        self.add(cs, self)
+    }
+    /// Computes `self - other` as `self + pad - other`, where `pad` is a
+    /// multiple of the modulus whose limbs exceed those of `other`.
+    fn sub<CS: ConstraintSystem<BaseF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        if max(self.limb_bits, other.limb_bits + 1) + 1 > Self::max_limb_bits() {
+            let a = self.reduce(cs.ns(|| "reduce lhs"))?;
+            let b = other.reduce(cs.ns(|| "reduce rhs"))?;
+            return a.sub(cs.ns(|| "sub reduced"), &b);
+        }
+        let pad = Self::pad(cs.ns(|| "pad"), other.limb_bits)?;
+        let padded = self.polynomial().add(cs.ns(|| "add pad"), &pad)?;
+        let mut limbs = Vec::with_capacity(padded.coeffs.len());
+        for (i, (a, b)) in padded.coeffs.iter().zip(&other.limbs).enumerate() {
+            limbs.push(a.sub(cs.ns(|| format!("sub {}", i)), b)?);
+        }
+        Ok(Self {
+            limbs,
+            limb_bits: padded.bits,
+            value: self.value.and_then(|a| Some(a - &other.value?)),
+        })
+    }
+    fn negate<CS: ConstraintSystem<BaseF>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        Self::zero(cs.ns(|| "zero"))?.sub(cs.ns(|| "negate"), self)
+    }
+    fn mul<CS: ConstraintSystem<BaseF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let value = self.value.and_then(|a| Some(a * &other.value?));
+        let result = Self::alloc_reduced(cs.ns(|| "alloc product"), value)?;
+        self.mul_equals(cs.ns(|| "check product"), other, &result)?;
+        Ok(result)
+    }
+    fn square<CS: ConstraintSystem<BaseF>>(&self, cs: CS) -> Result<Self, SynthesisError> {
+        self.mul(cs, self)
+    }
+    /// Enforces that `self * other = result`.
+    fn mul_equals<CS: ConstraintSystem<BaseF>>(
+        &self,
+        cs: CS,
+        other: &Self,
+        result: &Self,
+    ) -> Result<(), SynthesisError> {
+        Self::enforce_sum_of_products_equal(cs, &[(self, other)], &[], result)
+    }
+    fn add_constant<CS: ConstraintSystem<BaseF>>(
+        &self,
+        mut cs: CS,
+        other: &TargetF,
+    ) -> Result<Self, SynthesisError> {
+        let other = Self::alloc_constant(cs.ns(|| "constant"), other)?;
+        self.add(cs.ns(|| "add"), &other)
+    }
+    fn mul_by_constant<CS: ConstraintSystem<BaseF>>(
+        &self,
+        mut cs: CS,
+        other: &TargetF,
+    ) -> Result<Self, SynthesisError> {
+        let other = Self::alloc_constant(cs.ns(|| "constant"), other)?;
+        self.mul(cs.ns(|| "mul"), &other)
+    }
+    /// Returns the inverse of `self`; this is unsatisfiable if `self` is zero.
+    fn inverse<CS: ConstraintSystem<BaseF>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        let value = self
+            .value
+            .map(|v| v.inverse().unwrap_or_else(TargetF::zero));
+        let inverse = Self::alloc_reduced(cs.ns(|| "alloc inverse"), value)?;
+        let one = Self::one(cs.ns(|| "one"))?;
+        self.mul_equals(cs.ns(|| "check inverse"), &inverse, &one)?;
+        Ok(inverse)
+    }
+
+    fn frobenius_map<CS: ConstraintSystem<BaseF>>(
+        &self,
+        _: CS,
+        _: usize,
+    ) -> Result<Self, SynthesisError> {
+        Ok(self.clone())
+    }
+
+    /// The cost for reduced operands.
+    fn cost_of_mul() -> usize {
+        Self::alloc_cost() + Self::cost_of_mul_equals()
+    }
+
+    /// The cost for reduced operands and result.
+    fn cost_of_mul_equals() -> usize {
+        Self::mul_equals_cost(BITS_PER_LIMB, BITS_PER_LIMB)
+    }
+
+    fn cost_of_inv() -> usize {
+        Self::cost_of_mul()
+    }
+}
+
+impl<TargetF: PrimeField, BaseF: PrimeField> PartialEq for NonNativeFieldGadget<TargetF, BaseF> {
+    /// Assigned gadgets are compared by value, like `FpGadget`. Unassigned
+    /// gadgets, as when generating parameters, are equal when they are made
+    /// of the same limb variables, so that `eq` stays reflexive.
+    fn eq(&self, other: &Self) -> bool {
+        match (self.value, other.value) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => {
+                self.limbs.len() == other.limbs.len()
+                    && self
+                        .limbs
+                        .iter()
+                        .zip(&other.limbs)
+                        .all(|(a, b)| same_variable(&a.variable, &b.variable))
+            },
+            _ => false,
+        }
+    }
+}
+
+fn same_variable<F: PrimeField>(a: &ConstraintVar<F>, b: &ConstraintVar<F>) -> bool {
+    match (a, b) {
+        (ConstraintVar::Var(a), ConstraintVar::Var(b)) => a == b,
+        (ConstraintVar::LC(a), ConstraintVar::LC(b)) => a.0 == b.0,
+        _ => false,
+    }
+}
+
+impl<TargetF: PrimeField, BaseF: PrimeField> Eq for NonNativeFieldGadget<TargetF, BaseF> {}
+
+impl<TargetF: PrimeField, BaseF: PrimeField> EqGadget<BaseF>
+    for NonNativeFieldGadget<TargetF, BaseF>
+{
+    fn enforce_equal<CS: ConstraintSystem<BaseF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(), SynthesisError> {
+        enforce_congruent::<TargetF, _, _>(
+            cs.ns(|| "congruent"),
+            &self.polynomial(),
+            &other.polynomial(),
+        )
+    }
+
+    /// The cost for reduced operands.
+    fn cost() -> usize {
+        let reduced = (BITS_PER_LIMB, Self::num_limbs());
+        congruence_cost::<TargetF, BaseF>(reduced, reduced)
+    }
+}
+
+impl<TargetF: PrimeField, BaseF: PrimeField> ConditionalEqGadget<BaseF>
+    for NonNativeFieldGadget<TargetF, BaseF>
+{
+    /// Enforces that `self` equals `other` if `condition` holds, and that
+    /// `self` equals itself otherwise.
+    fn conditional_enforce_equal<CS: ConstraintSystem<BaseF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+        condition: &Boolean,
+    ) -> Result<(), SynthesisError> {
+        let other = Self::conditionally_select(cs.ns(|| "select"), condition, other, self)?;
+        self.enforce_equal(cs.ns(|| "equal"), &other)
+    }
+
+    /// The cost for reduced operands.
+    fn cost() -> usize {
+        <Self as CondSelectGadget<BaseF>>::cost() + <Self as EqGadget<BaseF>>::cost()
+    }
+}
+
+impl<TargetF: PrimeField, BaseF: PrimeField> NEqGadget<BaseF>
+    for NonNativeFieldGadget<TargetF, BaseF>
+{
+    fn enforce_not_equal<CS: ConstraintSystem<BaseF>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(), SynthesisError> {
+        let a_minus_b = self.sub(cs.ns(|| "A - B"), other)?;
+        a_minus_b.inverse(cs.ns(|| "Enforce inverse exists"))?;
+        Ok(())
+    }
+
+    /// The cost for reduced operands.
+    fn cost() -> usize {
+        // The difference has limbs of `BITS_PER_LIMB + 2` bits.
+        Self::alloc_cost() + Self::mul_equals_cost(BITS_PER_LIMB + 2, BITS_PER_LIMB)
+    }
+}
+
+impl<TargetF: PrimeField, BaseF: PrimeField> AllocGadget<TargetF, BaseF>
+    for NonNativeFieldGadget<TargetF, BaseF>
+{
+    fn alloc_constant<T, CS: ConstraintSystem<BaseF>>(
+        mut cs: CS,
+        t: T,
+    ) -> Result<Self, SynthesisError>
+    where
+        T: Borrow<TargetF>,
+    {
+        let value = *t.borrow();
+        let mut limbs = Vec::with_capacity(Self::num_limbs());
+        for (i, limb) in Self::limb_values(&value).into_iter().enumerate() {
+            limbs.push(FpGadget::alloc_constant(
+                cs.ns(|| format!("limb {}", i)),
+                &BaseF::from(limb),
+            )?);
+        }
+        Ok(Self {
+            limbs,
+            limb_bits: BITS_PER_LIMB,
+            value: Some(value),
+        })
+    }
+
+    fn alloc<F, T, CS: ConstraintSystem<BaseF>>(cs: CS, f: F) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<TargetF>,
+    {
+        Self::alloc_reduced(cs, f().map(|v| *v.borrow()).ok())
+    }
+
+    fn alloc_input<F, T, CS: ConstraintSystem<BaseF>>(
+        mut cs: CS,
+        f: F,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: FnOnce() -> Result<T, SynthesisError>,
+        T: Borrow<TargetF>,
+    {
+        let value = f().map(|v| *v.borrow()).ok();
+        let limb_values = value.map(|v| Self::limb_values(&v));
+        let mut limbs = Vec::with_capacity(Self::num_limbs());
+        let mut all_bits = Vec::with_capacity(Self::num_limbs() * BITS_PER_LIMB as usize);
+        for i in 0..Self::num_limbs() {
+            let limb = FpGadget::alloc_input(cs.ns(|| format!("input limb {}", i)), || {
+                Ok(BaseF::from(limb_values.as_ref().get()?[i]))
+            })?;
+            let bits = alloc_bits(
+                cs.ns(|| format!("limb {} bits", i)),
+                limb_values.as_ref().map(|l| &l[i..=i]),
+                BITS_PER_LIMB,
+            )?;
+            let packed = pack_bits(cs.ns(|| format!("pack limb {}", i)), &bits)?;
+            limb.enforce_equal(cs.ns(|| format!("range check limb {}", i)), &packed)?;
+            all_bits.extend(bits);
+            limbs.push(limb);
+        }
+        // The limbs must encode the canonical representative, so that a
+        // verifier cannot be handed `x + p` in place of `x`.
+        all_bits.reverse();
+        Boolean::enforce_in_field::<_, _, TargetF>(cs.ns(|| "in field"), &all_bits)?;
+        Ok(Self {
+            limbs,
+            limb_bits: BITS_PER_LIMB,
+            value,
+        })
+    }
+}
+
+impl<TargetF: PrimeField, BaseF: PrimeField> ToBitsGadget<BaseF>
+    for NonNativeFieldGadget<TargetF, BaseF>
+{
+    /// Outputs the canonical big-endian bit decomposition of `self`.
+    fn to_bits<CS: ConstraintSystem<BaseF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<Boolean>, SynthesisError> {
+        let bits = self.to_non_unique_bits(cs.ns(|| "bits"))?;
+        Boolean::enforce_in_field::<_, _, TargetF>(cs.ns(|| "in field"), &bits)?;
+        Ok(bits)
+    }
+
+    /// Outputs a big-endian decomposition of `self` into
+    /// `TargetF::Params::MODULUS_BITS` bits, which may not be reduced.
+    fn to_non_unique_bits<CS: ConstraintSystem<BaseF>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Vec<Boolean>, SynthesisError> {
+        let num_bits = TargetF::Params::MODULUS_BITS;
+        let value = self.value.map(|v| Self::limb_values(&v));
+        let mut bits = alloc_bits(cs.ns(|| "alloc"), value.as_deref(), num_bits)?;
+        bits.reverse();
+        let packed = Self::from_bits(cs.ns(|| "pack"), &bits)?;
+        self.enforce_equal(cs.ns(|| "check decomposition"), &packed)?;
+        Ok(bits)
+    }
+}
+
+impl<TargetF: PrimeField, BaseF: PrimeField> ToBytesGadget<BaseF>
+    for NonNativeFieldGadget<TargetF, BaseF>
+{
+    /// Outputs the canonical little-endian byte decomposition of `self`.
+    fn to_bytes<CS: ConstraintSystem<BaseF>>(&self, cs: CS) -> Result<Vec<UInt8>, SynthesisError> {
+        Ok(Self::bits_to_bytes(self.to_bits(cs)?))
+    }
+
+    fn to_non_unique_bytes<CS: ConstraintSystem<BaseF>>(
+        &self,
+        cs: CS,
+    ) -> Result<Vec<UInt8>, SynthesisError> {
+        Ok(Self::bits_to_bytes(self.to_non_unique_bits(cs)?))
+    }
+}
+
+impl<TargetF: PrimeField, BaseF: PrimeField> CondSelectGadget<BaseF>
+    for NonNativeFieldGadget<TargetF, BaseF>
+{
+    fn conditionally_select<CS: ConstraintSystem<BaseF>>(
+        mut cs: CS,
+        cond: &Boolean,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let mut limbs = Vec::with_capacity(Self::num_limbs());
+        for (i, (t, f)) in true_value.limbs.iter().zip(&false_value.limbs).enumerate() {
+            limbs.push(FpGadget::conditionally_select(
+                cs.ns(|| format!("limb {}", i)),
+                cond,
+                t,
+                f,
+            )?);
+        }
+        let value = cond.get_value().and_then(|cond| {
+            if cond {
+                true_value.value
+            } else {
+                false_value.value
+            }
+        });
+        Ok(Self {
+            limbs,
+            limb_bits: max(true_value.limb_bits, false_value.limb_bits),
+            value,
+        })
+    }
+
+    fn cost() -> usize {
+        Self::num_limbs() * <FpGadget<BaseF> as CondSelectGadget<BaseF>>::cost()
+    }
+}
+
+/// Uses two bits to perform a lookup into a table
+/// `b` is little-endian: `b[0]` is LSB.
+impl<TargetF: PrimeField, BaseF: PrimeField> TwoBitLookupGadget<BaseF>
+    for NonNativeFieldGadget<TargetF, BaseF>
+{
+    type TableConstant = TargetF;
+
+    fn two_bit_lookup<CS: ConstraintSystem<BaseF>>(
+        mut cs: CS,
+        b: &[Boolean],
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        debug_assert!(b.len() == 2);
+        debug_assert!(c.len() == 4);
+
+        let table: Vec<_> = c.iter().map(Self::limb_values).collect();
+        let mut limbs = Vec::with_capacity(Self::num_limbs());
+        for i in 0..Self::num_limbs() {
+            let constants: Vec<_> = table.iter().map(|t| BaseF::from(t[i])).collect();
+            limbs.push(FpGadget::two_bit_lookup(
+                cs.ns(|| format!("limb {}", i)),
+                b,
+                &constants,
+            )?);
+        }
+        let value = match (b[0].get_value(), b[1].get_value()) {
+            (Some(b0), Some(b1)) => Some(c[(b0 as usize) | ((b1 as usize) << 1)]),
+            _ => None,
+        };
+        Ok(Self {
+            limbs,
+            limb_bits: BITS_PER_LIMB,
+            value,
+        })
+    }
+
+    fn cost() -> usize {
+        Self::num_limbs() * <FpGadget<BaseF> as TwoBitLookupGadget<BaseF>>::cost()
+    }
+}
+
+/// Uses three bits to perform a lookup into a table, where the last bit
+/// performs negation.
+impl<TargetF: PrimeField, BaseF: PrimeField> ThreeBitCondNegLookupGadget<BaseF>
+    for NonNativeFieldGadget<TargetF, BaseF>
+{
+    type TableConstant = TargetF;
+
+    fn three_bit_cond_neg_lookup<CS: ConstraintSystem<BaseF>>(
+        mut cs: CS,
+        b: &[Boolean],
+        _: &Boolean,
+        c: &[Self::TableConstant],
+    ) -> Result<Self, SynthesisError> {
+        debug_assert!(b.len() == 3);
+        debug_assert!(c.len() == 4);
+
+        let y = Self::two_bit_lookup(cs.ns(|| "lookup"), &b[..2], c)?;
+        let negated = y.negate(cs.ns(|| "negate"))?;
+        Self::conditionally_select(cs.ns(|| "select"), &b[2], &negated, &y)
+    }
+
+    fn cost() -> usize {
+        <Self as TwoBitLookupGadget<BaseF>>::cost() + <Self as CondSelectGadget<BaseF>>::cost()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NonNativeFieldGadget;
+    use crate::{prelude::*, test_constraint_system::TestConstraintSystem};
+    use algebra::{
+        bls12_381::{Fq, Fr},
+        test_rng, BitIterator, Field, One, PrimeField, UniformRand, Zero,
+    };
+    use r1cs_core::ConstraintSystem;
+
+    type NonNativeFq = NonNativeFieldGadget<Fq, Fr>;
+
+    #[test]
+    fn nonnative_arithmetic_test() {
+        let mut rng = test_rng();
+        let mut cs = TestConstraintSystem::<Fr>::new();
+
+        let a_native = Fq::rand(&mut rng);
+        let b_native = Fq::rand(&mut rng);
+        let a = NonNativeFq::alloc(cs.ns(|| "a"), || Ok(a_native)).unwrap();
+        let b = NonNativeFq::alloc_input(cs.ns(|| "b"), || Ok(b_native)).unwrap();
+
+        // Lazily accumulate a few sums and differences before multiplying.
+        let mut c = a.clone();
+        let mut c_native = a_native;
+        for i in 0..10 {
+            c = c.add(cs.ns(|| format!("add {}", i)), &b).unwrap();
+            c = c.sub(cs.ns(|| format!("sub {}", i)), &a).unwrap();
+            c = c.double(cs.ns(|| format!("double {}", i))).unwrap();
+            c_native = (c_native + &b_native - &a_native).double();
+        }
+        assert_eq!(c.get_value().unwrap(), c_native);
+
+        let d = c.mul(cs.ns(|| "mul"), &b).unwrap();
+        let d_native = c_native * &b_native;
+        assert_eq!(d.get_value().unwrap(), d_native);
+
+        let e = d.negate(cs.ns(|| "negate")).unwrap();
+        let e = e.add_constant(cs.ns(|| "add constant"), &a_native).unwrap();
+        let e = e
+            .mul_by_constant(cs.ns(|| "mul by constant"), &b_native)
+            .unwrap();
+        let e_native = (a_native - &d_native) * &b_native;
+        let e_inv = e.inverse(cs.ns(|| "inverse")).unwrap();
+        assert_eq!(e_inv.get_value().unwrap(), e_native.inverse().unwrap());
+
+        let expected = NonNativeFq::alloc(cs.ns(|| "expected"), || Ok(e_native)).unwrap();
+        e.enforce_equal(cs.ns(|| "equal"), &expected).unwrap();
+
+        let bits = e.to_bits(cs.ns(|| "to bits")).unwrap();
+        let native_bits: Vec<_> = BitIterator::new(e_native.into_repr())
+            .skip(64 * 6 - 381)
+            .collect();
+        assert_eq!(
+            bits.iter()
+                .map(|b| b.get_value().unwrap())
+                .collect::<Vec<_>>(),
+            native_bits
+        );
+        assert!(cs.is_satisfied());
+
+        let wrong = NonNativeFq::alloc(cs.ns(|| "wrong"), || Ok(e_native.double())).unwrap();
+        e.enforce_equal(cs.ns(|| "not equal"), &wrong).unwrap();
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn nonnative_sum_of_products_test() {
+        let mut rng = test_rng();
+        let mut cs = TestConstraintSystem::<Fr>::new();
+
+        let values: Vec<_> = (0..5).map(|_| Fq::rand(&mut rng)).collect();
+        let vars: Vec<_> = values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| NonNativeFq::alloc(cs.ns(|| format!("alloc {}", i)), || Ok(v)).unwrap())
+            .collect();
+        // a * b + c * d = a * d + result
+        let result_native =
+            values[0] * &values[1] + &(values[2] * &values[3]) - &(values[0] * &values[3]);
+        let result = NonNativeFq::alloc(cs.ns(|| "result"), || Ok(result_native)).unwrap();
+        NonNativeFq::enforce_sum_of_products_equal(
+            cs.ns(|| "check"),
+            &[(&vars[0], &vars[1]), (&vars[2], &vars[3])],
+            &[(&vars[0], &vars[3])],
+            &result,
+        )
+        .unwrap();
+        assert!(cs.is_satisfied());
+
+        NonNativeFq::enforce_sum_of_products_equal(
+            cs.ns(|| "wrong check"),
+            &[(&vars[0], &vars[1]), (&vars[2], &vars[3])],
+            &[(&vars[0], &vars[4])],
+            &result,
+        )
+        .unwrap();
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn nonnative_field_test() {
+        crate::fields::tests::field_test::<_, Fr, NonNativeFq>();
+    }
+
+    #[test]
+    fn nonnative_cost_test() {
+        let mut rng = test_rng();
+        let mut cs = TestConstraintSystem::<Fr>::new();
+
+        let a_native = Fq::rand(&mut rng);
+        let b_native = Fq::rand(&mut rng);
+        let a = NonNativeFq::alloc(cs.ns(|| "a"), || Ok(a_native)).unwrap();
+        let b = NonNativeFq::alloc(cs.ns(|| "b"), || Ok(b_native)).unwrap();
+        assert_eq!(cs.num_constraints(), 2 * NonNativeFq::alloc_cost());
+
+        let n = cs.num_constraints();
+        let c = a.mul(cs.ns(|| "mul"), &b).unwrap();
+        assert_eq!(cs.num_constraints() - n, NonNativeFq::cost_of_mul());
+
+        let n = cs.num_constraints();
+        a.mul_equals(cs.ns(|| "mul equals"), &b, &c).unwrap();
+        assert_eq!(cs.num_constraints() - n, NonNativeFq::cost_of_mul_equals());
+
+        let n = cs.num_constraints();
+        a.inverse(cs.ns(|| "inverse")).unwrap();
+        assert_eq!(cs.num_constraints() - n, NonNativeFq::cost_of_inv());
+
+        let n = cs.num_constraints();
+        c.enforce_equal(cs.ns(|| "equal"), &c).unwrap();
+        assert_eq!(
+            cs.num_constraints() - n,
+            <NonNativeFq as EqGadget<Fr>>::cost()
+        );
+
+        let n = cs.num_constraints();
+        let condition = Boolean::alloc(cs.ns(|| "condition"), || Ok(true)).unwrap();
+        let n_condition = cs.num_constraints() - n;
+        a.conditional_enforce_equal(cs.ns(|| "conditional equal"), &a, &condition)
+            .unwrap();
+        assert_eq!(
+            cs.num_constraints() - n - n_condition,
+            <NonNativeFq as ConditionalEqGadget<Fr>>::cost()
+        );
+
+        let n = cs.num_constraints();
+        a.enforce_not_equal(cs.ns(|| "not equal"), &b).unwrap();
+        assert_eq!(
+            cs.num_constraints() - n,
+            <NonNativeFq as NEqGadget<Fr>>::cost()
+        );
+
+        let constants: Vec<_> = (0..4).map(|_| Fq::rand(&mut rng)).collect();
+        let bits: Vec<_> = (0..3)
+            .map(|i| Boolean::alloc(cs.ns(|| format!("bit {}", i)), || Ok(i != 1)).unwrap())
+            .collect();
+        let b0b1 = Boolean::and(cs.ns(|| "b0 and b1"), &bits[0], &bits[1]).unwrap();
+        let n = cs.num_constraints();
+        let result =
+            NonNativeFq::three_bit_cond_neg_lookup(cs.ns(|| "lookup"), &bits, &b0b1, &constants)
+                .unwrap();
+        assert_eq!(
+            cs.num_constraints() - n,
+            <NonNativeFq as ThreeBitCondNegLookupGadget<Fr>>::cost()
+        );
+        assert_eq!(result.get_value().unwrap(), -constants[1]);
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn nonnative_eq_without_values_test() {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let mut a = NonNativeFq::alloc(cs.ns(|| "a"), || Ok(Fq::one())).unwrap();
+        let mut b = NonNativeFq::alloc(cs.ns(|| "b"), || Ok(Fq::one())).unwrap();
+        // Forget the assignments, as when generating parameters.
+        a.value = None;
+        b.value = None;
+        assert!(a == a.clone());
+        assert!(a != b);
+    }
+
+    #[test]
+    fn nonnative_alloc_input_not_canonical_test() {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        NonNativeFq::alloc_input(cs.ns(|| "x"), || Ok(Fq::zero())).unwrap();
+        assert!(cs.is_satisfied());
+
+        // Replace the encoding of zero by the modulus itself, keeping every
+        // limb consistent with its bits.
+        let modulus = Fq::characteristic();
+        for (i, limb) in modulus.iter().enumerate() {
+            cs.set(&format!("x/input limb {}/alloc", i), Fr::from(*limb));
+            cs.set(&format!("x/pack limb {}/alloc/alloc", i), Fr::from(*limb));
+            for j in 0..64 {
+                let bit = if (limb >> j) & 1 == 1 {
+                    Fr::one()
+                } else {
+                    Fr::zero()
+                };
+                cs.set(&format!("x/limb {} bits/bit {}/boolean", i, j), bit);
+            }
+        }
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn nonnative_inverse_of_zero_test() {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let zero = NonNativeFq::alloc(cs.ns(|| "zero"), || Ok(Fq::zero())).unwrap();
+        zero.inverse(cs.ns(|| "inverse")).unwrap();
+        assert!(!cs.is_satisfied());
+    }
+}
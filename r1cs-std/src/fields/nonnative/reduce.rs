@@ -0,0 +1,489 @@
+use algebra::{BigInteger, FpParameters, PrimeField};
+use core::cmp::{max, min, Ordering};
+use r1cs_core::{ConstraintSystem, LinearCombination, SynthesisError};
+
+use crate::{
+    boolean::AllocatedBit,
+    fields::{fp::FpGadget, FieldGadget},
+    prelude::*,
+    Assignment, Vec,
+};
+
+use super::BITS_PER_LIMB;
+
+/// A polynomial in `X = 2^BITS_PER_LIMB` whose coefficients are non-negative
+/// integers smaller than `2^bits`, held in variables of the constraint field.
+pub(super) struct LimbPolynomial<F: PrimeField> {
+    pub(super) coeffs: Vec<FpGadget<F>>,
+    pub(super) bits: u32,
+}
+
+impl<F: PrimeField> LimbPolynomial<F> {
+    pub(super) fn new(coeffs: Vec<FpGadget<F>>, bits: u32) -> Self {
+        Self { coeffs, bits }
+    }
+
+    /// The polynomial whose coefficients are the limbs of the integer `limbs`.
+    pub(super) fn constant<CS: ConstraintSystem<F>>(
+        mut cs: CS,
+        limbs: &[u64],
+    ) -> Result<Self, SynthesisError> {
+        let mut coeffs = Vec::with_capacity(limbs.len());
+        for (i, limb) in limbs.iter().enumerate() {
+            coeffs.push(FpGadget::alloc_constant(
+                cs.ns(|| format!("limb {}", i)),
+                &F::from(*limb),
+            )?);
+        }
+        Ok(Self::new(coeffs, BITS_PER_LIMB))
+    }
+
+    /// An upper bound on the bit length of the integer this evaluates to.
+    pub(super) fn integer_bits(&self) -> u32 {
+        integer_bits(self.bits, self.coeffs.len())
+    }
+
+    /// The integer this evaluates to, as little-endian 64-bit limbs.
+    pub(super) fn integer_value(&self) -> Option<Vec<u64>> {
+        let mut result = vec![0u64; self.coeffs.len() + F::BigInt::NUM_LIMBS + 1];
+        for (i, coeff) in self.coeffs.iter().enumerate() {
+            let coeff = coeff.get_value()?.into_repr();
+            add_assign_shifted(&mut result, coeff.as_ref(), i);
+        }
+        Some(result)
+    }
+
+    fn is_constant(&self) -> bool {
+        self.coeffs.iter().all(|c| match &c.variable {
+            r1cs_core::ConstraintVar::LC(lc) => lc.is_constant(),
+            r1cs_core::ConstraintVar::Var(_) => false,
+        })
+    }
+
+    /// Adds `other` coefficient-wise.
+    pub(super) fn add<CS: ConstraintSystem<F>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let len = max(self.coeffs.len(), other.coeffs.len());
+        let mut coeffs = Vec::with_capacity(len);
+        for i in 0..len {
+            coeffs.push(match (self.coeffs.get(i), other.coeffs.get(i)) {
+                (Some(a), Some(b)) => a.add(cs.ns(|| format!("add {}", i)), b)?,
+                (Some(a), None) | (None, Some(a)) => a.clone(),
+                (None, None) => unreachable!(),
+            });
+        }
+        Ok(Self::new(coeffs, max(self.bits, other.bits) + 1))
+    }
+
+    /// Multiplies by `other`. Unless one side is constant, the coefficients of
+    /// the product are allocated, and the product is checked by evaluating
+    /// both sides at `0, 1, ..., 2n - 2`, which costs one constraint per
+    /// coefficient instead of one per pair of limbs.
+    pub(super) fn mul<CS: ConstraintSystem<F>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let len = self.coeffs.len() + other.coeffs.len() - 1;
+        let bits =
+            self.bits + other.bits + ceil_log2(min(self.coeffs.len(), other.coeffs.len()) as u64);
+
+        if self.is_constant() || other.is_constant() {
+            let (constant, variable) = if self.is_constant() {
+                (self, other)
+            } else {
+                (other, self)
+            };
+            let mut coeffs = vec![FpGadget::zero(cs.ns(|| "zero"))?; len];
+            for (i, c) in constant.coeffs.iter().enumerate() {
+                let c = c.get_value().get()?;
+                for (j, v) in variable.coeffs.iter().enumerate() {
+                    let term = v.mul_by_constant(cs.ns(|| format!("mul {} {}", i, j)), &c)?;
+                    coeffs[i + j] =
+                        coeffs[i + j].add(cs.ns(|| format!("add {} {}", i, j)), &term)?;
+                }
+            }
+            return Ok(Self::new(coeffs, bits));
+        }
+
+        let mut values = vec![Some(F::zero()); len];
+        for (i, a) in self.coeffs.iter().enumerate() {
+            for (j, b) in other.coeffs.iter().enumerate() {
+                values[i + j] = match (values[i + j], a.get_value(), b.get_value()) {
+                    (Some(acc), Some(a), Some(b)) => Some(acc + &(a * &b)),
+                    _ => None,
+                };
+            }
+        }
+        let mut coeffs = Vec::with_capacity(len);
+        for (i, value) in values.into_iter().enumerate() {
+            coeffs.push(FpGadget::alloc(
+                cs.ns(|| format!("product coefficient {}", i)),
+                || value.get(),
+            )?);
+        }
+
+        for x in 0..len {
+            let x = F::from(x as u64);
+            let mut cs = cs.ns(|| format!("evaluate at {}", x));
+            let a = evaluate(cs.ns(|| "lhs"), &self.coeffs, &x)?;
+            let b = evaluate(cs.ns(|| "rhs"), &other.coeffs, &x)?;
+            let c = evaluate(cs.ns(|| "product"), &coeffs, &x)?;
+            a.mul_equals(cs.ns(|| "check"), &b, &c)?;
+        }
+
+        Ok(Self::new(coeffs, bits))
+    }
+}
+
+/// Evaluates the polynomial with coefficients `coeffs` at the constant `x`.
+fn evaluate<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    coeffs: &[FpGadget<F>],
+    x: &F,
+) -> Result<FpGadget<F>, SynthesisError> {
+    let mut result = FpGadget::zero(cs.ns(|| "zero"))?;
+    let mut power = F::one();
+    for (i, coeff) in coeffs.iter().enumerate() {
+        let term = coeff.mul_by_constant(cs.ns(|| format!("term {}", i)), &power)?;
+        result = result.add(cs.ns(|| format!("add {}", i)), &term)?;
+        power *= x;
+    }
+    Ok(result)
+}
+
+/// Allocates `num_bits` bits holding the low bits of `value`, and returns them
+/// in little-endian order.
+pub(super) fn alloc_bits<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    value: Option<&[u64]>,
+    num_bits: u32,
+) -> Result<Vec<Boolean>, SynthesisError> {
+    let mut bits = Vec::with_capacity(num_bits as usize);
+    for i in 0..num_bits as usize {
+        let bit = value.map(|v| {
+            v.get(i / 64)
+                .map_or(false, |limb| (limb >> (i % 64)) & 1 == 1)
+        });
+        bits.push(AllocatedBit::alloc(cs.ns(|| format!("bit {}", i)), || bit.get())?.into());
+    }
+    Ok(bits)
+}
+
+/// Packs little-endian `bits` into a single variable.
+pub(super) fn pack_bits<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    bits: &[Boolean],
+) -> Result<FpGadget<F>, SynthesisError> {
+    let mut lc = LinearCombination::zero();
+    let mut value = Some(F::zero());
+    let mut coeff = F::one();
+    for bit in bits {
+        lc = lc + bit.lc(CS::one(), coeff);
+        value = match (value, bit.get_value()) {
+            (Some(acc), Some(true)) => Some(acc + &coeff),
+            (Some(acc), Some(false)) => Some(acc),
+            _ => None,
+        };
+        coeff.double_in_place();
+    }
+    let result = FpGadget::alloc(cs.ns(|| "alloc"), || value.get())?;
+    cs.enforce(|| "pack", |lc| lc, |lc| lc, |_| &result.variable - lc);
+    Ok(result)
+}
+
+/// Allocates an integer of at most `num_bits` bits as a polynomial with
+/// range-checked coefficients of `BITS_PER_LIMB` bits.
+pub(super) fn alloc_limbs<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    value: Option<&[u64]>,
+    num_bits: u32,
+) -> Result<LimbPolynomial<F>, SynthesisError> {
+    let bits = alloc_bits(cs.ns(|| "bits"), value, num_bits)?;
+    let mut coeffs = Vec::new();
+    for (i, chunk) in bits.chunks(BITS_PER_LIMB as usize).enumerate() {
+        coeffs.push(pack_bits(cs.ns(|| format!("limb {}", i)), chunk)?);
+    }
+    Ok(LimbPolynomial::new(coeffs, BITS_PER_LIMB))
+}
+
+/// Enforces that `lhs` and `rhs` evaluate to integers that are congruent
+/// modulo the characteristic `p` of `TargetF`.
+///
+/// This exhibits a quotient `q` such that `lhs + m = rhs + q * p`, where `m`
+/// is a constant multiple of `p` that is larger than `rhs`, so that `q` is
+/// non-negative and can be range-checked.
+pub(super) fn enforce_congruent<TargetF: PrimeField, F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    lhs: &LimbPolynomial<F>,
+    rhs: &LimbPolynomial<F>,
+) -> Result<(), SynthesisError> {
+    let modulus = TargetF::Params::MODULUS;
+    let modulus = modulus.as_ref();
+
+    let offset = congruence_offset::<TargetF>(rhs.integer_bits());
+    let offset = LimbPolynomial::constant(cs.ns(|| "offset"), &offset)?;
+    let lhs = lhs.add(cs.ns(|| "add offset"), &offset)?;
+
+    let quotient_bits = quotient_bits::<TargetF>(lhs.integer_bits());
+    let quotient_value = match (lhs.integer_value(), rhs.integer_value()) {
+        (Some(mut lhs), Some(rhs)) => {
+            if sub_assign(&mut lhs, &rhs) {
+                Some(vec![0u64])
+            } else {
+                Some(div_rem(&lhs, modulus).0)
+            }
+        },
+        _ => None,
+    };
+    let quotient = alloc_limbs(
+        cs.ns(|| "quotient"),
+        quotient_value.as_deref(),
+        quotient_bits,
+    )?;
+    let modulus = LimbPolynomial::constant(cs.ns(|| "modulus"), &trim(modulus.to_vec()))?;
+    let multiple = quotient.mul(cs.ns(|| "quotient times modulus"), &modulus)?;
+    let rhs = rhs.add(cs.ns(|| "add multiple"), &multiple)?;
+
+    enforce_equal_integers(cs.ns(|| "carries"), &lhs, &rhs)
+}
+
+/// Enforces that `lhs` and `rhs` evaluate to the same integer, by computing
+/// the difference of their coefficients and propagating signed carries.
+///
+/// Neighbouring coefficients are grouped as long as the groups still fit into
+/// the constraint field, which saves range checks on the carries.
+fn enforce_equal_integers<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    lhs: &LimbPolynomial<F>,
+    rhs: &LimbPolynomial<F>,
+) -> Result<(), SynthesisError> {
+    // Every difference is smaller than `2^bits` in absolute value, and we need
+    // the groups and carries to be smaller than half of the modulus.
+    let bits = max(lhs.bits, rhs.bits);
+    let len = max(lhs.coeffs.len(), rhs.coeffs.len());
+    let (group_size, carry_bits) = carry_layout::<F>(bits, len);
+    let group_shift = F::from(2u64).pow(&[(BITS_PER_LIMB * group_size as u32) as u64]);
+    let carry_offset = F::from(2u64).pow(&[(carry_bits - 1) as u64]);
+
+    let mut differences = Vec::with_capacity(len);
+    for i in 0..len {
+        let zero = FpGadget::zero(cs.ns(|| format!("zero {}", i)))?;
+        let a = lhs.coeffs.get(i).unwrap_or(&zero);
+        let b = rhs.coeffs.get(i).unwrap_or(&zero);
+        differences.push(a.sub(cs.ns(|| format!("difference {}", i)), b)?);
+    }
+
+    let num_groups = (len + group_size - 1) / group_size;
+    let mut carry: Option<FpGadget<F>> = None;
+    for (j, group) in differences.chunks(group_size).enumerate() {
+        let mut cs = cs.ns(|| format!("group {}", j));
+        let mut sum = evaluate(
+            cs.ns(|| "sum"),
+            group,
+            &F::from(2u64).pow(&[BITS_PER_LIMB as u64]),
+        )?;
+        if let Some(carry) = &carry {
+            sum = sum.add(cs.ns(|| "add carry"), carry)?;
+        }
+
+        if j + 1 == num_groups {
+            let zero = FpGadget::zero(cs.ns(|| "zero"))?;
+            sum.enforce_equal(cs.ns(|| "last carry is zero"), &zero)?;
+        } else {
+            let shifted_carry = sum
+                .get_value()
+                .map(|v| signed_shift(v, BITS_PER_LIMB * group_size as u32) + &carry_offset);
+            let bits = alloc_bits(
+                cs.ns(|| "carry bits"),
+                shifted_carry
+                    .map(|c| c.into_repr())
+                    .as_ref()
+                    .map(|c| c.as_ref()),
+                carry_bits,
+            )?;
+            let next_carry = pack_bits(cs.ns(|| "pack carry"), &bits)?
+                .add_constant(cs.ns(|| "remove offset"), &-carry_offset)?;
+            let shifted = next_carry.mul_by_constant(cs.ns(|| "shift carry"), &group_shift)?;
+            sum.enforce_equal(cs.ns(|| "carry"), &shifted)?;
+            carry = Some(next_carry);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the size of the groups of coefficients and the number of bits of
+/// the carries in `enforce_equal_integers`, for differences of `len`
+/// coefficients that are smaller than `2^bits` in absolute value.
+fn carry_layout<F: PrimeField>(bits: u32, len: usize) -> (usize, u32) {
+    // Groups and carries must be smaller than half of the modulus.
+    let capacity = F::Params::MODULUS_BITS - 2;
+    assert!(
+        bits + 2 <= capacity,
+        "limbs are too large for the constraint field"
+    );
+    let group_size = min(1 + (capacity - bits - 2) / BITS_PER_LIMB, len as u32) as usize;
+
+    // Carries are smaller than `2^(bits - BITS_PER_LIMB + 2)` in absolute
+    // value, and are range-checked after adding that bound.
+    let carry_bits = (bits + 2).saturating_sub(BITS_PER_LIMB) + 1;
+    (group_size, carry_bits)
+}
+
+/// An upper bound on the bit length of the integer that a polynomial with
+/// `len` coefficients of `bits` bits evaluates to.
+fn integer_bits(bits: u32, len: usize) -> u32 {
+    bits + BITS_PER_LIMB * (len as u32 - 1) + 1
+}
+
+/// The multiple of the characteristic `p` of `TargetF` that is added to the
+/// left-hand side of a congruence whose right-hand side has at most
+/// `rhs_integer_bits` bits.
+fn congruence_offset<TargetF: PrimeField>(rhs_integer_bits: u32) -> Vec<u64> {
+    let modulus = TargetF::Params::MODULUS;
+    let modulus = modulus.as_ref();
+    let mut bound = vec![0u64; (rhs_integer_bits / 64) as usize + 1];
+    bound[(rhs_integer_bits / 64) as usize] = 1 << (rhs_integer_bits % 64);
+    let mut offset = div_rem(&bound, modulus).0;
+    add_assign_shifted(&mut offset, &[1], 0);
+    trim(mul(&offset, modulus))
+}
+
+/// The number of bits of the quotient by the characteristic of `TargetF` of
+/// an integer of at most `integer_bits` bits.
+fn quotient_bits<TargetF: PrimeField>(integer_bits: u32) -> u32 {
+    integer_bits - TargetF::Params::MODULUS_BITS + 1
+}
+
+/// The number of constraints used by `alloc_limbs` for `num_bits` bits.
+pub(super) fn alloc_limbs_cost(num_bits: u32) -> usize {
+    (num_bits + (num_bits + BITS_PER_LIMB - 1) / BITS_PER_LIMB) as usize
+}
+
+/// The number of constraints used by `enforce_congruent` on polynomials
+/// given by the bit length and the number of their coefficients.
+pub(super) fn congruence_cost<TargetF: PrimeField, F: PrimeField>(
+    (lhs_bits, lhs_len): (u32, usize),
+    (rhs_bits, rhs_len): (u32, usize),
+) -> usize {
+    // This follows the shapes of the polynomials in `enforce_congruent`.
+    let offset_len = congruence_offset::<TargetF>(integer_bits(rhs_bits, rhs_len)).len();
+    let lhs_bits = max(lhs_bits, BITS_PER_LIMB) + 1;
+    let lhs_len = max(lhs_len, offset_len);
+
+    let quotient_bits = quotient_bits::<TargetF>(integer_bits(lhs_bits, lhs_len));
+    let quotient_len = ((quotient_bits + BITS_PER_LIMB - 1) / BITS_PER_LIMB) as usize;
+    let modulus_len = trim(TargetF::Params::MODULUS.as_ref().to_vec()).len();
+    let multiple_bits = 2 * BITS_PER_LIMB + ceil_log2(min(quotient_len, modulus_len) as u64);
+    let rhs_bits = max(rhs_bits, multiple_bits) + 1;
+    let rhs_len = max(rhs_len, quotient_len + modulus_len - 1);
+
+    // Every group but the last one range-checks and packs its carry.
+    let bits = max(lhs_bits, rhs_bits);
+    let len = max(lhs_len, rhs_len);
+    let (group_size, carry_bits) = carry_layout::<F>(bits, len);
+    let num_groups = (len + group_size - 1) / group_size;
+    alloc_limbs_cost(quotient_bits) + (num_groups - 1) * (carry_bits as usize + 2) + 1
+}
+
+/// Divides the signed integer `value` (i.e., interpreted in
+/// `(-p/2, p/2)`) by `2^shift`, rounding towards zero.
+fn signed_shift<F: PrimeField>(value: F, shift: u32) -> F {
+    let is_negative = value.into_repr() > F::Params::MODULUS_MINUS_ONE_DIV_TWO;
+    let mut magnitude = if is_negative { -value } else { value }.into_repr();
+    magnitude.divn(shift);
+    let result = F::from_repr(magnitude).unwrap();
+    if is_negative {
+        -result
+    } else {
+        result
+    }
+}
+
+pub(super) fn ceil_log2(x: u64) -> u32 {
+    64 - (x - 1).leading_zeros()
+}
+
+// Helpers for witness generation on little-endian multi-limb integers.
+
+fn trim(mut a: Vec<u64>) -> Vec<u64> {
+    while a.len() > 1 && *a.last().unwrap() == 0 {
+        a.pop();
+    }
+    a
+}
+
+fn add_assign_shifted(a: &mut Vec<u64>, b: &[u64], shift: usize) {
+    if a.len() < b.len() + shift + 1 {
+        a.resize(b.len() + shift + 1, 0);
+    }
+    let mut carry = 0u128;
+    for (i, a_i) in a.iter_mut().enumerate().skip(shift) {
+        let sum = u128::from(*a_i) + u128::from(*b.get(i - shift).unwrap_or(&0)) + carry;
+        *a_i = sum as u64;
+        carry = sum >> 64;
+        if carry == 0 && i >= b.len() + shift {
+            break;
+        }
+    }
+}
+
+/// Subtracts `b` from `a`, and returns whether this underflowed.
+fn sub_assign(a: &mut Vec<u64>, b: &[u64]) -> bool {
+    if a.len() < b.len() {
+        a.resize(b.len(), 0);
+    }
+    let mut borrow = 0i128;
+    for (i, a_i) in a.iter_mut().enumerate() {
+        let diff = i128::from(*a_i) - i128::from(*b.get(i).unwrap_or(&0)) - borrow;
+        *a_i = diff as u64;
+        borrow = if diff < 0 { 1 } else { 0 };
+    }
+    borrow != 0
+}
+
+fn mul(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut result = vec![0u64; a.len() + b.len()];
+    for (i, a_i) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, b_j) in b.iter().enumerate() {
+            let t = u128::from(*a_i) * u128::from(*b_j) + u128::from(result[i + j]) + carry;
+            result[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        result[i + b.len()] = carry as u64;
+    }
+    result
+}
+
+fn cmp(a: &[u64], b: &[u64]) -> Ordering {
+    for i in (0..max(a.len(), b.len())).rev() {
+        let ordering = a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0));
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// Schoolbook binary long division.
+fn div_rem(a: &[u64], m: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    let mut quotient = vec![0u64; a.len()];
+    let mut remainder = vec![0u64; m.len() + 1];
+    for i in (0..a.len() * 64).rev() {
+        let mut carry = (a[i / 64] >> (i % 64)) & 1;
+        for limb in remainder.iter_mut() {
+            let next = *limb >> 63;
+            *limb = (*limb << 1) | carry;
+            carry = next;
+        }
+        if cmp(&remainder, m) != Ordering::Less {
+            sub_assign(&mut remainder, m);
+            quotient[i / 64] |= 1 << (i % 64);
+        }
+    }
+    (quotient, remainder)
+}
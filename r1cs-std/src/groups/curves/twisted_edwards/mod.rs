@@ -280,7 +280,10 @@ where
 mod affine_impl {
     use super::*;
     use crate::Assignment;
-    use algebra::{curves::AffineCurve, Field, PrimeField};
+    use algebra::{
+        curves::{twisted_edwards_extended::GroupProjective as TEProjective, AffineCurve},
+        Field, PrimeField,
+    };
     use core::ops::Neg;
 
     impl<P, ConstraintF, F> GroupGadget<TEAffine<P>, ConstraintF> for AffineGadget<P, ConstraintF, F>
@@ -499,6 +502,32 @@ mod affine_impl {
             ))
         }
 
+        fn precomputed_base_3_bit_signed_digit_scalar_mul<'a, CS, I, J, B>(
+            cs: CS,
+            bases: &[B],
+            scalars: &[J],
+        ) -> Result<Self, SynthesisError>
+        where
+            CS: ConstraintSystem<ConstraintF>,
+            I: Borrow<[Boolean]>,
+            J: Borrow<[I]>,
+            B: Borrow<[TEAffine<P>]>,
+        {
+            let bases = bases
+                .iter()
+                .map(|segment| {
+                    segment
+                        .borrow()
+                        .iter()
+                        .map(|base| base.into_projective())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            <Self as GroupGadget<TEProjective<P>, ConstraintF>>::precomputed_base_3_bit_signed_digit_scalar_mul(
+                cs, &bases, scalars,
+            )
+        }
+
         fn cost_of_add() -> usize {
             4 + 2 * F::cost_of_mul()
         }
@@ -0,0 +1,130 @@
+//! The commitments of SnarkPack to vectors of group elements, which are
+//! pairs of target group elements under two commitment keys: `v`, the
+//! powers `{a^i H}` and `{b^i H}`, and `w`, the powers `{a^(n + i) G}` and
+//! `{b^(n + i) G}`.
+use crate::{Commitment, Vec};
+use algebra_core::{AffineCurve, PairingEngine, PrimeField, ProjectiveCurve, Zero};
+
+/// A commitment key, given by its powers of `a` and of `b`.
+#[derive(Clone, Debug)]
+pub(crate) struct Key<G: AffineCurve> {
+    pub(crate) a: Vec<G>,
+    pub(crate) b: Vec<G>,
+}
+
+impl<G: AffineCurve> Key<G> {
+    pub(crate) fn len(&self) -> usize {
+        self.a.len()
+    }
+
+    /// Splits the key into its halves.
+    pub(crate) fn split(&self) -> (Key<G>, Key<G>) {
+        let (a_left, a_right) = self.a.split_at(self.len() / 2);
+        let (b_left, b_right) = self.b.split_at(self.len() / 2);
+        let left = Key {
+            a: a_left.to_vec(),
+            b: b_left.to_vec(),
+        };
+        let right = Key {
+            a: a_right.to_vec(),
+            b: b_right.to_vec(),
+        };
+        (left, right)
+    }
+
+    /// Multiplies the `i`-th element of the key by `scalars[i]`.
+    pub(crate) fn scale(&self, scalars: &[G::ScalarField]) -> Key<G> {
+        Key {
+            a: scale(&self.a, scalars),
+            b: scale(&self.b, scalars),
+        }
+    }
+
+    /// Folds the key into `left + x right`.
+    pub(crate) fn fold(&self, x: G::ScalarField) -> Key<G> {
+        Key {
+            a: fold(&self.a, x),
+            b: fold(&self.b, x),
+        }
+    }
+
+    /// The only element of a fully folded key, for `a` and for `b`.
+    pub(crate) fn first(&self) -> (G, G) {
+        (self.a[0], self.b[0])
+    }
+}
+
+/// Computes `prod_i e(a_i, b_i)`.
+pub(crate) fn pairing_product<E: PairingEngine>(a: &[E::G1Affine], b: &[E::G2Affine]) -> E::Fqk {
+    let pairs = a
+        .iter()
+        .zip(b)
+        .map(|(a, b)| ((*a).into(), (*b).into()))
+        .collect::<Vec<(E::G1Prepared, E::G2Prepared)>>();
+    E::product_of_pairings(&pairs)
+}
+
+/// Computes the commitment `(prod_i e(a_i, v_a_i) e(w_a_i, b_i), prod_i
+/// e(a_i, v_b_i) e(w_b_i, b_i))` to the vectors `a` and `b`.
+pub(crate) fn commit_pair<E: PairingEngine>(
+    vkey: &Key<E::G2Affine>,
+    wkey: &Key<E::G1Affine>,
+    a: &[E::G1Affine],
+    b: &[E::G2Affine],
+) -> Commitment<E> {
+    let commit = |v: &[E::G2Affine], w: &[E::G1Affine]| {
+        let g1 = a.iter().chain(w).copied().collect::<Vec<_>>();
+        let g2 = v.iter().chain(b).copied().collect::<Vec<_>>();
+        pairing_product::<E>(&g1, &g2)
+    };
+    Commitment {
+        t: commit(&vkey.a, &wkey.a),
+        u: commit(&vkey.b, &wkey.b),
+    }
+}
+
+/// Computes the commitment `(prod_i e(c_i, v_a_i), prod_i e(c_i, v_b_i))` to
+/// the vector `c`.
+pub(crate) fn commit_single<E: PairingEngine>(
+    vkey: &Key<E::G2Affine>,
+    c: &[E::G1Affine],
+) -> Commitment<E> {
+    Commitment {
+        t: pairing_product::<E>(c, &vkey.a),
+        u: pairing_product::<E>(c, &vkey.b),
+    }
+}
+
+/// Multiplies the `i`-th element of `v` by `scalars[i]`.
+pub(crate) fn scale<G: AffineCurve>(v: &[G], scalars: &[G::ScalarField]) -> Vec<G> {
+    let scaled = v
+        .iter()
+        .zip(scalars)
+        .map(|(g, s)| g.mul(s.into_repr()))
+        .collect::<Vec<_>>();
+    G::Projective::batch_normalization_into_affine(&scaled)
+}
+
+/// Folds `v` into `left + x right`, where `left` and `right` are its
+/// halves.
+pub(crate) fn fold<G: AffineCurve>(v: &[G], x: G::ScalarField) -> Vec<G> {
+    let (left, right) = v.split_at(v.len() / 2);
+    let folded = left
+        .iter()
+        .zip(right)
+        .map(|(l, r)| {
+            let mut folded = r.mul(x.into_repr());
+            folded.add_assign_mixed(l);
+            folded
+        })
+        .collect::<Vec<_>>();
+    G::Projective::batch_normalization_into_affine(&folded)
+}
+
+/// Computes `sum_i v_i`.
+pub(crate) fn sum<G: AffineCurve>(v: &[G]) -> G::Projective {
+    v.iter().fold(G::Projective::zero(), |mut acc, g| {
+        acc.add_assign_mixed(g);
+        acc
+    })
+}
@@ -0,0 +1,80 @@
+use crate::Vec;
+use algebra_core::{
+    bytes::ToBytes,
+    io::{self, Write},
+    serialize::*,
+    Field, PairingEngine, PrimeField,
+};
+
+/// A commitment to one or two vectors of group elements: a pair of elements
+/// of the target group, one under the powers of `a` and one under the
+/// powers of `b`.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Commitment<E: PairingEngine> {
+    pub t: E::Fqk,
+    pub u: E::Fqk,
+}
+
+impl<E: PairingEngine> Commitment<E> {
+    /// The commitment to the vectors folded with the challenge `x`, given
+    /// the cross terms `left` and `right` that it picks up with `x^-1` and
+    /// `x` respectively.
+    pub(crate) fn fold(&self, left: &Self, right: &Self, x: E::Fr, x_inv: E::Fr) -> Self {
+        Self {
+            t: self.t * &left.t.pow(x_inv.into_repr()) * &right.t.pow(x.into_repr()),
+            u: self.u * &left.u.pow(x_inv.into_repr()) * &right.u.pow(x.into_repr()),
+        }
+    }
+}
+
+impl<E: PairingEngine> ToBytes for Commitment<E> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        self.t.write(&mut writer)?;
+        self.u.write(&mut writer)
+    }
+}
+
+/// The generalized inner product argument, which reduces the claims about
+/// the committed vectors `A`, `B` and `C` to claims about single elements
+/// by halving them in each round.
+///
+/// In every round, the prover sends the cross terms of the commitments and
+/// of the inner products, with the left one first.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct GipaProof<E: PairingEngine> {
+    /// The cross terms of the commitment to `A` and `B`.
+    pub comms_ab: Vec<(Commitment<E>, Commitment<E>)>,
+    /// The cross terms of the commitment to `C`.
+    pub comms_c: Vec<(Commitment<E>, Commitment<E>)>,
+    /// The cross terms of `prod_i e(A_i, B_i)`.
+    pub z_ab: Vec<(E::Fqk, E::Fqk)>,
+    /// The cross terms of `sum_i C_i`.
+    pub z_c: Vec<(E::G1Affine, E::G1Affine)>,
+    pub final_a: E::G1Affine,
+    pub final_b: E::G2Affine,
+    pub final_c: E::G1Affine,
+    /// The folded key `v`, for `a` and for `b`.
+    pub final_vkey: (E::G2Affine, E::G2Affine),
+    /// The folded key `w`, for `a` and for `b`.
+    pub final_wkey: (E::G1Affine, E::G1Affine),
+}
+
+/// A proof that `n` Groth16 proofs `(A_i, B_i, C_i)` for the same verifying
+/// key are valid, whose size is logarithmic in `n`.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AggregateProof<E: PairingEngine> {
+    /// The commitment to `A` and `B`.
+    pub com_ab: Commitment<E>,
+    /// The commitment to `C`.
+    pub com_c: Commitment<E>,
+    /// `prod_i e(A_i, B_i)^(r^i)`.
+    pub z_ab: E::Fqk,
+    /// `sum_i r^i C_i`.
+    pub z_c: E::G1Affine,
+    pub gipa: GipaProof<E>,
+    /// The KZG openings of the folded key `v` to its polynomial.
+    pub vkey_opening: (E::G2Affine, E::G2Affine),
+    /// The KZG openings of the folded key `w` to its polynomial.
+    pub wkey_opening: (E::G1Affine, E::G1Affine),
+}
@@ -0,0 +1,497 @@
+//! An implementation of [`SnarkPack`], which aggregates `n` Groth16 proofs
+//! for the same verifying key into one proof of size `O(log n)` that is
+//! verified with `O(log n)` group operations.
+//!
+//! The aggregator commits to the vectors `A`, `B` and `C` of the proofs,
+//! derives a random `r` from the commitments, and proves with an inner
+//! pairing product argument that `Z_AB = prod_i e(A_i, B_i)^(r^i)` and
+//! `Z_C = sum_i r^i C_i` are consistent with them. The verifier then checks
+//! the random linear combination
+//! `Z_AB = e(α, β)^(sum_i r^i) e(sum_i r^i IC_i, γ) e(Z_C, δ)`
+//! of the Groth16 verification equations.
+//!
+//! [`SnarkPack`]: https://eprint.iacr.org/2021/529
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(unused_import_braces, unused_qualifications, trivial_casts)]
+#![deny(trivial_numeric_casts, private_in_public, variant_size_differences)]
+#![deny(stable_features, unreachable_pub, non_shorthand_field_patterns)]
+#![deny(unused_attributes, unused_imports, unused_mut)]
+#![deny(renamed_and_removed_lints, stable_features, unused_allocation)]
+#![deny(unused_comparisons, bare_trait_objects, unused_must_use, const_err)]
+#![forbid(unsafe_code)]
+
+#[macro_use]
+extern crate bench_utils;
+
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::vec::Vec;
+
+#[cfg(feature = "std")]
+pub(crate) use std::vec::Vec;
+
+use algebra_core::{
+    msm::VariableBaseMSM, to_bytes, AffineCurve, Field, One, PairingEngine, PrimeField,
+    ProjectiveCurve, ToBytes, Zero,
+};
+use core::marker::PhantomData;
+use crypto_primitives::FiatShamirRng;
+use groth16::{PreparedVerifyingKey, Proof};
+use r1cs_core::SynthesisError;
+
+mod commitment;
+mod data_structures;
+mod opening;
+mod srs;
+
+pub use data_structures::*;
+pub use srs::*;
+
+#[cfg(test)]
+mod test;
+
+use commitment::{commit_pair, commit_single, fold, pairing_product, scale, sum, Key};
+use opening::{
+    check_g1_opening, check_g2_opening, evaluate_polynomial, open, polynomial_coefficients,
+};
+
+/// The errors of SnarkPack.
+#[derive(Debug)]
+pub enum Error {
+    /// The number of proofs is not a power of two.
+    InvalidNumberOfProofs(usize),
+    /// The SRS supports fewer proofs than are aggregated.
+    TooManyProofs {
+        num_proofs: usize,
+        max_num_proofs: usize,
+    },
+    /// The number of proofs or of public inputs does not match.
+    IncorrectInputLength(usize),
+    /// The public inputs do not match the verifying key, or serializing a
+    /// transcript message failed.
+    SynthesisError(SynthesisError),
+}
+
+impl From<SynthesisError> for Error {
+    fn from(error: SynthesisError) -> Self {
+        Error::SynthesisError(error)
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::InvalidNumberOfProofs(num_proofs) => {
+                write!(f, "{} proofs cannot be aggregated", num_proofs)
+            },
+            Error::TooManyProofs {
+                num_proofs,
+                max_num_proofs,
+            } => write!(
+                f,
+                "the SRS supports at most {} proofs, but {} were given",
+                max_num_proofs, num_proofs
+            ),
+            Error::IncorrectInputLength(len) => write!(f, "input length {} is incorrect", len),
+            Error::SynthesisError(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl algebra_core::Error for Error {}
+
+/// SnarkPack over the pairing `E`, made non-interactive with the
+/// Fiat-Shamir transcript `FS`.
+pub struct SnarkPack<E: PairingEngine, FS: FiatShamirRng<E::Fr>> {
+    _engine: PhantomData<E>,
+    _fiat_shamir_rng: PhantomData<FS>,
+}
+
+impl<E: PairingEngine, FS: FiatShamirRng<E::Fr>> SnarkPack<E, FS> {
+    /// The domain of the Fiat-Shamir transcripts of aggregate proofs.
+    pub const PROTOCOL_NAME: &'static [u8] = b"SNARKPACK-2021";
+
+    /// Aggregates `proofs`, which are valid for the respective
+    /// `public_inputs`, into one proof. Their number must be the one that
+    /// `srs` was specialized to.
+    pub fn aggregate_proofs(
+        srs: &ProverSRS<E>,
+        proofs: &[Proof<E>],
+        public_inputs: &[&[E::Fr]],
+    ) -> Result<AggregateProof<E>, Error> {
+        let aggregate_time =
+            start_timer!(|| format!("SnarkPack::Aggregate {} proofs", proofs.len()));
+        let num_proofs = srs.n;
+        if proofs.len() != num_proofs {
+            return Err(Error::IncorrectInputLength(proofs.len()));
+        }
+        if public_inputs.len() != num_proofs {
+            return Err(Error::IncorrectInputLength(public_inputs.len()));
+        }
+
+        let a = proofs.iter().map(|proof| proof.a).collect::<Vec<_>>();
+        let b = proofs.iter().map(|proof| proof.b).collect::<Vec<_>>();
+        let c = proofs.iter().map(|proof| proof.c).collect::<Vec<_>>();
+        let vkey = Key {
+            a: srs.h_alpha_powers[..num_proofs].to_vec(),
+            b: srs.h_beta_powers[..num_proofs].to_vec(),
+        };
+        let wkey = Key {
+            a: srs.g_alpha_powers[num_proofs..2 * num_proofs].to_vec(),
+            b: srs.g_beta_powers[num_proofs..2 * num_proofs].to_vec(),
+        };
+
+        let commit_time = start_timer!(|| "Committing to A, B and C");
+        let com_ab = commit_pair(&vkey, &wkey, &a, &b);
+        let com_c = commit_single(&vkey, &c);
+        end_timer!(commit_time);
+
+        let mut fs = Self::init_transcript(public_inputs);
+        absorb(&mut fs, &[&com_ab, &com_c])?;
+        let r = squeeze_challenge(&mut fs);
+        let r_inv = r.inverse().unwrap();
+
+        // `A' = {r^i A_i}` and `C' = {r^i C_i}` are committed to under
+        // `v' = {r^-i v_i}` by the same commitments.
+        let randomize_time = start_timer!(|| "Randomizing A, C and v");
+        let r_powers = powers(r, num_proofs);
+        let a = scale(&a, &r_powers);
+        let c = scale(&c, &r_powers);
+        let vkey = vkey.scale(&powers(r_inv, num_proofs));
+        end_timer!(randomize_time);
+
+        let z_ab = pairing_product::<E>(&a, &b);
+        let z_c = sum(&c).into_affine();
+        absorb(&mut fs, &[z_ab])?;
+        absorb(&mut fs, &[z_c])?;
+
+        let (gipa, challenges) = Self::prove_gipa(&mut fs, a, b, c, vkey, wkey)?;
+        let point = squeeze_challenge(&mut fs);
+
+        let opening_time = start_timer!(|| "Opening the folded keys");
+        let vkey_coeffs = polynomial_coefficients(&Self::vkey_factors(&challenges, r_inv));
+        let vkey_opening = (
+            open(&srs.h_alpha_powers[..num_proofs], &vkey_coeffs, point),
+            open(&srs.h_beta_powers[..num_proofs], &vkey_coeffs, point),
+        );
+        let mut wkey_coeffs = vec![E::Fr::zero(); num_proofs];
+        wkey_coeffs.extend(polynomial_coefficients(&challenges));
+        let wkey_opening = (
+            open(&srs.g_alpha_powers, &wkey_coeffs, point),
+            open(&srs.g_beta_powers, &wkey_coeffs, point),
+        );
+        end_timer!(opening_time);
+
+        end_timer!(aggregate_time);
+        Ok(AggregateProof {
+            com_ab,
+            com_c,
+            z_ab,
+            z_c,
+            gipa,
+            vkey_opening,
+            wkey_opening,
+        })
+    }
+
+    /// Verifies that `proof` aggregates valid proofs for the respective
+    /// `public_inputs` under the verifying key `pvk`.
+    pub fn verify_aggregate_proof(
+        vsrs: &VerifierSRS<E>,
+        pvk: &PreparedVerifyingKey<E>,
+        public_inputs: &[&[E::Fr]],
+        proof: &AggregateProof<E>,
+    ) -> Result<bool, Error> {
+        let verify_time = start_timer!(|| "SnarkPack::Verify");
+        let n = public_inputs.len();
+        if !n.is_power_of_two() {
+            return Err(Error::InvalidNumberOfProofs(n));
+        }
+        if public_inputs
+            .iter()
+            .any(|inputs| (inputs.len() + 1) != pvk.gamma_abc_g1.len())
+        {
+            return Err(SynthesisError::MalformedVerifyingKey.into());
+        }
+        let num_rounds = n.trailing_zeros() as usize;
+        let gipa = &proof.gipa;
+        if gipa.comms_ab.len() != num_rounds
+            || gipa.comms_c.len() != num_rounds
+            || gipa.z_ab.len() != num_rounds
+            || gipa.z_c.len() != num_rounds
+        {
+            return Ok(false);
+        }
+
+        let mut fs = Self::init_transcript(public_inputs);
+        absorb(&mut fs, &[&proof.com_ab, &proof.com_c])?;
+        let r = squeeze_challenge(&mut fs);
+        absorb(&mut fs, &[proof.z_ab])?;
+        absorb(&mut fs, &[proof.z_c])?;
+
+        // Replay the folding of the commitments and of the inner products.
+        let mut com_ab = proof.com_ab.clone();
+        let mut com_c = proof.com_c.clone();
+        let mut z_ab = proof.z_ab;
+        let mut z_c = proof.z_c.into_projective();
+        let mut z_c_scalar = E::Fr::one();
+        let mut challenges = Vec::with_capacity(num_rounds);
+        for (((com_ab_lr, com_c_lr), z_ab_lr), z_c_lr) in gipa
+            .comms_ab
+            .iter()
+            .zip(&gipa.comms_c)
+            .zip(&gipa.z_ab)
+            .zip(&gipa.z_c)
+        {
+            absorb(
+                &mut fs,
+                &[&com_ab_lr.0, &com_ab_lr.1, &com_c_lr.0, &com_c_lr.1],
+            )?;
+            absorb(&mut fs, &[z_ab_lr.0, z_ab_lr.1])?;
+            absorb(&mut fs, &[z_c_lr.0, z_c_lr.1])?;
+            let x = squeeze_challenge(&mut fs);
+            let x_inv = x.inverse().unwrap();
+
+            com_ab = com_ab.fold(&com_ab_lr.0, &com_ab_lr.1, x, x_inv);
+            com_c = com_c.fold(&com_c_lr.0, &com_c_lr.1, x, x_inv);
+            z_ab *= &(z_ab_lr.0.pow(x_inv.into_repr()) * &z_ab_lr.1.pow(x.into_repr()));
+            z_c += &(z_c_lr.0.mul(x_inv.into_repr()) + &z_c_lr.1.mul(x.into_repr()));
+            z_c_scalar *= &(E::Fr::one() + &x_inv);
+            challenges.push(x);
+        }
+        absorb(&mut fs, &[gipa.final_a, gipa.final_c])?;
+        absorb(&mut fs, &[gipa.final_b])?;
+        absorb(&mut fs, &[gipa.final_vkey.0, gipa.final_vkey.1])?;
+        absorb(&mut fs, &[gipa.final_wkey.0, gipa.final_wkey.1])?;
+        let point = squeeze_challenge(&mut fs);
+
+        // The folded vectors and keys are consistent with the folded
+        // commitments and inner products.
+        let final_check_time = start_timer!(|| "Checking the folded values");
+        let (v_a, v_b) = gipa.final_vkey;
+        let (w_a, w_b) = gipa.final_wkey;
+        let final_a = gipa.final_a;
+        let final_b = gipa.final_b;
+        let final_c = gipa.final_c;
+        let gipa_holds = com_ab.t == pairing_product::<E>(&[final_a, w_a], &[v_a, final_b])
+            && com_ab.u == pairing_product::<E>(&[final_a, w_b], &[v_b, final_b])
+            && com_c.t == pairing_product::<E>(&[final_c], &[v_a])
+            && com_c.u == pairing_product::<E>(&[final_c], &[v_b])
+            && z_ab == pairing_product::<E>(&[final_a], &[final_b])
+            && z_c == final_c.mul(z_c_scalar.into_repr());
+        end_timer!(final_check_time);
+
+        // The folded keys are the evaluations of the polynomials that the
+        // challenges determine.
+        let opening_check_time = start_timer!(|| "Checking the openings of the folded keys");
+        let vkey_value = evaluate_polynomial(
+            &Self::vkey_factors(&challenges, r.inverse().unwrap()),
+            point,
+        );
+        let wkey_value = point.pow(&[n as u64]) * &evaluate_polynomial(&challenges, point);
+        let openings_hold = check_g2_opening(
+            vsrs,
+            vsrs.g_alpha,
+            v_a,
+            point,
+            vkey_value,
+            proof.vkey_opening.0,
+        ) && check_g2_opening(
+            vsrs,
+            vsrs.g_beta,
+            v_b,
+            point,
+            vkey_value,
+            proof.vkey_opening.1,
+        ) && check_g1_opening(
+            vsrs,
+            vsrs.h_alpha,
+            w_a,
+            point,
+            wkey_value,
+            proof.wkey_opening.0,
+        ) && check_g1_opening(
+            vsrs,
+            vsrs.h_beta,
+            w_b,
+            point,
+            wkey_value,
+            proof.wkey_opening.1,
+        );
+        end_timer!(opening_check_time);
+
+        // The random linear combination of the Groth16 verification
+        // equations holds.
+        let groth16_check_time = start_timer!(|| "Checking the Groth16 equations");
+        let mut input_scalars = vec![E::Fr::zero(); pvk.gamma_abc_g1.len()];
+        let mut r_power = E::Fr::one();
+        for inputs in public_inputs {
+            input_scalars[0] += &r_power;
+            for (scalar, input) in input_scalars[1..].iter_mut().zip(*inputs) {
+                *scalar += &(r_power * input);
+            }
+            r_power *= &r;
+        }
+        let r_sum = input_scalars[0];
+        let input_scalars = input_scalars
+            .into_iter()
+            .map(|s| s.into_repr())
+            .collect::<Vec<_>>();
+        let g_ic = VariableBaseMSM::multi_scalar_mul_subgroup(&pvk.gamma_abc_g1, &input_scalars);
+        let qap = E::product_of_pairings(&[
+            (g_ic.into_affine().into(), pvk.gamma_g2_neg_pc.clone()),
+            (proof.z_c.into(), pvk.delta_g2_neg_pc.clone()),
+        ]);
+        let groth16_holds = proof.z_ab * &qap == pvk.alpha_g1_beta_g2.pow(r_sum.into_repr());
+        end_timer!(groth16_check_time);
+
+        end_timer!(verify_time);
+        Ok(gipa_holds && openings_hold && groth16_holds)
+    }
+
+    /// Proves the GIPA for the randomized vectors, returning the proof and
+    /// the challenges of its rounds.
+    fn prove_gipa(
+        fs: &mut FS,
+        mut a: Vec<E::G1Affine>,
+        mut b: Vec<E::G2Affine>,
+        mut c: Vec<E::G1Affine>,
+        mut vkey: Key<E::G2Affine>,
+        mut wkey: Key<E::G1Affine>,
+    ) -> Result<(GipaProof<E>, Vec<E::Fr>), Error> {
+        let gipa_time = start_timer!(|| "GIPA");
+        let num_rounds = a.len().trailing_zeros() as usize;
+        let mut comms_ab = Vec::with_capacity(num_rounds);
+        let mut comms_c = Vec::with_capacity(num_rounds);
+        let mut z_ab = Vec::with_capacity(num_rounds);
+        let mut z_c = Vec::with_capacity(num_rounds);
+        let mut challenges = Vec::with_capacity(num_rounds);
+        // All entries of the vector with which `C` is summed are equal.
+        let mut z_c_scalar = E::Fr::one();
+
+        while a.len() > 1 {
+            let round_time = start_timer!(|| format!("GIPA round of length {}", a.len()));
+            let half = a.len() / 2;
+            let (a_left, a_right) = a.split_at(half);
+            let (b_left, b_right) = b.split_at(half);
+            let (c_left, c_right) = c.split_at(half);
+            let (vkey_left, vkey_right) = vkey.split();
+            let (wkey_left, wkey_right) = wkey.split();
+
+            // `A`, `C` and `w` are folded with `x`, and `B` and `v` with
+            // `x^-1`, so the left cross terms pair the left halves of the
+            // former with the right halves of the latter.
+            let com_ab_lr = (
+                commit_pair(&vkey_right, &wkey_left, a_left, b_right),
+                commit_pair(&vkey_left, &wkey_right, a_right, b_left),
+            );
+            let com_c_lr = (
+                commit_single(&vkey_right, c_left),
+                commit_single(&vkey_left, c_right),
+            );
+            let z_ab_lr = (
+                pairing_product::<E>(a_left, b_right),
+                pairing_product::<E>(a_right, b_left),
+            );
+            let z_c_lr = (
+                sum(c_left).mul(z_c_scalar.into_repr()).into_affine(),
+                sum(c_right).mul(z_c_scalar.into_repr()).into_affine(),
+            );
+
+            absorb(fs, &[&com_ab_lr.0, &com_ab_lr.1, &com_c_lr.0, &com_c_lr.1])?;
+            absorb(fs, &[z_ab_lr.0, z_ab_lr.1])?;
+            absorb(fs, &[z_c_lr.0, z_c_lr.1])?;
+            let x = squeeze_challenge(fs);
+            let x_inv = x.inverse().unwrap();
+
+            a = fold(&a, x);
+            b = fold(&b, x_inv);
+            c = fold(&c, x);
+            vkey = vkey.fold(x_inv);
+            wkey = wkey.fold(x);
+            z_c_scalar *= &(E::Fr::one() + &x_inv);
+
+            comms_ab.push(com_ab_lr);
+            comms_c.push(com_c_lr);
+            z_ab.push(z_ab_lr);
+            z_c.push(z_c_lr);
+            challenges.push(x);
+            end_timer!(round_time);
+        }
+
+        let gipa = GipaProof {
+            comms_ab,
+            comms_c,
+            z_ab,
+            z_c,
+            final_a: a[0],
+            final_b: b[0],
+            final_c: c[0],
+            final_vkey: vkey.first(),
+            final_wkey: wkey.first(),
+        };
+        absorb(fs, &[gipa.final_a, gipa.final_c])?;
+        absorb(fs, &[gipa.final_b])?;
+        absorb(fs, &[gipa.final_vkey.0, gipa.final_vkey.1])?;
+        absorb(fs, &[gipa.final_wkey.0, gipa.final_wkey.1])?;
+        end_timer!(gipa_time);
+        Ok((gipa, challenges))
+    }
+
+    /// The factors of the polynomial of the folded key `v`: the key
+    /// `{r^-i v_i}` is folded with `x_j^-1` in round `j`, where the right
+    /// half is `r^-(n / 2^(j + 1))` times the left one.
+    fn vkey_factors(challenges: &[E::Fr], r_inv: E::Fr) -> Vec<E::Fr> {
+        let mut factors = Vec::with_capacity(challenges.len());
+        let mut r_inv_power = r_inv;
+        for x in challenges.iter().rev() {
+            factors.push(x.inverse().unwrap() * &r_inv_power);
+            r_inv_power.square_in_place();
+        }
+        factors.reverse();
+        factors
+    }
+
+    /// Starts the transcript of an aggregate proof with the public inputs.
+    fn init_transcript(public_inputs: &[&[E::Fr]]) -> FS {
+        let mut fs = FS::new(&FS::setup(), Self::PROTOCOL_NAME);
+        for inputs in public_inputs {
+            fs.absorb_field_elements(inputs);
+        }
+        fs
+    }
+}
+
+fn absorb<F: PrimeField, FS: FiatShamirRng<F>, T: ToBytes>(
+    fs: &mut FS,
+    messages: &[T],
+) -> Result<(), Error> {
+    for message in messages {
+        fs.absorb_bytes(&to_bytes![message].map_err(SynthesisError::from)?);
+    }
+    Ok(())
+}
+
+/// Squeezes a non-zero challenge, which is invertible.
+fn squeeze_challenge<F: PrimeField, FS: FiatShamirRng<F>>(fs: &mut FS) -> F {
+    loop {
+        let challenge = fs.squeeze_field_elements(1)[0];
+        if !challenge.is_zero() {
+            return challenge;
+        }
+    }
+}
+
+/// Computes `{s^i}` for `i` from `0` to `n - 1`.
+fn powers<F: Field>(s: F, n: usize) -> Vec<F> {
+    let mut powers = Vec::with_capacity(n);
+    let mut cur = F::one();
+    for _ in 0..n {
+        powers.push(cur);
+        cur *= &s;
+    }
+    powers
+}
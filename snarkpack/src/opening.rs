@@ -0,0 +1,99 @@
+//! The KZG openings which show that the folded commitment keys are the
+//! evaluations at `a` and `b` of the polynomials that the challenges of the
+//! GIPA determine.
+//!
+//! Folding a key `{s^i P}` of length `2^k` with the challenges
+//! `x_0, ..., x_(k-1)` yields `f(s) P` for
+//! `f(X) = prod_j (1 + c_j X^(2^(k - 1 - j)))`, where `c_j` is the
+//! coefficient with which round `j` adds the right half to the left one.
+use crate::{Vec, VerifierSRS};
+use algebra_core::{
+    msm::VariableBaseMSM, AffineCurve, Field, One, PairingEngine, PrimeField, ProjectiveCurve, Zero,
+};
+
+/// The coefficients of `prod_j (1 + c_j X^(2^(k - 1 - j)))`, where `c_j` is
+/// `factors[j]`.
+pub(crate) fn polynomial_coefficients<F: Field>(factors: &[F]) -> Vec<F> {
+    let mut coeffs = vec![F::one()];
+    for c in factors.iter().rev() {
+        let high = coeffs.iter().map(|coeff| *coeff * c).collect::<Vec<_>>();
+        coeffs.extend(high);
+    }
+    coeffs
+}
+
+/// Evaluates `prod_j (1 + c_j X^(2^(k - 1 - j)))`, where `c_j` is
+/// `factors[j]`, at `point` with `O(k)` operations.
+pub(crate) fn evaluate_polynomial<F: Field>(factors: &[F], point: F) -> F {
+    let mut power = point;
+    let mut result = F::one();
+    for c in factors.iter().rev() {
+        result *= &(F::one() + &(*c * &power));
+        power.square_in_place();
+    }
+    result
+}
+
+/// Computes the KZG opening at `point` of the polynomial `p` with the
+/// coefficients `coeffs`, which is the commitment to
+/// `(p(X) - p(point)) / (X - point)` under `powers`.
+pub(crate) fn open<G: AffineCurve>(
+    powers: &[G],
+    coeffs: &[G::ScalarField],
+    point: G::ScalarField,
+) -> G {
+    let mut quotient = vec![G::ScalarField::zero(); coeffs.len().saturating_sub(1)];
+    let mut carry = G::ScalarField::zero();
+    for i in (1..coeffs.len()).rev() {
+        carry = coeffs[i] + &(point * &carry);
+        quotient[i - 1] = carry;
+    }
+    if quotient.is_empty() {
+        return G::zero();
+    }
+    let quotient = quotient
+        .into_iter()
+        .map(|q| q.into_repr())
+        .collect::<Vec<_>>();
+    VariableBaseMSM::multi_scalar_mul(&powers[..quotient.len()], &quotient).into_affine()
+}
+
+/// Checks that the commitment in `G2` opens to `value` at `point`, given
+/// the power `g_tau` of the secret in `G1`:
+/// `e(g_tau - point g, proof) e(-g, commitment - value h) == 1`.
+pub(crate) fn check_g2_opening<E: PairingEngine>(
+    vsrs: &VerifierSRS<E>,
+    g_tau: E::G1Affine,
+    commitment: E::G2Affine,
+    point: E::Fr,
+    value: E::Fr,
+    proof: E::G2Affine,
+) -> bool {
+    let shifted_g_tau = g_tau.into_projective() - &vsrs.g.mul(point.into_repr());
+    let shifted_commitment = commitment.into_projective() - &vsrs.h.mul(value.into_repr());
+    E::product_of_pairings(&[
+        (shifted_g_tau.into_affine().into(), proof.into()),
+        ((-vsrs.g).into(), shifted_commitment.into_affine().into()),
+    ])
+    .is_one()
+}
+
+/// Checks that the commitment in `G1` opens to `value` at `point`, given
+/// the power `h_tau` of the secret in `G2`:
+/// `e(commitment - value g, -h) e(proof, h_tau - point h) == 1`.
+pub(crate) fn check_g1_opening<E: PairingEngine>(
+    vsrs: &VerifierSRS<E>,
+    h_tau: E::G2Affine,
+    commitment: E::G1Affine,
+    point: E::Fr,
+    value: E::Fr,
+    proof: E::G1Affine,
+) -> bool {
+    let shifted_commitment = commitment.into_projective() - &vsrs.g.mul(value.into_repr());
+    let shifted_h_tau = h_tau.into_projective() - &vsrs.h.mul(point.into_repr());
+    E::product_of_pairings(&[
+        (shifted_commitment.into_affine().into(), (-vsrs.h).into()),
+        (proof.into(), shifted_h_tau.into_affine().into()),
+    ])
+    .is_one()
+}
@@ -0,0 +1,158 @@
+use crate::{Error, Vec};
+use algebra_core::{
+    msm::FixedBaseMSM, serialize::*, One, PairingEngine, PrimeField, ProjectiveCurve, UniformRand,
+};
+use rand::RngCore;
+
+/// The structured reference string from which the keys for aggregating any
+/// number of proofs up to `max_num_proofs()` are derived.
+///
+/// It consists of the powers of two independent secrets `a` and `b` in both
+/// groups, so it can be taken from two powers-of-tau transcripts with the
+/// same generators.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct GenericSRS<E: PairingEngine> {
+    /// `{a^i G}`, for `i` from `0` to `2 max_num_proofs() - 1`.
+    pub g_alpha_powers: Vec<E::G1Affine>,
+    /// `{b^i G}`, for `i` from `0` to `2 max_num_proofs() - 1`.
+    pub g_beta_powers: Vec<E::G1Affine>,
+    /// `{a^i H}`, for `i` from `0` to `max_num_proofs() - 1`, and at least
+    /// to `1`.
+    pub h_alpha_powers: Vec<E::G2Affine>,
+    /// `{b^i H}`, for `i` from `0` to `max_num_proofs() - 1`, and at least
+    /// to `1`.
+    pub h_beta_powers: Vec<E::G2Affine>,
+}
+
+/// The powers needed to aggregate exactly `n` proofs.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ProverSRS<E: PairingEngine> {
+    /// The number of proofs that are aggregated.
+    pub n: usize,
+    /// `{a^i G}`, for `i` from `0` to `2n - 1`.
+    pub g_alpha_powers: Vec<E::G1Affine>,
+    /// `{b^i G}`, for `i` from `0` to `2n - 1`.
+    pub g_beta_powers: Vec<E::G1Affine>,
+    /// `{a^i H}`, for `i` from `0` to `n - 1`.
+    pub h_alpha_powers: Vec<E::G2Affine>,
+    /// `{b^i H}`, for `i` from `0` to `n - 1`.
+    pub h_beta_powers: Vec<E::G2Affine>,
+}
+
+/// The elements with which aggregate proofs of any size are verified.
+#[derive(Clone, Debug, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct VerifierSRS<E: PairingEngine> {
+    pub g: E::G1Affine,
+    pub h: E::G2Affine,
+    pub g_alpha: E::G1Affine,
+    pub g_beta: E::G1Affine,
+    pub h_alpha: E::G2Affine,
+    pub h_beta: E::G2Affine,
+}
+
+impl<E: PairingEngine> GenericSRS<E> {
+    /// Samples the SRS for aggregating up to `max_num_proofs` proofs. The
+    /// secrets `a` and `b` must be discarded afterwards, so this is only
+    /// suitable for testing; deployments take the powers from a ceremony.
+    pub fn setup<R: RngCore>(max_num_proofs: usize, rng: &mut R) -> Result<Self, Error> {
+        if max_num_proofs == 0 {
+            return Err(Error::InvalidNumberOfProofs(max_num_proofs));
+        }
+        let setup_time = start_timer!(|| format!("SnarkPack::Setup for {} proofs", max_num_proofs));
+        let g = E::G1Projective::rand(rng);
+        let h = E::G2Projective::rand(rng);
+        let alpha = E::Fr::rand(rng);
+        let beta = E::Fr::rand(rng);
+
+        let powers = |secret: E::Fr, len: usize| {
+            let mut powers = Vec::with_capacity(len);
+            let mut cur = E::Fr::one();
+            for _ in 0..len {
+                powers.push(cur);
+                cur *= &secret;
+            }
+            powers
+        };
+        let alpha_powers = powers(alpha, 2 * max_num_proofs);
+        let beta_powers = powers(beta, 2 * max_num_proofs);
+
+        let scalar_bits = E::Fr::size_in_bits();
+        let g_window_size = FixedBaseMSM::get_mul_window_size(2 * max_num_proofs);
+        let g_table = FixedBaseMSM::get_window_table(scalar_bits, g_window_size, g);
+        let g_powers = |powers: &[E::Fr]| {
+            E::G1Projective::batch_normalization_into_affine(&FixedBaseMSM::multi_scalar_mul::<
+                E::G1Projective,
+            >(
+                scalar_bits,
+                g_window_size,
+                &g_table,
+                powers,
+            ))
+        };
+        let num_h_powers = max_num_proofs.max(2);
+        let h_window_size = FixedBaseMSM::get_mul_window_size(num_h_powers);
+        let h_table = FixedBaseMSM::get_window_table(scalar_bits, h_window_size, h);
+        let h_powers = |powers: &[E::Fr]| {
+            E::G2Projective::batch_normalization_into_affine(&FixedBaseMSM::multi_scalar_mul::<
+                E::G2Projective,
+            >(
+                scalar_bits,
+                h_window_size,
+                &h_table,
+                &powers[..num_h_powers],
+            ))
+        };
+
+        let srs = Self {
+            g_alpha_powers: g_powers(&alpha_powers),
+            g_beta_powers: g_powers(&beta_powers),
+            h_alpha_powers: h_powers(&alpha_powers),
+            h_beta_powers: h_powers(&beta_powers),
+        };
+        end_timer!(setup_time);
+        Ok(srs)
+    }
+
+    /// The maximum number of proofs that can be aggregated.
+    pub fn max_num_proofs(&self) -> usize {
+        (self.g_alpha_powers.len() / 2)
+            .min(self.g_beta_powers.len() / 2)
+            .min(self.h_alpha_powers.len())
+            .min(self.h_beta_powers.len())
+    }
+
+    /// Derives the keys for aggregating `num_proofs` proofs, which must be a
+    /// power of two.
+    pub fn specialize(&self, num_proofs: usize) -> Result<(ProverSRS<E>, VerifierSRS<E>), Error> {
+        if !num_proofs.is_power_of_two() {
+            return Err(Error::InvalidNumberOfProofs(num_proofs));
+        }
+        if num_proofs > self.max_num_proofs() {
+            return Err(Error::TooManyProofs {
+                num_proofs,
+                max_num_proofs: self.max_num_proofs(),
+            });
+        }
+        let prover_srs = ProverSRS {
+            n: num_proofs,
+            g_alpha_powers: self.g_alpha_powers[..2 * num_proofs].to_vec(),
+            g_beta_powers: self.g_beta_powers[..2 * num_proofs].to_vec(),
+            h_alpha_powers: self.h_alpha_powers[..num_proofs].to_vec(),
+            h_beta_powers: self.h_beta_powers[..num_proofs].to_vec(),
+        };
+        Ok((prover_srs, self.verifier_srs()))
+    }
+
+    /// The SRS of the verifier, which does not depend on the number of
+    /// proofs.
+    pub fn verifier_srs(&self) -> VerifierSRS<E> {
+        VerifierSRS {
+            g: self.g_alpha_powers[0],
+            h: self.h_alpha_powers[0],
+            g_alpha: self.g_alpha_powers[1],
+            g_beta: self.g_beta_powers[1],
+            h_alpha: self.h_alpha_powers[1],
+            h_beta: self.h_beta_powers[1],
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use algebra_core::Field;
+use r1cs_core::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+
+struct MySillyCircuit<F: Field> {
+    a: Option<F>,
+    b: Option<F>,
+}
+
+impl<ConstraintF: Field> ConstraintSynthesizer<ConstraintF> for MySillyCircuit<ConstraintF> {
+    fn generate_constraints<CS: ConstraintSystem<ConstraintF>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
+        let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
+        let c = cs.alloc_input(
+            || "c",
+            || {
+                let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+                let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
+
+                a.mul_assign(&b);
+                Ok(a)
+            },
+        )?;
+        let d = cs.alloc_input(
+            || "d",
+            || {
+                let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+                let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
+
+                a.add_assign(&b);
+                Ok(a)
+            },
+        )?;
+
+        cs.enforce(|| "a*b=c", |lc| lc + a, |lc| lc + b, |lc| lc + c);
+        cs.enforce(
+            || "(a+b)*1=d",
+            |lc| lc + a + b,
+            |lc| lc + CS::one(),
+            |lc| lc + d,
+        );
+
+        Ok(())
+    }
+}
+
+mod bls12_381 {
+    use super::*;
+    use crate::{Error, GenericSRS, SnarkPack};
+    use crypto_primitives::fiat_shamir::blake2s::Blake2sFiatShamirRng;
+    use groth16::{
+        create_random_proof, generate_random_parameters, prepare_verifying_key, Parameters, Proof,
+    };
+
+    use algebra::bls12_381::{Bls12_381, Fr};
+    use algebra_core::{
+        serialize::{CanonicalDeserialize, CanonicalSerialize},
+        test_rng, UniformRand,
+    };
+    use rand::Rng;
+
+    type SnarkPackInst = SnarkPack<Bls12_381, Blake2sFiatShamirRng>;
+
+    fn prove<R: Rng>(
+        params: &Parameters<Bls12_381>,
+        num_proofs: usize,
+        rng: &mut R,
+    ) -> (Vec<Proof<Bls12_381>>, Vec<Vec<Fr>>) {
+        let mut proofs = Vec::new();
+        let mut public_inputs = Vec::new();
+        for _ in 0..num_proofs {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+            let circuit = MySillyCircuit {
+                a: Some(a),
+                b: Some(b),
+            };
+            proofs.push(create_random_proof(circuit, params, rng).unwrap());
+            public_inputs.push(vec![a * &b, a + &b]);
+        }
+        (proofs, public_inputs)
+    }
+
+    #[test]
+    fn aggregate_and_verify() {
+        let rng = &mut test_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_381, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+        let srs = GenericSRS::<Bls12_381>::setup(16, rng).unwrap();
+
+        for &num_proofs in &[1, 2, 8, 16] {
+            let (prover_srs, verifier_srs) = srs.specialize(num_proofs).unwrap();
+            let (proofs, public_inputs) = prove(&params, num_proofs, rng);
+            let public_inputs = public_inputs
+                .iter()
+                .map(|v| v.as_slice())
+                .collect::<Vec<_>>();
+
+            let aggregate =
+                SnarkPackInst::aggregate_proofs(&prover_srs, &proofs, &public_inputs).unwrap();
+            assert_eq!(
+                aggregate.gipa.comms_ab.len(),
+                num_proofs.trailing_zeros() as usize
+            );
+            assert!(SnarkPackInst::verify_aggregate_proof(
+                &verifier_srs,
+                &pvk,
+                &public_inputs,
+                &aggregate
+            )
+            .unwrap());
+
+            let wrong_inputs = [Fr::rand(rng), Fr::rand(rng)];
+            let mut wrong_public_inputs = public_inputs.clone();
+            wrong_public_inputs[num_proofs - 1] = &wrong_inputs;
+            assert!(!SnarkPackInst::verify_aggregate_proof(
+                &verifier_srs,
+                &pvk,
+                &wrong_public_inputs,
+                &aggregate
+            )
+            .unwrap());
+        }
+    }
+
+    #[test]
+    fn reject_invalid_proofs() {
+        let rng = &mut test_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_381, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+        let srs = GenericSRS::<Bls12_381>::setup(8, rng).unwrap();
+        let (prover_srs, verifier_srs) = srs.specialize(8).unwrap();
+        let (mut proofs, public_inputs) = prove(&params, 8, rng);
+        let public_inputs = public_inputs
+            .iter()
+            .map(|v| v.as_slice())
+            .collect::<Vec<_>>();
+
+        // An aggregate of one invalid proof is rejected.
+        proofs.swap(2, 5);
+        let aggregate =
+            SnarkPackInst::aggregate_proofs(&prover_srs, &proofs, &public_inputs).unwrap();
+        assert!(!SnarkPackInst::verify_aggregate_proof(
+            &verifier_srs,
+            &pvk,
+            &public_inputs,
+            &aggregate
+        )
+        .unwrap());
+        proofs.swap(2, 5);
+
+        let aggregate =
+            SnarkPackInst::aggregate_proofs(&prover_srs, &proofs, &public_inputs).unwrap();
+        assert!(SnarkPackInst::verify_aggregate_proof(
+            &verifier_srs,
+            &pvk,
+            &public_inputs,
+            &aggregate
+        )
+        .unwrap());
+
+        let mut tampered = aggregate.clone();
+        tampered.z_c = verifier_srs.g;
+        assert!(!SnarkPackInst::verify_aggregate_proof(
+            &verifier_srs,
+            &pvk,
+            &public_inputs,
+            &tampered
+        )
+        .unwrap());
+
+        let mut tampered = aggregate.clone();
+        tampered.z_ab.square_in_place();
+        assert!(!SnarkPackInst::verify_aggregate_proof(
+            &verifier_srs,
+            &pvk,
+            &public_inputs,
+            &tampered
+        )
+        .unwrap());
+
+        let mut tampered = aggregate.clone();
+        tampered.gipa.final_vkey.0 = tampered.gipa.final_vkey.1;
+        assert!(!SnarkPackInst::verify_aggregate_proof(
+            &verifier_srs,
+            &pvk,
+            &public_inputs,
+            &tampered
+        )
+        .unwrap());
+
+        let mut tampered = aggregate.clone();
+        tampered.wkey_opening.1 = tampered.wkey_opening.0;
+        assert!(!SnarkPackInst::verify_aggregate_proof(
+            &verifier_srs,
+            &pvk,
+            &public_inputs,
+            &tampered
+        )
+        .unwrap());
+
+        let mut truncated = aggregate.clone();
+        truncated.gipa.z_c.pop();
+        assert!(!SnarkPackInst::verify_aggregate_proof(
+            &verifier_srs,
+            &pvk,
+            &public_inputs,
+            &truncated
+        )
+        .unwrap());
+
+        assert!(matches!(
+            SnarkPackInst::verify_aggregate_proof(
+                &verifier_srs,
+                &pvk,
+                &public_inputs[..6],
+                &aggregate
+            ),
+            Err(Error::InvalidNumberOfProofs(6))
+        ));
+        assert!(matches!(
+            SnarkPackInst::aggregate_proofs(&prover_srs, &proofs[..4], &public_inputs[..4]),
+            Err(Error::IncorrectInputLength(4))
+        ));
+    }
+
+    #[test]
+    fn serialize_aggregate_proof() {
+        let rng = &mut test_rng();
+
+        let params =
+            generate_random_parameters::<Bls12_381, _, _>(MySillyCircuit { a: None, b: None }, rng)
+                .unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+        let srs = GenericSRS::<Bls12_381>::setup(4, rng).unwrap();
+        let (prover_srs, verifier_srs) = srs.specialize(4).unwrap();
+        let (proofs, public_inputs) = prove(&params, 4, rng);
+        let public_inputs = public_inputs
+            .iter()
+            .map(|v| v.as_slice())
+            .collect::<Vec<_>>();
+        let aggregate =
+            SnarkPackInst::aggregate_proofs(&prover_srs, &proofs, &public_inputs).unwrap();
+
+        let mut bytes = Vec::new();
+        aggregate.serialize(&mut bytes).unwrap();
+        let deserialized = crate::AggregateProof::deserialize(&mut &bytes[..]).unwrap();
+        assert_eq!(aggregate, deserialized);
+        assert!(SnarkPackInst::verify_aggregate_proof(
+            &verifier_srs,
+            &pvk,
+            &public_inputs,
+            &deserialized
+        )
+        .unwrap());
+
+        let mut bytes = Vec::new();
+        srs.serialize(&mut bytes).unwrap();
+        assert_eq!(srs, GenericSRS::deserialize(&mut &bytes[..]).unwrap());
+    }
+
+    #[test]
+    fn specialize_checks_the_number_of_proofs() {
+        let rng = &mut test_rng();
+
+        let srs = GenericSRS::<Bls12_381>::setup(4, rng).unwrap();
+        assert_eq!(srs.max_num_proofs(), 4);
+        assert!(matches!(
+            srs.specialize(8),
+            Err(Error::TooManyProofs {
+                num_proofs: 8,
+                max_num_proofs: 4
+            })
+        ));
+        assert!(matches!(
+            srs.specialize(3),
+            Err(Error::InvalidNumberOfProofs(3))
+        ));
+        assert!(matches!(
+            srs.specialize(0),
+            Err(Error::InvalidNumberOfProofs(0))
+        ));
+    }
+}